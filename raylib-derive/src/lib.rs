@@ -0,0 +1,91 @@
+//! Derive macros for raylib-rs. Use them through the `derive` feature of the `raylib` crate.
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Implements `raylib::core::shaders::ShaderUniforms` for a struct with named fields.
+///
+/// Every field is a uniform of the same name, and its type must implement `UniformValue`.
+/// `#[uniform(name = "...")]` uploads a field to a differently named uniform and
+/// `#[uniform(skip)]` leaves a field out.
+#[proc_macro_derive(ShaderUniforms, attributes(uniform))]
+pub fn derive_shader_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match shader_uniforms(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn shader_uniforms(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "ShaderUniforms can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "ShaderUniforms can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut names = Vec::new();
+    let mut members = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let mut name = ident.to_string();
+        let mut skip = false;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("uniform")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"...\"` or `skip`"))
+                }
+            })?;
+        }
+        if skip {
+            continue;
+        }
+        if names.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("uniform `{}` is set by more than one field", name),
+            ));
+        }
+        names.push(name);
+        members.push(ident);
+    }
+    let indices = 0..members.len();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::raylib::core::shaders::ShaderUniforms for #ident #ty_generics #where_clause {
+            const UNIFORM_NAMES: &'static [&'static str] = &[#(#names),*];
+
+            fn set_uniforms(&self, shader: ::raylib::ffi::Shader, locations: &[i32]) {
+                #(
+                    if locations[#indices] >= 0 {
+                        ::raylib::core::shaders::UniformValue::set_uniform(
+                            &self.#members,
+                            shader,
+                            locations[#indices],
+                        );
+                    }
+                )*
+            }
+        }
+    })
+}
@@ -8,16 +8,34 @@ extern "C"
 #endif
 
 #include "utils_log.h"
-#include <stdio.h>	// Required for: vprintf()
-#include <string.h> // Required for: strcpy(), strcat()
+#include <stdio.h>	// Required for: vsnprintf()
+#include <stdlib.h> // Required for: malloc(), free()
+#include <stdarg.h> // Required for: va_copy()
 
-#define MAX_TRACELOG_BUFFER_SIZE 128 // As defined in utils.c from raylib
+#define MAX_TRACELOG_BUFFER_SIZE 256 // As defined in utils.c from raylib
 
 	void rayLogWrapperCallback(int logType, const char *text, va_list args)
 	{
 		char buffer[MAX_TRACELOG_BUFFER_SIZE] = {0};
-
-		vsnprintf(buffer, MAX_TRACELOG_BUFFER_SIZE, text, args);
+		va_list argsCopy;
+
+		va_copy(argsCopy, args);
+		int length = vsnprintf(buffer, MAX_TRACELOG_BUFFER_SIZE, text, args);
+
+		// Shader compile logs and the like don't fit in the stack buffer
+		if (length >= MAX_TRACELOG_BUFFER_SIZE)
+		{
+			char *message = (char *)malloc(length + 1);
+			if (message != NULL)
+			{
+				vsnprintf(message, length + 1, text, argsCopy);
+				custom_trace_log_callback(logType, message);
+				free(message);
+				va_end(argsCopy);
+				return;
+			}
+		}
+		va_end(argsCopy);
 
 		custom_trace_log_callback(logType, buffer);
 	}
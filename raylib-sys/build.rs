@@ -147,7 +147,10 @@ fn build_with_cmake(src_path: &str) {
     }
 
     match platform {
+        #[cfg(not(feature = "sdl"))]
         Platform::Desktop => conf.define("PLATFORM", "Desktop"),
+        #[cfg(feature = "sdl")]
+        Platform::Desktop => conf.define("PLATFORM", "SDL"),
         Platform::Web => conf
             .define("PLATFORM", "Web")
             .define("CMAKE_C_FLAGS", "-s ASYNCIFY"),
@@ -271,17 +274,28 @@ fn link(platform: Platform, platform_os: PlatformOS) {
             println!("cargo:rustc-link-lib=dylib=gdi32");
             println!("cargo:rustc-link-lib=dylib=user32");
             println!("cargo:rustc-link-lib=dylib=shell32");
+
+            // SDL linking
+            #[cfg(feature = "sdl")]
+            println!("cargo:rustc-link-lib=SDL2");
         }
         PlatformOS::Linux => {
+            // SDL linking
+            #[cfg(feature = "sdl")]
+            {
+                println!("cargo:rustc-link-search=/usr/local/lib");
+                println!("cargo:rustc-link-lib=SDL2");
+            }
+
             // X11 linking
-            #[cfg(not(feature = "wayland"))]
+            #[cfg(all(not(feature = "wayland"), not(feature = "sdl")))]
             {
                 println!("cargo:rustc-link-search=/usr/local/lib");
                 println!("cargo:rustc-link-lib=X11");
             }
 
             // Wayland linking
-            #[cfg(feature = "wayland")]
+            #[cfg(all(feature = "wayland", not(feature = "sdl")))]
             {
                 println!("cargo:rustc-link-search=/usr/local/lib");
                 println!("cargo:rustc-link-lib=wayland-client");
@@ -295,6 +309,10 @@ fn link(platform: Platform, platform_os: PlatformOS) {
             println!("cargo:rustc-link-lib=framework=IOKit");
             println!("cargo:rustc-link-lib=framework=CoreFoundation");
             println!("cargo:rustc-link-lib=framework=CoreVideo");
+
+            // SDL linking
+            #[cfg(feature = "sdl")]
+            println!("cargo:rustc-link-lib=SDL2");
         }
         _ => (),
     }
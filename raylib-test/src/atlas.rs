@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod atlas_test {
+    use raylib::prelude::*;
+
+    #[test]
+    fn test_atlas_pack() {
+        for algorithm in [PackAlgorithm::Skyline, PackAlgorithm::MaxRects].iter() {
+            let mut builder = TextureAtlasBuilder::new()
+                .algorithm(*algorithm)
+                .padding(2)
+                .extrude(1);
+            builder
+                .add("red", Image::gen_image_color(16, 16, Color::RED))
+                .add("green", Image::gen_image_color(8, 24, Color::GREEN))
+                .add("blue", Image::gen_image_color(30, 5, Color::BLUE));
+            let atlas = builder.build().expect("could not pack atlas");
+            let image = atlas.image().clone();
+            assert_eq!(atlas.frames().len(), 3);
+
+            for (name, color) in [
+                ("red", Color::RED),
+                ("green", Color::GREEN),
+                ("blue", Color::BLUE),
+            ]
+            .iter()
+            {
+                let r = atlas.get(name).unwrap();
+                let (x, y) = (r.x as i32, r.y as i32);
+                let (right, bottom) = (x + r.width as i32 - 1, y + r.height as i32 - 1);
+                assert_eq!(image.get_color(x, y), *color);
+                assert_eq!(image.get_color(right, bottom), *color);
+                // Extruded edges and corners
+                assert_eq!(image.get_color(x - 1, y - 1), *color);
+                assert_eq!(image.get_color(right + 1, y), *color);
+                assert_eq!(image.get_color(x, bottom + 1), *color);
+                // Padding
+                assert_eq!(image.get_color(x - 2, y), Color::BLANK);
+                assert_eq!(image.get_color(x, y - 2), Color::BLANK);
+            }
+        }
+
+        let mut builder = TextureAtlasBuilder::new();
+        builder
+            .add("a", Image::gen_image_color(4, 4, Color::RED))
+            .add("a", Image::gen_image_color(4, 4, Color::RED));
+        builder.build().expect_err("duplicate sprite names");
+
+        let mut builder = TextureAtlasBuilder::new().max_size(64);
+        builder.add("big", Image::gen_image_color(100, 4, Color::RED));
+        builder.build().expect_err("sprite larger than the atlas");
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_atlas_json_round_trip() {
+        let mut builder = TextureAtlasBuilder::new().padding(1);
+        builder
+            .add("hero/idle", Image::gen_image_color(16, 16, Color::RED))
+            .add("hero/walk", Image::gen_image_color(12, 20, Color::GREEN))
+            .add("coin", Image::gen_image_color(6, 6, Color::GOLD));
+        let atlas = builder.build().expect("could not pack atlas");
+
+        for (format, name) in [
+            (AtlasJsonFormat::Hash, "atlas_hash"),
+            (AtlasJsonFormat::Array, "atlas_array"),
+        ] {
+            let json = atlas.to_json(format, "atlas.png").unwrap();
+            let parsed = TextureAtlas::from_json(&json, atlas.image().clone()).unwrap();
+            assert_eq!(parsed.frames(), atlas.frames(), "{:?}", format);
+
+            let json_path = format!("test_out/{}.json", name);
+            atlas.export(&json_path, format).unwrap();
+            let loaded = TextureAtlas::load(&json_path).unwrap();
+            assert_eq!(loaded.frames(), atlas.frames(), "{:?}", format);
+            assert_eq!(
+                (loaded.image().width(), loaded.image().height()),
+                (atlas.image().width(), atlas.image().height())
+            );
+            let coin = loaded.get("coin").unwrap();
+            assert_eq!(
+                loaded.image().get_color(coin.x as i32, coin.y as i32),
+                Color::GOLD
+            );
+        }
+    }
+}
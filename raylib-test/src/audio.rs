@@ -18,6 +18,175 @@ mod audio_test {
         w.export_wave_as_code("test_out/wave.h");
     }*/
 
+    // Needs an audio device. Running the closures through the trampolines is unit tested in
+    // raylib/src/core/callbacks.rs.
+    #[test]
+    fn test_audio_processors() {
+        if let Ok(audio) = RaylibAudio::init_audio_device() {
+            let mut stream = audio.new_audio_stream(44100, 32, 2);
+            {
+                let processor = audio
+                    .attach_audio_stream_processor(&stream, |frames| {
+                        frames.iter_mut().for_each(|s| *s *= 0.5)
+                    })
+                    .unwrap();
+                let mixed = audio.attach_audio_mixed_processor(|_| {}).unwrap();
+                drop(processor);
+                drop(mixed);
+            }
+
+            // 32 bit stream can't be fed 16 bit samples
+            assert!(audio
+                .set_audio_stream_callback(&mut stream, |_: &mut [i16]| {})
+                .is_err());
+            let _callback = audio
+                .set_audio_stream_callback(&mut stream, |samples: &mut [f32]| samples.fill(0.0))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_spatial_mix() {
+        let listener = AudioListener::default();
+        let mut emitter = AudioEmitter::new(()).position(Vector3::new(10.0, 0.0, 0.0));
+        let mix = emitter.mix(&listener);
+        assert!((mix.volume - 0.1).abs() < 1e-5);
+        assert_eq!(mix.pan, 1.0);
+        assert_eq!(mix.raylib_pan(), 0.0);
+        assert_eq!(mix.pitch, 1.0);
+
+        emitter.attenuation = Attenuation::Linear;
+        emitter.max_distance = 10.0;
+        assert_eq!(emitter.mix(&listener).volume, 0.0);
+        emitter.attenuation = Attenuation::Exponential;
+        assert!((emitter.gain_at(2.0) - 0.5).abs() < 1e-5);
+        assert_eq!(emitter.gain_at(0.5), 1.0);
+
+        // Centered when on top of the listener, approaching raises the pitch.
+        emitter.teleport(Vector3::zero());
+        assert_eq!(emitter.mix(&listener).pan, 0.0);
+        let mut car = AudioEmitter::new(())
+            .position(Vector3::new(0.0, 0.0, -50.0))
+            .doppler(1.0);
+        car.move_to(Vector3::new(0.0, 0.0, -40.0), 0.5);
+        assert!(car.mix(&listener).pitch > 1.0);
+        car.move_to(Vector3::new(0.0, 0.0, -50.0), 0.5);
+        assert!(car.mix(&listener).pitch < 1.0);
+
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0, 0.0, 5.0),
+            Vector3::zero(),
+            Vector3::up(),
+            45.0,
+        );
+        let mut follower = AudioListener::from_camera(&camera);
+        assert_eq!(follower.right(), Vector3::new(1.0, 0.0, 0.0));
+        let moved = Camera3D::perspective(
+            Vector3::new(0.0, 0.0, 4.0),
+            Vector3::zero(),
+            Vector3::up(),
+            45.0,
+        );
+        follower.follow_camera(&moved, 0.5);
+        assert_eq!(follower.velocity, Vector3::new(0.0, 0.0, -2.0));
+    }
+
+    fn sine(freq: f32, amplitude: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let s = (i as f32 * freq * std::f32::consts::TAU / 48000.0).sin() * amplitude;
+                vec![s, s]
+            })
+            .collect()
+    }
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples.iter().fold(0.0, |p, s| p.max(s.abs()))
+    }
+
+    #[test]
+    fn test_audio_dsp() {
+        let mut low = Biquad::low_pass(500.0, 48000.0);
+        let mut high = sine(8000.0, 1.0, 4800);
+        low.process(&mut high);
+        assert!(peak(&high[4800..]) < 0.05);
+        low.reset();
+        let mut bass = sine(50.0, 1.0, 4800);
+        low.process(&mut bass);
+        assert!(peak(&bass[4800..]) > 0.9);
+
+        let mut hp = Biquad::high_pass(2000.0, 48000.0);
+        let mut bass = sine(50.0, 1.0, 4800);
+        hp.process(&mut bass);
+        assert!(peak(&bass[4800..]) < 0.05);
+
+        let mut compressor = Compressor::new(-12.0, 4.0, 48000.0);
+        let mut loud = sine(440.0, 1.0, 4800);
+        compressor.process(&mut loud);
+        assert!(compressor.gain_reduction_db() < -6.0);
+        assert!(peak(&loud[4800..]) < 0.6);
+        let mut quiet = sine(440.0, 0.1, 48000);
+        compressor.process(&mut quiet);
+        assert!((peak(&quiet[48000..]) - 0.1).abs() < 0.01);
+
+        let mut reverb = Reverb::new(0.8, 0.5, 1.0, 48000.0);
+        let mut impulse = vec![0.0; 9600];
+        impulse[0] = 1.0;
+        impulse[1] = 1.0;
+        reverb.process(&mut impulse);
+        assert!(peak(&impulse[4800..]) > 0.0);
+        reverb.reset();
+        let mut silence = vec![0.0; 9600];
+        reverb.process(&mut silence);
+        assert_eq!(peak(&silence), 0.0);
+    }
+
+    #[test]
+    fn test_audio_mixer() {
+        if let Ok(audio) = RaylibAudio::init_audio_device() {
+            let mut mixer = audio.new_mixer().sample_rate(44100);
+            assert_eq!(
+                mixer.buses().map(|b| b.name()).collect::<Vec<_>>(),
+                ["music", "sfx", "voice", "ui"]
+            );
+            mixer.add_bus("ambience");
+            mixer
+                .add_effect("music", "duck", AudioEffect::duck("voice", 0.5))
+                .unwrap();
+            mixer
+                .add_effect("music", "lp", AudioEffect::low_pass(1000.0))
+                .unwrap();
+            assert!(mixer
+                .add_effect("music", "duck", AudioEffect::duck("music", 0.5))
+                .is_err());
+            assert!(mixer
+                .add_effect("music", "duck", AudioEffect::duck("radio", 0.5))
+                .is_err());
+            mixer
+                .set_effect("music", "lp", AudioEffect::low_pass(2000.0))
+                .unwrap();
+            mixer.set_effect_enabled("music", "duck", false).unwrap();
+            let effects = mixer.effects("music").unwrap();
+            assert_eq!(effects.len(), 2);
+            assert_eq!(effects[1].1, AudioEffect::low_pass(2000.0));
+            assert!(!effects[0].2);
+            mixer.remove_effect("music", "lp").unwrap();
+            assert!(mixer.remove_effect("music", "lp").is_err());
+
+            let music = mixer.bus("music").unwrap();
+            music.set_volume(0.5);
+            music.set_muted(true);
+            assert_eq!(music.volume(), 0.5);
+            assert!(music.is_muted());
+            assert_eq!(music.level(), 0.0);
+
+            let stream = audio.new_audio_stream(44100, 32, 2);
+            let route = mixer.route(&stream, "ambience").unwrap();
+            assert_eq!(route.bus().name(), "ambience");
+            assert!(mixer.route(&stream, "radio").is_err());
+        }
+    }
+
     ray_test!(test_load_music);
     fn test_load_music(_thread: &RaylibThread) {
         // TODO uncomment when music is fixed
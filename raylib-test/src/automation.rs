@@ -24,13 +24,10 @@ pub(crate) mod automation_test {
         rl.set_automation_event_list(&mut aelist);
 
         let mut is_recording = false;
-        let mut is_event_playing = false;
-
-        let mut current_play_frame = 0;
-        let mut play_frame_counter = 0;
+        let mut replayer: Option<Replayer> = None;
         rl.set_target_fps(60);
 
-        let mut events = aelist.events();
+        let mut replay = Replay::from_list(&aelist);
 
         while !rl.window_should_close() {
             rl.update_camera(&mut camera, CameraMode::CAMERA_FIRST_PERSON);
@@ -43,16 +40,14 @@ pub(crate) mod automation_test {
                     rl.stop_automation_event_recording();
                     aelist.export("test_out/automation.rae");
                     println!("RECORDED FRAMES: {}", aelist.count());
-                    events = aelist.events();
+                    replay = Replay::from_list(&aelist);
                 }
                 is_recording = !is_recording;
             }
 
             if rl.is_key_released(KeyboardKey::KEY_BACKSPACE) {
-                if !is_event_playing && aelist.count() > 0 {
-                    is_event_playing = true;
-                    play_frame_counter = 0;
-                    current_play_frame = 0;
+                if replayer.is_none() && !replay.events.is_empty() {
+                    replayer = Some(Replayer::new(replay.clone()));
                     camera.position = camera_base.position;
                     camera.target = camera_base.target;
                     camera.up = camera_base.up;
@@ -60,22 +55,15 @@ pub(crate) mod automation_test {
                 }
             }
 
-            if is_event_playing {
-                while play_frame_counter == events[current_play_frame as usize].frame() {
-                    if let Some(ev) = events.get(current_play_frame as usize) {
-                        println!("PLAYING: PlayFrameCount: {} | currentPlayFrame: {} | Event Frame: {}, param: {}",play_frame_counter,current_play_frame,ev.frame(), ev.params()[0]);
-                        ev.play();
-                        current_play_frame += 1;
-
-                        if current_play_frame == aelist.count() as usize {
-                            is_event_playing = false;
-                            current_play_frame = 0;
-                            play_frame_counter = 0;
-                            break;
-                        }
-                    }
+            if let Some(r) = replayer.as_mut() {
+                let frame = r.frame();
+                for ev in r.step_events() {
+                    println!("PLAYING: frame: {} | event: {:?}", frame, ev.kind());
+                    ev.play();
+                }
+                if r.is_finished() {
+                    replayer = None;
                 }
-                play_frame_counter += 1;
             }
             let mut d = rl.begin_drawing(&thread);
 
@@ -120,3 +108,96 @@ pub(crate) mod automation_test {
         }
     }
 }
+
+#[cfg(test)]
+mod replay_test {
+    use raylib::prelude::*;
+
+    fn sample_replay() -> Replay {
+        Replay::new(vec![
+            ReplayEvent::new(3, AutomationEventKind::KeyUp(KeyboardKey::KEY_W)),
+            ReplayEvent::new(0, AutomationEventKind::KeyDown(KeyboardKey::KEY_W)),
+            ReplayEvent::new(0, AutomationEventKind::MousePosition { x: 10, y: 20 }),
+            ReplayEvent::new(
+                2,
+                AutomationEventKind::GamepadAxisMotion {
+                    gamepad: 0,
+                    axis: GamepadAxis::GAMEPAD_AXIS_LEFT_X,
+                    value: -0.5,
+                },
+            ),
+            ReplayEvent::new(
+                5,
+                AutomationEventKind::WindowResize {
+                    width: 800,
+                    height: 600,
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_event_kind_raw_roundtrip() {
+        for e in &sample_replay().events {
+            let kind = e.kind().expect("known event");
+            assert_eq!(kind.to_raw(), (e.event_type, e.params));
+        }
+        assert_eq!(AutomationEventKind::from_raw(1, [-5, 0, 0, 0]), None);
+        assert_eq!(AutomationEventKind::from_raw(17, [3, 0, 0, 0]), None);
+        assert_eq!(AutomationEventKind::from_raw(99, [0; 4]), None);
+    }
+
+    #[test]
+    fn test_replay_binary_roundtrip() {
+        let replay = sample_replay();
+        let mut buf = Vec::new();
+        replay.write_binary(&mut buf).unwrap();
+        assert_eq!(Replay::read_binary(&buf[..]).unwrap(), replay);
+        Replay::read_binary(&buf[..buf.len() - 1]).expect_err("truncated replay");
+        Replay::read_binary(&b"nope"[..]).expect_err("not a replay");
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_replay_json_roundtrip() {
+        let mut replay = sample_replay();
+        // Events this crate can't decode keep their raw type
+        replay.events.push(ReplayEvent {
+            frame: 7,
+            event_type: 99,
+            params: [1, 2, 3, 4],
+        });
+        let json = replay.to_json().unwrap();
+        assert!(json.contains("\"KeyDown\""), "{}", json);
+        assert!(json.contains("\"99\""), "{}", json);
+        assert_eq!(Replay::from_json(&json).unwrap(), replay);
+
+        Replay::from_json(r#"{"events":[{"frame":0,"type":"Bogus","params":[0,0,0,0]}]}"#)
+            .expect_err("unknown event type");
+    }
+
+    #[test]
+    fn test_replayer_steps_by_frame() {
+        let mut replayer = Replayer::new(sample_replay());
+        assert_eq!(replayer.step_events().len(), 2);
+        assert_eq!(replayer.step_events().len(), 0);
+        assert_eq!(
+            replayer.step_events()[0].kind(),
+            Some(AutomationEventKind::GamepadAxisMotion {
+                gamepad: 0,
+                axis: GamepadAxis::GAMEPAD_AXIS_LEFT_X,
+                value: -0.5,
+            })
+        );
+        assert_eq!(replayer.step_events().len(), 1);
+        assert!(!replayer.is_finished());
+        assert_eq!(replayer.step_events().len(), 0);
+        assert_eq!(replayer.step_events().len(), 1);
+        assert!(replayer.is_finished());
+        assert_eq!(replayer.frame(), replayer.replay().frame_count());
+
+        replayer.reset();
+        assert_eq!(replayer.frame(), 0);
+        assert_eq!(replayer.step_events().len(), 2);
+    }
+}
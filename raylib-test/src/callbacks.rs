@@ -101,7 +101,7 @@ pub mod callback_tests {
             let mut handle = TEST_HANDLE.write().unwrap();
             let rl = handle.as_mut().unwrap();
             {
-                rl.set_trace_log_callback(custom_callback).unwrap();
+                rl.set_trace_log_callback(custom_callback).leak();
                 for _ in 0..5 {
                     let noise = Image::gen_image_white_noise(10, 10, 1.0);
                     let _ = rl.load_texture_from_image(&thread, &noise).unwrap();
@@ -119,7 +119,7 @@ pub mod callback_tests {
         let rl = handle.as_mut().unwrap();
         {
             rl.set_save_file_data_callback(custom_save_file_data_callback)
-                .unwrap();
+                .leak();
         }
     }
 
@@ -132,7 +132,7 @@ pub mod callback_tests {
         let rl = handle.as_mut().unwrap();
         {
             rl.set_save_file_text_callback(custom_save_file_text_callback)
-                .unwrap();
+                .leak();
         }
     }
 
@@ -145,9 +145,34 @@ pub mod callback_tests {
         let rl = handle.as_mut().unwrap();
         {
             rl.set_load_file_data_callback(custom_read_file_data_callback)
-                .unwrap();
+                .leak();
         }
     }
 
     pub fn set_file_text_loader(_: &RaylibThread) {}
+
+    ray_test!(trace_log_guard);
+    fn trace_log_guard(_: &RaylibThread) {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+
+        let seen = Arc::new(AtomicUsize::new(0));
+        let counter = seen.clone();
+        let guard = rl.set_trace_log_callback(move |_, text| {
+            if text.contains("trace_log_guard") {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        rl.trace_log(TraceLogLevel::LOG_WARNING, "from trace_log_guard");
+
+        // The previous logger takes over again
+        drop(guard);
+        rl.trace_log(TraceLogLevel::LOG_WARNING, "from trace_log_guard");
+        assert_eq!(seen.load(Ordering::SeqCst), 1);
+    }
 }
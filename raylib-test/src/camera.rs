@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod camera_test {
+    use raylib::prelude::*;
+
+    fn close(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-3
+    }
+
+    #[test]
+    fn test_orbit_camera() {
+        let mut camera = Camera3D::perspective(
+            Vector3::new(0.0, 2.0, 6.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::up(),
+            45.0,
+        );
+        let start = camera.position;
+        let mut orbit = OrbitCamera::from_camera(&camera);
+        orbit.apply(&mut camera, &CameraInput::default(), 0.016);
+        assert!(close(camera.position, start), "{:?}", camera.position);
+
+        let input = CameraInput {
+            look: Vector2::new(100.0, 0.0),
+            zoom: 100.0,
+            ..Default::default()
+        };
+        orbit.apply(&mut camera, &input, 0.016);
+        assert_eq!(orbit.distance, orbit.min_distance);
+        assert!((camera.position - camera.target).length() - orbit.min_distance < 1e-3);
+        assert!(camera.position.x != 0.0);
+    }
+
+    #[test]
+    fn test_fly_camera() {
+        let mut camera = Camera3D::perspective(
+            Vector3::zero(),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::up(),
+            45.0,
+        );
+        let mut fly = FlyCamera::from_camera(&camera);
+        fly.speed = 2.0;
+        let forward = CameraInput {
+            movement: Vector3::new(0.0, 0.0, 1.0),
+            ..Default::default()
+        };
+        fly.apply(&mut camera, &forward, 1.0);
+        assert!(close(camera.position, Vector3::new(0.0, 0.0, -2.0)));
+
+        let strafe = CameraInput {
+            movement: Vector3::new(1.0, 0.0, 0.0),
+            boost: true,
+            ..Default::default()
+        };
+        fly.apply(&mut camera, &strafe, 0.5);
+        let moved = 2.0 * fly.boost_multiplier * 0.5;
+        assert!(close(camera.position, Vector3::new(moved, 0.0, -2.0)));
+    }
+
+    #[test]
+    fn test_third_person_collision() {
+        let mut camera =
+            Camera3D::perspective(Vector3::zero(), Vector3::zero(), Vector3::up(), 45.0);
+        let mut follow = ThirdPersonCamera::new();
+        follow.pitch = 0.0;
+        let wall = BoundingBox::new(Vector3::new(-5.0, -5.0, 2.0), Vector3::new(5.0, 5.0, 3.0));
+        let idle = CameraInput::default();
+
+        follow.apply(&mut camera, &idle, 0.016, Vector3::zero(), &[wall]);
+        let expected = 2.0 - follow.collision_radius;
+        assert!((follow.current_distance() - expected).abs() < 1e-3);
+        assert!(camera.position.z < 2.0);
+
+        for _ in 0..200 {
+            follow.apply(&mut camera, &idle, 0.016, Vector3::zero(), &());
+        }
+        assert!((follow.current_distance() - follow.distance).abs() < 1e-2);
+        assert!(close(camera.target, Vector3::new(0.0, follow.height, 0.0)));
+    }
+
+    #[test]
+    fn test_follow_camera_2d() {
+        let screen = Vector2::new(800.0, 600.0);
+        let mut camera = Camera2D {
+            zoom: 1.0,
+            ..Default::default()
+        };
+        let mut follow = FollowCamera2D::new();
+        follow.smoothing = 0.0;
+        follow.deadzone = Vector2::new(50.0, 50.0);
+        follow.apply(&mut camera, Vector2::new(1000.0, 1000.0), 0.016, screen);
+        assert_eq!(camera.target, Vector2::new(1000.0, 1000.0));
+        assert_eq!(camera.offset, Vector2::new(400.0, 300.0));
+
+        // Inside the deadzone nothing moves, outside the point stays on its edge.
+        follow.apply(&mut camera, Vector2::new(1030.0, 1000.0), 0.016, screen);
+        assert_eq!(camera.target, Vector2::new(1000.0, 1000.0));
+        follow.apply(&mut camera, Vector2::new(1100.0, 1000.0), 0.016, screen);
+        assert_eq!(camera.target, Vector2::new(1050.0, 1000.0));
+
+        follow.bounds = Some(Rectangle::new(0.0, 0.0, 1200.0, 400.0));
+        follow.apply(&mut camera, Vector2::new(1150.0, 0.0), 0.016, screen);
+        assert_eq!(camera.target, Vector2::new(800.0, 200.0));
+
+        follow.shake(1.0);
+        follow.apply(&mut camera, Vector2::new(1150.0, 0.0), 0.1, screen);
+        assert!(camera.target != Vector2::new(800.0, 200.0));
+        follow.apply(&mut camera, Vector2::new(1150.0, 0.0), 1.0, screen);
+        assert_eq!(follow.trauma(), 0.0);
+        assert_eq!(camera.target, Vector2::new(800.0, 200.0));
+        assert_eq!(camera.rotation, 0.0);
+    }
+}
@@ -24,4 +24,39 @@ mod data_test {
         let fin = std::str::from_utf8(&decoded).unwrap();
         assert!(fin == "This is a test")
     }
+
+    ray_test!(compression);
+    fn compression(_: &RaylibThread) {
+        let input = "The quick brown fox jumped over the lazy dog. ".repeat(64);
+        let compressed = compress_data(input.as_bytes()).unwrap();
+        assert!(compressed.len() < input.len());
+
+        let decompressed = decompress_data(&compressed).unwrap();
+        assert_eq!(decompressed, input.as_bytes());
+
+        assert!(decompress_data(&compressed[..compressed.len() / 2]).is_err());
+        assert!(decompress_data(&[7, 7, 7]).is_err());
+    }
+
+    ray_test!(compression_stream);
+    fn compression_stream(_: &RaylibThread) {
+        use std::io::{Read, Write};
+
+        let input = "The quick brown fox jumped over the lazy dog. ".repeat(64);
+        let mut encoder = DeflateEncoder::new(Vec::new());
+        for chunk in input.as_bytes().chunks(100) {
+            encoder.write_all(chunk).unwrap();
+        }
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(decompress_data(&compressed).unwrap(), input.as_bytes());
+
+        // Output of raylib's CompressData streams back through the decoder
+        let raylib_compressed = compress_data(input.as_bytes()).unwrap();
+        let mut out = String::new();
+        DeflateDecoder::new(&raylib_compressed[..])
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, input);
+    }
 }
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod draw_test {
+    use crate::snapshot::{assert_pixel, render_image};
     use crate::tests::*;
     use raylib::prelude::*;
     ray_draw_test!(test_pixel);
@@ -221,4 +222,62 @@ mod draw_test {
 
         d.draw_mesh(mesh, material, Matrix::translate(0.0, 0.0, 0.0));
     }
+
+    ray_test!(test_shape_pixels);
+    fn test_shape_pixels(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let image = render_image(rl, thread, 512, 384, |rl, thread, target| {
+            let mut d = rl.begin_texture_mode(thread, target);
+            d.draw_pixel(5, 5, Color::BLACK);
+            d.draw_line_ex(
+                Vector2::new(10.0, 40.0),
+                Vector2::new(200.0, 60.0),
+                6.0,
+                Color::GREEN,
+            );
+            d.draw_circle(60, 120, 40.0, Color::BLUE);
+            d.draw_circle_lines(160, 120, 40.0, Color::MAROON);
+            d.draw_rectangle(20, 200, 80, 50, Color::PURPLE);
+            d.draw_rectangle_lines(120, 200, 80, 50, Color::DARKGREEN);
+            d.draw_rectangle_rounded(
+                Rectangle::new(220.0, 200.0, 80.0, 50.0),
+                0.5,
+                8,
+                Color::SKYBLUE,
+            );
+            d.draw_triangle(
+                Vector2::new(360.0, 200.0),
+                Vector2::new(330.0, 250.0),
+                Vector2::new(390.0, 250.0),
+                Color::GOLD,
+            );
+            d.draw_poly(Vector2::new(460.0, 225.0), 6, 30.0, 0.0, Color::BROWN);
+            d.draw_rectangle_gradient_h(20, 300, 200, 40, Color::RED, Color::BLUE);
+        });
+
+        let tolerance = crate::snapshot::DEFAULT_TOLERANCE;
+        assert_pixel(&image, 5, 5, Color::BLACK, tolerance);
+        assert_pixel(&image, 105, 50, Color::GREEN, tolerance);
+        assert_pixel(&image, 60, 120, Color::BLUE, tolerance);
+        // Outlines leave the inside untouched
+        assert_pixel(&image, 160, 120, Color::WHITE, tolerance);
+        assert_pixel(&image, 60, 225, Color::PURPLE, tolerance);
+        assert_pixel(&image, 160, 225, Color::WHITE, tolerance);
+        assert_pixel(&image, 260, 225, Color::SKYBLUE, tolerance);
+        // Rounded corners are cut off
+        assert_pixel(&image, 220, 200, Color::WHITE, tolerance);
+        assert_pixel(&image, 360, 235, Color::GOLD, tolerance);
+        assert_pixel(&image, 460, 225, Color::BROWN, tolerance);
+        let left = image.get_color(25, 320);
+        let right = image.get_color(215, 320);
+        assert!(
+            left.r > left.b && right.b > right.r,
+            "{:?} {:?}",
+            left,
+            right
+        );
+        // Nothing is drawn outside the shapes
+        assert_pixel(&image, 500, 370, Color::WHITE, 0);
+    }
 }
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod image_test {
+    use crate::snapshot::assert_pixel;
     use crate::tests::*;
     use raylib::prelude::*;
     fn default_image() -> Image {
@@ -111,4 +112,56 @@ mod image_test {
             &mut |_img| {},
         );
     }
+
+    // Image drawing is done on the CPU, so the pixels must match exactly
+    ray_test!(test_image_draw_pixels);
+    fn test_image_draw_pixels(_: &RaylibThread) {
+        let mut img = Image::gen_image_color(128, 128, Color::WHITE);
+        img.draw_pixel(2, 2, Color::BLACK);
+        img.draw_line(4, 10, 120, 30, Color::RED);
+        img.draw_circle(32, 64, 20, Color::BLUE);
+        img.draw_circle_lines(90, 64, 20, Color::GREEN);
+        img.draw_rectangle(8, 96, 40, 24, Color::PURPLE);
+        img.draw_rectangle_lines(Rectangle::new(60.0, 96.0, 40.0, 24.0), 2, Color::ORANGE);
+        img.draw_text("raylib", 4, 40, 10, Color::BLACK);
+
+        assert_pixel(&img, 2, 2, Color::BLACK, 0);
+        assert_pixel(&img, 4, 10, Color::RED, 0);
+        assert_pixel(&img, 32, 64, Color::BLUE, 0);
+        assert_pixel(&img, 90, 44, Color::GREEN, 0);
+        assert_pixel(&img, 90, 64, Color::WHITE, 0);
+        assert_pixel(&img, 28, 108, Color::PURPLE, 0);
+        assert_pixel(&img, 61, 97, Color::ORANGE, 0);
+        assert_pixel(&img, 80, 108, Color::WHITE, 0);
+        let mut text = (40..50).flat_map(|y| (4..40).map(move |x| (x, y)));
+        assert!(text.any(|(x, y)| img.get_color(x, y) == Color::BLACK));
+        assert_pixel(&img, 127, 127, Color::WHITE, 0);
+    }
+
+    #[test]
+    fn test_pixel_views() {
+        let mut img = Image::gen_image_color(64, 32, Color::BLACK);
+        for (y, row) in img.rows_mut::<Rgba8>().unwrap().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = Color::new(x as u8 * 4, y as u8 * 8, 0, 255);
+            }
+        }
+        assert_eq!(img.get_color(10, 5), Color::new(40, 40, 0, 255));
+        img.set_pixel(63, 31, Color::BLUE).unwrap();
+        assert_eq!(img.get_pixel::<Rgba8>(63, 31).unwrap(), Color::BLUE);
+        assert!(img.get_pixel::<Rgba8>(64, 0).is_err());
+        assert!(img.pixels::<Gray8>().is_err());
+
+        img.set_format(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R5G6B5);
+        let p: Rgb565 = img.get_pixel(63, 31).unwrap();
+        assert_eq!((p.r(), p.g(), p.b()), (0, 0, 255));
+
+        img.set_format(PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAYSCALE);
+        assert_eq!(img.rows::<Gray8>().unwrap().count(), 32);
+
+        let floats = vec![Rgba32f::from(Color::RED); 16];
+        let img = Image::from_pixels(4, 4, &floats).unwrap();
+        assert_eq!(img.get_color(3, 3), Color::RED);
+        assert!(Image::from_pixels(5, 4, &floats).is_err());
+    }
 }
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod input_test {
+    use crate::tests::*;
+    use raylib::prelude::*;
+
+    #[test]
+    fn test_input_source_names() {
+        let sources = [
+            InputSource::Key(KeyboardKey::KEY_W),
+            InputSource::Key(KeyboardKey::KEY_KP_ADD),
+            InputSource::Modifier(Modifier::Control),
+            InputSource::Mouse(MouseButton::MOUSE_BUTTON_LEFT),
+            InputSource::MouseWheel(AxisDirection::Negative),
+            InputSource::GamepadButton(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN),
+            InputSource::GamepadAxis(GamepadAxis::GAMEPAD_AXIS_LEFT_X, AxisDirection::Positive),
+        ];
+        for source in sources.iter() {
+            let parsed: InputSource = source.to_string().parse().unwrap();
+            assert_eq!(&parsed, source);
+        }
+        assert_eq!(
+            "GAMEPAD_AXIS_LEFT_Y-".parse::<InputSource>().unwrap(),
+            InputSource::GamepadAxis(GamepadAxis::GAMEPAD_AXIS_LEFT_Y, AxisDirection::Negative)
+        );
+        assert!("KEY_NOPE".parse::<InputSource>().is_err());
+
+        let save = Binding::chord(vec![
+            InputSource::Modifier(Modifier::Control),
+            InputSource::Key(KeyboardKey::KEY_S),
+        ]);
+        assert_eq!(save.to_string(), "CONTROL + KEY_S");
+    }
+
+    #[test]
+    fn test_input_bindings() {
+        let mut map = InputMap::new();
+        map.bind_action("jump", KeyboardKey::KEY_SPACE)
+            .bind_action("jump", GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN)
+            .bind_axis(
+                "move_x",
+                AxisBinding::buttons(KeyboardKey::KEY_A, KeyboardKey::KEY_D),
+            );
+        assert_eq!(map.action_bindings("jump").len(), 2);
+        assert!(map.action_bindings("missing").is_empty());
+
+        let bindings = map.bindings().clone();
+        let mut restored = InputMap::new();
+        restored.set_bindings(bindings);
+        assert_eq!(restored.bindings(), map.bindings());
+        restored.unbind_action("jump");
+        assert!(restored.action_bindings("jump").is_empty());
+        assert_eq!(restored.axis_bindings("move_x").len(), 1);
+    }
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_input_bindings_json_roundtrip() {
+        let mut map = InputMap::new();
+        map.bind_action(
+            "save",
+            Binding::chord(vec![
+                InputSource::Modifier(Modifier::Control),
+                InputSource::Key(KeyboardKey::KEY_S),
+            ]),
+        )
+        .bind_action("jump", GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN)
+        .bind_axis(
+            "look_x",
+            AxisBinding::analog(GamepadAxis::GAMEPAD_AXIS_RIGHT_X),
+        )
+        .bind_axis(
+            "move_x",
+            AxisBinding::buttons(KeyboardKey::KEY_A, KeyboardKey::KEY_D),
+        );
+
+        let json = map.bindings().to_json().unwrap();
+        assert!(json.contains("KEY_S"), "{}", json);
+        assert_eq!(&InputBindings::from_json(&json).unwrap(), map.bindings());
+
+        let empty = InputBindings::from_json("{}").unwrap();
+        assert!(empty.actions.is_empty() && empty.axes.is_empty());
+        InputBindings::from_json(r#"{"actions":{"jump":[["KEY_NOPE"]]}}"#)
+            .expect_err("unknown key");
+    }
+
+    ray_test!(test_input_map_update);
+    fn test_input_map_update(_: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let mut map = InputMap::new();
+        map.bind_action("jump", KeyboardKey::KEY_SPACE).bind_axis(
+            "move_x",
+            AxisBinding::buttons(KeyboardKey::KEY_A, KeyboardKey::KEY_D),
+        );
+        map.update(rl);
+        assert!(!map.is_action_pressed("jump"));
+        assert!(!map.is_action_down("jump"));
+        assert!(!map.is_action_released("jump"));
+        assert_eq!(map.get_axis("move_x"), 0.0);
+        assert_eq!(map.get_axis_pair("move_x", "move_y"), Vector2::zero());
+
+        map.capture_action("jump", 0);
+        assert!(map.is_capturing());
+        map.update(rl);
+        assert!(map.is_capturing());
+        map.cancel_capture();
+        assert!(!map.is_capturing());
+        assert_eq!(map.action_bindings("jump").len(), 1);
+    }
+}
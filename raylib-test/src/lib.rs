@@ -32,6 +32,9 @@ extern crate test;
 #[macro_use]
 pub mod tests;
 
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
+mod atlas;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
 mod audio;
@@ -41,6 +44,9 @@ mod audio;
 mod callbacks;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
+mod camera;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
 mod data;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
@@ -48,25 +54,51 @@ mod drawing;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
 mod image;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
+mod input;
 #[cfg(feature = "custom_frame_control")]
 mod manual;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
+mod lighting;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
 mod misc;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
 mod models;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
+mod postprocess;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
 mod random;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
+mod rlgl;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
+mod scene;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
+mod shaders;
+#[cfg(test)]
+pub mod snapshot;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
+mod sprite;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
 mod text;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
 mod texture;
 #[cfg(not(feature = "custom_frame_control"))]
 #[cfg(not(feature = "automation_event_test"))]
+mod vfs;
+#[cfg(not(feature = "custom_frame_control"))]
+#[cfg(not(feature = "automation_event_test"))]
 mod window;
 
 #[cfg(not(feature = "custom_frame_control"))]
@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod lighting_test {
+    use crate::snapshot::render_image;
+    use crate::tests::*;
+    use raylib::prelude::*;
+
+    fn lit_model(
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        mesh: Mesh,
+        shader: &Shader,
+    ) -> Model {
+        let mesh = unsafe { mesh.make_weak() };
+        let mut model = rl.load_model_from_mesh(thread, mesh).unwrap();
+        for material in model.materials_mut() {
+            material.shader = **shader;
+        }
+        model
+    }
+
+    ray_test!(test_lighting);
+    fn test_lighting(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+
+        let shader = rl
+            .load_lit_shader(thread)
+            .expect("could not compile lit shader");
+        let block = UniformBlock::<LightUniforms>::new(&shader);
+        assert!(block.missing().is_empty(), "{:?}", block.missing());
+        let mut cube = lit_model(
+            rl,
+            thread,
+            Mesh::gen_mesh_cube(thread, 1.0, 1.0, 1.0),
+            &shader,
+        );
+
+        assert!(rl.load_shadow_map(thread, 256, 0).is_err());
+        assert!(rl.load_shadow_map(thread, 256, MAX_CASCADES + 1).is_err());
+        let shadows = rl.load_shadow_map(thread, 256, 3).unwrap().distance(30.0);
+        let mut lighting = Lighting::new().shadows(shadows);
+        lighting
+            .lights
+            .push(Light::point(Vector3::new(0.0, 2.0, 0.0), Color::RED));
+        lighting.lights.push(Light::directional(
+            Vector3::new(-1.0, -2.0, -1.0),
+            Color::WHITE,
+        ));
+        assert_eq!(lighting.sun(), Some(1));
+
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0, 4.0, 8.0),
+            Vector3::zero(),
+            Vector3::up(),
+            45.0,
+        );
+        // No shadows until the shadow map has been rendered.
+        assert_eq!(lighting.uniforms(&camera).shadow_cascades, 0);
+
+        let mut drawn = Vec::new();
+        lighting.render_shadows(rl, thread, &camera, 4.0 / 3.0, |d, cascade| {
+            d.draw_model(&cube, Vector3::zero(), 1.0, Color::WHITE);
+            drawn.push(cascade);
+        });
+        assert_eq!(drawn, [0, 1, 2]);
+
+        let splits = lighting.shadow_map().unwrap().splits();
+        assert!(splits.windows(2).all(|s| s[0] < s[1]));
+        assert!((splits[2] - 30.0).abs() < 1e-3);
+
+        let uniforms = lighting.uniforms(&camera);
+        assert_eq!(uniforms.light_count, 2);
+        assert_eq!(uniforms.light_type, [1, 0]);
+        assert_eq!(uniforms.shadow_cascades, 3);
+        assert_eq!(uniforms.light_params[1].w, 1.0);
+        assert_eq!(uniforms.light_params[0].w, 0.0);
+        lighting.upload_to_model(&mut cube, &camera);
+    }
+
+    ray_3d_draw_test!(test_draw_lighting);
+    fn test_draw_lighting(
+        d: &mut RaylibMode3D<RaylibDrawHandle>,
+        thread: &RaylibThread,
+        _: &TestAssets,
+    ) {
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0, 4.0, 8.0),
+            Vector3::zero(),
+            Vector3::up(),
+            45.0,
+        );
+        let shader = d.load_lit_shader(thread).unwrap();
+        let mut sphere = lit_model(
+            d,
+            thread,
+            Mesh::gen_mesh_sphere(thread, 1.0, 32, 32),
+            &shader,
+        );
+        let mut lighting = Lighting::new().ambient(Color::new(20, 20, 30, 255));
+        lighting.lights.push(Light::directional(
+            Vector3::new(1.0, -1.0, -1.0),
+            Color::WHITE,
+        ));
+        lighting
+            .lights
+            .push(Light::point(Vector3::new(-2.0, 1.0, 1.0), Color::RED).intensity(4.0));
+        lighting.lights.push(
+            Light::spot(
+                Vector3::new(0.0, 3.0, 0.0),
+                Vector3::new(0.0, -1.0, 0.0),
+                0.3,
+                0.5,
+                Color::BLUE,
+            )
+            .intensity(8.0)
+            .range(6.0),
+        );
+        lighting.upload_to_model(&mut sphere, &camera);
+        d.draw_model(&sphere, Vector3::zero(), 1.0, Color::WHITE);
+    }
+
+    // A cube hovering between the sun and a plane has to cast its shadow onto the plane.
+    ray_test!(test_shadow_pixels);
+    fn test_shadow_pixels(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0, 6.0, 8.0),
+            Vector3::zero(),
+            Vector3::up(),
+            45.0,
+        );
+        let shader = rl.load_lit_shader(thread).unwrap();
+        let mut plane = lit_model(
+            rl,
+            thread,
+            Mesh::gen_mesh_plane(thread, 10.0, 10.0, 1, 1),
+            &shader,
+        );
+        let mut cube = lit_model(
+            rl,
+            thread,
+            Mesh::gen_mesh_cube(thread, 1.0, 1.0, 1.0),
+            &shader,
+        );
+        let shadows = rl.load_shadow_map(thread, 1024, 1).unwrap().distance(20.0);
+        let mut lighting = Lighting::new()
+            .ambient(Color::new(40, 40, 40, 255))
+            .shadows(shadows);
+        let sun = Vector3::new(0.3, -1.0, 0.2);
+        lighting.lights.push(Light::directional(sun, Color::WHITE));
+
+        let caster = Vector3::new(0.0, 2.0, 0.0);
+        let aspect = TEST_WIDTH as f32 / TEST_HEIGHT as f32;
+        lighting.render_shadows(rl, thread, &camera, aspect, |d, _| {
+            d.draw_model(&cube, caster, 1.0, Color::WHITE);
+            d.draw_model(&plane, Vector3::zero(), 1.0, Color::WHITE);
+        });
+        lighting.upload_to_model(&mut plane, &camera);
+        lighting.upload_to_model(&mut cube, &camera);
+
+        let (width, height) = (TEST_WIDTH as u32, TEST_HEIGHT as u32);
+        let image = render_image(rl, thread, width, height, |rl, thread, target| {
+            let mut d = rl.begin_texture_mode(thread, target);
+            let mut d3 = d.begin_mode3D(camera);
+            d3.draw_model(&plane, Vector3::zero(), 1.0, Color::WHITE);
+            d3.draw_model(&cube, caster, 1.0, Color::ORANGE);
+        });
+
+        // The sun hits the plane under the cube where the ray through its center lands, and the
+        // camera sees that spot in front of the cube.
+        let shadowed = caster + sun * (caster.y / -sun.y);
+        let lit = Vector3::new(-3.0, 0.0, 2.0);
+        let brightness = |p: Vector3| {
+            let screen = rl.get_world_to_screen_ex(p, camera, TEST_WIDTH, TEST_HEIGHT);
+            let c = image.get_color(screen.x as i32, screen.y as i32);
+            c.r as u32 + c.g as u32 + c.b as u32
+        };
+        let (shadowed, lit) = (brightness(shadowed), brightness(lit));
+        assert!(
+            shadowed * 2 < lit,
+            "shadowed {} should be much darker than lit {}",
+            shadowed,
+            lit
+        );
+    }
+}
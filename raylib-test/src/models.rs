@@ -46,4 +46,188 @@ mod model_test {
 
         world.draw_model(&model, zero, 1.0, Color::RED);
     }
+
+    ray_test!(test_mesh_builder);
+    fn test_mesh_builder(thread: &RaylibThread) {
+        let quad = || {
+            MeshBuilder::new(vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(1.0, 1.0, 0.0),
+                Vector3::new(0.0, 1.0, 0.0),
+            ])
+            .texcoords(vec![
+                Vector2::new(0.0, 1.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(1.0, 0.0),
+                Vector2::new(0.0, 0.0),
+            ])
+        };
+
+        assert!(quad().indices(vec![0, 1, 2, 0, 2]).build(thread).is_err());
+        assert!(quad().indices(vec![0, 1, 4]).build(thread).is_err());
+        assert!(quad().colors(vec![Color::RED]).build(thread).is_err());
+        assert!(quad().build(thread).is_err());
+        assert!(MeshBuilder::new(vec![Vector3::zero(); 3])
+            .compute_tangents()
+            .build(thread)
+            .is_err());
+
+        let mut mesh = quad()
+            .indices(vec![0, 1, 2, 0, 2, 3])
+            .compute_normals()
+            .compute_tangents()
+            .upload(thread, true)
+            .unwrap();
+        assert_eq!(mesh.as_ref().vertexCount, 4);
+        assert_eq!(mesh.as_ref().triangleCount, 2);
+        assert!(mesh
+            .normals()
+            .iter()
+            .all(|n| (*n - Vector3::new(0.0, 0.0, 1.0)).length() < 1e-5));
+        let bounds = mesh.get_mesh_bounding_box();
+        assert_eq!(bounds.max, Vector3::new(1.0, 1.0, 0.0));
+
+        mesh.update_buffer(
+            thread,
+            MeshBuffer::Positions,
+            &[Vector3::new(0.0, 2.0, 0.0)],
+            3,
+        )
+        .unwrap();
+        mesh.update_buffer(thread, MeshBuffer::Indices, &[0u16, 2, 3], 3)
+            .unwrap();
+        assert!(mesh
+            .update_buffer(thread, MeshBuffer::Positions, &[Vector2::zero()], 0)
+            .is_err());
+        assert!(mesh
+            .update_buffer(thread, MeshBuffer::Colors, &[Color::RED], 0)
+            .is_err());
+        assert!(mesh
+            .update_buffer(thread, MeshBuffer::Indices, &[0u16; 4], 3)
+            .is_err());
+        assert!(mesh
+            .update_buffer(thread, MeshBuffer::Indices, &[0u16], usize::MAX)
+            .is_err());
+
+        // Too many vertices for 16 bit indices, so the mesh is unindexed.
+        let positions: Vec<Vector3> = (0..70000)
+            .map(|i| Vector3::new(i as f32, (i % 2) as f32, 0.0))
+            .collect();
+        let indices: Vec<u32> = (0..69998).flat_map(|i| vec![i, i + 1, i + 2]).collect();
+        let strip = MeshBuilder::new(positions)
+            .indices(indices)
+            .build(thread)
+            .unwrap();
+        assert_eq!(strip.as_ref().vertexCount, 69998 * 3);
+        assert_eq!(strip.as_ref().triangleCount, 69998);
+        assert!(strip.as_ref().indices.is_null());
+    }
+
+    ray_test!(test_animator);
+    fn test_animator(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+
+        let mut model = rl.load_model(thread, "resources/guy/guy.iqm").unwrap();
+        let anims = rl
+            .load_model_animations(thread, "resources/guy/guyanim.iqm")
+            .unwrap();
+        let mut animator = Animator::new(&model).unwrap();
+        assert_eq!(animator.bone_count(), model.as_ref().boneCount as usize);
+
+        let clip =
+            AnimationClip::from_animation("run", &anims[0], PlayMode::Once).event(0.1, "step");
+        let duration = clip.duration();
+        animator.add_clip(clip).unwrap();
+        animator
+            .add_layer("upper", LayerBlend::Override)
+            .set_weight(0.5);
+        assert!(animator.play("run", 0.0));
+        assert!(!animator.play_on("upper", "walk", 0.2));
+
+        let events = animator.update(0.2).to_vec();
+        assert_eq!(events.len(), 1);
+        let events = animator.update(duration).to_vec();
+        assert!(matches!(
+            events.as_slice(),
+            [AnimationEvent::Finished { .. }]
+        ));
+        // The matrix moves points where the transform says they go
+        let point = Vector3::new(0.0, 1.0, 0.0);
+        for bone in animator.bone_names().to_vec() {
+            let t = animator.bone_transform(&bone).unwrap();
+            let expected = (point * t.scale).rotate_by(t.rotation) + t.translation;
+            let moved = point.transform_with(animator.bone_matrix(&bone).unwrap());
+            assert!((moved - expected).length() < 1e-3, "{}", bone);
+        }
+        animator.apply(thread, &mut model).unwrap();
+    }
+
+    #[cfg(feature = "with_serde")]
+    ray_test!(test_gltf_scene);
+    #[cfg(feature = "with_serde")]
+    fn test_gltf_scene(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+
+        std::fs::write(
+            "test_out/scene.gltf",
+            r#"{
+                "asset": { "version": "2.0" },
+                "scene": 0,
+                "scenes": [{ "nodes": [0] }],
+                "nodes": [
+                    { "name": "Rig", "children": [1, 2, 3], "translation": [1, 0, 0] },
+                    { "name": "Camera", "camera": 0, "translation": [0, 0, 5] },
+                    {
+                        "name": "Sun",
+                        "rotation": [-0.7071068, 0, 0, 0.7071068],
+                        "extensions": { "KHR_lights_punctual": { "light": 0 } }
+                    },
+                    { "name": "Body", "mesh": 0, "translation": [0, 0, -1] }
+                ],
+                "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }],
+                "accessors": [{
+                    "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                    "min": [0, 0, 0], "max": [1, 1, 0]
+                }],
+                "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+                "buffers": [{
+                    "byteLength": 36,
+                    "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAA"
+                }],
+                "cameras": [{ "type": "perspective", "perspective": { "yfov": 0.7853982, "znear": 0.1 } }],
+                "extensions": {
+                    "KHR_lights_punctual": { "lights": [{ "type": "directional", "intensity": 3 }] }
+                }
+            }"#,
+        )
+        .unwrap();
+        let mut scene = rl.load_gltf_scene(thread, "test_out/scene.gltf").unwrap();
+        assert_eq!(scene.roots, [0]);
+        let rig = scene.find("Rig").unwrap();
+        assert_eq!(scene.nodes[rig].children, [1, 2, 3]);
+        assert_eq!(scene.nodes[1].parent, Some(rig));
+        let body = scene.find("Body").unwrap();
+        assert_eq!(scene.model.meshes().len(), 1);
+        assert_eq!(scene.nodes[body].meshes, 0..1);
+
+        let (_, camera) = scene.cameras().next().unwrap();
+        assert!((camera.position - Vector3::new(1.0, 0.0, 5.0)).length() < 1e-5);
+        assert!((camera.fovy - 45.0).abs() < 1e-3);
+        let (_, sun) = scene.lights().next().unwrap();
+        assert_eq!(sun.kind, LightKind::Directional);
+        assert_eq!(sun.intensity, 3.0);
+        assert!((sun.direction - Vector3::new(0.0, -1.0, 0.0)).length() < 1e-5);
+
+        scene.nodes[rig].transform.translation = Vector3::new(0.0, 2.0, 0.0);
+        let camera = scene.camera(1).unwrap();
+        assert!((camera.position - Vector3::new(0.0, 2.0, 5.0)).length() < 1e-5);
+        // raylib baked the load time world transform into the vertex at (1, 0, 0)
+        let baked = Vector3::new(2.0, 0.0, -1.0);
+        let moved = baked.transform_with(scene.mesh_matrix(body));
+        assert!((moved - Vector3::new(1.0, 2.0, -1.0)).length() < 1e-5);
+        assert!(rl.load_gltf_scene(thread, "test_out/missing.gltf").is_err());
+    }
 }
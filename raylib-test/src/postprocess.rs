@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod postprocess_test {
+    use crate::tests::*;
+    use raylib::prelude::*;
+
+    const INVERT_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+uniform sampler2D texture0;
+out vec4 finalColor;
+void main() {
+    vec4 c = texture(texture0, fragTexCoord);
+    finalColor = vec4(1.0 - c.rgb, 1.0);
+}
+"#;
+
+    const PICK_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+uniform sampler2D texture0;
+uniform sampler2D picked;
+out vec4 finalColor;
+void main() {
+    finalColor = texture(picked, fragTexCoord);
+}
+"#;
+
+    ray_test!(test_post_process_chain);
+    fn test_post_process_chain(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let mut load = |fs| {
+            rl.load_shader_from_memory(thread, None, Some(fs))
+                .expect("could not compile shader")
+        };
+        let (invert, again, pick, broken) = (
+            load(INVERT_FS),
+            load(INVERT_FS),
+            load(PICK_FS),
+            load(INVERT_FS),
+        );
+
+        let mut chain =
+            PostProcessChain::with_size(rl, thread, 64, 64).expect("could not create chain");
+        chain
+            .add_pass(PostPass::new("invert", invert))
+            .unwrap()
+            .add_pass(PostPass::new("again", again))
+            .unwrap()
+            .add_pass(PostPass::new("pick", pick).input("picked", "invert"))
+            .unwrap();
+        chain
+            .add_pass(PostPass::new("broken", broken).input("picked", "later"))
+            .expect_err("read a pass that doesn't exist");
+
+        {
+            let mut d = rl.begin_texture_mode(thread, chain.scene_mut());
+            d.clear_background(Color::BLUE);
+            d.draw_rectangle(0, 0, 64, 16, Color::RED);
+        }
+        chain.apply(rl, thread).expect("could not apply chain");
+        let mut out = rl.load_render_texture(thread, 64, 64).unwrap();
+        {
+            let mut d = rl.begin_texture_mode(thread, &mut out);
+            d.clear_background(Color::BLACK);
+            chain.draw(&mut d);
+            chain
+                .apply(&mut d, thread)
+                .expect_err("applied the chain inside a texture mode");
+        }
+        assert!(chain.output("invert").is_some());
+        assert!(chain.output("again").is_none());
+
+        // "pick" shows the output of "invert", which "again" must not have overwritten.
+        let image = out.load_rendered_image().unwrap();
+        assert_eq!(image.get_color(0, 0), Color::new(0, 255, 255, 255));
+        assert_eq!(image.get_color(0, 63), Color::new(255, 255, 0, 255));
+
+        let mut builtins = PostProcessChain::with_size(rl, thread, 64, 64).unwrap();
+        builtins
+            .add_bloom(rl, thread, BloomSettings::default())
+            .expect("bloom")
+            .add_tonemap(rl, thread, ToneMapSettings::default())
+            .expect("tonemap")
+            .add_fxaa(rl, thread)
+            .expect("fxaa")
+            .add_vignette(rl, thread, VignetteSettings::default())
+            .expect("vignette");
+        builtins
+            .add_fxaa(rl, thread)
+            .expect_err("added two passes with the same name");
+        builtins.apply(rl, thread).unwrap();
+        let mut d = rl.begin_texture_mode(thread, &mut out);
+        builtins.draw(&mut d);
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod rlgl_test {
+    use crate::tests::*;
+    use raylib::prelude::*;
+
+    ray_draw_test!(test_rl_immediate_quad);
+    fn test_rl_immediate_quad(d: &mut RaylibDrawHandle, _: &TestAssets) {
+        d.clear_background(Color::WHITE);
+        let mut quad = d.rl_begin(RlDrawMode::Quads);
+        quad.color(Color::RED);
+        quad.vertex2(Vector2::new(10.0, 10.0));
+        quad.vertex2(Vector2::new(10.0, 110.0));
+        quad.vertex2(Vector2::new(110.0, 110.0));
+        quad.vertex2(Vector2::new(110.0, 10.0));
+    }
+
+    ray_draw_test!(test_rl_push_matrix);
+    fn test_rl_push_matrix(d: &mut RaylibDrawHandle, _: &TestAssets) {
+        d.clear_background(Color::WHITE);
+        {
+            let mut m = d.rl_push_matrix();
+            m.translate(200.0, 200.0, 0.0);
+            m.rotate(45.0, 0.0, 0.0, 1.0);
+            m.draw_rectangle(-25, -25, 50, 50, Color::BLUE);
+        }
+        // Popped, drawn without the transform above
+        d.draw_rectangle(10, 10, 50, 50, Color::GREEN);
+    }
+
+    ray_3d_draw_test!(test_rl_triangles_3d);
+    fn test_rl_triangles_3d(
+        d: &mut RaylibMode3D<RaylibDrawHandle>,
+        _: &RaylibThread,
+        _: &TestAssets,
+    ) {
+        d.rl_disable_backface_culling();
+        {
+            let mut m = d.rl_push_matrix();
+            m.scale(2.0, 2.0, 2.0);
+            let mut tris = m.rl_begin(RlDrawMode::Triangles);
+            tris.color(Color::ORANGE);
+            tris.triangle(
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(0.0, 1.0, 0.0),
+            );
+        }
+        d.rl_enable_backface_culling();
+    }
+
+    ray_test!(test_rl_buffers);
+    fn test_rl_buffers(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+
+        let positions = [0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        let mut vao = rl.load_vertex_array(thread).expect("no vertex array support");
+        let mut vbo = rl
+            .load_vertex_buffer(thread, &positions, true)
+            .expect("could not load vertex buffer");
+        assert_eq!(vbo.size(), 36);
+        vao.set_attribute(&vbo, VertexAttribute::floats(0, 3));
+        vbo.update(&[0.5f32, 0.5, 0.0], 24).unwrap();
+        assert!(vbo.update(&positions, 4).is_err());
+        assert!(vbo.update(&positions, usize::MAX).is_err());
+
+        let ebo = rl
+            .load_element_buffer(thread, &[0, 1, 2], false)
+            .expect("could not load element buffer");
+        vao.set_element_buffer(&ebo);
+    }
+
+    ray_test!(test_rl_framebuffer);
+    fn test_rl_framebuffer(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+
+        let mut fb = rl.load_framebuffer(thread).expect("could not load framebuffer");
+        let depth = rl
+            .load_texture_depth(thread, 64, 64)
+            .expect("could not load depth texture");
+        fb.attach(
+            &depth,
+            rlFramebufferAttachType::RL_ATTACHMENT_DEPTH,
+            rlFramebufferAttachTextureType::RL_ATTACHMENT_TEXTURE2D,
+            0,
+        );
+        assert!(fb.is_complete());
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod scene_test {
+    use crate::snapshot::render_image;
+    use crate::tests::*;
+    use raylib::prelude::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn cube(rl: &mut RaylibHandle, thread: &RaylibThread, size: f32) -> Model {
+        let mesh = unsafe { Mesh::gen_mesh_cube(thread, size, size, size).make_weak() };
+        rl.load_model_from_mesh(thread, mesh).unwrap()
+    }
+
+    ray_test!(test_scene_graph);
+    fn test_scene_graph(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+
+        let mut scene = SceneGraph::new();
+        let model = scene.add_model(cube(rl, thread, 1.0));
+        let arm = scene.add_root("arm");
+        let hand = scene.add_child(arm, "hand").unwrap();
+        let behind = scene.add_root("behind");
+        scene.transform_mut(arm).unwrap().translation = Vector3::new(0.0, 0.0, -5.0);
+        scene.transform_mut(hand).unwrap().translation = Vector3::new(0.0, 1.0, 0.0);
+        scene.transform_mut(behind).unwrap().translation = Vector3::new(0.0, 0.0, 5.0);
+        for node in [arm, hand, behind] {
+            scene
+                .attach(node, Attachment::model(model, Color::WHITE))
+                .unwrap();
+        }
+        scene
+            .attach(hand, Attachment::transparent_model(model, Color::WHITE))
+            .unwrap();
+        scene
+            .attach(
+                hand,
+                Attachment::light(Light::point(Vector3::zero(), Color::WHITE)),
+            )
+            .unwrap();
+
+        let hand_world = scene.world_matrix(hand).unwrap();
+        assert_eq!(
+            Vector3::zero().transform_with(hand_world),
+            Vector3::new(0.0, 1.0, -5.0)
+        );
+        // Moving the parent moves the child.
+        scene.transform_mut(arm).unwrap().translation = Vector3::new(1.0, 0.0, -5.0);
+        let (_, light) = scene.lights()[0];
+        assert_eq!(light.position, Vector3::new(1.0, 1.0, -5.0));
+
+        let camera = Camera3D::perspective(
+            Vector3::zero(),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::up(),
+            60.0,
+        );
+        let frustum = Frustum::from_camera(&camera, 4.0 / 3.0);
+        let items: Vec<_> = scene
+            .visible_items(&camera, &frustum)
+            .iter()
+            .map(|i| (i.node, i.attachment))
+            .collect();
+        assert_eq!(items, [(arm, 0), (hand, 0), (hand, 1)]);
+
+        assert!(scene.set_parent(arm, Some(hand)).is_err());
+        scene.set_parent(behind, Some(hand)).unwrap();
+        assert_eq!(scene.nodes(), [arm, hand, behind]);
+        scene.remove(hand).unwrap();
+        assert!(!scene.contains(behind));
+        assert_eq!(scene.len(), 1);
+        assert!(scene.transform_mut(hand).is_none());
+    }
+
+    #[test]
+    fn test_rotated_parent() {
+        let mut scene = SceneGraph::new();
+        let arm = scene.add_root("arm");
+        let hand = scene.add_child(arm, "hand").unwrap();
+        scene
+            .set_transform(
+                arm,
+                Transform {
+                    translation: Vector3::new(1.0, 0.0, 0.0),
+                    rotation: Quaternion::from_axis_angle(Vector3::up(), FRAC_PI_2),
+                    scale: Vector3::new(2.0, 2.0, 2.0),
+                },
+            )
+            .unwrap();
+        scene
+            .set_transform(
+                hand,
+                Transform {
+                    translation: Vector3::new(0.0, 0.0, -1.0),
+                    rotation: Quaternion::from_axis_angle(Vector3::up(), FRAC_PI_2),
+                    scale: Vector3::one(),
+                },
+            )
+            .unwrap();
+
+        // The offset is scaled and rotated by the parent: -Z turns into -X.
+        let world = scene.world_matrix(hand).unwrap();
+        let origin = Vector3::zero().transform_with(world);
+        assert!((origin - Vector3::new(-1.0, 0.0, 0.0)).length() < 1e-5);
+        // Rotations add up, so +X points backwards.
+        let x = Vector3::new(1.0, 0.0, 0.0).transform_with(world) - origin;
+        assert!((x - Vector3::new(-2.0, 0.0, 0.0)).length() < 1e-5);
+    }
+
+    ray_3d_draw_test!(test_draw_scene_graph);
+    fn test_draw_scene_graph(
+        d: &mut RaylibMode3D<RaylibDrawHandle>,
+        thread: &RaylibThread,
+        _: &TestAssets,
+    ) {
+        let camera = Camera3D::orthographic(
+            Vector3::new(-125.0, 125.0, 125.0),
+            Vector3::zero(),
+            Vector3::up(),
+            90.0,
+        );
+        let mut scene = SceneGraph::new();
+        let model = scene.add_model(cube(d, thread, 20.0));
+        let base = scene.add_root("base");
+        scene
+            .attach(base, Attachment::model(model, Color::RED))
+            .unwrap();
+        let top = scene.add_child(base, "top").unwrap();
+        scene
+            .set_transform(
+                top,
+                Transform {
+                    translation: Vector3::new(0.0, 30.0, 0.0),
+                    rotation: Quaternion::from_euler(0.0, 0.8, 0.0),
+                    scale: Vector3::new(1.0, 0.5, 1.0),
+                },
+            )
+            .unwrap();
+        scene
+            .attach(
+                top,
+                Attachment::transparent_model(model, Color::BLUE.alpha(0.5)),
+            )
+            .unwrap();
+        scene.draw(d, &camera);
+    }
+
+    // Opaque models first, then transparent ones from back to front. The near red cube is added
+    // first, so drawing in insertion order would hide the blue one behind it.
+    ray_test!(test_scene_draw_order);
+    fn test_scene_draw_order(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0, 0.0, 10.0),
+            Vector3::zero(),
+            Vector3::up(),
+            45.0,
+        );
+        let mut scene = SceneGraph::new();
+        let model = scene.add_model(cube(rl, thread, 2.0));
+        for (name, x, z, attachment) in [
+            (
+                "near",
+                0.5,
+                2.0,
+                Attachment::transparent_model(model, Color::RED.alpha(0.5)),
+            ),
+            (
+                "far",
+                -0.5,
+                0.0,
+                Attachment::transparent_model(model, Color::BLUE.alpha(0.5)),
+            ),
+            ("wall", 0.0, -3.0, Attachment::model(model, Color::GREEN)),
+        ] {
+            let node = scene.add_root(name);
+            scene.transform_mut(node).unwrap().translation = Vector3::new(x, 0.0, z);
+            scene.attach(node, attachment).unwrap();
+        }
+        let (width, height) = (TEST_WIDTH as u32, TEST_HEIGHT as u32);
+        let image = render_image(rl, thread, width, height, |rl, thread, target| {
+            let mut d = rl.begin_texture_mode(thread, target);
+            let mut d3 = d.begin_mode3D(camera);
+            scene.draw(&mut d3, &camera);
+        });
+
+        // All three overlap in the middle: half red over half blue over the green wall.
+        let channels = |c: Color| [c.r, c.g, c.b];
+        let (near, far, wall) = (
+            channels(Color::RED),
+            channels(Color::BLUE),
+            channels(Color::GREEN),
+        );
+        let center = channels(image.get_color(TEST_WIDTH / 2, TEST_HEIGHT / 2));
+        let mix = |dst: f32, src: u8| (dst + src as f32) / 2.0;
+        for i in 0..3 {
+            let expected = mix(mix(wall[i] as f32, far[i]), near[i]);
+            assert!(
+                (center[i] as f32 - expected).abs() <= 8.0,
+                "center is {:?}, expected channel {} to be {}",
+                center,
+                i,
+                expected
+            );
+        }
+        assert_eq!(image.get_color(0, 0), Color::WHITE);
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod shader_test {
+    use crate::tests::*;
+    use raylib::prelude::*;
+
+    const TINT_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+uniform vec4 tint;
+uniform float strength;
+uniform float unused;
+uniform vec2 offset;
+out vec4 finalColor;
+void main() {
+    vec4 texel = texture(texture0, fragTexCoord + offset);
+    finalColor = mix(texel*colDiffuse*fragColor, tint, strength);
+}
+"#;
+
+    #[derive(ShaderUniforms)]
+    struct Tint {
+        #[uniform(name = "tint")]
+        color: Color,
+        strength: f32,
+        unused: f32,
+        missing: Vector3,
+        #[uniform(skip)]
+        _label: &'static str,
+    }
+
+    ray_test!(test_shader_compile_error);
+    fn test_shader_compile_error(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let err = rl
+            .load_shader_from_memory(thread, None, Some("#version 330\nvoid main() { oops }"))
+            .expect_err("broken shader compiled");
+        assert!(
+            err.to_string().contains("Compile error"),
+            "compile log missing from: {}",
+            err
+        );
+        rl.load_shader(thread, None, Some("resources/doesnt_exist.fs"))
+            .expect_err("shader found?");
+        rl.load_shader(thread, None, None)
+            .expect("default shader should always load");
+    }
+
+    ray_test!(test_shader_uniforms);
+    fn test_shader_uniforms(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let mut shader = rl
+            .load_shader_from_memory(thread, None, Some(TINT_FS))
+            .expect("could not compile shader");
+        let block = UniformBlock::<Tint>::with_sources(&shader, &[TINT_FS]);
+        assert_eq!(
+            Tint::UNIFORM_NAMES,
+            &["tint", "strength", "unused", "missing"]
+        );
+        assert!(block.location("tint").unwrap() >= 0);
+        assert_eq!(block.missing(), &["unused", "missing"]);
+        assert_eq!(block.unset(), &["offset".to_string()]);
+        block.upload(
+            &mut shader,
+            &Tint {
+                color: Color::RED,
+                strength: 0.5,
+                unused: 0.0,
+                missing: Vector3::zero(),
+                _label: "tint",
+            },
+        );
+    }
+
+    /// Rewrites `path` and pushes its modification time forward, file times only have a
+    /// resolution of a second.
+    fn touch(path: &str, contents: &[u8], seconds: u64) {
+        std::fs::write(path, contents).unwrap();
+        let time = std::time::SystemTime::now() + std::time::Duration::from_secs(seconds);
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    ray_test!(test_hot_reload);
+    fn test_hot_reload(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let shader_path = "test_out/hot_reload.fs";
+        let texture_path = "test_out/hot_reload.png";
+        std::fs::create_dir_all("test_out").unwrap();
+        std::fs::write(shader_path, TINT_FS).unwrap();
+        Image::gen_image_color(16, 16, Color::RED).export_image(texture_path);
+
+        let mut watcher = ShaderWatcher::new().poll_interval(0.0);
+        let shader = watcher
+            .load_shader(rl, thread, None, Some(shader_path))
+            .expect("could not load watched shader");
+        let texture = watcher
+            .load_texture(rl, thread, texture_path)
+            .expect("could not load watched texture");
+        assert!(watcher.update(rl, thread).is_empty());
+
+        let good_id = watcher.shader(shader).id;
+        touch(shader_path, b"#version 330\nvoid main() { oops }", 2);
+        let events = watcher.update(rl, thread);
+        assert!(matches!(events, [ReloadEvent::ShaderFailed { .. }]));
+        assert_eq!(watcher.shader(shader).id, good_id);
+        assert!(watcher.shader_error(shader).is_some());
+
+        let mesh = unsafe { Mesh::gen_mesh_cube(thread, 1.0, 1.0, 1.0).make_weak() };
+        let mut model = rl.load_model_from_mesh(thread, mesh).unwrap();
+        watcher.bind_material(shader, &mut model.materials_mut()[0]);
+
+        touch(shader_path, TINT_FS.as_bytes(), 4);
+        let events = watcher.update(rl, thread);
+        assert!(matches!(events, [ReloadEvent::ShaderReloaded(s)] if *s == shader));
+        assert!(watcher.shader(shader).is_shader_valid());
+        assert!(watcher.shader_error(shader).is_none());
+        assert_ne!(watcher.shader(shader).id, good_id);
+
+        // The material still draws with the old program, which must stay loaded
+        assert_eq!(model.materials()[0].shader.id, good_id);
+        let mut target = rl.load_render_texture(thread, 16, 16).unwrap();
+        {
+            let mut d = rl.begin_texture_mode(thread, &mut target);
+            let mut d = d.begin_mode3D(Camera3D::perspective(
+                Vector3::new(2.0, 2.0, 2.0),
+                Vector3::zero(),
+                Vector3::up(),
+                45.0,
+            ));
+            d.draw_model(&model, Vector3::zero(), 1.0, Color::WHITE);
+        }
+        watcher.bind_material(shader, &mut model.materials_mut()[0]);
+        assert_eq!(model.materials()[0].shader.id, watcher.shader(shader).id);
+        assert_eq!(watcher.collect_retired(), 1);
+        assert_eq!(watcher.collect_retired(), 0);
+
+        let texture_id = watcher.texture(texture).id;
+        Image::gen_image_color(16, 16, Color::BLUE).export_image(texture_path);
+        touch(texture_path, &std::fs::read(texture_path).unwrap(), 2);
+        let events = watcher.update(rl, thread);
+        assert!(matches!(
+            events,
+            [ReloadEvent::TextureReloaded {
+                replaced: false,
+                ..
+            }]
+        ));
+        assert_eq!(watcher.texture(texture).id, texture_id);
+
+        Image::gen_image_color(32, 32, Color::BLUE).export_image(texture_path);
+        touch(texture_path, &std::fs::read(texture_path).unwrap(), 4);
+        let events = watcher.update(rl, thread);
+        assert!(matches!(
+            events,
+            [ReloadEvent::TextureReloaded { replaced: true, .. }]
+        ));
+        assert_eq!(watcher.texture(texture).width(), 32);
+    }
+}
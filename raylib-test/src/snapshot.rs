@@ -0,0 +1,216 @@
+//! Golden-image snapshot testing.
+//!
+//! Rendered images are compared against PNGs stored in `goldens/`, channel by channel with a
+//! tolerance. When a snapshot doesn't match, the actual image and a diff image (mismatching pixels
+//! in red over a dimmed copy of the golden) are written to `test_out/snapshots/`.
+//!
+//! Set `RAYLIB_TEST_BLESS=1` to (re)write the goldens from the current output. A snapshot without
+//! a golden fails, so new snapshot tests must be blessed and their goldens committed. Tests that
+//! can't ship a golden use [`render_image`] and check individual pixels with [`assert_pixel`].
+use raylib::prelude::*;
+use std::path::{Path, PathBuf};
+
+pub const GOLDEN_DIR: &str = "goldens";
+pub const SNAPSHOT_OUT_DIR: &str = "test_out/snapshots";
+/// Per-channel difference allowed by default. Absorbs rounding differences between GL drivers.
+pub const DEFAULT_TOLERANCE: u8 = 2;
+
+/// Whether goldens should be overwritten with the current output.
+pub fn bless_mode() -> bool {
+    matches!(std::env::var("RAYLIB_TEST_BLESS"), Ok(v) if !v.is_empty() && v != "0")
+}
+
+pub fn golden_path(name: &str) -> PathBuf {
+    Path::new(GOLDEN_DIR).join(format!("{}.png", name))
+}
+
+/// Result of comparing two images of the same size.
+pub struct SnapshotDiff {
+    /// Number of pixels with at least one channel outside the tolerance.
+    pub mismatched: usize,
+    /// Largest per-channel difference found.
+    pub max_delta: u8,
+    /// Mismatching pixels in red, over a dimmed copy of the expected image.
+    pub diff: Image,
+}
+
+/// Compares `actual` against `expected`. Returns `None` if every channel of every pixel is within
+/// `tolerance`. Both images must have the same size.
+pub fn compare_images(expected: &Image, actual: &Image, tolerance: u8) -> Option<SnapshotDiff> {
+    assert_eq!(
+        (expected.width(), expected.height()),
+        (actual.width(), actual.height()),
+        "images must have the same size"
+    );
+    let width = expected.width();
+    let expected_colors = expected.get_image_data();
+    let actual_colors = actual.get_image_data();
+
+    let mut mismatched = Vec::new();
+    let mut max_delta = 0;
+    for (i, (e, a)) in expected_colors.iter().zip(actual_colors.iter()).enumerate() {
+        let delta = [
+            e.r.abs_diff(a.r),
+            e.g.abs_diff(a.g),
+            e.b.abs_diff(a.b),
+            e.a.abs_diff(a.a),
+        ]
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(0);
+        max_delta = max_delta.max(delta);
+        if delta > tolerance {
+            mismatched.push(i);
+        }
+    }
+    if mismatched.is_empty() {
+        return None;
+    }
+
+    let mut diff = Image::gen_image_color(width, expected.height(), Color::BLACK);
+    for (i, e) in expected_colors.iter().enumerate() {
+        let l = ((e.r as u16 + e.g as u16 + e.b as u16) / 12) as u8;
+        diff.draw_pixel(i as i32 % width, i as i32 / width, Color::new(l, l, l, 255));
+    }
+    for &i in &mismatched {
+        diff.draw_pixel(i as i32 % width, i as i32 / width, Color::RED);
+    }
+
+    Some(SnapshotDiff {
+        mismatched: mismatched.len(),
+        max_delta,
+        diff,
+    })
+}
+
+fn export(image: &Image, path: &Path) {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).expect("could not create snapshot directory");
+    }
+    image.export_image(path.to_str().expect("non utf-8 snapshot path"));
+}
+
+/// Checks `image` against the golden called `name`, see the module documentation.
+pub fn check_snapshot(name: &str, image: &Image, tolerance: u8) -> Result<(), String> {
+    let golden = golden_path(name);
+    let out = Path::new(SNAPSHOT_OUT_DIR);
+    let actual_path = out.join(format!("{}.actual.png", name));
+
+    if bless_mode() {
+        export(image, &golden);
+        return Ok(());
+    }
+    if !golden.exists() {
+        export(image, &actual_path);
+        return Err(format!(
+            "snapshot `{}` has no golden at {}, run with RAYLIB_TEST_BLESS=1 to record it from {}",
+            name,
+            golden.display(),
+            actual_path.display()
+        ));
+    }
+
+    let expected = Image::load_image(golden.to_str().expect("non utf-8 snapshot path"))
+        .map_err(|e| format!("could not load golden {}: {}", golden.display(), e))?;
+    if (expected.width(), expected.height()) != (image.width(), image.height()) {
+        export(image, &actual_path);
+        return Err(format!(
+            "snapshot `{}` is {}x{} but golden is {}x{}, see {}",
+            name,
+            image.width(),
+            image.height(),
+            expected.width(),
+            expected.height(),
+            actual_path.display()
+        ));
+    }
+    match compare_images(&expected, image, tolerance) {
+        None => Ok(()),
+        Some(diff) => {
+            let diff_path = out.join(format!("{}.diff.png", name));
+            export(image, &actual_path);
+            export(&diff.diff, &diff_path);
+            Err(format!(
+                "snapshot `{}` differs from golden: {} pixels exceed tolerance {} (max delta {}), see {} and {}",
+                name,
+                diff.mismatched,
+                tolerance,
+                diff.max_delta,
+                actual_path.display(),
+                diff_path.display()
+            ))
+        }
+    }
+}
+
+/// Panicking version of [`check_snapshot`] for use inside tests.
+pub fn assert_snapshot(name: &str, image: &Image, tolerance: u8) {
+    if let Err(e) = check_snapshot(name, image, tolerance) {
+        panic!("{}", e);
+    }
+}
+
+/// Renders `draw` into a white `width`x`height` render texture and reads it back. For tests that
+/// check a few pixels instead of shipping a golden.
+pub fn render_image<F>(
+    rl: &mut RaylibHandle,
+    thread: &RaylibThread,
+    width: u32,
+    height: u32,
+    draw: F,
+) -> Image
+where
+    F: FnOnce(&mut RaylibHandle, &RaylibThread, &mut RenderTexture2D),
+{
+    let mut target = rl
+        .load_render_texture(thread, width, height)
+        .expect("could not create render texture");
+    {
+        let mut d = rl.begin_texture_mode(thread, &mut target);
+        d.clear_background(Color::WHITE);
+    }
+    draw(rl, thread, &mut target);
+    target
+        .load_rendered_image()
+        .expect("could not read back render texture")
+}
+
+/// Asserts that every channel of the pixel at `(x, y)` is within `tolerance` of `expected`.
+pub fn assert_pixel(image: &Image, x: i32, y: i32, expected: Color, tolerance: u8) {
+    let actual = image.get_color(x, y);
+    let delta = [
+        actual.r.abs_diff(expected.r),
+        actual.g.abs_diff(expected.g),
+        actual.b.abs_diff(expected.b),
+        actual.a.abs_diff(expected.a),
+    ];
+    assert!(
+        delta.iter().all(|&d| d <= tolerance),
+        "pixel ({}, {}) is {:?}, expected {:?} within {}",
+        x,
+        y,
+        actual,
+        expected,
+        tolerance
+    );
+}
+
+#[cfg(test)]
+mod snapshot_test {
+    use super::*;
+
+    #[test]
+    fn test_compare_images() {
+        let a = Image::gen_image_color(8, 8, Color::new(100, 100, 100, 255));
+        let mut b = Image::gen_image_color(8, 8, Color::new(102, 99, 100, 255));
+        assert!(compare_images(&a, &b, 2).is_none());
+
+        b.draw_pixel(3, 5, Color::RED);
+        let diff = compare_images(&a, &b, 2).expect("images should differ");
+        assert_eq!(diff.mismatched, 1);
+        assert_eq!(diff.max_delta, 155);
+        assert_eq!(diff.diff.get_image_data()[5 * 8 + 3], Color::RED);
+        assert_ne!(diff.diff.get_image_data()[0], Color::RED);
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod sprite_test {
+    use raylib::prelude::*;
+
+    fn frames(events: &[SpriteEvent]) -> Vec<usize> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                SpriteEvent::Frame { frame, .. } => Some(*frame),
+                SpriteEvent::Finished { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sprite_play_modes() {
+        let size = Vector2::new(16.0, 16.0);
+        let mut anim = SpriteAnimation::new();
+        anim.add_clip(SpriteClip::from_grid(
+            "walk",
+            PlayMode::Loop,
+            4,
+            size,
+            0..3,
+            0.1,
+        ))
+        .add_clip(SpriteClip::from_grid(
+            "wave",
+            PlayMode::PingPong,
+            4,
+            size,
+            4..8,
+            0.1,
+        ))
+        .add_clip(SpriteClip::from_grid(
+            "die",
+            PlayMode::Once,
+            4,
+            size,
+            8..10,
+            0.1,
+        ));
+
+        // The first clip plays automatically and loops.
+        assert_eq!(frames(anim.update(0.0)), vec![0]);
+        assert_eq!(frames(anim.update(0.35)), vec![1, 2, 0]);
+        let rec = anim.source_rec().unwrap();
+        assert_eq!((rec.x, rec.y), (0.0, 0.0));
+
+        assert!(anim.play("wave"));
+        let mut seen = frames(anim.update(0.0));
+        for _ in 0..7 {
+            seen.extend(frames(anim.update(0.1)));
+        }
+        assert_eq!(seen, vec![0, 1, 2, 3, 2, 1, 0, 1]);
+        let rec = anim.source_rec().unwrap();
+        assert_eq!((rec.x, rec.y), (16.0, 16.0));
+
+        assert!(anim.play("die"));
+        let events = anim.update(1.0).to_vec();
+        assert_eq!(frames(&events), vec![0, 1]);
+        assert!(matches!(
+            events.last(),
+            Some(SpriteEvent::Finished { clip }) if &**clip == "die"
+        ));
+        assert!(anim.is_finished());
+        assert!(anim.update(1.0).is_empty());
+        assert!(!anim.play("missing"));
+
+        anim.set_flip(true, false);
+        let rec = anim.source_rec().unwrap();
+        assert_eq!((rec.width, rec.height), (-16.0, 16.0));
+    }
+
+    #[cfg(feature = "with_serde")]
+    const ASEPRITE_JSON: &str = r#"{
+        "frames": {
+            "hero 0.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "duration": 100 },
+            "hero 1.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "duration": 200 },
+            "hero 2.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "duration": 150 },
+            "hero 3.aseprite": { "frame": { "x": 0, "y": 16, "w": 16, "h": 16 }, "duration": 50 }
+        },
+        "meta": {
+            "image": "hero.png",
+            "frameTags": [
+                { "name": "walk", "from": 0, "to": 1, "direction": "forward" },
+                { "name": "wave", "from": 1, "to": 3, "direction": "pingpong" },
+                { "name": "back", "from": 0, "to": 2, "direction": "reverse" },
+                { "name": "die", "from": 2, "to": 3, "direction": "forward", "repeat": "1" }
+            ]
+        }
+    }"#;
+
+    #[cfg(feature = "with_serde")]
+    #[test]
+    fn test_aseprite_json() {
+        let mut anim = SpriteAnimation::from_aseprite_json(ASEPRITE_JSON).unwrap();
+        let names: Vec<_> = anim.clips().iter().map(|c| c.name()).collect();
+        assert_eq!(names, vec!["walk", "wave", "back", "die"]);
+
+        let xs =
+            |clip: &SpriteClip| -> Vec<f32> { clip.frames.iter().map(|f| f.source.x).collect() };
+        let walk = anim.clip("walk").unwrap();
+        assert_eq!(walk.mode, PlayMode::Loop);
+        let durations: Vec<f32> = walk.frames.iter().map(|f| f.duration).collect();
+        assert_eq!(durations, vec![0.1, 0.2]);
+        assert!((walk.duration() - 0.3).abs() < 1e-6);
+        assert_eq!(anim.clip("wave").unwrap().mode, PlayMode::PingPong);
+        let back = anim.clip("back").unwrap();
+        assert_eq!(back.mode, PlayMode::Loop);
+        assert_eq!(xs(back), vec![32.0, 16.0, 0.0]);
+        assert_eq!(anim.clip("die").unwrap().mode, PlayMode::Once);
+
+        // Ping-pong turns around on the last frame, each frame shown for its own duration.
+        assert!(anim.play("wave"));
+        let mut seen = frames(anim.update(0.0));
+        for dt in [0.2, 0.15, 0.05, 0.15, 0.2] {
+            seen.extend(frames(anim.update(dt)));
+        }
+        assert_eq!(seen, vec![0, 1, 2, 1, 0, 1]);
+        assert_eq!(anim.source_rec().unwrap().x, 32.0);
+
+        let array = r#"{ "frames": [
+            { "filename": "a", "frame": { "x": 0, "y": 0, "w": 8, "h": 8 } },
+            { "filename": "b", "frame": { "x": 8, "y": 0, "w": 8, "h": 8 }, "duration": 250 }
+        ] }"#;
+        let anim = SpriteAnimation::from_aseprite_json(array).unwrap();
+        let clip = anim.clip("default").unwrap();
+        assert_eq!(clip.mode, PlayMode::Loop);
+        assert_eq!(xs(clip), vec![0.0, 8.0]);
+        assert_eq!(clip.frames[1].duration, 0.25);
+
+        let bad_tag =
+            r#"{ "frames": [], "meta": { "frameTags": [{ "name": "x", "from": 0, "to": 1 }] } }"#;
+        assert!(SpriteAnimation::from_aseprite_json(bad_tag).is_err());
+    }
+
+    #[test]
+    fn test_gif_sheet() {
+        let (sheet, anim) = SpriteAnimation::load_gif_sheet("resources/frames.gif", 0.1).unwrap();
+        // Three 4x4 frames fill a 2x2 grid
+        assert_eq!((sheet.width(), sheet.height()), (8, 8));
+        let clip = anim.clip("default").unwrap();
+        assert_eq!(clip.mode, PlayMode::Loop);
+        assert_eq!(clip.frames.len(), 3);
+        for (frame, color) in clip
+            .frames
+            .iter()
+            .zip([Color::RED, Color::GREEN, Color::BLUE])
+        {
+            assert_eq!(frame.duration, 0.1);
+            assert_eq!((frame.source.width, frame.source.height), (4.0, 4.0));
+            let (x, y) = (frame.source.x as i32, frame.source.y as i32);
+            assert_eq!(sheet.get_color(x, y), color);
+            assert_eq!(sheet.get_color(x + 3, y + 3), color);
+        }
+        assert_eq!(sheet.get_color(6, 6), Color::BLANK);
+
+        assert!(SpriteAnimation::load_gif_sheet("resources/missing.gif", 0.1).is_err());
+    }
+}
@@ -31,10 +31,14 @@ fn clone_testfn(testfn: &TestFn) -> TestFn {
 
 pub fn initialize_globals() -> (RaylibThread, TestAssets) {
     let mut handle = TEST_HANDLE.write().unwrap();
-    let (rl, thread) = raylib::init()
-        .size(TEST_WIDTH, TEST_HEIGHT)
-        .title("Hello, World")
-        .build();
+    let mut builder = raylib::init();
+    builder.size(TEST_WIDTH, TEST_HEIGHT).title("Hello, World");
+    // Run without a GPU (e.g. on CI) by setting RAYLIB_TEST_HEADLESS. This runs before the
+    // test threads are spawned, so `headless` can safely set its environment variables.
+    if std::env::var_os("RAYLIB_TEST_HEADLESS").is_some() {
+        builder.headless();
+    }
+    let (rl, thread) = builder.build();
     *handle = Some(rl);
     let asset = TestAssets {
         font: handle
@@ -104,6 +108,7 @@ pub fn test_runner(tests: &[&dyn Testable]) {
     let mut seq_test: Vec<&RayTest> = Vec::new();
     let mut draw_test: Vec<&RayDrawTest> = Vec::new();
     let mut draw_test_3d: Vec<&Ray3DDrawTest> = Vec::new();
+    let mut snapshot_test: Vec<&RaySnapshotTest> = Vec::new();
 
     for t in tests {
         match t.get_test() {
@@ -120,6 +125,9 @@ pub fn test_runner(tests: &[&dyn Testable]) {
             TestType::Draw3D(test) => {
                 draw_test_3d.push(test);
             }
+            TestType::Snapshot(test) => {
+                snapshot_test.push(test);
+            }
         }
     }
 
@@ -189,6 +197,31 @@ pub fn test_runner(tests: &[&dyn Testable]) {
         }
         //assert!(std::path::Path::new(&format!("{}.png", t.name)).exists());
     }
+
+    let mut target = rl
+        .load_render_texture(&thread, TEST_WIDTH as u32, TEST_HEIGHT as u32)
+        .expect("could not create snapshot render texture");
+    let mut failed_snapshots = Vec::new();
+    for t in &snapshot_test {
+        if opts.nocapture {
+            println!("running snapshot test: {}", t.name);
+        }
+        {
+            let mut d = rl.begin_texture_mode(&thread, &mut target);
+            d.clear_background(Color::WHITE);
+        }
+        (t.test)(&mut rl, &thread, &mut target, &assets);
+        let image = target
+            .load_rendered_image()
+            .expect("could not read back snapshot render texture");
+        if let Err(e) = crate::snapshot::check_snapshot(t.name, &image, t.tolerance) {
+            println!("{}", e.red());
+            failed_snapshots.push(t.name);
+        }
+    }
+    if !failed_snapshots.is_empty() {
+        panic!("snapshot tests failed: {}", failed_snapshots.join(", "));
+    }
     println!("{}","Test has succeeded! You will see that the test has failed due to a segfault, this is a known bug. If you are seeing this message then it definitely has succeeded!".green().bold());
 }
 
@@ -200,6 +233,8 @@ pub enum TestType<'a> {
     Draw(&'a RayDrawTest),
     /// take screenshot after test (3D),
     Draw3D(&'a Ray3DDrawTest),
+    /// render into a texture and compare against a golden image
+    Snapshot(&'a RaySnapshotTest),
 }
 
 pub struct RayTest {
@@ -217,6 +252,14 @@ pub struct Ray3DDrawTest {
     pub test: fn(&mut RaylibMode3D<RaylibDrawHandle>, &RaylibThread, &TestAssets),
 }
 
+pub struct RaySnapshotTest {
+    pub name: &'static str,
+    /// Allowed per-channel difference to the golden image
+    pub tolerance: u8,
+    /// Draws into the render texture, which starts out white. Offscreen passes can run first.
+    pub test: fn(&mut RaylibHandle, &RaylibThread, &mut RenderTexture2D, &TestAssets),
+}
+
 macro_rules! ray_test {
     ($name:ident) => {
         #[test_case]
@@ -249,6 +292,23 @@ macro_rules! ray_3d_draw_test {
         };
     };
 }
+// Only for tests whose golden is committed to `goldens/`.
+#[allow(unused_macros)]
+macro_rules! ray_snapshot_test {
+    ($name:ident) => {
+        ray_snapshot_test!($name, crate::snapshot::DEFAULT_TOLERANCE);
+    };
+    ($name:ident, $tolerance:expr) => {
+        #[test_case]
+        #[allow(non_upper_case_globals)]
+        static $name: RaySnapshotTest = RaySnapshotTest {
+            name: stringify!($name),
+            tolerance: $tolerance,
+            test: $name,
+        };
+    };
+}
+
 pub trait Testable {
     fn get_test(&self) -> TestType;
 }
@@ -276,3 +336,9 @@ impl Testable for Ray3DDrawTest {
         TestType::Draw3D(self)
     }
 }
+
+impl Testable for RaySnapshotTest {
+    fn get_test(&self) -> TestType {
+        TestType::Snapshot(self)
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod text_test {
+    use crate::snapshot::render_image;
     use crate::tests::*;
     use raylib::prelude::*;
     ray_test!(test_font_load);
@@ -64,4 +65,203 @@ mod text_test {
             Color::RED,
         );
     }
+
+    ray_test!(test_text_ex_pixels);
+    fn test_text_ex_pixels(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let font = rl
+            .load_font(thread, "resources/alagard.png")
+            .expect("couldn't load font");
+        let font_ex = rl
+            .load_font_ex(thread, "resources/pixeloid.ttf", 32, None)
+            .expect("couldn't load font");
+        let text = "Hello World";
+        let lines = [
+            (&font, Vector2::new(20.0, 20.0), Color::RED),
+            (&font_ex, Vector2::new(20.0, 80.0), Color::BLACK),
+        ];
+        let image = render_image(rl, thread, 320, 160, |rl, thread, target| {
+            let mut d = rl.begin_texture_mode(thread, target);
+            for (font, position, color) in lines {
+                d.draw_text_ex(font, text, position, 32.0, 5.0, color);
+            }
+        });
+
+        // Glyph edges are anti-aliased, so only look for pixels close to the text color inside
+        // each line's bounds and require everything else to stay white.
+        let bounds: Vec<Rectangle> = lines
+            .iter()
+            .map(|(font, position, _)| {
+                let size = font.measure_text(text, 32.0, 5.0);
+                Rectangle::new(
+                    position.x - 2.0,
+                    position.y - 2.0,
+                    size.x + 4.0,
+                    size.y + 4.0,
+                )
+            })
+            .collect();
+        let mut found = [0; 2];
+        let pixels = image.get_image_data();
+        for y in 0..image.height() {
+            for x in 0..image.width() {
+                let pixel = pixels[(y * image.width() + x) as usize];
+                let point = Vector2::new(x as f32, y as f32);
+                match bounds
+                    .iter()
+                    .position(|b| b.check_collision_point_rec(point))
+                {
+                    Some(i) => {
+                        let color = lines[i].2;
+                        if pixel.r.abs_diff(color.r) < 32
+                            && pixel.g.abs_diff(color.g) < 32
+                            && pixel.b.abs_diff(color.b) < 32
+                        {
+                            found[i] += 1;
+                        }
+                    }
+                    None => assert_eq!(pixel, Color::WHITE, "stray pixel at ({}, {})", x, y),
+                }
+            }
+        }
+        assert!(found.iter().all(|&n| n > 0), "text missing: {:?}", found);
+    }
+
+    ray_test!(test_text_layout);
+    fn test_text_layout(_: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let font = rl.get_font_default();
+        let text = "The quick brown fox jumps over the lazy dog";
+        let layout = TextLayout::new(&font, 20.0).width(120.0);
+
+        let block = layout.layout(text);
+        assert!(block.lines().len() > 1);
+        assert!(!block.is_truncated());
+        for line in block.lines() {
+            assert!(line.rect.width <= 120.0, "{:?}", line);
+        }
+        let shown: String = block.glyphs().iter().map(|g| g.codepoint).collect();
+        assert_eq!(shown, text);
+        for (i, g) in block.glyphs().iter().enumerate() {
+            let center = Vector2::new(g.rect.x + g.rect.width / 2.0, g.rect.y + 1.0);
+            assert_eq!(block.hit_test(center), Some(i));
+            assert_eq!(block.caret_rect(g.byte_index).x, g.rect.x);
+        }
+
+        rl.set_text_line_spacing(6);
+        let lines = layout.layout(text);
+        assert_eq!(lines.lines()[1].rect.y, 26.0);
+        rl.set_text_line_spacing(2);
+
+        let short = layout.clone().max_lines(2).layout(text);
+        assert_eq!(short.lines().len(), 2);
+        assert!(short.is_truncated());
+        let last: String = short
+            .glyphs()
+            .iter()
+            .rev()
+            .take(3)
+            .map(|g| g.codepoint)
+            .collect();
+        assert_eq!(last, "...");
+
+        let rich = TextLayout::new(&font, 20.0)
+            .layout_markup("A [color=#ff0000][size=40]big[/size][/color] word")
+            .unwrap();
+        let big = &rich.glyphs()[2];
+        assert_eq!(big.codepoint, 'b');
+        assert_eq!(big.byte_index, 26);
+        assert_eq!(big.font_size, 40.0);
+        assert_eq!(big.color, Color::RED);
+        assert_eq!(rich.lines()[0].rect.height, 40.0);
+        assert!(layout.layout_markup("[size=40]unclosed").is_err());
+    }
+
+    ray_test!(test_dynamic_font);
+    fn test_dynamic_font(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let baked = rl
+            .load_font_ex(thread, "resources/pixeloid.ttf", 16, None)
+            .expect("couldn't load font");
+        let mut font = rl
+            .load_dynamic_font(thread, "resources/pixeloid.ttf", 16)
+            .expect("couldn't load dynamic font")
+            .page_size(72)
+            .max_pages(1);
+        assert_eq!(font.glyphs_per_page(), 9);
+        assert!(rl
+            .load_dynamic_font_from_memory(thread, vec![0u8; 64], 16)
+            .is_err());
+
+        let hello = font.measure_text(thread, "Hello", 16.0, 1.0).unwrap();
+        assert_eq!(hello, baked.measure_text("Hello", 16.0, 1.0));
+        assert_eq!(font.page_count(), 1);
+        assert_eq!(font.glyph_count(), 4);
+
+        // Filling the only page evicts the least recently used glyphs.
+        font.font_for(thread, "abcdefg").unwrap();
+        assert_eq!(font.glyph_count(), 9);
+        let page = font.font_for(thread, "Hello").unwrap();
+        assert_eq!(page.get_glyph_info('H').value, 'H' as i32);
+        assert!(font.font_for(thread, "abcdefghij").is_err());
+
+        // Box drawing characters are missing from pixeloid and come from the fallback.
+        let page = font.font_for(thread, "\u{250c}").unwrap();
+        assert_eq!(page.get_glyph_info('\u{250c}').value, '?' as i32);
+        font.add_fallback_file("resources/AnonymousPro-Bold.ttf")
+            .expect("couldn't load fallback font");
+        let page = font.font_for(thread, "\u{250c}\u{2550}").unwrap();
+        assert_eq!(page.get_glyph_info('\u{250c}').value, 0x250c);
+        assert_eq!(page.get_glyph_info('\u{2550}').value, 0x2550);
+        font.add_fallback_file("resources/missing.ttf")
+            .expect_err("loaded a missing font");
+
+        // Codepoints missing from every font fall back to '?'.
+        let page = font.font_for(thread, "\u{4e2d}").unwrap();
+        assert_eq!(page.get_glyph_info('\u{4e2d}').value, '?' as i32);
+
+        let mut out = rl.load_render_texture(thread, 64, 64).unwrap();
+        let mut d = rl.begin_texture_mode(thread, &mut out);
+        let page = font.font_for(thread, "Hello").unwrap();
+        d.draw_text_ex(page, "Hello", Vector2::zero(), 16.0, 1.0, Color::BLACK);
+    }
+
+    ray_test!(test_font_sdf);
+    fn test_font_sdf(thread: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let font = rl
+            .load_font_sdf(thread, "resources/pixeloid.ttf", 32, None)
+            .expect("couldn't load sdf font");
+        assert_eq!(font.base_size(), 32);
+        assert_eq!(font.chars().len(), 95);
+        assert!(font.shader().is_shader_valid());
+        let subset = rl
+            .load_font_sdf(thread, "resources/pixeloid.ttf", 32, Some("AB"))
+            .expect("couldn't load sdf subset");
+        assert_eq!(subset.chars().len(), 2);
+        assert!(rl
+            .load_font_sdf(thread, "resources/pixeloid.ttf", 32, Some(""))
+            .is_err());
+        assert!(rl
+            .load_font_sdf(thread, "resources/missing.ttf", 32, None)
+            .is_err());
+
+        let style = SdfTextStyle::new(Color::WHITE)
+            .outline(1.0, Color::BLACK)
+            .glow(1.5, Color::GOLD)
+            .shadow(Vector2::new(4.0, 4.0), 0.5, Color::BLACK.alpha(0.5));
+        let mut out = rl.load_render_texture(thread, 256, 128).unwrap();
+        {
+            let mut d = rl.begin_texture_mode(thread, &mut out);
+            d.clear_background(Color::BLANK);
+            d.draw_text_sdf(&font, "Hello", Vector2::new(8.0, 8.0), 96.0, &style);
+        }
+        let image = out.load_rendered_image().unwrap();
+        let drawn = (0..128).any(|y| (0..256).any(|x| image.get_color(x, y).a > 0));
+        assert!(drawn);
+    }
 }
@@ -36,6 +36,28 @@ mod texture_test {
             .expect("render texture created");
     }
 
+    ray_test!(test_render_texture_readback);
+    fn test_render_texture_readback(t: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let mut target = rl
+            .load_render_texture(t, 64, 64)
+            .expect("render texture created");
+        {
+            let mut d = rl.begin_texture_mode(t, &mut target);
+            d.clear_background(Color::BLUE);
+            d.draw_rectangle(0, 0, 64, 16, Color::RED);
+        }
+        let image = target
+            .load_rendered_image()
+            .expect("could not read back render texture");
+        assert_eq!(image.width(), 64);
+        assert_eq!(image.height(), 64);
+        assert_eq!(image.get_color(0, 0), Color::RED);
+        assert_eq!(image.get_color(0, 63), Color::BLUE);
+        image.export_image("test_out/render_texture_readback.png");
+    }
+
     #[test]
     fn test_image_manipulations() {
         // Just checking that nothing segfaults. Not ensuring they work as expected.
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod vfs_test {
+    use crate::tests::*;
+    use raylib::prelude::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_pack_roundtrip() {
+        let billboard = std::fs::read("resources/billboard.png").unwrap();
+        let text = "The quick brown fox jumped over the lazy dog. ".repeat(32);
+
+        let mut pack = PackBuilder::new(Cursor::new(Vec::new())).unwrap();
+        pack.add("images/billboard.png", &billboard).unwrap();
+        pack.add("text/fox.txt", text.as_bytes()).unwrap();
+        let archive = pack.finish().unwrap().into_inner();
+        assert!(archive.len() < billboard.len() + text.len());
+
+        let pack = PackFs::new(Cursor::new(archive)).unwrap();
+        assert_eq!(pack.read("images/billboard.png").unwrap(), billboard);
+        assert_eq!(pack.read("text/fox.txt").unwrap(), text.as_bytes());
+        assert!(!pack.exists("text/missing.txt"));
+
+        let mut vfs = Vfs::new();
+        vfs.mount("", MemoryFs::new());
+        vfs.mount("assets", pack);
+        assert!(vfs.exists("assets/./images/../text/fox.txt"));
+        // Packs are read-only, writes fall through to the memory mount
+        vfs.write("assets/save.txt", b"ok").unwrap();
+        assert_eq!(vfs.read("assets/save.txt").unwrap(), b"ok");
+    }
+
+    #[test]
+    fn test_vfs_absolute_paths() {
+        let path = std::fs::canonicalize("resources/billboard.png").unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut vfs = Vfs::new();
+        vfs.mount("", MemoryFs::new());
+        assert!(vfs.exists(path));
+        assert_eq!(
+            vfs.read(path).unwrap(),
+            std::fs::read("resources/billboard.png").unwrap()
+        );
+        assert!(!vfs.exists("resources/billboard.png"));
+    }
+
+    ray_test!(test_vfs_load_texture);
+    fn test_vfs_load_texture(thread: &RaylibThread) {
+        let mut pack = PackBuilder::new(Cursor::new(Vec::new())).unwrap();
+        pack.add(
+            "billboard.png",
+            &std::fs::read("resources/billboard.png").unwrap(),
+        )
+        .unwrap();
+        let archive = pack.finish().unwrap().into_inner();
+
+        let mut vfs = Vfs::new();
+        // Other tests keep loading from disk while the vfs is mounted
+        vfs.mount("", DirectoryFs::new("."));
+        vfs.mount("pack", PackFs::new(Cursor::new(archive)).unwrap());
+
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+        let guard = rl.mount_vfs(vfs);
+        let texture = rl
+            .load_texture(thread, "pack/billboard.png")
+            .expect("texture not served from the pack");
+        assert!(texture.width() > 0);
+        Image::load_image("pack/missing.png").expect_err("image found?");
+        drop(guard);
+
+        rl.load_texture(thread, "pack/billboard.png")
+            .expect_err("vfs still mounted");
+    }
+
+    fn pack_with(path: &str) -> PackFs<Cursor<Vec<u8>>> {
+        let mut pack = PackBuilder::new(Cursor::new(Vec::new())).unwrap();
+        pack.add(path, &std::fs::read("resources/billboard.png").unwrap())
+            .unwrap();
+        PackFs::new(Cursor::new(pack.finish().unwrap().into_inner())).unwrap()
+    }
+
+    ray_test!(test_vfs_guards_out_of_order);
+    fn test_vfs_guards_out_of_order(_: &RaylibThread) {
+        let mut handle = TEST_HANDLE.write().unwrap();
+        let rl = handle.as_mut().unwrap();
+
+        let mut first = Vfs::new();
+        first.mount("", DirectoryFs::new("."));
+        first.mount("first", pack_with("billboard.png"));
+        let mut second = Vfs::new();
+        second.mount("", DirectoryFs::new("."));
+        second.mount("second", pack_with("billboard.png"));
+
+        let first = rl.mount_vfs(first);
+        let second = rl.mount_vfs(second);
+        drop(first);
+        Image::load_image("second/billboard.png").expect("second vfs unmounted");
+        Image::load_image("first/billboard.png").expect_err("first vfs still mounted");
+
+        drop(second);
+        Image::load_image("first/billboard.png").expect_err("first vfs came back");
+        Image::load_image("second/billboard.png").expect_err("second vfs still mounted");
+    }
+}
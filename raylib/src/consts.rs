@@ -20,9 +20,12 @@ pub use ffi::TextureFilter;
 pub use ffi::TextureWrap;
 pub use ffi::TraceLogLevel;
 pub use ffi::DEG2RAD;
-// TODO Fix when rlgl bindings are in
+pub use ffi::rlCullMode;
+pub use ffi::rlFramebufferAttachTextureType;
+pub use ffi::rlFramebufferAttachType;
+// MAX_MATERIAL_MAPS lives in raylib's config.h, which the bindings don't include
 pub const MAX_MATERIAL_MAPS: u32 = 12;
-pub const MAX_SHADER_LOCATIONS: u32 = 32;
+pub const MAX_SHADER_LOCATIONS: u32 = ffi::RL_MAX_SHADER_LOCATIONS;
 pub use ffi::GuiCheckBoxProperty;
 pub use ffi::GuiColorPickerProperty;
 pub use ffi::GuiComboBoxProperty;
@@ -0,0 +1,744 @@
+//! Sprite atlas packing. Packs many images into one, and reads and writes TexturePacker /
+//! Aseprite style JSON descriptors (with the `with_serde` feature).
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//!
+//! let (mut rl, thread) = raylib::init().build();
+//!
+//! let mut builder = TextureAtlasBuilder::new().padding(2).extrude(1);
+//! builder.add_file("sprites/player.png").unwrap();
+//! builder.add_file("sprites/enemy.png").unwrap();
+//! let atlas = builder.build().unwrap();
+//! let texture = atlas.load_texture(&mut rl, &thread).unwrap();
+//!
+//! let mut d = rl.begin_drawing(&thread);
+//! d.draw_texture_rec(&texture, atlas.get("player").unwrap(), Vector2::zero(), Color::WHITE);
+//! ```
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::core::color::Color;
+use crate::core::math::Rectangle;
+use crate::core::texture::{Image, Texture2D};
+use crate::core::{RaylibHandle, RaylibThread};
+use crate::error::{error, Error};
+use crate::ffi;
+
+#[cfg(feature = "with_serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rectangle packing algorithm used by [`TextureAtlasBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackAlgorithm {
+    /// Bottom-left skyline. Fast, good for sprites of similar heights.
+    Skyline,
+    /// Maximal rectangles, best short side fit. Slower, usually packs tighter.
+    MaxRects,
+}
+
+/// Packs images into a [`TextureAtlas`].
+pub struct TextureAtlasBuilder {
+    images: Vec<(String, Image)>,
+    algorithm: PackAlgorithm,
+    padding: i32,
+    extrude: i32,
+    max_size: i32,
+    power_of_two: bool,
+}
+
+impl Default for TextureAtlasBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextureAtlasBuilder {
+    pub fn new() -> Self {
+        TextureAtlasBuilder {
+            images: Vec::new(),
+            algorithm: PackAlgorithm::MaxRects,
+            padding: 0,
+            extrude: 0,
+            max_size: 4096,
+            power_of_two: true,
+        }
+    }
+
+    pub fn algorithm(mut self, algorithm: PackAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Transparent pixels left between sprites and around the atlas border.
+    pub fn padding(mut self, padding: i32) -> Self {
+        self.padding = padding.max(0);
+        self
+    }
+
+    /// Repeats the edge pixels of each sprite outwards, so filtering doesn't bleed neighbours in.
+    pub fn extrude(mut self, extrude: i32) -> Self {
+        self.extrude = extrude.max(0);
+        self
+    }
+
+    /// Largest width and height the atlas may grow to. Defaults to 4096.
+    pub fn max_size(mut self, max_size: i32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Whether the atlas size is rounded up to powers of two (the default), or cropped to the
+    /// packed sprites.
+    pub fn power_of_two(mut self, power_of_two: bool) -> Self {
+        self.power_of_two = power_of_two;
+        self
+    }
+
+    pub fn add(&mut self, name: impl Into<String>, image: Image) -> &mut Self {
+        self.images.push((name.into(), image));
+        self
+    }
+
+    /// Loads an image file and adds it under its file name without extension.
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> Result<&mut Self, Error> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| error!("Invalid sprite file name", path))?;
+        let image = Image::load_image(
+            path.to_str()
+                .ok_or_else(|| error!("Invalid sprite file name", path))?,
+        )?;
+        Ok(self.add(name, image))
+    }
+
+    /// Packs every added image into a single atlas image.
+    pub fn build(self) -> Result<TextureAtlas, Error> {
+        let pad = self.padding;
+        let border = self.extrude;
+        let mut sizes = Vec::with_capacity(self.images.len());
+        for (i, (name, image)) in self.images.iter().enumerate() {
+            if image.width() <= 0 || image.height() <= 0 {
+                return Err(error!(std::borrow::Cow::Owned(format!(
+                    "Sprite `{}` is empty",
+                    name
+                ))));
+            }
+            if self.images[..i].iter().any(|(n, _)| n == name) {
+                return Err(error!(std::borrow::Cow::Owned(format!(
+                    "Sprite `{}` was added twice",
+                    name
+                ))));
+            }
+            sizes.push((
+                image.width() + 2 * border + pad,
+                image.height() + 2 * border + pad,
+            ));
+        }
+
+        let (positions, width, height) = pack_sizes(
+            &sizes,
+            self.algorithm,
+            pad,
+            self.max_size,
+            self.power_of_two,
+        )
+        .ok_or_else(|| error!("Sprites don't fit in the maximum atlas size"))?;
+
+        let mut image = Image::gen_image_color(width, height, Color::BLANK);
+        let mut frames = BTreeMap::new();
+        for ((name, sprite), (x, y)) in self.images.into_iter().zip(positions) {
+            let rect = Rectangle::new(
+                (x + border) as f32,
+                (y + border) as f32,
+                sprite.width() as f32,
+                sprite.height() as f32,
+            );
+            image.draw(
+                &sprite,
+                Rectangle::new(0.0, 0.0, rect.width, rect.height),
+                rect,
+                Color::WHITE,
+            );
+            extrude_edges(&mut image, &sprite, rect, border);
+            frames.insert(name, rect);
+        }
+        Ok(TextureAtlas { image, frames })
+    }
+}
+
+/// Copies the outermost pixels of `sprite`, drawn at `rect`, `amount` pixels outwards.
+fn extrude_edges(atlas: &mut Image, sprite: &Image, rect: Rectangle, amount: i32) {
+    let (w, h) = (rect.width, rect.height);
+    for i in 1..=amount {
+        let i = i as f32;
+        let edges = [
+            // left, right, top, bottom
+            (Rectangle::new(0.0, 0.0, 1.0, h), rect.x - i, rect.y),
+            (
+                Rectangle::new(w - 1.0, 0.0, 1.0, h),
+                rect.x + w - 1.0 + i,
+                rect.y,
+            ),
+            (Rectangle::new(0.0, 0.0, w, 1.0), rect.x, rect.y - i),
+            (
+                Rectangle::new(0.0, h - 1.0, w, 1.0),
+                rect.x,
+                rect.y + h - 1.0 + i,
+            ),
+        ];
+        for (src, x, y) in edges.iter().copied() {
+            atlas.draw(
+                sprite,
+                src,
+                Rectangle::new(x, y, src.width, src.height),
+                Color::WHITE,
+            );
+        }
+    }
+    if amount > 0 {
+        let corners = [
+            (0, 0, rect.x as i32 - amount, rect.y as i32 - amount),
+            (w as i32 - 1, 0, (rect.x + w) as i32, rect.y as i32 - amount),
+            (0, h as i32 - 1, rect.x as i32 - amount, (rect.y + h) as i32),
+            (
+                w as i32 - 1,
+                h as i32 - 1,
+                (rect.x + w) as i32,
+                (rect.y + h) as i32,
+            ),
+        ];
+        for (sx, sy, x, y) in corners.iter().copied() {
+            let color = unsafe { ffi::GetImageColor(sprite.0, sx, sy) };
+            atlas.draw_rectangle(x, y, amount, amount, color);
+        }
+    }
+}
+
+/// Top-left corner of every packed size, then the atlas width and height.
+type Packing = (Vec<(i32, i32)>, i32, i32);
+
+/// Finds the smallest atlas the sizes fit in, growing from the smallest power of two.
+fn pack_sizes(
+    sizes: &[(i32, i32)],
+    algorithm: PackAlgorithm,
+    padding: i32,
+    max_size: i32,
+    power_of_two: bool,
+) -> Option<Packing> {
+    let area: i64 = sizes.iter().map(|&(w, h)| w as i64 * h as i64).sum();
+    let max_w = sizes.iter().map(|s| s.0).max().unwrap_or(0) + padding;
+    let max_h = sizes.iter().map(|s| s.1).max().unwrap_or(0) + padding;
+    let mut side = 1;
+    while (side as i64) * (side as i64) < area {
+        side *= 2;
+    }
+    let mut width = side.max(next_power_of_two(max_w));
+    let mut height = side.max(next_power_of_two(max_h));
+
+    // Biggest first, ties broken by insertion order so the output is deterministic
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| (std::cmp::Reverse(sizes[i].1.max(sizes[i].0)), i));
+
+    loop {
+        if width > max_size || height > max_size {
+            return None;
+        }
+        let packed = match algorithm {
+            PackAlgorithm::Skyline => {
+                pack_skyline(sizes, &order, width - padding, height - padding)
+            }
+            PackAlgorithm::MaxRects => {
+                pack_maxrects(sizes, &order, width - padding, height - padding)
+            }
+        };
+        if let Some(positions) = packed {
+            let positions: Vec<_> = positions
+                .into_iter()
+                .map(|(x, y)| (x + padding, y + padding))
+                .collect();
+            if !power_of_two {
+                let used = |f: fn(&(i32, i32)) -> i32, g: fn(&(i32, i32)) -> i32| {
+                    positions
+                        .iter()
+                        .zip(sizes)
+                        .map(|(p, s)| f(p) + g(s))
+                        .max()
+                        .unwrap_or(padding)
+                };
+                width = used(|p| p.0, |s| s.0);
+                height = used(|p| p.1, |s| s.1);
+            }
+            return Some((positions, width.max(1), height.max(1)));
+        }
+        if width <= height {
+            width *= 2;
+        } else {
+            height *= 2;
+        }
+    }
+}
+
+fn next_power_of_two(v: i32) -> i32 {
+    (v.max(1) as u32).next_power_of_two() as i32
+}
+
+/// Bottom-left skyline packing. Skyline segments are `(x, y, width)`.
+fn pack_skyline(
+    sizes: &[(i32, i32)],
+    order: &[usize],
+    bin_w: i32,
+    bin_h: i32,
+) -> Option<Vec<(i32, i32)>> {
+    let mut skyline = vec![(0, 0, bin_w)];
+    let mut positions = vec![(0, 0); sizes.len()];
+    for &i in order {
+        let (w, h) = sizes[i];
+        // Lowest top edge first, then the narrowest segment
+        let mut best: Option<((i32, i32), usize, i32)> = None;
+        for n in 0..skyline.len() {
+            if let Some(y) = skyline_fit(&skyline, n, w, h, bin_w, bin_h) {
+                let score = (y + h, skyline[n].2);
+                if best.map(|(s, _, _)| score < s).unwrap_or(true) {
+                    best = Some((score, n, y));
+                }
+            }
+        }
+        let (_, n, y) = best?;
+        let x = skyline[n].0;
+        positions[i] = (x, y);
+
+        skyline.insert(n, (x, y + h, w));
+        let right = x + w;
+        while n + 1 < skyline.len() && skyline[n + 1].0 < right {
+            let shrink = right - skyline[n + 1].0;
+            if skyline[n + 1].2 <= shrink {
+                skyline.remove(n + 1);
+            } else {
+                skyline[n + 1].0 += shrink;
+                skyline[n + 1].2 -= shrink;
+                break;
+            }
+        }
+        let mut j = 0;
+        while j + 1 < skyline.len() {
+            if skyline[j].1 == skyline[j + 1].1 {
+                skyline[j].2 += skyline[j + 1].2;
+                skyline.remove(j + 1);
+            } else {
+                j += 1;
+            }
+        }
+    }
+    Some(positions)
+}
+
+/// The y a `w` by `h` rectangle rests at when placed at the start of skyline segment `n`.
+fn skyline_fit(
+    skyline: &[(i32, i32, i32)],
+    n: usize,
+    w: i32,
+    h: i32,
+    bin_w: i32,
+    bin_h: i32,
+) -> Option<i32> {
+    if skyline[n].0 + w > bin_w {
+        return None;
+    }
+    let mut y = 0;
+    let mut remaining = w;
+    for segment in &skyline[n..] {
+        if remaining <= 0 {
+            break;
+        }
+        y = y.max(segment.1);
+        remaining -= segment.2;
+    }
+    if y + h > bin_h {
+        None
+    } else {
+        Some(y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+impl Rect {
+    fn intersects(&self, o: &Rect) -> bool {
+        self.x < o.x + o.w && o.x < self.x + self.w && self.y < o.y + o.h && o.y < self.y + self.h
+    }
+
+    fn contains(&self, o: &Rect) -> bool {
+        o.x >= self.x
+            && o.y >= self.y
+            && o.x + o.w <= self.x + self.w
+            && o.y + o.h <= self.y + self.h
+    }
+}
+
+/// Maximal rectangles packing with the best short side fit heuristic.
+fn pack_maxrects(
+    sizes: &[(i32, i32)],
+    order: &[usize],
+    bin_w: i32,
+    bin_h: i32,
+) -> Option<Vec<(i32, i32)>> {
+    let mut free = vec![Rect {
+        x: 0,
+        y: 0,
+        w: bin_w,
+        h: bin_h,
+    }];
+    let mut positions = vec![(0, 0); sizes.len()];
+    for &i in order {
+        let (w, h) = sizes[i];
+        let mut best: Option<((i32, i32), Rect)> = None;
+        for f in &free {
+            if w <= f.w && h <= f.h {
+                let (lw, lh) = (f.w - w, f.h - h);
+                let score = (lw.min(lh), lw.max(lh));
+                if best.map(|(s, _)| score < s).unwrap_or(true) {
+                    best = Some((
+                        score,
+                        Rect {
+                            x: f.x,
+                            y: f.y,
+                            w,
+                            h,
+                        },
+                    ));
+                }
+            }
+        }
+        let (_, placed) = best?;
+        positions[i] = (placed.x, placed.y);
+
+        let mut split = Vec::new();
+        free.retain(|f| {
+            if !f.intersects(&placed) {
+                return true;
+            }
+            if placed.x > f.x {
+                split.push(Rect {
+                    w: placed.x - f.x,
+                    ..*f
+                });
+            }
+            if placed.x + placed.w < f.x + f.w {
+                split.push(Rect {
+                    x: placed.x + placed.w,
+                    w: f.x + f.w - placed.x - placed.w,
+                    ..*f
+                });
+            }
+            if placed.y > f.y {
+                split.push(Rect {
+                    h: placed.y - f.y,
+                    ..*f
+                });
+            }
+            if placed.y + placed.h < f.y + f.h {
+                split.push(Rect {
+                    y: placed.y + placed.h,
+                    h: f.y + f.h - placed.y - placed.h,
+                    ..*f
+                });
+            }
+            false
+        });
+        free.extend(split);
+
+        // Drop free rectangles contained in another one
+        let mut j = 0;
+        while j < free.len() {
+            let contained = (0..free.len())
+                .any(|k| k != j && free[k].contains(&free[j]) && (free[k] != free[j] || k < j));
+            if contained {
+                free.remove(j);
+            } else {
+                j += 1;
+            }
+        }
+    }
+    Some(positions)
+}
+
+/// Layout of the frames array in exported JSON.
+#[cfg(feature = "with_serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtlasJsonFormat {
+    /// `"frames": { "name": { ... } }`
+    Hash,
+    /// `"frames": [ { "filename": "name", ... } ]`
+    Array,
+}
+
+/// A packed atlas image and the rectangle of every sprite in it.
+#[derive(Debug)]
+pub struct TextureAtlas {
+    image: Image,
+    frames: BTreeMap<String, Rectangle>,
+}
+
+impl TextureAtlas {
+    pub fn new(image: Image, frames: BTreeMap<String, Rectangle>) -> Self {
+        TextureAtlas { image, frames }
+    }
+
+    /// Source rectangle of a sprite, for `draw_texture_rec` and `draw_texture_pro`.
+    pub fn get(&self, name: &str) -> Option<Rectangle> {
+        self.frames.get(name).copied()
+    }
+
+    pub fn frames(&self) -> &BTreeMap<String, Rectangle> {
+        &self.frames
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn into_image(self) -> Image {
+        self.image
+    }
+
+    /// Uploads the atlas image to the GPU.
+    pub fn load_texture(
+        &self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+    ) -> Result<Texture2D, Error> {
+        rl.load_texture_from_image(thread, &self.image)
+    }
+
+    /// Serializes the frames in the TexturePacker / Aseprite JSON format. `image_file` is written
+    /// to `meta.image`.
+    #[cfg(feature = "with_serde")]
+    pub fn to_json(&self, format: AtlasJsonFormat, image_file: &str) -> Result<String, Error> {
+        let frame = |name: &str, r: &Rectangle| JsonFrame {
+            filename: match format {
+                AtlasJsonFormat::Hash => None,
+                AtlasJsonFormat::Array => Some(name.to_owned()),
+            },
+            frame: JsonRect::from(r),
+            rotated: false,
+            trimmed: false,
+            sprite_source_size: Some(JsonRect {
+                x: 0,
+                y: 0,
+                w: r.width as i32,
+                h: r.height as i32,
+            }),
+            source_size: Some(JsonSize {
+                w: r.width as i32,
+                h: r.height as i32,
+            }),
+        };
+        let frames = match format {
+            AtlasJsonFormat::Hash => JsonFrames::Hash(
+                self.frames
+                    .iter()
+                    .map(|(n, r)| (n.clone(), frame(n, r)))
+                    .collect(),
+            ),
+            AtlasJsonFormat::Array => {
+                JsonFrames::Array(self.frames.iter().map(|(n, r)| frame(n, r)).collect())
+            }
+        };
+        let json = JsonAtlas {
+            frames,
+            meta: JsonMeta {
+                app: Some("raylib-rs".to_owned()),
+                version: Some("1.0".to_owned()),
+                image: Some(image_file.to_owned()),
+                format: Some("RGBA8888".to_owned()),
+                size: Some(JsonSize {
+                    w: self.image.width(),
+                    h: self.image.height(),
+                }),
+                scale: Some(serde_json::Value::from("1")),
+            },
+        };
+        serde_json::to_string_pretty(&json).map_err(|e| {
+            error!(std::borrow::Cow::Owned(format!(
+                "Could not serialize atlas: {}",
+                e
+            )))
+        })
+    }
+
+    /// Reads frames from TexturePacker / Aseprite JSON (hash or array), paired with the atlas
+    /// image. Trim offsets are ignored. Rotated frames aren't supported.
+    #[cfg(feature = "with_serde")]
+    pub fn from_json(json: &str, image: Image) -> Result<Self, Error> {
+        let (frames, _) = parse_json(json)?;
+        Ok(TextureAtlas { image, frames })
+    }
+
+    /// Loads a JSON descriptor and the image named in its `meta.image`, relative to the JSON file.
+    #[cfg(feature = "with_serde")]
+    pub fn load(json_path: impl AsRef<Path>) -> Result<Self, Error> {
+        let json_path = json_path.as_ref();
+        let json = std::fs::read_to_string(json_path)
+            .map_err(|_| error!("Could not read atlas descriptor", json_path))?;
+        let (frames, image_file) = parse_json(&json)?;
+        let image_file = image_file.ok_or_else(|| error!("Atlas has no meta.image", json_path))?;
+        let image_path = json_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(image_file);
+        let image = Image::load_image(
+            image_path
+                .to_str()
+                .ok_or_else(|| error!("Invalid atlas image path", &image_path))?,
+        )?;
+        Ok(TextureAtlas { image, frames })
+    }
+
+    /// Exports the atlas image as a PNG next to `json_path`, with the same file stem, and the
+    /// descriptor to `json_path`.
+    #[cfg(feature = "with_serde")]
+    pub fn export(
+        &self,
+        json_path: impl AsRef<Path>,
+        format: AtlasJsonFormat,
+    ) -> Result<(), Error> {
+        let json_path = json_path.as_ref();
+        let image_path = json_path.with_extension("png");
+        let image_file = image_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| error!("Invalid atlas file name", json_path))?;
+        let c_path = std::ffi::CString::new(
+            image_path
+                .to_str()
+                .ok_or_else(|| error!("Invalid atlas file name", json_path))?,
+        )
+        .map_err(|_| error!("Invalid atlas file name", json_path))?;
+        if !unsafe { ffi::ExportImage(self.image.0, c_path.as_ptr()) } {
+            return Err(error!("Could not export atlas image", image_path));
+        }
+        std::fs::write(json_path, self.to_json(format, image_file)?)
+            .map_err(|_| error!("Could not write atlas descriptor", json_path))
+    }
+}
+
+#[cfg(feature = "with_serde")]
+fn parse_json(json: &str) -> Result<(BTreeMap<String, Rectangle>, Option<String>), Error> {
+    let atlas: JsonAtlas = serde_json::from_str(json).map_err(|e| {
+        error!(std::borrow::Cow::Owned(format!(
+            "Could not parse atlas: {}",
+            e
+        )))
+    })?;
+    let named: Vec<(String, JsonFrame)> = match atlas.frames {
+        JsonFrames::Hash(frames) => frames.into_iter().collect(),
+        JsonFrames::Array(frames) => frames
+            .into_iter()
+            .enumerate()
+            .map(|(i, f)| (f.filename.clone().unwrap_or_else(|| i.to_string()), f))
+            .collect(),
+    };
+    let mut frames = BTreeMap::new();
+    for (name, f) in named {
+        if f.rotated {
+            return Err(error!(std::borrow::Cow::Owned(format!(
+                "Frame `{}` is rotated, rotated frames are not supported",
+                name
+            ))));
+        }
+        let r = f.frame;
+        frames.insert(
+            name,
+            Rectangle::new(r.x as f32, r.y as f32, r.w as f32, r.h as f32),
+        );
+    }
+    Ok((frames, atlas.meta.image))
+}
+
+#[cfg(feature = "with_serde")]
+#[derive(Serialize, Deserialize)]
+struct JsonAtlas {
+    frames: JsonFrames,
+    #[serde(default)]
+    meta: JsonMeta,
+}
+
+#[cfg(feature = "with_serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonFrames {
+    Hash(BTreeMap<String, JsonFrame>),
+    Array(Vec<JsonFrame>),
+}
+
+#[cfg(feature = "with_serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFrame {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    filename: Option<String>,
+    frame: JsonRect,
+    #[serde(default)]
+    rotated: bool,
+    #[serde(default)]
+    trimmed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sprite_source_size: Option<JsonRect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_size: Option<JsonSize>,
+}
+
+#[cfg(feature = "with_serde")]
+#[derive(Serialize, Deserialize)]
+struct JsonRect {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+#[cfg(feature = "with_serde")]
+impl From<&Rectangle> for JsonRect {
+    fn from(r: &Rectangle) -> JsonRect {
+        JsonRect {
+            x: r.x as i32,
+            y: r.y as i32,
+            w: r.width as i32,
+            h: r.height as i32,
+        }
+    }
+}
+
+#[cfg(feature = "with_serde")]
+#[derive(Serialize, Deserialize)]
+struct JsonSize {
+    w: i32,
+    h: i32,
+}
+
+#[cfg(feature = "with_serde")]
+#[derive(Default, Serialize, Deserialize)]
+struct JsonMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    app: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size: Option<JsonSize>,
+    // TexturePacker writes a string, Aseprite a number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scale: Option<serde_json::Value>,
+}
@@ -8,6 +8,13 @@ use std::mem::ManuallyDrop;
 use std::ops::{Deref, DerefMut};
 use std::path::Path;
 
+mod dsp;
+mod mixer;
+mod spatial;
+pub use dsp::*;
+pub use mixer::*;
+pub use spatial::*;
+
 make_thin_wrapper_lifetime!(Wave, ffi::Wave, RaylibAudio, ffi::UnloadWave);
 
 make_thin_wrapper_lifetime!(Sound, ffi::Sound, RaylibAudio, (ffi::UnloadSound), true);
@@ -435,6 +442,24 @@ impl Drop for SoundAlias<'_, '_> {
     }
 }
 
+impl AsRef<ffi::AudioStream> for SoundAlias<'_, '_> {
+    fn as_ref(&self) -> &ffi::AudioStream {
+        &self.0.stream
+    }
+}
+
+impl<'aud> AsRef<ffi::AudioStream> for Music<'aud> {
+    fn as_ref(&self) -> &ffi::AudioStream {
+        &self.0.stream
+    }
+}
+
+impl<'aud> AsMut<ffi::AudioStream> for Music<'aud> {
+    fn as_mut(&mut self) -> &mut ffi::AudioStream {
+        &mut self.0.stream
+    }
+}
+
 impl<'aud> Music<'aud> {
     /// Starts music playing.
     #[inline]
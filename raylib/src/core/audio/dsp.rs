@@ -0,0 +1,318 @@
+//! Effects over interleaved stereo `f32` frames, the format audio processors receive.
+//!
+//! They can be used directly with [`RaylibAudio::attach_audio_stream_processor`] or through the
+//! buses of an [`AudioMixer`].
+//!
+//! [`RaylibAudio::attach_audio_stream_processor`]: crate::audio::RaylibAudio::attach_audio_stream_processor
+//! [`AudioMixer`]: crate::audio::AudioMixer
+use std::f32::consts::PI;
+
+/// Response of a [`Biquad`] filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BiquadKind {
+    LowPass,
+    HighPass,
+}
+
+/// Two-pole filter with the coefficients from the RBJ audio EQ cookbook.
+#[derive(Debug, Clone)]
+pub struct Biquad {
+    kind: BiquadKind,
+    cutoff: f32,
+    q: f32,
+    sample_rate: f32,
+    b: [f32; 3],
+    a: [f32; 2],
+    state: [[f32; 2]; 2],
+}
+
+impl Biquad {
+    pub fn new(kind: BiquadKind, cutoff: f32, q: f32, sample_rate: f32) -> Self {
+        let mut filter = Biquad {
+            kind,
+            cutoff,
+            q,
+            sample_rate,
+            b: [1.0, 0.0, 0.0],
+            a: [0.0, 0.0],
+            state: [[0.0; 2]; 2],
+        };
+        filter.update_coefficients();
+        filter
+    }
+
+    /// Low-pass at `cutoff` Hz with a flat (Butterworth) response.
+    pub fn low_pass(cutoff: f32, sample_rate: f32) -> Self {
+        Biquad::new(
+            BiquadKind::LowPass,
+            cutoff,
+            std::f32::consts::FRAC_1_SQRT_2,
+            sample_rate,
+        )
+    }
+
+    /// High-pass at `cutoff` Hz with a flat (Butterworth) response.
+    pub fn high_pass(cutoff: f32, sample_rate: f32) -> Self {
+        Biquad::new(
+            BiquadKind::HighPass,
+            cutoff,
+            std::f32::consts::FRAC_1_SQRT_2,
+            sample_rate,
+        )
+    }
+
+    pub fn kind(&self) -> BiquadKind {
+        self.kind
+    }
+
+    pub fn cutoff(&self) -> f32 {
+        self.cutoff
+    }
+
+    /// Changes the response without clearing the filter state, so it can be swept while playing.
+    pub fn set_params(&mut self, kind: BiquadKind, cutoff: f32, q: f32) {
+        self.kind = kind;
+        self.cutoff = cutoff;
+        self.q = q;
+        self.update_coefficients();
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.update_coefficients();
+    }
+
+    fn update_coefficients(&mut self) {
+        let nyquist = self.sample_rate * 0.5;
+        let cutoff = self.cutoff.clamp(10.0, nyquist * 0.99);
+        let w0 = 2.0 * PI * cutoff / self.sample_rate;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * self.q.max(0.01));
+        let a0 = 1.0 + alpha;
+        let b = match self.kind {
+            BiquadKind::LowPass => [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            BiquadKind::HighPass => [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+        };
+        self.b = [b[0] / a0, b[1] / a0, b[2] / a0];
+        self.a = [-2.0 * cos / a0, (1.0 - alpha) / a0];
+    }
+
+    pub fn reset(&mut self) {
+        self.state = [[0.0; 2]; 2];
+    }
+
+    pub fn process(&mut self, frames: &mut [f32]) {
+        let (b, a) = (self.b, self.a);
+        for frame in frames.chunks_exact_mut(2) {
+            for (x, z) in frame.iter_mut().zip(self.state.iter_mut()) {
+                let y = b[0] * *x + z[0];
+                z[0] = b[1] * *x - a[0] * y + z[1];
+                z[1] = b[2] * *x - a[1] * y;
+                *x = y;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Comb {
+    buffer: Vec<f32>,
+    index: usize,
+    store: f32,
+}
+
+impl Comb {
+    fn new(len: usize) -> Self {
+        Comb {
+            buffer: vec![0.0; len.max(1)],
+            index: 0,
+            store: 0.0,
+        }
+    }
+
+    fn tick(&mut self, input: f32, feedback: f32, damp: f32) -> f32 {
+        let out = self.buffer[self.index];
+        self.store = out * (1.0 - damp) + self.store * damp;
+        self.buffer[self.index] = input + self.store * feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AllPass {
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+impl AllPass {
+    fn new(len: usize) -> Self {
+        AllPass {
+            buffer: vec![0.0; len.max(1)],
+            index: 0,
+        }
+    }
+
+    fn tick(&mut self, input: f32) -> f32 {
+        let delayed = self.buffer[self.index];
+        self.buffer[self.index] = input + delayed * 0.5;
+        self.index = (self.index + 1) % self.buffer.len();
+        delayed - input
+    }
+}
+
+/// Freeverb tunings at 44.1kHz.
+const COMB_TUNING: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+const ALLPASS_TUNING: [usize; 4] = [556, 441, 341, 225];
+const STEREO_SPREAD: usize = 23;
+
+/// Room reverb after Jezar's Freeverb: parallel comb filters into series all-pass filters.
+#[derive(Debug, Clone)]
+pub struct Reverb {
+    combs: [Vec<Comb>; 2],
+    allpasses: [Vec<AllPass>; 2],
+    /// 0 is a small room, 1 a large hall.
+    pub room_size: f32,
+    /// How quickly high frequencies die out, 0 to 1.
+    pub damping: f32,
+    /// Mix between the dry input (0) and the reverb (1).
+    pub wet: f32,
+}
+
+impl Reverb {
+    pub fn new(room_size: f32, damping: f32, wet: f32, sample_rate: f32) -> Self {
+        let scale = sample_rate / 44100.0;
+        let delay = |tuning: usize, channel: usize| {
+            ((tuning + channel * STEREO_SPREAD) as f32 * scale) as usize
+        };
+        let combs = |channel: usize| -> Vec<Comb> {
+            COMB_TUNING
+                .iter()
+                .map(|&t| Comb::new(delay(t, channel)))
+                .collect()
+        };
+        let allpasses = |channel: usize| -> Vec<AllPass> {
+            ALLPASS_TUNING
+                .iter()
+                .map(|&t| AllPass::new(delay(t, channel)))
+                .collect()
+        };
+        Reverb {
+            combs: [combs(0), combs(1)],
+            allpasses: [allpasses(0), allpasses(1)],
+            room_size,
+            damping,
+            wet,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for comb in self.combs.iter_mut().flatten() {
+            comb.buffer.iter_mut().for_each(|s| *s = 0.0);
+            comb.store = 0.0;
+        }
+        for allpass in self.allpasses.iter_mut().flatten() {
+            allpass.buffer.iter_mut().for_each(|s| *s = 0.0);
+        }
+    }
+
+    pub fn process(&mut self, frames: &mut [f32]) {
+        let feedback = self.room_size.clamp(0.0, 1.0) * 0.28 + 0.7;
+        let damp = self.damping.clamp(0.0, 1.0) * 0.4;
+        let wet = self.wet.clamp(0.0, 1.0);
+        for frame in frames.chunks_exact_mut(2) {
+            let input = (frame[0] + frame[1]) * 0.015;
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                let mut out: f32 = self.combs[channel]
+                    .iter_mut()
+                    .map(|c| c.tick(input, feedback, damp))
+                    .sum();
+                for allpass in self.allpasses[channel].iter_mut() {
+                    out = allpass.tick(out);
+                }
+                *sample = *sample * (1.0 - wet) + out * 3.0 * wet;
+            }
+        }
+    }
+}
+
+/// Feed-forward compressor with a peak detector linked across both channels.
+#[derive(Debug, Clone)]
+pub struct Compressor {
+    /// Level in dBFS above which gain is reduced.
+    pub threshold_db: f32,
+    /// How many dB over the threshold become 1 dB, like 4 for 4:1.
+    pub ratio: f32,
+    /// Seconds to react to louder input.
+    pub attack: f32,
+    /// Seconds to recover once the input gets quieter.
+    pub release: f32,
+    /// Gain in dB applied after compression.
+    pub makeup_db: f32,
+    sample_rate: f32,
+    envelope_db: f32,
+}
+
+impl Compressor {
+    pub fn new(threshold_db: f32, ratio: f32, sample_rate: f32) -> Self {
+        Compressor {
+            threshold_db,
+            ratio,
+            attack: 0.01,
+            release: 0.1,
+            makeup_db: 0.0,
+            sample_rate,
+            envelope_db: -120.0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// Current gain reduction in dB, 0 or negative.
+    pub fn gain_reduction_db(&self) -> f32 {
+        let over = self.envelope_db - self.threshold_db;
+        if over > 0.0 {
+            -over * (1.0 - 1.0 / self.ratio.max(1.0))
+        } else {
+            0.0
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.envelope_db = -120.0;
+    }
+
+    pub fn process(&mut self, frames: &mut [f32]) {
+        let attack = smoothing(self.attack, self.sample_rate);
+        let release = smoothing(self.release, self.sample_rate);
+        for frame in frames.chunks_exact_mut(2) {
+            let peak = frame[0].abs().max(frame[1].abs());
+            let level = 20.0 * peak.max(1e-6).log10();
+            let coef = if level > self.envelope_db {
+                attack
+            } else {
+                release
+            };
+            self.envelope_db = level + coef * (self.envelope_db - level);
+            let gain = db_to_gain(self.gain_reduction_db() + self.makeup_db);
+            frame[0] *= gain;
+            frame[1] *= gain;
+        }
+    }
+}
+
+/// One-pole smoothing coefficient reaching ~63% of a step in `seconds`.
+pub(crate) fn smoothing(seconds: f32, sample_rate: f32) -> f32 {
+    if seconds <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / (seconds * sample_rate)).exp()
+    }
+}
+
+pub(crate) fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
@@ -0,0 +1,792 @@
+//! Named buses that sounds, music and streams are routed through, each with a volume, mute and
+//! a chain of effects.
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//! let audio = RaylibAudio::init_audio_device().unwrap();
+//! let mut mixer = audio.new_mixer();
+//! mixer
+//!     .add_effect("music", "duck", AudioEffect::duck("voice", 0.6))
+//!     .unwrap();
+//! mixer
+//!     .add_effect("sfx", "muffle", AudioEffect::low_pass(800.0))
+//!     .unwrap();
+//! mixer.set_effect_enabled("sfx", "muffle", false).unwrap();
+//!
+//! let music = audio.new_music("theme.ogg").unwrap();
+//! let line = audio.new_sound("hello.wav").unwrap();
+//! let _music_route = mixer.route(&music, "music").unwrap();
+//! let _line_route = mixer.route(&line, "voice").unwrap();
+//! mixer.bus("music").unwrap().set_volume(0.8);
+//! music.play_stream();
+//! line.play();
+//! ```
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::core::audio::{Biquad, BiquadKind, Compressor, RaylibAudio, Reverb};
+use crate::core::callbacks::AudioStreamProcessor;
+use crate::error::{error, Error};
+use crate::ffi;
+
+use super::dsp::smoothing;
+
+/// Buses every mixer starts with.
+pub const DEFAULT_BUSES: [&str; 4] = ["music", "sfx", "voice", "ui"];
+
+/// How long a bus level is held before it counts as silence, in milliseconds.
+const METER_HOLD_MS: u32 = 50;
+
+/// An effect in a bus chain.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioEffect {
+    /// Removes frequencies above `cutoff` Hz. `q` around 0.707 gives a flat response.
+    LowPass { cutoff: f32, q: f32 },
+    /// Removes frequencies below `cutoff` Hz.
+    HighPass { cutoff: f32, q: f32 },
+    /// Room reverb, see [`Reverb`].
+    Reverb {
+        room_size: f32,
+        damping: f32,
+        wet: f32,
+    },
+    /// Dynamic range compression, see [`Compressor`].
+    Compressor {
+        threshold_db: f32,
+        ratio: f32,
+        attack: f32,
+        release: f32,
+        makeup_db: f32,
+    },
+    /// Lowers the volume by `amount` (0 to 1) while the `sidechain` bus is louder than
+    /// `threshold`, e.g. music under dialogue. `attack` and `release` are in seconds.
+    Duck {
+        sidechain: String,
+        amount: f32,
+        threshold: f32,
+        attack: f32,
+        release: f32,
+    },
+}
+
+impl AudioEffect {
+    pub fn low_pass(cutoff: f32) -> Self {
+        AudioEffect::LowPass {
+            cutoff,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    pub fn high_pass(cutoff: f32) -> Self {
+        AudioEffect::HighPass {
+            cutoff,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    pub fn reverb(room_size: f32, wet: f32) -> Self {
+        AudioEffect::Reverb {
+            room_size,
+            damping: 0.5,
+            wet,
+        }
+    }
+
+    pub fn compressor(threshold_db: f32, ratio: f32) -> Self {
+        AudioEffect::Compressor {
+            threshold_db,
+            ratio,
+            attack: 0.01,
+            release: 0.1,
+            makeup_db: 0.0,
+        }
+    }
+
+    pub fn duck(sidechain: &str, amount: f32) -> Self {
+        AudioEffect::Duck {
+            sidechain: sidechain.to_owned(),
+            amount,
+            threshold: 0.01,
+            attack: 0.05,
+            release: 0.4,
+        }
+    }
+
+    fn sidechain(&self) -> Option<&str> {
+        match self {
+            AudioEffect::Duck { sidechain, .. } => Some(sidechain),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct EffectSlot {
+    name: String,
+    effect: AudioEffect,
+    enabled: bool,
+    sidechain: Option<Arc<BusShared>>,
+}
+
+#[derive(Debug)]
+struct BusShared {
+    name: String,
+    volume: AtomicU32,
+    muted: AtomicBool,
+    sample_rate: AtomicU32,
+    /// Bumped whenever `effects` or the sample rate change, so processors know to resync.
+    generation: AtomicUsize,
+    effects: Mutex<Vec<EffectSlot>>,
+    /// Loudest recent peak as f32 bits, with the time it was measured in the high 32 bits.
+    meter: AtomicU64,
+    epoch: Instant,
+}
+
+impl BusShared {
+    fn now_ms(&self) -> u32 {
+        self.epoch.elapsed().as_millis() as u32
+    }
+
+    fn level(&self) -> f32 {
+        let meter = self.meter.load(Ordering::Relaxed);
+        if self.now_ms().wrapping_sub((meter >> 32) as u32) > METER_HOLD_MS {
+            0.0
+        } else {
+            f32::from_bits(meter as u32)
+        }
+    }
+
+    fn report_level(&self, peak: f32) {
+        let now = self.now_ms();
+        let _ = self
+            .meter
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |meter| {
+                let stale = now.wrapping_sub((meter >> 32) as u32) > METER_HOLD_MS;
+                let level = if stale {
+                    peak
+                } else {
+                    peak.max(f32::from_bits(meter as u32))
+                };
+                Some((now as u64) << 32 | level.to_bits() as u64)
+            });
+    }
+
+    fn changed(&self) {
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// Handle to a bus of an [`AudioMixer`]. Clones control the same bus and can be moved to other
+/// threads.
+#[derive(Debug, Clone)]
+pub struct MixerBus(Arc<BusShared>);
+
+impl MixerBus {
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    pub fn volume(&self) -> f32 {
+        f32::from_bits(self.0.volume.load(Ordering::Relaxed))
+    }
+
+    /// Sets the bus volume, applied on top of each stream's own. Changes are ramped over one
+    /// buffer to avoid clicks.
+    pub fn set_volume(&self, volume: f32) {
+        self.0
+            .volume
+            .store(volume.max(0.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.0.muted.load(Ordering::Relaxed)
+    }
+
+    pub fn set_muted(&self, muted: bool) {
+        self.0.muted.store(muted, Ordering::Relaxed);
+    }
+
+    /// Peak sample of the bus output over the last few milliseconds, 0 when nothing is playing.
+    pub fn level(&self) -> f32 {
+        self.0.level()
+    }
+}
+
+/// Stream routed to a bus with [`AudioMixer::route`]. Dropping it takes the stream off the bus.
+#[must_use = "the stream is taken off the bus as soon as the route is dropped"]
+#[derive(Debug)]
+pub struct BusRoute<'s> {
+    bus: MixerBus,
+    _processor: AudioStreamProcessor<'s>,
+}
+
+impl BusRoute<'_> {
+    pub fn bus(&self) -> &MixerBus {
+        &self.bus
+    }
+}
+
+/// Buses with volume, mute and effects, applied through audio stream processors.
+///
+/// Every bus runs its effects separately on each stream routed to it, so a reverb on a bus with
+/// three sounds playing runs three times. That keeps streams independent of the order raylib
+/// mixes them in, but heavy effects are best kept on buses with few streams, like music.
+///
+/// raylib doesn't report the device sample rate, so effects assume 48000Hz unless told otherwise
+/// with [`AudioMixer::sample_rate`].
+#[derive(Debug)]
+pub struct AudioMixer<'aud> {
+    audio: &'aud RaylibAudio,
+    buses: Vec<MixerBus>,
+    sample_rate: u32,
+    epoch: Instant,
+}
+
+impl<'aud> AudioMixer<'aud> {
+    /// A mixer with the [`DEFAULT_BUSES`].
+    pub fn new(audio: &'aud RaylibAudio) -> Self {
+        let mut mixer = AudioMixer {
+            audio,
+            buses: Vec::new(),
+            sample_rate: 48000,
+            epoch: Instant::now(),
+        };
+        for name in DEFAULT_BUSES.iter() {
+            mixer.add_bus(name);
+        }
+        mixer
+    }
+
+    /// Sample rate of the audio device, which the effects are tuned for.
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        for bus in self.buses.iter() {
+            bus.0.sample_rate.store(sample_rate, Ordering::Relaxed);
+            bus.0.changed();
+        }
+        self
+    }
+
+    /// Adds a bus named `name`, or returns the existing one.
+    pub fn add_bus(&mut self, name: &str) -> &MixerBus {
+        if let Some(index) = self.buses.iter().position(|b| b.name() == name) {
+            return &self.buses[index];
+        }
+        self.buses.push(MixerBus(Arc::new(BusShared {
+            name: name.to_owned(),
+            volume: AtomicU32::new(1f32.to_bits()),
+            muted: AtomicBool::new(false),
+            sample_rate: AtomicU32::new(self.sample_rate),
+            generation: AtomicUsize::new(0),
+            effects: Mutex::new(Vec::new()),
+            meter: AtomicU64::new(0),
+            epoch: self.epoch,
+        })));
+        self.buses.last().unwrap()
+    }
+
+    pub fn bus(&self, name: &str) -> Option<&MixerBus> {
+        self.buses.iter().find(|b| b.name() == name)
+    }
+
+    pub fn buses(&self) -> impl Iterator<Item = &MixerBus> {
+        self.buses.iter()
+    }
+
+    fn find_bus(&self, name: &str) -> Result<&MixerBus, Error> {
+        self.bus(name).ok_or_else(|| {
+            error!(std::borrow::Cow::Owned(format!(
+                "no mixer bus named {}",
+                name
+            )))
+        })
+    }
+
+    fn sidechain(&self, bus: &str, effect: &AudioEffect) -> Result<Option<Arc<BusShared>>, Error> {
+        match effect.sidechain() {
+            Some(sidechain) if sidechain == bus => Err(error!("a bus can't duck itself")),
+            Some(sidechain) => Ok(Some(self.find_bus(sidechain)?.0.clone())),
+            None => Ok(None),
+        }
+    }
+
+    /// Appends `effect` to the chain of `bus` under `name`, or replaces the effect already called
+    /// `name`.
+    pub fn add_effect(&mut self, bus: &str, name: &str, effect: AudioEffect) -> Result<(), Error> {
+        let sidechain = self.sidechain(bus, &effect)?;
+        let shared = &self.find_bus(bus)?.0;
+        {
+            let mut effects = shared.effects.lock().unwrap();
+            match effects.iter_mut().find(|e| e.name == name) {
+                Some(slot) => {
+                    slot.effect = effect;
+                    slot.sidechain = sidechain;
+                }
+                None => effects.push(EffectSlot {
+                    name: name.to_owned(),
+                    effect,
+                    enabled: true,
+                    sidechain,
+                }),
+            }
+        }
+        shared.changed();
+        Ok(())
+    }
+
+    /// Changes the parameters of the effect `name`. Filters, compressors and duckers keep their
+    /// state when the kind of effect stays the same, so parameters can be swept while playing.
+    pub fn set_effect(&mut self, bus: &str, name: &str, effect: AudioEffect) -> Result<(), Error> {
+        let sidechain = self.sidechain(bus, &effect)?;
+        self.with_effect(bus, name, |slot| {
+            slot.effect = effect;
+            slot.sidechain = sidechain;
+        })
+    }
+
+    /// Bypasses the effect `name` without losing its place in the chain.
+    pub fn set_effect_enabled(
+        &mut self,
+        bus: &str,
+        name: &str,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.with_effect(bus, name, |slot| slot.enabled = enabled)
+    }
+
+    pub fn remove_effect(&mut self, bus: &str, name: &str) -> Result<(), Error> {
+        let shared = &self.find_bus(bus)?.0;
+        {
+            let mut effects = shared.effects.lock().unwrap();
+            let index = effects
+                .iter()
+                .position(|e| e.name == name)
+                .ok_or_else(|| no_effect(name))?;
+            effects.remove(index);
+        }
+        shared.changed();
+        Ok(())
+    }
+
+    /// The effects of `bus` in the order they run, with whether each is enabled.
+    pub fn effects(&self, bus: &str) -> Result<Vec<(String, AudioEffect, bool)>, Error> {
+        let effects = self.find_bus(bus)?.0.effects.lock().unwrap();
+        Ok(effects
+            .iter()
+            .map(|e| (e.name.clone(), e.effect.clone(), e.enabled))
+            .collect())
+    }
+
+    fn with_effect(
+        &mut self,
+        bus: &str,
+        name: &str,
+        f: impl FnOnce(&mut EffectSlot),
+    ) -> Result<(), Error> {
+        let shared = &self.find_bus(bus)?.0;
+        {
+            let mut effects = shared.effects.lock().unwrap();
+            let slot = effects
+                .iter_mut()
+                .find(|e| e.name == name)
+                .ok_or_else(|| no_effect(name))?;
+            f(slot);
+        }
+        shared.changed();
+        Ok(())
+    }
+
+    /// Sends `stream` ([`crate::audio::Sound`], [`crate::audio::SoundAlias`],
+    /// [`crate::audio::Music`] or [`crate::audio::AudioStream`]) through `bus` until the returned
+    /// route is dropped.
+    ///
+    /// Every route is an audio stream processor, which takes one of the
+    /// [`crate::callbacks::AUDIO_CALLBACK_SLOTS`] slots shared by all audio processors and
+    /// callbacks of the program. Routing fails while they are all in use.
+    pub fn route<'s>(
+        &self,
+        stream: &'s impl AsRef<ffi::AudioStream>,
+        bus: &str,
+    ) -> Result<BusRoute<'s>, Error> {
+        let bus = self.find_bus(bus)?.clone();
+        let mut state = RouteState {
+            bus: bus.0.clone(),
+            generation: None,
+            sample_rate: 0,
+            effects: Vec::new(),
+            gain: None,
+        };
+        let processor = self
+            .audio
+            .attach_audio_stream_processor(stream, move |frames| state.process(frames))
+            .map_err(|e| {
+                error!(std::borrow::Cow::Owned(format!(
+                    "could not route stream to bus {}: {}",
+                    bus.name(),
+                    e
+                )))
+            })?;
+        Ok(BusRoute {
+            bus,
+            _processor: processor,
+        })
+    }
+}
+
+fn no_effect(name: &str) -> Error {
+    error!(std::borrow::Cow::Owned(format!(
+        "no mixer effect named {}",
+        name
+    )))
+}
+
+impl RaylibAudio {
+    /// Creates an [`AudioMixer`] with the default music, sfx, voice and ui buses.
+    pub fn new_mixer(&self) -> AudioMixer<'_> {
+        AudioMixer::new(self)
+    }
+}
+
+#[derive(Debug)]
+struct Ducker {
+    sidechain: Arc<BusShared>,
+    gain: f32,
+}
+
+#[derive(Debug)]
+enum EffectState {
+    Filter(Biquad),
+    Reverb(Box<Reverb>),
+    Compressor(Compressor),
+    Duck(Ducker),
+}
+
+impl EffectState {
+    fn new(slot: &EffectSlot, sample_rate: f32) -> Self {
+        match &slot.effect {
+            AudioEffect::LowPass { cutoff, q } => {
+                EffectState::Filter(Biquad::new(BiquadKind::LowPass, *cutoff, *q, sample_rate))
+            }
+            AudioEffect::HighPass { cutoff, q } => {
+                EffectState::Filter(Biquad::new(BiquadKind::HighPass, *cutoff, *q, sample_rate))
+            }
+            AudioEffect::Reverb {
+                room_size,
+                damping,
+                wet,
+            } => EffectState::Reverb(Box::new(Reverb::new(
+                *room_size,
+                *damping,
+                *wet,
+                sample_rate,
+            ))),
+            AudioEffect::Compressor { .. } => {
+                let mut compressor = Compressor::new(0.0, 1.0, sample_rate);
+                EffectState::update_compressor(&mut compressor, &slot.effect);
+                EffectState::Compressor(compressor)
+            }
+            AudioEffect::Duck { .. } => EffectState::Duck(Ducker {
+                sidechain: slot
+                    .sidechain
+                    .clone()
+                    .expect("sidechain resolved when added"),
+                gain: 1.0,
+            }),
+        }
+    }
+
+    /// Applies the parameters of `slot` if it's the same kind of effect, keeping the state.
+    fn update(&mut self, slot: &EffectSlot) -> bool {
+        match (self, &slot.effect) {
+            (EffectState::Filter(filter), AudioEffect::LowPass { cutoff, q }) => {
+                filter.set_params(BiquadKind::LowPass, *cutoff, *q)
+            }
+            (EffectState::Filter(filter), AudioEffect::HighPass { cutoff, q }) => {
+                filter.set_params(BiquadKind::HighPass, *cutoff, *q)
+            }
+            (
+                EffectState::Reverb(reverb),
+                AudioEffect::Reverb {
+                    room_size,
+                    damping,
+                    wet,
+                },
+            ) => {
+                reverb.room_size = *room_size;
+                reverb.damping = *damping;
+                reverb.wet = *wet;
+            }
+            (EffectState::Compressor(compressor), effect @ AudioEffect::Compressor { .. }) => {
+                EffectState::update_compressor(compressor, effect)
+            }
+            (EffectState::Duck(ducker), AudioEffect::Duck { .. }) => {
+                ducker.sidechain = slot
+                    .sidechain
+                    .clone()
+                    .expect("sidechain resolved when added");
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn update_compressor(compressor: &mut Compressor, effect: &AudioEffect) {
+        if let AudioEffect::Compressor {
+            threshold_db,
+            ratio,
+            attack,
+            release,
+            makeup_db,
+        } = effect
+        {
+            compressor.threshold_db = *threshold_db;
+            compressor.ratio = *ratio;
+            compressor.attack = *attack;
+            compressor.release = *release;
+            compressor.makeup_db = *makeup_db;
+        }
+    }
+
+    fn process(&mut self, effect: &AudioEffect, frames: &mut [f32], sample_rate: f32) {
+        match self {
+            EffectState::Filter(filter) => filter.process(frames),
+            EffectState::Reverb(reverb) => reverb.process(frames),
+            EffectState::Compressor(compressor) => compressor.process(frames),
+            EffectState::Duck(ducker) => {
+                if let AudioEffect::Duck {
+                    amount,
+                    threshold,
+                    attack,
+                    release,
+                    ..
+                } = effect
+                {
+                    let target = if ducker.sidechain.level() > *threshold {
+                        1.0 - amount.clamp(0.0, 1.0)
+                    } else {
+                        1.0
+                    };
+                    let coef = if target < ducker.gain {
+                        smoothing(*attack, sample_rate)
+                    } else {
+                        smoothing(*release, sample_rate)
+                    };
+                    for frame in frames.chunks_exact_mut(2) {
+                        ducker.gain = target + coef * (ducker.gain - target);
+                        frame[0] *= ducker.gain;
+                        frame[1] *= ducker.gain;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Copy of a bus chain owned by one routed stream's processor.
+struct RouteState {
+    bus: Arc<BusShared>,
+    generation: Option<usize>,
+    sample_rate: u32,
+    effects: Vec<(String, AudioEffect, bool, EffectState)>,
+    gain: Option<f32>,
+}
+
+impl RouteState {
+    /// Picks up effect changes without blocking the audio thread; if the mixer is mid-edit, the
+    /// old chain runs for one more buffer.
+    fn sync(&mut self) {
+        let generation = self.bus.generation.load(Ordering::Acquire);
+        if self.generation == Some(generation) {
+            return;
+        }
+        let slots = match self.bus.effects.try_lock() {
+            Ok(slots) => slots,
+            Err(_) => return,
+        };
+        let sample_rate = self.bus.sample_rate.load(Ordering::Relaxed);
+        if sample_rate != self.sample_rate {
+            self.effects.clear();
+            self.sample_rate = sample_rate;
+        }
+        let mut old = std::mem::take(&mut self.effects);
+        for slot in slots.iter() {
+            let state = match old.iter().position(|(name, ..)| *name == slot.name) {
+                Some(index) => {
+                    let (_, _, _, mut state) = old.swap_remove(index);
+                    if !state.update(slot) {
+                        state = EffectState::new(slot, sample_rate as f32);
+                    }
+                    state
+                }
+                None => EffectState::new(slot, sample_rate as f32),
+            };
+            self.effects
+                .push((slot.name.clone(), slot.effect.clone(), slot.enabled, state));
+        }
+        self.generation = Some(generation);
+    }
+
+    fn process(&mut self, frames: &mut [f32]) {
+        self.sync();
+        let sample_rate = self.sample_rate as f32;
+        for (_, effect, enabled, state) in self.effects.iter_mut() {
+            if *enabled {
+                state.process(effect, frames, sample_rate);
+            }
+        }
+
+        let target = if self.bus.muted.load(Ordering::Relaxed) {
+            0.0
+        } else {
+            f32::from_bits(self.bus.volume.load(Ordering::Relaxed))
+        };
+        let start = self.gain.unwrap_or(target);
+        let count = frames.len() / 2;
+        let mut peak = 0f32;
+        for (i, frame) in frames.chunks_exact_mut(2).enumerate() {
+            let gain = start + (target - start) * (i + 1) as f32 / count as f32;
+            frame[0] *= gain;
+            frame[1] *= gain;
+            peak = peak.max(frame[0].abs()).max(frame[1].abs());
+        }
+        self.gain = Some(target);
+        self.bus.report_level(peak);
+    }
+}
+
+#[cfg(test)]
+mod mixer_test {
+    use super::*;
+
+    fn bus(name: &str) -> Arc<BusShared> {
+        Arc::new(BusShared {
+            name: name.to_owned(),
+            volume: AtomicU32::new(1f32.to_bits()),
+            muted: AtomicBool::new(false),
+            sample_rate: AtomicU32::new(48000),
+            generation: AtomicUsize::new(0),
+            effects: Mutex::new(Vec::new()),
+            meter: AtomicU64::new(0),
+            epoch: Instant::now(),
+        })
+    }
+
+    fn route(bus: &Arc<BusShared>) -> RouteState {
+        RouteState {
+            bus: bus.clone(),
+            generation: None,
+            sample_rate: 0,
+            effects: Vec::new(),
+            gain: None,
+        }
+    }
+
+    fn add_effect(bus: &BusShared, effect: AudioEffect, sidechain: Option<&Arc<BusShared>>) {
+        bus.effects.lock().unwrap().push(EffectSlot {
+            name: "effect".to_owned(),
+            effect,
+            enabled: true,
+            sidechain: sidechain.cloned(),
+        });
+        bus.changed();
+    }
+
+    fn assert_frames(frames: &[f32], expected: &[f32]) {
+        assert_eq!(frames.len(), expected.len());
+        for (frame, expected) in frames.iter().zip(expected) {
+            assert!(
+                (frame - expected).abs() < 1e-5,
+                "{:?} != {:?}",
+                frames,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_volume_ramp() {
+        let bus = bus("music");
+        let mut route = route(&bus);
+        let mut frames = [1.0; 8];
+        route.process(&mut frames);
+        assert_frames(&frames, &[1.0; 8]);
+        assert_eq!(bus.level(), 1.0);
+
+        // Volume changes are ramped over the next buffer, one step per stereo frame
+        MixerBus(bus.clone()).set_volume(0.5);
+        let mut frames = [1.0; 8];
+        route.process(&mut frames);
+        assert_frames(&frames, &[0.875, 0.875, 0.75, 0.75, 0.625, 0.625, 0.5, 0.5]);
+        let mut frames = [1.0; 8];
+        route.process(&mut frames);
+        assert_frames(&frames, &[0.5; 8]);
+
+        MixerBus(bus.clone()).set_muted(true);
+        let mut frames = [-1.0; 4];
+        route.process(&mut frames);
+        assert_frames(&frames, &[-0.25, -0.25, 0.0, 0.0]);
+        let mut frames = [1.0; 4];
+        route.process(&mut frames);
+        assert_frames(&frames, &[0.0; 4]);
+    }
+
+    #[test]
+    fn test_ducking() {
+        let voice = bus("voice");
+        let music = bus("music");
+        add_effect(
+            &music,
+            AudioEffect::Duck {
+                sidechain: "voice".to_owned(),
+                amount: 0.6,
+                threshold: 0.1,
+                attack: 0.0,
+                release: 0.5,
+            },
+            Some(&voice),
+        );
+        let mut route = route(&music);
+
+        let mut frames = [1.0; 4];
+        route.process(&mut frames);
+        assert_frames(&frames, &[1.0; 4]);
+
+        // Over the threshold, the instant attack lowers the gain by `amount`
+        voice.report_level(0.5);
+        let mut frames = [1.0; 4];
+        route.process(&mut frames);
+        assert_frames(&frames, &[0.4; 4]);
+
+        // Below the threshold the gain recovers with the release time
+        voice
+            .meter
+            .store((voice.now_ms() as u64) << 32, Ordering::Relaxed);
+        let mut frames = [1.0; 4];
+        route.process(&mut frames);
+        let coef = smoothing(0.5, 48000.0);
+        let first = 1.0 + coef * (0.4 - 1.0);
+        let second = 1.0 + coef * (first - 1.0);
+        assert_frames(&frames, &[first, first, second, second]);
+        assert!(frames[3] < 1.0);
+    }
+
+    #[test]
+    fn test_effect_changes_are_picked_up() {
+        let bus = bus("sfx");
+        let mut route = route(&bus);
+        add_effect(&bus, AudioEffect::high_pass(1000.0), None);
+
+        // A high pass removes the constant signal
+        let mut frames = vec![1.0; 4096];
+        route.process(&mut frames);
+        assert!(frames[frames.len() - 1].abs() < 1e-3);
+
+        bus.effects.lock().unwrap()[0].enabled = false;
+        bus.changed();
+        let mut frames = [1.0; 4];
+        route.process(&mut frames);
+        assert_frames(&frames, &[1.0; 4]);
+    }
+}
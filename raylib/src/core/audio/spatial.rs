@@ -0,0 +1,364 @@
+//! 3D positioned sounds: distance attenuation, stereo panning and doppler on top of raylib's
+//! volume, pan and pitch setters.
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//! let (mut rl, thread) = raylib::init().build();
+//! let audio = RaylibAudio::init_audio_device().unwrap();
+//! let sound = audio.new_sound("engine.ogg").unwrap();
+//! let mut engine = AudioEmitter::new(&sound)
+//!     .attenuation(Attenuation::Inverse)
+//!     .distance(2.0, 50.0)
+//!     .doppler(1.0);
+//! sound.play();
+//!
+//! let camera = Camera3D::perspective(
+//!     Vector3::new(0.0, 2.0, 10.0),
+//!     Vector3::zero(),
+//!     Vector3::up(),
+//!     45.0,
+//! );
+//! let mut listener = AudioListener::from_camera(&camera);
+//! while !rl.window_should_close() {
+//!     let dt = rl.get_frame_time();
+//!     listener.follow_camera(&camera, dt);
+//!     engine.move_to(Vector3::new(rl.get_time().sin() as f32 * 10.0, 0.0, 0.0), dt);
+//!     engine.update(&listener);
+//! }
+//! ```
+use crate::core::audio::{AudioStream, Music, Sound, SoundAlias};
+use crate::core::camera::Camera3D;
+use crate::core::math::Vector3;
+
+/// Speed of sound in world units per second, assuming one unit is a meter.
+pub const SPEED_OF_SOUND: f32 = 343.3;
+
+/// How gain falls off between an emitter's `min_distance` and `max_distance`.
+///
+/// These are the clamped models from OpenAL: closer than `min_distance` plays at full volume,
+/// and the distance stops growing at `max_distance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Attenuation {
+    /// Fades linearly to silence at `max_distance`.
+    Linear,
+    /// `min / (min + rolloff * (d - min))`, like real sound.
+    Inverse,
+    /// `(d / min) ^ -rolloff`.
+    Exponential,
+}
+
+/// Anything whose volume, pan and pitch an `AudioEmitter` can drive.
+pub trait SpatialSource {
+    fn set_volume(&self, volume: f32);
+    /// `pan` is raylib's: 0.5 is centered.
+    fn set_pan(&self, pan: f32);
+    fn set_pitch(&self, pitch: f32);
+}
+
+impl SpatialSource for Sound<'_> {
+    fn set_volume(&self, volume: f32) {
+        Sound::set_volume(self, volume)
+    }
+    fn set_pan(&self, pan: f32) {
+        Sound::set_pan(self, pan)
+    }
+    fn set_pitch(&self, pitch: f32) {
+        Sound::set_pitch(self, pitch)
+    }
+}
+
+impl SpatialSource for SoundAlias<'_, '_> {
+    fn set_volume(&self, volume: f32) {
+        SoundAlias::set_volume(self, volume)
+    }
+    fn set_pan(&self, pan: f32) {
+        SoundAlias::set_pan(self, pan)
+    }
+    fn set_pitch(&self, pitch: f32) {
+        SoundAlias::set_pitch(self, pitch)
+    }
+}
+
+impl SpatialSource for Music<'_> {
+    fn set_volume(&self, volume: f32) {
+        Music::set_volume(self, volume)
+    }
+    fn set_pan(&self, pan: f32) {
+        Music::set_pan(self, pan)
+    }
+    fn set_pitch(&self, pitch: f32) {
+        Music::set_pitch(self, pitch)
+    }
+}
+
+impl SpatialSource for AudioStream<'_> {
+    fn set_volume(&self, volume: f32) {
+        AudioStream::set_volume(self, volume)
+    }
+    fn set_pan(&self, pan: f32) {
+        AudioStream::set_pan(self, pan)
+    }
+    fn set_pitch(&self, pitch: f32) {
+        AudioStream::set_pitch(self, pitch)
+    }
+}
+
+impl<T: SpatialSource + ?Sized> SpatialSource for &T {
+    fn set_volume(&self, volume: f32) {
+        (**self).set_volume(volume)
+    }
+    fn set_pan(&self, pan: f32) {
+        (**self).set_pan(pan)
+    }
+    fn set_pitch(&self, pitch: f32) {
+        (**self).set_pitch(pitch)
+    }
+}
+
+/// The ears emitters are heard from, usually following the camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AudioListener {
+    pub position: Vector3,
+    pub forward: Vector3,
+    pub up: Vector3,
+    /// Used for doppler, in units per second.
+    pub velocity: Vector3,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        AudioListener {
+            position: Vector3::zero(),
+            forward: Vector3::new(0.0, 0.0, -1.0),
+            up: Vector3::up(),
+            velocity: Vector3::zero(),
+        }
+    }
+}
+
+impl AudioListener {
+    pub fn new(position: Vector3, forward: Vector3, up: Vector3) -> Self {
+        AudioListener {
+            position,
+            forward,
+            up,
+            velocity: Vector3::zero(),
+        }
+    }
+
+    /// A listener at the camera looking towards its target.
+    pub fn from_camera(camera: &Camera3D) -> Self {
+        AudioListener::new(camera.position, camera.target - camera.position, camera.up)
+    }
+
+    /// Moves to `camera`, deriving the velocity from how far it moved in `dt` seconds.
+    pub fn follow_camera(&mut self, camera: &Camera3D, dt: f32) {
+        self.move_to(camera.position, dt);
+        self.forward = camera.target - camera.position;
+        self.up = camera.up;
+    }
+
+    /// Moves to `position`, deriving the velocity from how far it moved in `dt` seconds.
+    pub fn move_to(&mut self, position: Vector3, dt: f32) {
+        if dt > 0.0 {
+            self.velocity = (position - self.position) / dt;
+        }
+        self.position = position;
+    }
+
+    /// Unit vector pointing to the listener's right.
+    pub fn right(&self) -> Vector3 {
+        self.forward.cross(self.up).normalized()
+    }
+}
+
+/// Volume, pan and pitch computed for an emitter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialMix {
+    /// Final volume, including the emitter's own.
+    pub volume: f32,
+    /// -1 is fully left, 1 fully right.
+    pub pan: f32,
+    /// Final pitch, including the emitter's own.
+    pub pitch: f32,
+}
+
+impl SpatialMix {
+    /// `pan` in raylib's convention, where 0.5 is centered and 1.0 is fully left.
+    pub fn raylib_pan(&self) -> f32 {
+        0.5 - self.pan * 0.5
+    }
+}
+
+/// A `Sound`, `SoundAlias`, `Music` or `AudioStream` placed in the world.
+///
+/// Call `update` every frame after moving the emitter and the listener.
+#[derive(Debug, Clone)]
+pub struct AudioEmitter<S> {
+    source: S,
+    pub position: Vector3,
+    /// Used for doppler, in units per second.
+    pub velocity: Vector3,
+    /// Volume at or inside `min_distance`.
+    pub volume: f32,
+    /// Pitch with no doppler shift.
+    pub pitch: f32,
+    pub attenuation: Attenuation,
+    /// Distance up to which the emitter plays at full volume.
+    pub min_distance: f32,
+    /// Distance after which it gets no quieter. Silent here with `Attenuation::Linear`.
+    pub max_distance: f32,
+    /// How quickly volume falls off with distance, 1 is realistic.
+    pub rolloff: f32,
+    /// Scales the doppler shift, 0 turns it off.
+    pub doppler_factor: f32,
+    /// Speed of sound for doppler, in units per second.
+    pub speed_of_sound: f32,
+    /// How much direction affects panning, 0 keeps the emitter centered.
+    pub pan_strength: f32,
+}
+
+impl<S> AudioEmitter<S> {
+    pub fn new(source: S) -> Self {
+        AudioEmitter {
+            source,
+            position: Vector3::zero(),
+            velocity: Vector3::zero(),
+            volume: 1.0,
+            pitch: 1.0,
+            attenuation: Attenuation::Inverse,
+            min_distance: 1.0,
+            max_distance: 100.0,
+            rolloff: 1.0,
+            doppler_factor: 0.0,
+            speed_of_sound: SPEED_OF_SOUND,
+            pan_strength: 1.0,
+        }
+    }
+
+    pub fn position(mut self, position: Vector3) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        self
+    }
+
+    /// Sets `min_distance` and `max_distance`.
+    pub fn distance(mut self, min_distance: f32, max_distance: f32) -> Self {
+        self.min_distance = min_distance;
+        self.max_distance = max_distance;
+        self
+    }
+
+    pub fn rolloff(mut self, rolloff: f32) -> Self {
+        self.rolloff = rolloff;
+        self
+    }
+
+    /// Enables doppler pitch shift, scaled by `factor`.
+    pub fn doppler(mut self, factor: f32) -> Self {
+        self.doppler_factor = factor;
+        self
+    }
+
+    pub fn volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    pub fn pitch(mut self, pitch: f32) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    /// Moves to `position`, deriving the velocity from how far it moved in `dt` seconds.
+    pub fn move_to(&mut self, position: Vector3, dt: f32) {
+        if dt > 0.0 {
+            self.velocity = (position - self.position) / dt;
+        }
+        self.position = position;
+    }
+
+    /// Moves to `position` without a velocity, so the jump causes no doppler shift.
+    pub fn teleport(&mut self, position: Vector3) {
+        self.position = position;
+        self.velocity = Vector3::zero();
+    }
+
+    /// Volume multiplier from `attenuation` at `distance`.
+    pub fn gain_at(&self, distance: f32) -> f32 {
+        let min = self.min_distance.max(f32::EPSILON);
+        let max = self.max_distance.max(min);
+        let d = distance.max(min).min(max);
+        let gain = match self.attenuation {
+            Attenuation::Linear if max > min => 1.0 - self.rolloff * (d - min) / (max - min),
+            Attenuation::Linear => 1.0,
+            Attenuation::Inverse => min / (min + self.rolloff * (d - min)),
+            Attenuation::Exponential => (d / min).powf(-self.rolloff),
+        };
+        gain.clamp(0.0, 1.0)
+    }
+
+    /// Computes how the emitter sounds to `listener`, without touching the source.
+    pub fn mix(&self, listener: &AudioListener) -> SpatialMix {
+        let offset = self.position - listener.position;
+        let distance = offset.length();
+        let direction = if distance > 0.0 {
+            offset / distance
+        } else {
+            Vector3::zero()
+        };
+
+        // Fade to the center inside min_distance so passing through the listener doesn't flip sides.
+        let closeness = if self.min_distance > 0.0 {
+            (distance / self.min_distance).min(1.0)
+        } else {
+            1.0
+        };
+        let pan = direction.dot(listener.right()) * closeness * self.pan_strength;
+
+        let mut pitch = self.pitch;
+        if self.doppler_factor > 0.0 && distance > 0.0 {
+            let c = self.speed_of_sound;
+            let limit = c / self.doppler_factor * 0.9;
+            let toward_source = listener.velocity.dot(direction).clamp(-limit, limit);
+            let away_from_listener = self.velocity.dot(direction).clamp(-limit, limit);
+            pitch *= (c + self.doppler_factor * toward_source)
+                / (c + self.doppler_factor * away_from_listener);
+        }
+
+        SpatialMix {
+            volume: self.volume * self.gain_at(distance),
+            pan: pan.clamp(-1.0, 1.0),
+            pitch,
+        }
+    }
+}
+
+impl<S: SpatialSource> AudioEmitter<S> {
+    /// Pushes the mix for `listener` to the source's volume, pan and pitch.
+    pub fn update(&mut self, listener: &AudioListener) -> SpatialMix {
+        let mix = self.mix(listener);
+        self.source.set_volume(mix.volume);
+        self.source.set_pan(mix.raylib_pan());
+        self.source.set_pitch(mix.pitch);
+        mix
+    }
+}
@@ -1,11 +1,17 @@
 use std::{
+    convert::TryFrom,
     ffi::CString,
+    io::{self, Read, Write},
     path::{Path, PathBuf},
     ptr::null,
 };
 
+use crate::consts::{GamepadAxis, GamepadButton, Gesture, KeyboardKey, MouseButton};
 use crate::{ffi, RaylibHandle};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 fn unload_automation_event_list(mut s: ffi::AutomationEventList) {
     unsafe {
         ffi::UnloadAutomationEventList(s);
@@ -61,6 +67,10 @@ impl AutomationEvent {
     pub const fn params(&self) -> [i32; 4] {
         self.0.params
     }
+    /// Decodes the event type and parameters. `None` for event types this crate doesn't know.
+    pub fn kind(&self) -> Option<AutomationEventKind> {
+        AutomationEventKind::from_raw(self.0.type_, self.0.params)
+    }
 }
 
 impl AutomationEvent {
@@ -98,3 +108,482 @@ impl RaylibHandle {
         unsafe { ffi::StopAutomationEventRecording() };
     }
 }
+
+/// Names of raylib's `AutomationEventType` values (defined in rcore.c), indexed by type id.
+const EVENT_TYPE_NAMES: [&str; 24] = [
+    "None",
+    "KeyUp",
+    "KeyDown",
+    "KeyPressed",
+    "KeyReleased",
+    "MouseButtonUp",
+    "MouseButtonDown",
+    "MousePosition",
+    "MouseWheelMotion",
+    "GamepadConnect",
+    "GamepadDisconnect",
+    "GamepadButtonUp",
+    "GamepadButtonDown",
+    "GamepadAxisMotion",
+    "TouchUp",
+    "TouchDown",
+    "TouchPosition",
+    "Gesture",
+    "WindowClose",
+    "WindowMaximize",
+    "WindowMinimize",
+    "WindowResize",
+    "TakeScreenshot",
+    "SetTargetFps",
+];
+
+/// Gamepad axis values are stored as `value * 32768` in the event parameters.
+const AXIS_SCALE: f32 = 32768.0;
+
+/// A typed automation event, see `AutomationEventType` in raylib's rcore.c.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AutomationEventKind {
+    KeyUp(KeyboardKey),
+    KeyDown(KeyboardKey),
+    KeyPressed(KeyboardKey),
+    KeyReleased(KeyboardKey),
+    MouseButtonUp(MouseButton),
+    MouseButtonDown(MouseButton),
+    MousePosition {
+        x: i32,
+        y: i32,
+    },
+    MouseWheelMotion {
+        x: i32,
+        y: i32,
+    },
+    GamepadConnect {
+        gamepad: i32,
+    },
+    GamepadDisconnect {
+        gamepad: i32,
+    },
+    GamepadButtonUp {
+        gamepad: i32,
+        button: GamepadButton,
+    },
+    GamepadButtonDown {
+        gamepad: i32,
+        button: GamepadButton,
+    },
+    GamepadAxisMotion {
+        gamepad: i32,
+        axis: GamepadAxis,
+        value: f32,
+    },
+    TouchUp {
+        point: i32,
+    },
+    TouchDown {
+        point: i32,
+    },
+    TouchPosition {
+        point: i32,
+        x: i32,
+        y: i32,
+    },
+    Gesture(Gesture),
+    WindowClose,
+    WindowMaximize,
+    WindowMinimize,
+    WindowResize {
+        width: i32,
+        height: i32,
+    },
+    TakeScreenshot,
+    SetTargetFps(i32),
+}
+
+impl AutomationEventKind {
+    /// Decodes a raw event type and its parameters.
+    /// Returns `None` for unknown types and out of range keys, buttons, axes or gestures.
+    pub fn from_raw(event_type: u32, params: [i32; 4]) -> Option<Self> {
+        use AutomationEventKind::*;
+        let [p0, p1, p2, _] = params;
+        Some(match event_type {
+            1 => KeyUp(crate::input::key_from_i32(p0)?),
+            2 => KeyDown(crate::input::key_from_i32(p0)?),
+            3 => KeyPressed(crate::input::key_from_i32(p0)?),
+            4 => KeyReleased(crate::input::key_from_i32(p0)?),
+            5 => MouseButtonUp(mouse_button_from_i32(p0)?),
+            6 => MouseButtonDown(mouse_button_from_i32(p0)?),
+            7 => MousePosition { x: p0, y: p1 },
+            8 => MouseWheelMotion { x: p0, y: p1 },
+            9 => GamepadConnect { gamepad: p0 },
+            10 => GamepadDisconnect { gamepad: p0 },
+            11 => GamepadButtonUp {
+                gamepad: p0,
+                button: gamepad_button_from_i32(p1)?,
+            },
+            12 => GamepadButtonDown {
+                gamepad: p0,
+                button: gamepad_button_from_i32(p1)?,
+            },
+            13 => GamepadAxisMotion {
+                gamepad: p0,
+                axis: gamepad_axis_from_i32(p1)?,
+                value: p2 as f32 / AXIS_SCALE,
+            },
+            14 => TouchUp { point: p0 },
+            15 => TouchDown { point: p0 },
+            16 => TouchPosition {
+                point: p0,
+                x: p1,
+                y: p2,
+            },
+            17 => Gesture(gesture_from_i32(p0)?),
+            18 => WindowClose,
+            19 => WindowMaximize,
+            20 => WindowMinimize,
+            21 => WindowResize {
+                width: p0,
+                height: p1,
+            },
+            22 => TakeScreenshot,
+            23 => SetTargetFps(p0),
+            _ => return None,
+        })
+    }
+
+    /// Encodes the event into raylib's event type and parameters.
+    pub fn to_raw(&self) -> (u32, [i32; 4]) {
+        use AutomationEventKind::*;
+        match *self {
+            KeyUp(key) => (1, [key as i32, 0, 0, 0]),
+            KeyDown(key) => (2, [key as i32, 0, 0, 0]),
+            KeyPressed(key) => (3, [key as i32, 0, 0, 0]),
+            KeyReleased(key) => (4, [key as i32, 0, 0, 0]),
+            MouseButtonUp(button) => (5, [button as i32, 0, 0, 0]),
+            MouseButtonDown(button) => (6, [button as i32, 0, 0, 0]),
+            MousePosition { x, y } => (7, [x, y, 0, 0]),
+            MouseWheelMotion { x, y } => (8, [x, y, 0, 0]),
+            GamepadConnect { gamepad } => (9, [gamepad, 0, 0, 0]),
+            GamepadDisconnect { gamepad } => (10, [gamepad, 0, 0, 0]),
+            GamepadButtonUp { gamepad, button } => (11, [gamepad, button as i32, 0, 0]),
+            GamepadButtonDown { gamepad, button } => (12, [gamepad, button as i32, 0, 0]),
+            GamepadAxisMotion {
+                gamepad,
+                axis,
+                value,
+            } => (13, [gamepad, axis as i32, (value * AXIS_SCALE) as i32, 0]),
+            TouchUp { point } => (14, [point, 0, 0, 0]),
+            TouchDown { point } => (15, [point, 0, 0, 0]),
+            TouchPosition { point, x, y } => (16, [point, x, y, 0]),
+            Gesture(gesture) => (17, [gesture as i32, 0, 0, 0]),
+            WindowClose => (18, [0; 4]),
+            WindowMaximize => (19, [0; 4]),
+            WindowMinimize => (20, [0; 4]),
+            WindowResize { width, height } => (21, [width, height, 0, 0]),
+            TakeScreenshot => (22, [0; 4]),
+            SetTargetFps(fps) => (23, [fps, 0, 0, 0]),
+        }
+    }
+}
+
+fn mouse_button_from_i32(button: i32) -> Option<MouseButton> {
+    if (0..=MouseButton::MOUSE_BUTTON_BACK as i32).contains(&button) {
+        Some(unsafe { std::mem::transmute::<u32, MouseButton>(button as u32) })
+    } else {
+        None
+    }
+}
+
+fn gamepad_button_from_i32(button: i32) -> Option<GamepadButton> {
+    if (0..=GamepadButton::GAMEPAD_BUTTON_RIGHT_THUMB as i32).contains(&button) {
+        Some(unsafe { std::mem::transmute::<u32, GamepadButton>(button as u32) })
+    } else {
+        None
+    }
+}
+
+fn gamepad_axis_from_i32(axis: i32) -> Option<GamepadAxis> {
+    if (0..=GamepadAxis::GAMEPAD_AXIS_RIGHT_TRIGGER as i32).contains(&axis) {
+        Some(unsafe { std::mem::transmute::<u32, GamepadAxis>(axis as u32) })
+    } else {
+        None
+    }
+}
+
+fn gesture_from_i32(gesture: i32) -> Option<Gesture> {
+    // Gestures are single bits (or none)
+    if gesture == 0 || (gesture.count_ones() == 1 && gesture <= Gesture::GESTURE_PINCH_OUT as i32) {
+        Some(unsafe { std::mem::transmute::<u32, Gesture>(gesture as u32) })
+    } else {
+        None
+    }
+}
+
+/// An owned, serializable automation event.
+///
+/// The raw type and parameters are kept so events this crate can't decode survive a round trip.
+/// With the `serde` feature the event type is serialized by name, e.g.
+/// `{"frame":12,"type":"KeyDown","params":[65,0,0,0]}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "SerdeReplayEvent", into = "SerdeReplayEvent")
+)]
+pub struct ReplayEvent {
+    pub frame: u32,
+    pub event_type: u32,
+    pub params: [i32; 4],
+}
+
+impl ReplayEvent {
+    pub fn new(frame: u32, kind: AutomationEventKind) -> Self {
+        let (event_type, params) = kind.to_raw();
+        ReplayEvent {
+            frame,
+            event_type,
+            params,
+        }
+    }
+
+    pub fn kind(&self) -> Option<AutomationEventKind> {
+        AutomationEventKind::from_raw(self.event_type, self.params)
+    }
+
+    /// Injects the event into raylib's input state.
+    pub fn play(&self) {
+        unsafe { ffi::PlayAutomationEvent((*self).into()) }
+    }
+}
+
+impl From<&AutomationEvent> for ReplayEvent {
+    fn from(e: &AutomationEvent) -> ReplayEvent {
+        ReplayEvent {
+            frame: e.0.frame,
+            event_type: e.0.type_,
+            params: e.0.params,
+        }
+    }
+}
+
+impl From<ReplayEvent> for ffi::AutomationEvent {
+    fn from(e: ReplayEvent) -> ffi::AutomationEvent {
+        ffi::AutomationEvent {
+            frame: e.frame,
+            type_: e.event_type,
+            params: e.params,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SerdeReplayEvent {
+    frame: u32,
+    #[serde(rename = "type")]
+    event_type: String,
+    params: [i32; 4],
+}
+
+#[cfg(feature = "serde")]
+impl From<ReplayEvent> for SerdeReplayEvent {
+    fn from(e: ReplayEvent) -> SerdeReplayEvent {
+        SerdeReplayEvent {
+            frame: e.frame,
+            event_type: EVENT_TYPE_NAMES
+                .get(e.event_type as usize)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| e.event_type.to_string()),
+            params: e.params,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerdeReplayEvent> for ReplayEvent {
+    type Error = String;
+
+    fn try_from(e: SerdeReplayEvent) -> Result<ReplayEvent, String> {
+        let event_type = match EVENT_TYPE_NAMES.iter().position(|n| *n == e.event_type) {
+            Some(i) => i as u32,
+            None => e
+                .event_type
+                .parse()
+                .map_err(|_| format!("unknown automation event type `{}`", e.event_type))?,
+        };
+        Ok(ReplayEvent {
+            frame: e.frame,
+            event_type,
+            params: e.params,
+        })
+    }
+}
+
+const REPLAY_MAGIC: &[u8; 4] = b"RRPL";
+const REPLAY_VERSION: u32 = 1;
+
+/// A recorded sequence of automation events, ordered by frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Replay {
+    pub events: Vec<ReplayEvent>,
+}
+
+impl Replay {
+    pub fn new(mut events: Vec<ReplayEvent>) -> Self {
+        events.sort_by_key(|e| e.frame);
+        Replay { events }
+    }
+
+    /// Copies the events recorded into `list`.
+    pub fn from_list(list: &AutomationEventList) -> Self {
+        Self::new(list.events().iter().map(ReplayEvent::from).collect())
+    }
+
+    /// Number of frames covered by the replay.
+    pub fn frame_count(&self) -> u32 {
+        self.events.last().map_or(0, |e| e.frame + 1)
+    }
+
+    #[cfg(feature = "with_serde")]
+    pub fn to_json(&self) -> Result<String, crate::error::Error> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            crate::error::error!(std::borrow::Cow::Owned(format!(
+                "could not serialize replay: {}",
+                e
+            )))
+        })
+    }
+
+    #[cfg(feature = "with_serde")]
+    pub fn from_json(json: &str) -> Result<Self, crate::error::Error> {
+        let replay: Replay = serde_json::from_str(json).map_err(|e| {
+            crate::error::error!(std::borrow::Cow::Owned(format!(
+                "could not parse replay: {}",
+                e
+            )))
+        })?;
+        Ok(Self::new(replay.events))
+    }
+
+    /// Writes the replay in a compact little endian binary format.
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let count = u32::try_from(self.events.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many events"))?;
+        writer.write_all(REPLAY_MAGIC)?;
+        writer.write_all(&REPLAY_VERSION.to_le_bytes())?;
+        writer.write_all(&count.to_le_bytes())?;
+        for e in &self.events {
+            writer.write_all(&e.frame.to_le_bytes())?;
+            writer.write_all(&e.event_type.to_le_bytes())?;
+            for p in &e.params {
+                writer.write_all(&p.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a replay written by [`Replay::write_binary`].
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Self> {
+        fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+            let mut buf = [0; 4];
+            reader.read_exact(&mut buf)?;
+            Ok(u32::from_le_bytes(buf))
+        }
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != REPLAY_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a replay file",
+            ));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != REPLAY_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported replay version {}", version),
+            ));
+        }
+        let count = read_u32(&mut reader)?;
+        let mut events = Vec::new();
+        for _ in 0..count {
+            let frame = read_u32(&mut reader)?;
+            let event_type = read_u32(&mut reader)?;
+            let mut params = [0; 4];
+            for p in &mut params {
+                *p = read_u32(&mut reader)? as i32;
+            }
+            events.push(ReplayEvent {
+                frame,
+                event_type,
+                params,
+            });
+        }
+        Ok(Self::new(events))
+    }
+}
+
+/// Plays a [`Replay`] back one frame at a time.
+///
+/// Call [`Replayer::step`] once per frame, after input has been polled (with
+/// `custom_frame_control`, right after `poll_input_events`), so the injected events aren't
+/// overwritten by the real input.
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    replay: Replay,
+    next: usize,
+    frame: u32,
+}
+
+impl Replayer {
+    pub fn new(replay: Replay) -> Self {
+        Replayer {
+            replay,
+            next: 0,
+            frame: 0,
+        }
+    }
+
+    /// The frame the next call to `step` will play.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Whether every event has been played.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.replay.events.len()
+    }
+
+    /// Rewinds to the first frame.
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.frame = 0;
+    }
+
+    /// Returns the events of the current frame and advances to the next frame, without playing
+    /// them.
+    pub fn step_events(&mut self) -> &[ReplayEvent] {
+        let start = self.next;
+        let events = &self.replay.events;
+        while self.next < events.len() && events[self.next].frame <= self.frame {
+            self.next += 1;
+        }
+        self.frame += 1;
+        &events[start..self.next]
+    }
+
+    /// Plays the events of the current frame and advances to the next frame.
+    /// Returns the number of events played.
+    pub fn step(&mut self, _: &RaylibHandle) -> usize {
+        let events = self.step_events();
+        for e in events {
+            e.play();
+        }
+        events.len()
+    }
+}
@@ -1,15 +1,21 @@
 #![allow(non_camel_case_types)]
 
-use crate::{audio::AudioStream, ffi, RaylibHandle};
+use crate::{
+    audio::{AudioSample, AudioStream, RaylibAudio},
+    error::{error, Error},
+    ffi, RaylibHandle,
+};
 pub use raylib_sys::TraceLogLevel;
 use std::{
     borrow::Cow,
+    cell::{Cell, RefCell},
     convert::TryInto,
-    ffi::{c_char, c_int, c_void, CStr, CString},
-    mem::{size_of, transmute},
+    ffi::{c_char, c_int, c_uint, c_void, CStr},
+    marker::PhantomData,
+    mem::size_of,
     ptr::null_mut,
     slice::from_raw_parts_mut,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::{Mutex, MutexGuard},
 };
 
 type TraceLogCallback = unsafe extern "C" fn(*mut i8, *const i8, ...);
@@ -17,61 +23,159 @@ extern "C" {
     fn SetTraceLogCallback(cb: Option<TraceLogCallback>);
 }
 
-type RustTraceLogCallback = fn(TraceLogLevel, &str);
-type RustSaveFileDataCallback = fn(&str, &[u8]) -> bool;
-type RustLoadFileDataCallback = fn(&str) -> Vec<u8>;
-type RustSaveFileTextCallback = fn(&str, &str) -> bool;
-type RustLoadFileTextCallback = fn(&str) -> String;
-type RustAudioStreamCallback = fn(&[u8]);
-
-static TRACE_LOG_CALLBACK: AtomicUsize = AtomicUsize::new(0);
-static SAVE_FILE_DATA_CALLBACK: AtomicUsize = AtomicUsize::new(0);
-static LOAD_FILE_DATA_CALLBACK: AtomicUsize = AtomicUsize::new(0);
-static SAVE_FILE_TEXT_CALLBACK: AtomicUsize = AtomicUsize::new(0);
-static LOAD_FILE_TEXT_CALLBACK: AtomicUsize = AtomicUsize::new(0);
-static AUDIO_STREAM_CALLBACK: AtomicUsize = AtomicUsize::new(0);
+type RustTraceLogCallback = dyn FnMut(TraceLogLevel, &str) + Send;
+type RustSaveFileDataCallback = dyn FnMut(&str, &[u8]) -> bool + Send;
+type RustLoadFileDataCallback = dyn FnMut(&str) -> Vec<u8> + Send;
+type RustSaveFileTextCallback = dyn FnMut(&str, &str) -> bool + Send;
+type RustLoadFileTextCallback = dyn FnMut(&str) -> String + Send;
+type RustAudioCallback = dyn FnMut(*mut c_void, u32) + Send;
 
-fn trace_log_callback() -> Option<RustTraceLogCallback> {
-    const { assert!(size_of::<RustTraceLogCallback>() == size_of::<usize>()) };
-    unsafe { transmute(TRACE_LOG_CALLBACK.load(Ordering::Relaxed)) }
+/// Every closure registered for one raylib callback. The most recent registration is the active one.
+///
+/// A closure is taken out of the stack while it runs, so it can make raylib call back again (load
+/// a texture from a file loader, for example) without deadlocking. Such nested calls go to the
+/// closure registered before it, or to raylib's default behaviour.
+struct CallbackStack<F: ?Sized> {
+    next_id: u64,
+    entries: Vec<(u64, Option<Box<F>>)>,
 }
 
-fn save_file_data_callback() -> Option<RustSaveFileDataCallback> {
-    const { assert!(size_of::<RustSaveFileDataCallback>() == size_of::<usize>()) };
-    unsafe { transmute(SAVE_FILE_DATA_CALLBACK.load(Ordering::Relaxed)) }
-}
+impl<F: ?Sized> CallbackStack<F> {
+    const fn new() -> Self {
+        Self {
+            next_id: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, cb: Box<F>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push((id, Some(cb)));
+        id
+    }
 
-fn load_file_data_callback() -> Option<RustLoadFileDataCallback> {
-    const { assert!(size_of::<RustLoadFileDataCallback>() == size_of::<usize>()) };
-    unsafe { transmute(LOAD_FILE_DATA_CALLBACK.load(Ordering::Relaxed)) }
+    /// Removes the entry registered as `id`, returns true if nothing is registered anymore.
+    fn remove(&mut self, id: u64) -> bool {
+        self.entries.retain(|(entry, _)| *entry != id);
+        self.entries.is_empty()
+    }
+
+    /// Takes out the most recent closure that isn't already running.
+    fn take_active(&mut self) -> Option<(u64, Box<F>)> {
+        self.entries
+            .iter_mut()
+            .rev()
+            .find_map(|(id, cb)| cb.take().map(|cb| (*id, cb)))
+    }
+
+    /// Puts back a closure taken with [`CallbackStack::take_active`], unless it was unregistered
+    /// while running.
+    fn put_back(&mut self, id: u64, cb: Box<F>) {
+        if let Some((_, entry)) = self.entries.iter_mut().find(|(entry, _)| *entry == id) {
+            *entry = Some(cb);
+        }
+    }
 }
 
-fn save_file_text_callback() -> Option<RustSaveFileTextCallback> {
-    const { assert!(size_of::<RustSaveFileTextCallback>() == size_of::<usize>()) };
-    unsafe { transmute(SAVE_FILE_TEXT_CALLBACK.load(Ordering::Relaxed)) }
+/// Locks a callback mutex, ignoring poisoning: it's locked from `extern "C"` trampolines, which
+/// must not panic.
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
 }
 
-fn load_file_text_callback() -> Option<RustLoadFileTextCallback> {
-    const { assert!(size_of::<RustLoadFileTextCallback>() == size_of::<usize>()) };
-    unsafe { transmute(LOAD_FILE_TEXT_CALLBACK.load(Ordering::Relaxed)) }
+/// Runs `f` with the active closure of `stack`, without keeping the stack locked. `None` if no
+/// closure is available.
+fn with_active<F: ?Sized, R>(
+    stack: &Mutex<CallbackStack<F>>,
+    f: impl FnOnce(&mut F) -> R,
+) -> Option<R> {
+    let (id, mut cb) = lock(stack).take_active()?;
+    let result = f(&mut cb);
+    lock(stack).put_back(id, cb);
+    Some(result)
 }
 
-fn audio_stream_callback() -> Option<RustAudioStreamCallback> {
-    const { assert!(size_of::<RustAudioStreamCallback>() == size_of::<usize>()) };
-    unsafe { transmute(AUDIO_STREAM_CALLBACK.load(Ordering::Relaxed)) }
+static TRACE_LOG_CALLBACK: Mutex<CallbackStack<RustTraceLogCallback>> =
+    Mutex::new(CallbackStack::new());
+static SAVE_FILE_DATA_CALLBACK: Mutex<CallbackStack<RustSaveFileDataCallback>> =
+    Mutex::new(CallbackStack::new());
+static LOAD_FILE_DATA_CALLBACK: Mutex<CallbackStack<RustLoadFileDataCallback>> =
+    Mutex::new(CallbackStack::new());
+static SAVE_FILE_TEXT_CALLBACK: Mutex<CallbackStack<RustSaveFileTextCallback>> =
+    Mutex::new(CallbackStack::new());
+static LOAD_FILE_TEXT_CALLBACK: Mutex<CallbackStack<RustLoadFileTextCallback>> =
+    Mutex::new(CallbackStack::new());
+
+thread_local! {
+    // Set while a trace log closure runs, so raylib logging from inside it can't deadlock
+    static IN_TRACE_LOG: Cell<bool> = const { Cell::new(false) };
+    // Messages logged while `capture_trace_log` runs
+    static TRACE_LOG_CAPTURE: RefCell<Option<Vec<(TraceLogLevel, String)>>> =
+        const { RefCell::new(None) };
 }
 
+/// Called by raylib-sys' C log wrapper.
+///
+/// # Safety
+///
+/// `text` must be null or a valid nul-terminated string.
 #[no_mangle]
-pub extern "C" fn custom_trace_log_callback(level: TraceLogLevel, text: *const c_char) {
-    if let Some(trace_log) = trace_log_callback() {
-        let text = if text.is_null() {
-            Cow::Borrowed("(MESSAGE WAS NULL)")
-        } else {
-            unsafe { CStr::from_ptr(text).to_string_lossy() }
-        };
+pub unsafe extern "C" fn custom_trace_log_callback(level: TraceLogLevel, text: *const c_char) {
+    /// Clears `IN_TRACE_LOG` even if the closure panics.
+    struct InTraceLog;
+    impl Drop for InTraceLog {
+        fn drop(&mut self) {
+            IN_TRACE_LOG.with(|f| f.set(false));
+        }
+    }
 
-        trace_log(level, &text)
+    if IN_TRACE_LOG.with(|f| f.replace(true)) {
+        return;
+    }
+    let _in_trace_log = InTraceLog;
+    let message = if text.is_null() {
+        Cow::Borrowed("(MESSAGE WAS NULL)")
+    } else {
+        CStr::from_ptr(text).to_string_lossy()
+    };
+    TRACE_LOG_CAPTURE.with(|capture| {
+        if let Some(messages) = capture.borrow_mut().as_mut() {
+            messages.push((level, message.to_string()));
+        }
+    });
+    if with_active(&TRACE_LOG_CALLBACK, |trace_log| trace_log(level, &message)).is_some() {
+        return;
     }
+    if !text.is_null() {
+        // No closure for this message (installed for a capture, maybe on another thread), let
+        // raylib print it as it would have
+        SetTraceLogCallback(None);
+        ffi::TraceLog(level as c_int, b"%s\0".as_ptr() as *const c_char, text);
+        ffi::setLogCallbackWrapper();
+    }
+}
+
+/// Runs `f` and returns what raylib logged on this thread meanwhile. The messages still reach the
+/// registered trace log callback, or raylib's default output if there is none.
+pub(crate) fn capture_trace_log<R>(f: impl FnOnce() -> R) -> (R, Vec<(TraceLogLevel, String)>) {
+    let outer = TRACE_LOG_CAPTURE.with(|capture| capture.replace(Some(Vec::new())));
+    unsafe { ffi::setLogCallbackWrapper() };
+    let result = f();
+    let nested = outer.is_some();
+    let messages = TRACE_LOG_CAPTURE
+        .with(|capture| {
+            let messages = capture.replace(outer);
+            if let (Some(outer), Some(messages)) = (capture.borrow_mut().as_mut(), &messages) {
+                outer.extend(messages.iter().cloned());
+            }
+            messages
+        })
+        .unwrap_or_default();
+    if !nested && lock(&TRACE_LOG_CALLBACK).entries.is_empty() {
+        unsafe { SetTraceLogCallback(None) };
+    }
+    (result, messages)
 }
 
 extern "C" fn custom_save_file_data_callback(
@@ -79,19 +183,22 @@ extern "C" fn custom_save_file_data_callback(
     buffer: *mut c_void,
     size: c_int,
 ) -> bool {
-    let save_file_data = save_file_data_callback().expect("no callback");
-    let path = unsafe { CStr::from_ptr(path) };
+    let path = unsafe { CStr::from_ptr(path) }.to_string_lossy();
     let buffer = unsafe { from_raw_parts_mut(buffer as *mut u8, size as usize) };
 
-    save_file_data(path.to_str().expect("path is non utf-8"), buffer)
+    with_active(&SAVE_FILE_DATA_CALLBACK, |save_file_data| {
+        save_file_data(&path, buffer)
+    })
+    .unwrap_or_else(|| std::fs::write(&*path, &*buffer).is_ok())
 }
 
 extern "C" fn custom_load_file_data_callback(path: *const c_char, size: *mut c_int) -> *mut u8 {
-    let load_file_data = load_file_data_callback().expect("no callback");
-
     if let Some(size) = unsafe { size.as_mut() } {
-        let path = unsafe { CStr::from_ptr(path) };
-        let buffer = load_file_data(path.to_str().expect("path is non utf-8"));
+        let path = unsafe { CStr::from_ptr(path) }.to_string_lossy();
+        let buffer = with_active(&LOAD_FILE_DATA_CALLBACK, |load_file_data| {
+            load_file_data(&path)
+        })
+        .unwrap_or_else(|| std::fs::read(&*path).unwrap_or_default());
         *size = buffer.len().try_into().expect("out of range buffer size");
 
         // Copy everything to the raylib world
@@ -109,126 +216,398 @@ extern "C" fn custom_load_file_data_callback(path: *const c_char, size: *mut c_i
 }
 
 extern "C" fn custom_save_file_text_callback(a: *const c_char, b: *mut c_char) -> bool {
-    let save_file_text = save_file_text_callback().unwrap();
-    let a = unsafe { CStr::from_ptr(a) };
-    let b = unsafe { CStr::from_ptr(b) };
-    return save_file_text(a.to_str().unwrap(), b.to_str().unwrap());
+    let a = unsafe { CStr::from_ptr(a) }.to_string_lossy();
+    let b = unsafe { CStr::from_ptr(b) }.to_string_lossy();
+    with_active(&SAVE_FILE_TEXT_CALLBACK, |save_file_text| {
+        save_file_text(&a, &b)
+    })
+    .unwrap_or_else(|| std::fs::write(&*a, &*b).is_ok())
 }
+
 extern "C" fn custom_load_file_text_callback(a: *const c_char) -> *mut c_char {
-    let load_file_text = load_file_text_callback().unwrap();
-    let a = unsafe { CStr::from_ptr(a) };
-    let st = load_file_text(a.to_str().unwrap());
-    let oh = Box::leak(Box::new(CString::new(st).unwrap()));
-    oh.as_ptr() as *mut c_char
-}
+    let a = unsafe { CStr::from_ptr(a) }.to_string_lossy();
+    let text = match with_active(&LOAD_FILE_TEXT_CALLBACK, |load_file_text| {
+        load_file_text(&a)
+    }) {
+        Some(text) => text,
+        None => match std::fs::read_to_string(&*a) {
+            Ok(text) => text,
+            Err(_) => return null_mut(),
+        },
+    };
 
-extern "C" fn custom_audio_stream_callback(a: *mut c_void, b: u32) {
-    let audio_stream = audio_stream_callback().unwrap();
-    let a = unsafe { std::slice::from_raw_parts(a as *mut u8, b as usize) };
-    audio_stream(a);
+    // raylib frees the text with UnloadFileText, so it has to come from its allocator
+    unsafe {
+        let text_ffi = ffi::MemAlloc((text.len() + 1) as u32) as *mut u8;
+        text_ffi.copy_from_nonoverlapping(text.as_ptr(), text.len());
+        *text_ffi.add(text.len()) = 0;
+        text_ffi as *mut c_char
+    }
 }
+
+/// Keeps a callback registered. Dropping it unregisters the callback and makes the previously
+/// registered one (or raylib's default behaviour) active again.
+#[must_use = "the callback is unregistered as soon as the guard is dropped"]
 #[derive(Debug)]
-pub struct SetLogError<'a>(&'a str);
+pub struct CallbackGuard {
+    id: u64,
+    remove: fn(u64),
+}
 
-impl<'a> std::fmt::Display for SetLogError<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("There is a {} callback already set.", self.0))
+impl CallbackGuard {
+    /// Keeps the callback registered for the rest of the program.
+    pub fn leak(self) {
+        std::mem::forget(self);
     }
 }
 
-impl<'a> std::error::Error for SetLogError<'a> {}
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        (self.remove)(self.id)
+    }
+}
 
-macro_rules! safe_callback_set_func {
-    ($cb:expr, $target_cb:expr, $rawsetter:expr, $ogfunc:expr, $ty:literal) => {
-        if $target_cb.load(Ordering::Acquire) == 0 {
-            $target_cb.store($cb as usize, Ordering::Release);
-            unsafe { $rawsetter(Some($ogfunc)) };
-            Ok(())
-        } else {
-            Err(SetLogError($ty))
+macro_rules! callback_stack_remove_fn {
+    ($name:ident, $stack:expr, $rawsetter:expr) => {
+        fn $name(id: u64) {
+            if lock(&$stack).remove(id) {
+                unsafe { $rawsetter(None) };
+            }
         }
     };
 }
 
+callback_stack_remove_fn!(remove_trace_log, TRACE_LOG_CALLBACK, SetTraceLogCallback);
+callback_stack_remove_fn!(
+    remove_save_file_data,
+    SAVE_FILE_DATA_CALLBACK,
+    ffi::SetSaveFileDataCallback
+);
+callback_stack_remove_fn!(
+    remove_load_file_data,
+    LOAD_FILE_DATA_CALLBACK,
+    ffi::SetLoadFileDataCallback
+);
+callback_stack_remove_fn!(
+    remove_save_file_text,
+    SAVE_FILE_TEXT_CALLBACK,
+    ffi::SetSaveFileTextCallback
+);
+callback_stack_remove_fn!(
+    remove_load_file_text,
+    LOAD_FILE_TEXT_CALLBACK,
+    ffi::SetLoadFileTextCallback
+);
+
 impl RaylibHandle {
     /// Set custom trace log
     pub fn set_trace_log_callback(
         &mut self,
-        cb: fn(TraceLogLevel, &str),
-    ) -> Result<(), SetLogError> {
-        TRACE_LOG_CALLBACK.store(cb as usize, Ordering::Relaxed);
+        cb: impl FnMut(TraceLogLevel, &str) + Send + 'static,
+    ) -> CallbackGuard {
+        let id = lock(&TRACE_LOG_CALLBACK).push(Box::new(cb));
         unsafe { ffi::setLogCallbackWrapper() };
-        Ok(())
+        CallbackGuard {
+            id,
+            remove: remove_trace_log,
+        }
     }
     /// Set custom file binary data saver
     pub fn set_save_file_data_callback(
         &mut self,
-        cb: fn(&str, &[u8]) -> bool,
-    ) -> Result<(), SetLogError> {
-        safe_callback_set_func!(
-            cb,
-            SAVE_FILE_DATA_CALLBACK,
-            ffi::SetSaveFileDataCallback,
-            custom_save_file_data_callback,
-            "save file data"
-        )
+        cb: impl FnMut(&str, &[u8]) -> bool + Send + 'static,
+    ) -> CallbackGuard {
+        let id = lock(&SAVE_FILE_DATA_CALLBACK).push(Box::new(cb));
+        unsafe { ffi::SetSaveFileDataCallback(Some(custom_save_file_data_callback)) };
+        CallbackGuard {
+            id,
+            remove: remove_save_file_data,
+        }
     }
     /// Set custom file binary data loader
     ///
-    /// Whatever you return from your callback will be intentionally leaked as Raylib is relied on to free it.
-    pub fn set_load_file_data_callback<'b>(
+    /// Whatever you return from your callback is copied into a buffer owned by raylib.
+    pub fn set_load_file_data_callback(
         &mut self,
-        cb: fn(&str) -> Vec<u8>,
-    ) -> Result<(), SetLogError> {
-        safe_callback_set_func!(
-            cb,
-            LOAD_FILE_DATA_CALLBACK,
-            ffi::SetLoadFileDataCallback,
-            custom_load_file_data_callback,
-            "load file data"
-        )
+        cb: impl FnMut(&str) -> Vec<u8> + Send + 'static,
+    ) -> CallbackGuard {
+        let id = lock(&LOAD_FILE_DATA_CALLBACK).push(Box::new(cb));
+        unsafe { ffi::SetLoadFileDataCallback(Some(custom_load_file_data_callback)) };
+        CallbackGuard {
+            id,
+            remove: remove_load_file_data,
+        }
     }
     /// Set custom file text data saver
     pub fn set_save_file_text_callback(
         &mut self,
-        cb: fn(&str, &str) -> bool,
-    ) -> Result<(), SetLogError> {
-        safe_callback_set_func!(
-            cb,
-            SAVE_FILE_TEXT_CALLBACK,
-            ffi::SetSaveFileTextCallback,
-            custom_save_file_text_callback,
-            "load file data"
-        )
+        cb: impl FnMut(&str, &str) -> bool + Send + 'static,
+    ) -> CallbackGuard {
+        let id = lock(&SAVE_FILE_TEXT_CALLBACK).push(Box::new(cb));
+        unsafe { ffi::SetSaveFileTextCallback(Some(custom_save_file_text_callback)) };
+        CallbackGuard {
+            id,
+            remove: remove_save_file_text,
+        }
     }
     /// Set custom file text data loader
     ///
-    /// Whatever you return from your callback will be intentionally leaked as Raylib is relied on to free it.
+    /// Whatever you return from your callback is copied into a buffer owned by raylib.
     pub fn set_load_file_text_callback(
         &mut self,
-        cb: fn(&str) -> String,
-    ) -> Result<(), SetLogError> {
-        safe_callback_set_func!(
-            cb,
-            LOAD_FILE_TEXT_CALLBACK,
-            ffi::SetLoadFileTextCallback,
-            custom_load_file_text_callback,
-            "load file text"
-        )
-    }
-
-    /// Audio thread callback to request new data
+        cb: impl FnMut(&str) -> String + Send + 'static,
+    ) -> CallbackGuard {
+        let id = lock(&LOAD_FILE_TEXT_CALLBACK).push(Box::new(cb));
+        unsafe { ffi::SetLoadFileTextCallback(Some(custom_load_file_text_callback)) };
+        CallbackGuard {
+            id,
+            remove: remove_load_file_text,
+        }
+    }
+}
+
+/// How many audio stream callbacks and processors (including mixer routes) can be set at once.
+/// raylib's audio callbacks carry no user data, so every closure gets its own trampoline.
+pub const AUDIO_CALLBACK_SLOTS: usize = 64;
+/// Channels of raylib's mixing format, `AUDIO_DEVICE_CHANNELS` in config.h.
+const AUDIO_DEVICE_CHANNELS: usize = 2;
+
+static AUDIO_CALLBACKS: [Mutex<Option<Box<RustAudioCallback>>>; AUDIO_CALLBACK_SLOTS] =
+    [const { Mutex::new(None) }; AUDIO_CALLBACK_SLOTS];
+
+unsafe extern "C" fn custom_audio_callback<const SLOT: usize>(buffer: *mut c_void, frames: c_uint) {
+    if let Ok(mut cb) = AUDIO_CALLBACKS[SLOT].lock() {
+        if let Some(cb) = cb.as_mut() {
+            cb(buffer, frames)
+        }
+    }
+}
+
+type AudioTrampoline = unsafe extern "C" fn(*mut c_void, c_uint);
+
+macro_rules! audio_trampolines {
+    ($($slot:literal)*) => {
+        [$(custom_audio_callback::<$slot>,)*]
+    };
+}
+
+const AUDIO_TRAMPOLINES: [AudioTrampoline; AUDIO_CALLBACK_SLOTS] = audio_trampolines!(
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33
+    34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63
+);
+
+/// Stores `cb` in a free slot and returns its index.
+fn claim_audio_slot(cb: Box<RustAudioCallback>) -> Result<usize, Error> {
+    for (slot, entry) in AUDIO_CALLBACKS.iter().enumerate() {
+        let mut entry = entry.lock().unwrap();
+        if entry.is_none() {
+            *entry = Some(cb);
+            return Ok(slot);
+        }
+    }
+    Err(error!(Cow::Owned(format!(
+        "all {} audio callback slots are in use, drop unused audio processors or callbacks",
+        AUDIO_CALLBACK_SLOTS
+    ))))
+}
+
+fn release_audio_slot(slot: usize) {
+    *AUDIO_CALLBACKS[slot].lock().unwrap() = None;
+}
+
+/// Wraps a processor closure so it sees the interleaved `f32` frames of raylib's mixing format.
+fn mixing_format_callback(
+    mut cb: impl FnMut(&mut [f32]) + Send + 'static,
+) -> Box<RustAudioCallback> {
+    Box::new(move |buffer, frames| {
+        let samples = frames as usize * AUDIO_DEVICE_CHANNELS;
+        cb(unsafe { from_raw_parts_mut(buffer as *mut f32, samples) })
+    })
+}
+
+/// Audio stream data callback set with [`RaylibAudio::set_audio_stream_callback`]. Dropping it
+/// removes the callback from the stream.
+#[must_use = "the callback is removed as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct AudioStreamCallback<'s> {
+    stream: ffi::AudioStream,
+    slot: usize,
+    _stream: PhantomData<&'s mut ()>,
+}
+
+impl Drop for AudioStreamCallback<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::SetAudioStreamCallback(self.stream, None) };
+        release_audio_slot(self.slot);
+    }
+}
+
+/// Processor attached with [`RaylibAudio::attach_audio_stream_processor`]. Dropping it detaches
+/// the processor.
+#[must_use = "the processor is detached as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct AudioStreamProcessor<'s> {
+    stream: ffi::AudioStream,
+    slot: usize,
+    _stream: PhantomData<&'s ()>,
+}
+
+impl Drop for AudioStreamProcessor<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::DetachAudioStreamProcessor(self.stream, Some(AUDIO_TRAMPOLINES[self.slot])) };
+        release_audio_slot(self.slot);
+    }
+}
+
+/// Processor attached with [`RaylibAudio::attach_audio_mixed_processor`]. Dropping it detaches
+/// the processor.
+#[must_use = "the processor is detached as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct AudioMixedProcessor<'aud> {
+    slot: usize,
+    _audio: PhantomData<&'aud RaylibAudio>,
+}
+
+impl Drop for AudioMixedProcessor<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::DetachAudioMixedProcessor(Some(AUDIO_TRAMPOLINES[self.slot])) };
+        release_audio_slot(self.slot);
+    }
+}
+
+impl RaylibAudio {
+    /// Audio thread callback to request new data.
+    ///
+    /// `T` must match the sample size of the stream. The slice holds `frames * channels`
+    /// interleaved samples to fill.
+    pub fn set_audio_stream_callback<'s, T: AudioSample + 'static>(
+        &self,
+        stream: &'s mut AudioStream,
+        mut cb: impl FnMut(&mut [T]) + Send + 'static,
+    ) -> Result<AudioStreamCallback<'s>, Error> {
+        if stream.sample_size() as usize != size_of::<T>() * 8 {
+            return Err(error!(
+                "sample type does not match the sample size of the audio stream"
+            ));
+        }
+        let channels = stream.channels() as usize;
+        let slot = claim_audio_slot(Box::new(move |buffer, frames| {
+            let samples = frames as usize * channels;
+            cb(unsafe { from_raw_parts_mut(buffer as *mut T, samples) })
+        }))?;
+        unsafe { ffi::SetAudioStreamCallback(stream.0, Some(AUDIO_TRAMPOLINES[slot])) };
+        Ok(AudioStreamCallback {
+            stream: stream.0,
+            slot,
+            _stream: PhantomData,
+        })
+    }
+
+    /// Attach audio stream processor to a stream ([`AudioStream`], [`crate::audio::Music`] or
+    /// [`crate::audio::Sound`]). Processors run in the order they were attached.
+    ///
+    /// The processor receives interleaved stereo `f32` frames and may modify them in place.
+    pub fn attach_audio_stream_processor<'s>(
+        &self,
+        stream: &'s impl AsRef<ffi::AudioStream>,
+        cb: impl FnMut(&mut [f32]) + Send + 'static,
+    ) -> Result<AudioStreamProcessor<'s>, Error> {
+        let stream = *stream.as_ref();
+        let slot = claim_audio_slot(mixing_format_callback(cb))?;
+        unsafe { ffi::AttachAudioStreamProcessor(stream, Some(AUDIO_TRAMPOLINES[slot])) };
+        Ok(AudioStreamProcessor {
+            stream,
+            slot,
+            _stream: PhantomData,
+        })
+    }
+
+    /// Attach audio stream processor to the entire audio pipeline, after every stream was mixed.
+    ///
+    /// The processor receives interleaved stereo `f32` frames and may modify them in place.
+    pub fn attach_audio_mixed_processor(
+        &self,
+        cb: impl FnMut(&mut [f32]) + Send + 'static,
+    ) -> Result<AudioMixedProcessor<'_>, Error> {
+        let slot = claim_audio_slot(mixing_format_callback(cb))?;
+        unsafe { ffi::AttachAudioMixedProcessor(Some(AUDIO_TRAMPOLINES[slot])) };
+        Ok(AudioMixedProcessor {
+            slot,
+            _audio: PhantomData,
+        })
+    }
+}
+
+impl RaylibHandle {
+    /// Audio thread callback to request new data, as raw bytes. The callback stays set, and the
+    /// stream loaded, for the rest of the program.
+    #[deprecated(note = "use `RaylibAudio::set_audio_stream_callback`, which takes a closure")]
     pub fn set_audio_stream_callback(
         &mut self,
         stream: AudioStream,
         cb: fn(&[u8]),
-    ) -> Result<(), SetLogError> {
-        if AUDIO_STREAM_CALLBACK.load(Ordering::Acquire) == 0 {
-            AUDIO_STREAM_CALLBACK.store(cb as _, Ordering::Release);
-            unsafe { ffi::SetAudioStreamCallback(stream.0, Some(custom_audio_stream_callback)) }
-            Ok(())
-        } else {
-            Err(SetLogError("audio stream"))
-        }
+    ) -> Result<(), Error> {
+        let frame_size = (stream.sample_size() / 8 * stream.channels()) as usize;
+        let stream = unsafe { stream.inner() };
+        let slot = claim_audio_slot(Box::new(move |buffer, frames| {
+            cb(unsafe { from_raw_parts_mut(buffer as *mut u8, frames as usize * frame_size) })
+        }))?;
+        unsafe { ffi::SetAudioStreamCallback(stream, Some(AUDIO_TRAMPOLINES[slot])) };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod callbacks_test {
+    use super::*;
+
+    #[test]
+    fn test_audio_trampolines() {
+        let slot = claim_audio_slot(mixing_format_callback(|samples| {
+            samples.iter_mut().for_each(|s| *s *= 0.5)
+        }))
+        .unwrap();
+        let mut buffer = [1.0f32, -1.0, 0.5, 0.25];
+        unsafe { AUDIO_TRAMPOLINES[slot](buffer.as_mut_ptr() as *mut c_void, 2) };
+        assert_eq!(buffer, [0.5, -0.5, 0.25, 0.125]);
+
+        release_audio_slot(slot);
+        unsafe { AUDIO_TRAMPOLINES[slot](buffer.as_mut_ptr() as *mut c_void, 2) };
+        assert_eq!(buffer, [0.5, -0.5, 0.25, 0.125]);
+    }
+
+    #[test]
+    fn test_nested_callbacks() {
+        type Callback = dyn FnMut(u32) -> u32 + Send;
+        static STACK: Mutex<CallbackStack<Callback>> = Mutex::new(CallbackStack::new());
+
+        let outer = lock(&STACK).push(Box::new(|x| x + 1));
+        // Calling back from inside a closure reaches the one registered before it.
+        let inner = lock(&STACK).push(Box::new(|x| with_active(&STACK, |f| f(x)).unwrap() * 10));
+        assert_eq!(with_active(&STACK, |f| f(1)), Some(20));
+        assert_eq!(with_active(&STACK, |f| f(2)), Some(30));
+
+        lock(&STACK).remove(inner);
+        assert_eq!(with_active(&STACK, |f| f(1)), Some(2));
+        assert!(lock(&STACK).remove(outer));
+        assert_eq!(with_active(&STACK, |f| f(1)), None);
+    }
+
+    #[test]
+    fn test_trace_log_closure_runs_unlocked() {
+        static SEEN: Mutex<Vec<(usize, String)>> = Mutex::new(Vec::new());
+        // Registering or dropping callbacks locks the stack, the closure must be able to do that.
+        let id = lock(&TRACE_LOG_CALLBACK).push(Box::new(|_, text| {
+            let registered = lock(&TRACE_LOG_CALLBACK).entries.len();
+            lock(&SEEN).push((registered, text.to_owned()));
+        }));
+        unsafe {
+            custom_trace_log_callback(
+                TraceLogLevel::LOG_INFO,
+                b"hello\0".as_ptr() as *const c_char,
+            )
+        };
+        lock(&TRACE_LOG_CALLBACK).remove(id);
+        assert_eq!(*lock(&SEEN), [(1, "hello".to_owned())]);
     }
 }
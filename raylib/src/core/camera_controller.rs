@@ -0,0 +1,654 @@
+//! Configurable controllers for [`Camera2D`] and [`Camera3D`].
+//!
+//! Each controller has an `update` reading input and the frame time from the
+//! [`RaylibHandle`], and an `apply` taking them explicitly for replays, other input sources and
+//! fixed time steps.
+//!
+//! ```no_run
+//! # use raylib::prelude::*;
+//! # let (mut rl, thread) = raylib::init().build();
+//! let mut camera = Camera3D::perspective(
+//!     Vector3::new(0.0, 2.0, 6.0),
+//!     Vector3::zero(),
+//!     Vector3::up(),
+//!     45.0,
+//! );
+//! let mut orbit = OrbitCamera::from_camera(&camera);
+//! orbit.rotate_sensitivity = 0.01;
+//! while !rl.window_should_close() {
+//!     orbit.update(&rl, &mut camera);
+//!     let mut d = rl.begin_drawing(&thread);
+//!     let mut d3 = d.begin_mode3D(camera);
+//!     d3.draw_grid(10, 1.0);
+//! }
+//! ```
+use crate::consts::{KeyboardKey, MouseButton};
+use crate::core::camera::{Camera2D, Camera3D};
+use crate::core::math::{BoundingBox, Matrix, Ray, RayCollision, Rectangle, Vector2, Vector3};
+use crate::core::models::{Model, RaylibModel, WeakModel};
+use crate::core::RaylibHandle;
+use crate::ffi;
+
+/// Input for one controller update, built by [`CameraBindings::read`] or by hand.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct CameraInput {
+    /// Right, up and forward, each in -1..=1.
+    pub movement: Vector3,
+    /// Mouse movement in pixels that rotates the camera.
+    pub look: Vector2,
+    /// Mouse movement in pixels that pans the camera.
+    pub pan: Vector2,
+    /// Mouse wheel movement, positive zooms in.
+    pub zoom: f32,
+    pub boost: bool,
+}
+
+/// Keys and mouse buttons a 3D controller reads.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CameraBindings {
+    pub forward: KeyboardKey,
+    pub back: KeyboardKey,
+    pub left: KeyboardKey,
+    pub right: KeyboardKey,
+    pub up: KeyboardKey,
+    pub down: KeyboardKey,
+    pub boost: KeyboardKey,
+    /// Button to hold for mouse look, `None` looks on any mouse movement.
+    pub look_button: Option<MouseButton>,
+    /// Button to hold for panning, `None` disables panning.
+    pub pan_button: Option<MouseButton>,
+}
+
+impl Default for CameraBindings {
+    fn default() -> Self {
+        CameraBindings {
+            forward: KeyboardKey::KEY_W,
+            back: KeyboardKey::KEY_S,
+            left: KeyboardKey::KEY_A,
+            right: KeyboardKey::KEY_D,
+            up: KeyboardKey::KEY_SPACE,
+            down: KeyboardKey::KEY_LEFT_CONTROL,
+            boost: KeyboardKey::KEY_LEFT_SHIFT,
+            look_button: None,
+            pan_button: None,
+        }
+    }
+}
+
+impl CameraBindings {
+    pub fn read(&self, rl: &RaylibHandle) -> CameraInput {
+        let axis = |positive, negative| {
+            rl.is_key_down(positive) as i32 as f32 - rl.is_key_down(negative) as i32 as f32
+        };
+        let held = |button: Option<MouseButton>| match button {
+            Some(button) => rl.is_mouse_button_down(button),
+            None => true,
+        };
+        let delta = rl.get_mouse_delta();
+        CameraInput {
+            movement: Vector3::new(
+                axis(self.right, self.left),
+                axis(self.up, self.down),
+                axis(self.forward, self.back),
+            ),
+            look: if held(self.look_button) {
+                delta
+            } else {
+                Vector2::zero()
+            },
+            pan: if self.pan_button.is_some() && held(self.pan_button) {
+                delta
+            } else {
+                Vector2::zero()
+            },
+            zoom: rl.get_mouse_wheel_move(),
+            boost: rl.is_key_down(self.boost),
+        }
+    }
+}
+
+/// Something a camera shouldn't see through, see [`ThirdPersonCamera`].
+pub trait CameraCollider {
+    /// Distance along `ray` to the first hit closer than `max_distance`.
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32>;
+}
+
+fn closer(hit: RayCollision, max_distance: f32) -> Option<f32> {
+    (hit.hit && hit.distance >= 0.0 && hit.distance <= max_distance).then_some(hit.distance)
+}
+
+impl CameraCollider for BoundingBox {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        closer(self.get_ray_collision_box(ray), max_distance)
+    }
+}
+
+/// A mesh and its transform.
+impl<M: AsRef<ffi::Mesh>> CameraCollider for (M, Matrix) {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        let hit = unsafe { ffi::GetRayCollisionMesh(ray.into(), *self.0.as_ref(), self.1.into()) };
+        closer(hit.into(), max_distance)
+    }
+}
+
+fn cast_model(model: &impl RaylibModel, ray: Ray, max_distance: f32) -> Option<f32> {
+    let transform = *model.transform();
+    model
+        .meshes()
+        .iter()
+        .filter_map(|mesh| (mesh, transform).cast(ray, max_distance))
+        .reduce(f32::min)
+}
+
+impl CameraCollider for Model {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        cast_model(self, ray, max_distance)
+    }
+}
+
+impl CameraCollider for WeakModel {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        cast_model(self, ray, max_distance)
+    }
+}
+
+impl<T: CameraCollider> CameraCollider for [T] {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        self.iter()
+            .filter_map(|c| c.cast(ray, max_distance))
+            .reduce(f32::min)
+    }
+}
+
+impl<T: CameraCollider, const N: usize> CameraCollider for [T; N] {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        self.as_slice().cast(ray, max_distance)
+    }
+}
+
+impl<T: CameraCollider> CameraCollider for Vec<T> {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        self.as_slice().cast(ray, max_distance)
+    }
+}
+
+impl<T: CameraCollider + ?Sized> CameraCollider for &T {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        (**self).cast(ray, max_distance)
+    }
+}
+
+/// Boxes and meshes together.
+impl<A: CameraCollider, B: CameraCollider> CameraCollider for (A, B) {
+    fn cast(&self, ray: Ray, max_distance: f32) -> Option<f32> {
+        match (
+            self.0.cast(ray, max_distance),
+            self.1.cast(ray, max_distance),
+        ) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+/// No collision.
+impl CameraCollider for () {
+    fn cast(&self, _: Ray, _: f32) -> Option<f32> {
+        None
+    }
+}
+
+/// Unit vector for yaw and pitch in radians, yaw 0 pointing along +Z.
+fn direction(yaw: f32, pitch: f32) -> Vector3 {
+    Vector3::new(
+        pitch.cos() * yaw.sin(),
+        pitch.sin(),
+        pitch.cos() * yaw.cos(),
+    )
+}
+
+/// Yaw and pitch of a non-zero vector, the inverse of [`direction`].
+fn angles(v: Vector3) -> (f32, f32) {
+    let v = v.normalized();
+    (v.x.atan2(v.z), v.y.clamp(-1.0, 1.0).asin())
+}
+
+/// Fraction to move towards a target this frame for exponential smoothing, 0 snaps.
+fn smoothing_factor(smoothing: f32, dt: f32) -> f32 {
+    if smoothing > 0.0 {
+        1.0 - (-smoothing * dt).exp()
+    } else {
+        1.0
+    }
+}
+
+const PITCH_LIMIT: f32 = 89.0 * std::f32::consts::PI / 180.0;
+
+/// Rotates around a target with the mouse, zooms with the wheel and pans while
+/// [`CameraBindings::pan_button`] is held. Movement keys rotate too.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OrbitCamera {
+    pub target: Vector3,
+    pub distance: f32,
+    /// Radians around the up axis.
+    pub yaw: f32,
+    /// Radians above the horizon.
+    pub pitch: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub min_pitch: f32,
+    pub max_pitch: f32,
+    /// Radians per pixel of mouse movement.
+    pub rotate_sensitivity: f32,
+    /// Radians per second while a movement key is held.
+    pub rotate_speed: f32,
+    /// Fraction of the distance per wheel step.
+    pub zoom_speed: f32,
+    /// Fraction of the distance per pixel of mouse movement.
+    pub pan_speed: f32,
+    pub bindings: CameraBindings,
+}
+
+impl OrbitCamera {
+    pub fn new(target: Vector3, distance: f32) -> Self {
+        OrbitCamera {
+            target,
+            distance,
+            yaw: 0.0,
+            pitch: 0.3,
+            min_distance: 0.5,
+            max_distance: 100.0,
+            min_pitch: -PITCH_LIMIT,
+            max_pitch: PITCH_LIMIT,
+            rotate_sensitivity: 0.005,
+            rotate_speed: 1.5,
+            zoom_speed: 0.1,
+            pan_speed: 0.0015,
+            bindings: CameraBindings {
+                look_button: Some(MouseButton::MOUSE_BUTTON_LEFT),
+                pan_button: Some(MouseButton::MOUSE_BUTTON_MIDDLE),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Orbits `camera.target` from where `camera` is now.
+    pub fn from_camera(camera: &Camera3D) -> Self {
+        let offset = camera.position - camera.target;
+        let (yaw, pitch) = angles(offset);
+        OrbitCamera {
+            yaw,
+            pitch,
+            ..Self::new(camera.target, offset.length())
+        }
+    }
+
+    pub fn update(&mut self, rl: &RaylibHandle, camera: &mut Camera3D) {
+        let input = self.bindings.read(rl);
+        self.apply(camera, &input, rl.get_frame_time());
+    }
+
+    pub fn apply(&mut self, camera: &mut Camera3D, input: &CameraInput, dt: f32) {
+        self.yaw -=
+            input.look.x * self.rotate_sensitivity + input.movement.x * self.rotate_speed * dt;
+        self.pitch +=
+            input.look.y * self.rotate_sensitivity + input.movement.z * self.rotate_speed * dt;
+        self.pitch = self.pitch.clamp(self.min_pitch, self.max_pitch);
+        self.distance = (self.distance * (1.0 - input.zoom * self.zoom_speed))
+            .clamp(self.min_distance, self.max_distance);
+
+        let back = direction(self.yaw, self.pitch);
+        if input.pan != Vector2::zero() {
+            let right = Vector3::up().cross(back).normalized();
+            let up = back.cross(right);
+            let scale = self.pan_speed * self.distance;
+            self.target += (up * input.pan.y - right * input.pan.x) * scale;
+        }
+
+        camera.position = self.target + back * self.distance;
+        camera.target = self.target;
+        camera.up = Vector3::up();
+    }
+}
+
+/// Free flight with the movement keys and mouse look.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FlyCamera {
+    pub position: Vector3,
+    /// Radians around the up axis.
+    pub yaw: f32,
+    /// Radians above the horizon.
+    pub pitch: f32,
+    pub min_pitch: f32,
+    pub max_pitch: f32,
+    /// Units per second.
+    pub speed: f32,
+    /// Speed factor while the boost key is held.
+    pub boost_multiplier: f32,
+    /// Radians per pixel of mouse movement.
+    pub look_sensitivity: f32,
+    pub bindings: CameraBindings,
+}
+
+impl FlyCamera {
+    pub fn new(position: Vector3) -> Self {
+        FlyCamera {
+            position,
+            yaw: 0.0,
+            pitch: 0.0,
+            min_pitch: -PITCH_LIMIT,
+            max_pitch: PITCH_LIMIT,
+            speed: 5.0,
+            boost_multiplier: 4.0,
+            look_sensitivity: 0.003,
+            bindings: CameraBindings::default(),
+        }
+    }
+
+    /// Flies from where `camera` is now, looking the same way.
+    pub fn from_camera(camera: &Camera3D) -> Self {
+        let (yaw, pitch) = angles(camera.target - camera.position);
+        FlyCamera {
+            yaw,
+            pitch,
+            ..Self::new(camera.position)
+        }
+    }
+
+    /// Direction the camera looks in.
+    pub fn forward(&self) -> Vector3 {
+        direction(self.yaw, self.pitch)
+    }
+
+    pub fn update(&mut self, rl: &RaylibHandle, camera: &mut Camera3D) {
+        let input = self.bindings.read(rl);
+        self.apply(camera, &input, rl.get_frame_time());
+    }
+
+    pub fn apply(&mut self, camera: &mut Camera3D, input: &CameraInput, dt: f32) {
+        self.yaw -= input.look.x * self.look_sensitivity;
+        self.pitch = (self.pitch - input.look.y * self.look_sensitivity)
+            .clamp(self.min_pitch, self.max_pitch);
+
+        let forward = self.forward();
+        let right = forward.cross(Vector3::up()).normalized();
+        let mut movement = right * input.movement.x
+            + Vector3::up() * input.movement.y
+            + forward * input.movement.z;
+        if movement.length() > 1.0 {
+            movement = movement.normalized();
+        }
+        let boost = if input.boost {
+            self.boost_multiplier
+        } else {
+            1.0
+        };
+        self.position += movement * (self.speed * boost * dt);
+
+        camera.position = self.position;
+        camera.target = self.position + forward;
+        camera.up = Vector3::up();
+    }
+}
+
+/// Follows a point from behind, pulled in when something is between the camera and the
+/// point.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ThirdPersonCamera {
+    /// Height of the point looked at above the followed position.
+    pub height: f32,
+    pub distance: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    /// Radians around the up axis, 0 looks along -Z.
+    pub yaw: f32,
+    /// Radians the camera is raised above the point.
+    pub pitch: f32,
+    pub min_pitch: f32,
+    pub max_pitch: f32,
+    /// Radians per pixel of mouse movement.
+    pub look_sensitivity: f32,
+    /// Fraction of the distance per wheel step.
+    pub zoom_speed: f32,
+    /// How fast the camera catches up with the point and eases back out after a collision,
+    /// 0 snaps.
+    pub smoothing: f32,
+    /// Space kept between the camera and colliders.
+    pub collision_radius: f32,
+    pub bindings: CameraBindings,
+    pivot: Option<Vector3>,
+    current_distance: f32,
+}
+
+impl Default for ThirdPersonCamera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThirdPersonCamera {
+    pub fn new() -> Self {
+        ThirdPersonCamera {
+            height: 1.5,
+            distance: 5.0,
+            min_distance: 1.0,
+            max_distance: 15.0,
+            yaw: 0.0,
+            pitch: 0.3,
+            min_pitch: -0.5,
+            max_pitch: 1.4,
+            look_sensitivity: 0.003,
+            zoom_speed: 0.1,
+            smoothing: 10.0,
+            collision_radius: 0.2,
+            bindings: CameraBindings::default(),
+            pivot: None,
+            current_distance: 5.0,
+        }
+    }
+
+    /// Horizontal direction the camera faces, to move the followed character relative to it.
+    pub fn forward(&self) -> Vector3 {
+        -direction(self.yaw, 0.0)
+    }
+
+    /// Horizontal direction to the right of the camera.
+    pub fn right(&self) -> Vector3 {
+        self.forward().cross(Vector3::up())
+    }
+
+    /// Distance to the point after collisions.
+    pub fn current_distance(&self) -> f32 {
+        self.current_distance
+    }
+
+    /// Jumps to the point without smoothing on the next update.
+    pub fn reset(&mut self) {
+        self.pivot = None;
+    }
+
+    pub fn update(
+        &mut self,
+        rl: &RaylibHandle,
+        camera: &mut Camera3D,
+        target: Vector3,
+        colliders: &(impl CameraCollider + ?Sized),
+    ) {
+        let input = self.bindings.read(rl);
+        self.apply(camera, &input, rl.get_frame_time(), target, colliders);
+    }
+
+    pub fn apply(
+        &mut self,
+        camera: &mut Camera3D,
+        input: &CameraInput,
+        dt: f32,
+        target: Vector3,
+        colliders: &(impl CameraCollider + ?Sized),
+    ) {
+        self.yaw -= input.look.x * self.look_sensitivity;
+        self.pitch = (self.pitch + input.look.y * self.look_sensitivity)
+            .clamp(self.min_pitch, self.max_pitch);
+        self.distance = (self.distance * (1.0 - input.zoom * self.zoom_speed))
+            .clamp(self.min_distance, self.max_distance);
+
+        let goal = target + Vector3::up() * self.height;
+        let t = smoothing_factor(self.smoothing, dt);
+        let pivot = match self.pivot {
+            Some(pivot) => pivot.lerp(goal, t),
+            None => {
+                self.current_distance = self.distance;
+                goal
+            }
+        };
+        self.pivot = Some(pivot);
+
+        let back = direction(self.yaw, self.pitch);
+        let ray = Ray {
+            position: pivot,
+            direction: back,
+        };
+        let allowed = colliders
+            .cast(ray, self.distance + self.collision_radius)
+            .map_or(self.distance, |hit| {
+                (hit - self.collision_radius).clamp(0.0, self.distance)
+            });
+        // Pull in at once so walls never come between, ease back out.
+        if allowed < self.current_distance {
+            self.current_distance = allowed;
+        } else {
+            self.current_distance += (allowed - self.current_distance) * t;
+        }
+
+        camera.position = pivot + back * self.current_distance;
+        camera.target = pivot;
+        camera.up = Vector3::up();
+    }
+}
+
+/// Follows a point in 2D with a deadzone, smoothing, world bounds and screen shake.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FollowCamera2D {
+    /// Where the followed point sits on screen, (0.5, 0.5) is the center.
+    pub anchor: Vector2,
+    /// Half size in pixels of the area around the anchor the point moves in freely.
+    pub deadzone: Vector2,
+    /// How fast the camera catches up, 0 snaps.
+    pub smoothing: f32,
+    /// World area the view stays inside. Views larger than it are centered on it.
+    pub bounds: Option<Rectangle>,
+    /// Offset in pixels at full shake.
+    pub max_shake_offset: f32,
+    /// Rotation in degrees at full shake.
+    pub max_shake_angle: f32,
+    /// Shake lost per second.
+    pub shake_decay: f32,
+    /// Shake oscillations per second.
+    pub shake_frequency: f32,
+    position: Option<Vector2>,
+    trauma: f32,
+    time: f32,
+}
+
+impl Default for FollowCamera2D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FollowCamera2D {
+    pub fn new() -> Self {
+        FollowCamera2D {
+            anchor: Vector2::new(0.5, 0.5),
+            deadzone: Vector2::zero(),
+            smoothing: 8.0,
+            bounds: None,
+            max_shake_offset: 24.0,
+            max_shake_angle: 3.0,
+            shake_decay: 1.5,
+            shake_frequency: 15.0,
+            position: None,
+            trauma: 0.0,
+            time: 0.0,
+        }
+    }
+
+    /// Adds shake, the total is capped at 1. Its strength grows with the square of the total.
+    pub fn shake(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Jumps to the point without smoothing on the next update.
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+
+    pub fn update(&mut self, rl: &RaylibHandle, camera: &mut Camera2D, target: Vector2) {
+        let screen = Vector2::new(rl.get_screen_width() as f32, rl.get_screen_height() as f32);
+        self.apply(camera, target, rl.get_frame_time(), screen);
+    }
+
+    pub fn apply(&mut self, camera: &mut Camera2D, target: Vector2, dt: f32, screen: Vector2) {
+        let zoom = if camera.zoom > 0.0 { camera.zoom } else { 1.0 };
+        let offset = Vector2::new(screen.x * self.anchor.x, screen.y * self.anchor.y);
+        let mut position = self.position.unwrap_or(target);
+
+        let deadzone = self.deadzone / zoom;
+        let outside = |d: f32, half: f32| d - d.clamp(-half, half);
+        let desired = position
+            + Vector2::new(
+                outside(target.x - position.x, deadzone.x),
+                outside(target.y - position.y, deadzone.y),
+            );
+        position = match self.position {
+            Some(_) => position.lerp(desired, smoothing_factor(self.smoothing, dt)),
+            None => desired,
+        };
+
+        if let Some(bounds) = self.bounds {
+            let clamp = |pos: f32, offset: f32, view: f32, min: f32, size: f32| {
+                let view_min = pos - offset;
+                let view_min = if view >= size {
+                    min + (size - view) / 2.0
+                } else {
+                    view_min.clamp(min, min + size - view)
+                };
+                view_min + offset
+            };
+            position.x = clamp(
+                position.x,
+                offset.x / zoom,
+                screen.x / zoom,
+                bounds.x,
+                bounds.width,
+            );
+            position.y = clamp(
+                position.y,
+                offset.y / zoom,
+                screen.y / zoom,
+                bounds.y,
+                bounds.height,
+            );
+        }
+        self.position = Some(position);
+
+        self.time += dt;
+        self.trauma = (self.trauma - self.shake_decay * dt).max(0.0);
+        let shake = self.trauma * self.trauma;
+        let t = self.time * self.shake_frequency;
+        let noise = |seed: f32| {
+            (t + seed).sin() * 0.5
+                + (t * 2.13 + seed * 1.7).sin() * 0.3
+                + (t * 3.71 + seed * 2.9).sin() * 0.2
+        };
+
+        camera.offset = offset;
+        camera.target = position
+            + Vector2::new(noise(0.0), noise(17.0)) * (self.max_shake_offset * shake / zoom);
+        camera.rotation = self.max_shake_angle * shake * noise(41.0);
+    }
+}
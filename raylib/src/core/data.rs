@@ -1,22 +1,34 @@
 //! Data manipulation functions. Compress and Decompress with DEFLATE
 use std::{
     ffi::{c_char, CString},
+    io::{self, Read, Write},
+    mem::ManuallyDrop,
     path::Path,
 };
 
+use miniz_oxide::{
+    deflate::core::CompressorOxide, inflate::stream::InflateState, DataFormat, MZError, MZFlush,
+    MZStatus,
+};
+
 use crate::{
     error::{error, Error},
     ffi,
 };
 
+make_rslice!(DataBuffer, u8, ffi::MemFree);
+
+/// Size of the intermediate buffers used by the streaming codecs.
+const STREAM_CHUNK: usize = 32 * 1024;
+
 /// Compress data (DEFLATE algorythm)
 /// ```rust
 /// use raylib::prelude::*;
 /// let data = compress_data(b"11111").unwrap();
 /// let expected: &[u8] = &[1, 5, 0, 250, 255, 49, 49, 49, 49, 49];
-/// assert_eq!(data, expected);
+/// assert_eq!(&data[..], expected);
 /// ```
-pub fn compress_data(data: &[u8]) -> Result<&'static [u8], Error> {
+pub fn compress_data(data: &[u8]) -> Result<DataBuffer, Error> {
     let mut out_length: i32 = 0;
     // CompressData doesn't actually modify the data, but the header is wrong
     let buffer = {
@@ -25,31 +37,218 @@ pub fn compress_data(data: &[u8]) -> Result<&'static [u8], Error> {
     if buffer.is_null() {
         return Err(error!("could not compress data"));
     }
-    let buffer = unsafe { std::slice::from_raw_parts(buffer, out_length as usize) };
-    return Ok(buffer);
+    Ok(unsafe {
+        DataBuffer(ManuallyDrop::new(Box::from_raw(
+            std::ptr::slice_from_raw_parts_mut(buffer, out_length as usize),
+        )))
+    })
 }
 
 /// Decompress data (DEFLATE algorythm)
+///
+/// Unlike raylib's `DecompressData`, corrupt or truncated input is reported as an error
+/// instead of silently producing partial output.
 /// ```rust
 /// use raylib::prelude::*;
 /// let input: &[u8] = &[1, 5, 0, 250, 255, 49, 49, 49, 49, 49];
 /// let expected: &[u8] = b"11111";
 /// let data = decompress_data(input).unwrap();
 /// assert_eq!(data, expected);
+/// assert!(decompress_data(&[7, 7, 7]).is_err());
 /// ```
-pub fn decompress_data(data: &[u8]) -> Result<&'static [u8], Error> {
-    println!("{:?}", data.len());
+pub fn decompress_data(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::new();
+    DeflateDecoder::new(data)
+        .read_to_end(&mut out)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => error!("could not decompress data: truncated input"),
+            _ => error!("could not decompress data: corrupt input"),
+        })?;
+    Ok(out)
+}
 
-    let mut out_length: i32 = 0;
-    // CompressData doesn't actually modify the data, but the header is wrong
-    let buffer = {
-        unsafe { ffi::DecompressData(data.as_ptr() as *mut _, data.len() as i32, &mut out_length) }
-    };
-    if buffer.is_null() {
-        return Err(error!("could not compress data"));
+/// Streaming DEFLATE compressor. Everything written is compressed into the wrapped writer.
+///
+/// The stream is terminated by [`DeflateEncoder::finish`]. Dropping the encoder finishes it
+/// too, but any error is lost.
+/// ```rust
+/// use raylib::prelude::*;
+/// use std::io::{Read, Write};
+///
+/// let mut encoder = DeflateEncoder::new(Vec::new());
+/// encoder.write_all(b"save game").unwrap();
+/// let compressed = encoder.finish().unwrap();
+///
+/// let mut out = Vec::new();
+/// DeflateDecoder::new(&compressed[..]).read_to_end(&mut out).unwrap();
+/// assert_eq!(out, b"save game");
+/// ```
+pub struct DeflateEncoder<W: Write> {
+    inner: Option<W>,
+    compressor: Box<CompressorOxide>,
+    buffer: Box<[u8]>,
+}
+
+impl<W: Write> DeflateEncoder<W> {
+    /// Wraps `inner` using the default compression level.
+    pub fn new(inner: W) -> Self {
+        Self::with_level(inner, 6)
+    }
+
+    /// Wraps `inner` using a compression level between 0 (store) and 10 (best).
+    pub fn with_level(inner: W, level: u8) -> Self {
+        let mut compressor = Box::<CompressorOxide>::default();
+        compressor.set_format_and_level(DataFormat::Raw, level.min(10));
+        Self {
+            inner: Some(inner),
+            compressor,
+            buffer: vec![0; STREAM_CHUNK].into_boxed_slice(),
+        }
+    }
+
+    /// Gets a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().unwrap()
+    }
+
+    /// Writes the end of the DEFLATE stream and returns the wrapped writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.run(&[], MZFlush::Finish)?;
+        let mut inner = self.inner.take().unwrap();
+        inner.flush()?;
+        Ok(inner)
+    }
+
+    /// Feeds `input` to the compressor, returning how much of it was consumed.
+    fn run(&mut self, mut input: &[u8], flush: MZFlush) -> io::Result<usize> {
+        let inner = self.inner.as_mut().unwrap();
+        let mut consumed = 0;
+        loop {
+            let res = miniz_oxide::deflate::stream::deflate(
+                &mut self.compressor,
+                input,
+                &mut self.buffer,
+                flush,
+            );
+            inner.write_all(&self.buffer[..res.bytes_written])?;
+            input = &input[res.bytes_consumed..];
+            consumed += res.bytes_consumed;
+
+            match res.status {
+                Ok(MZStatus::StreamEnd) => return Ok(consumed),
+                // Output buffer was filled, there may be more pending
+                Ok(_) if res.bytes_written == self.buffer.len() => {}
+                Ok(_) if flush == MZFlush::None && input.is_empty() => return Ok(consumed),
+                Ok(_) if flush == MZFlush::Sync => return Ok(consumed),
+                Ok(_) => {}
+                Err(MZError::Buf) => return Ok(consumed),
+                Err(_) => return Err(io::Error::other("DEFLATE compression failed")),
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for DeflateEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.run(buf, MZFlush::None)
+    }
+
+    /// Flushes pending compressed data to the wrapped writer without ending the stream.
+    fn flush(&mut self) -> io::Result<()> {
+        self.run(&[], MZFlush::Sync)?;
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: Write> Drop for DeflateEncoder<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.run(&[], MZFlush::Finish);
+        }
+    }
+}
+
+/// Streaming DEFLATE decompressor reading compressed data from the wrapped reader.
+///
+/// Corrupt input fails with [`io::ErrorKind::InvalidData`], and a stream that ends early fails
+/// with [`io::ErrorKind::UnexpectedEof`].
+pub struct DeflateDecoder<R: Read> {
+    inner: R,
+    state: Box<InflateState>,
+    buffer: Box<[u8]>,
+    pos: usize,
+    len: usize,
+    eof: bool,
+    done: bool,
+}
+
+impl<R: Read> DeflateDecoder<R> {
+    /// Wraps a reader producing raw DEFLATE data, such as the output of [`compress_data`].
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: InflateState::new_boxed(DataFormat::Raw),
+            buffer: vec![0; STREAM_CHUNK].into_boxed_slice(),
+            pos: 0,
+            len: 0,
+            eof: false,
+            done: false,
+        }
+    }
+
+    /// Gets a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the wrapped reader. Bytes read ahead past the end of the stream are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for DeflateDecoder<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if self.done || out.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if self.pos == self.len && !self.eof {
+                self.len = self.inner.read(&mut self.buffer)?;
+                self.pos = 0;
+                self.eof = self.len == 0;
+            }
+
+            let res = miniz_oxide::inflate::stream::inflate(
+                &mut self.state,
+                &self.buffer[self.pos..self.len],
+                out,
+                MZFlush::None,
+            );
+            self.pos += res.bytes_consumed;
+
+            match res.status {
+                Ok(MZStatus::StreamEnd) => {
+                    self.done = true;
+                    return Ok(res.bytes_written);
+                }
+                Ok(_) | Err(MZError::Buf) if res.bytes_written > 0 => return Ok(res.bytes_written),
+                Ok(_) | Err(MZError::Buf) if self.eof => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "DEFLATE stream ended unexpectedly",
+                    ))
+                }
+                Ok(_) | Err(MZError::Buf) => {}
+                Err(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "corrupt DEFLATE stream",
+                    ))
+                }
+            }
+        }
     }
-    let buffer = unsafe { std::slice::from_raw_parts(buffer, out_length as usize) };
-    return Ok(buffer);
 }
 
 #[cfg(unix)]
@@ -76,8 +275,12 @@ pub fn encode_data_base64(data: &[u8]) -> Vec<c_char> {
     let bytes =
         unsafe { ffi::EncodeDataBase64(data.as_ptr(), data.len() as i32, &mut output_size) };
 
+    if bytes.is_null() {
+        return Vec::new();
+    }
+
     let s = unsafe { std::slice::from_raw_parts(bytes, output_size as usize) };
-    if s.contains(&0) {
+    let out = if s.contains(&0) {
         // Work around a bug in Rust's from_raw_parts function
         let mut keep = true;
         let b: Vec<c_char> = s
@@ -93,17 +296,23 @@ pub fn encode_data_base64(data: &[u8]) -> Vec<c_char> {
         b
     } else {
         s.to_vec()
-    }
+    };
+    unsafe { ffi::MemFree(bytes as *mut _) };
+    out
 }
 
-// Decode Base64 data
+/// Decode Base64 data
 pub fn decode_data_base64(data: &[u8]) -> Vec<u8> {
     let mut output_size = 0;
 
     let bytes = unsafe { ffi::DecodeDataBase64(data.as_ptr(), &mut output_size) };
 
+    if bytes.is_null() {
+        return Vec::new();
+    }
+
     let s = unsafe { std::slice::from_raw_parts(bytes, output_size as usize) };
-    if s.contains(&0) {
+    let out = if s.contains(&0) {
         // Work around a bug in Rust's from_raw_parts function
         let mut keep = true;
         let b: Vec<u8> = s
@@ -119,5 +328,7 @@ pub fn decode_data_base64(data: &[u8]) -> Vec<u8> {
         b
     } else {
         s.to_vec()
-    }
+    };
+    unsafe { ffi::MemFree(bytes as *mut _) };
+    out
 }
@@ -13,6 +13,7 @@ use crate::ffi;
 use crate::math::Matrix;
 use crate::models::{Mesh, WeakMaterial};
 use crate::text::Codepoints;
+use crate::text::{SdfFont, SdfTextStyle};
 use std::convert::AsRef;
 use std::ffi::CString;
 
@@ -1209,6 +1210,50 @@ pub trait RaylibDraw {
         }
     }
 
+    /// Draw text with an SDF font, sharp at any `font_size`, with the effects in `style`.
+    fn draw_text_sdf(
+        &mut self,
+        font: &SdfFont,
+        text: &str,
+        position: impl Into<ffi::Vector2>,
+        font_size: f32,
+        style: &SdfTextStyle,
+    ) {
+        let c_text = CString::new(text).unwrap();
+        let position = position.into();
+        // Each pass gets its own shader mode so the batch is flushed before uniforms change.
+        unsafe {
+            if let Some(shadow) = style.shadow {
+                let dilate = style.outline.map_or(0.0, |o| o.width.max(0.0));
+                font.set_effects(dilate, shadow.softness.max(0.0), None, None);
+                ffi::BeginShaderMode(*font.shader.as_ref());
+                ffi::DrawTextEx(
+                    *font.as_ref(),
+                    c_text.as_ptr(),
+                    ffi::Vector2 {
+                        x: position.x + shadow.offset.x,
+                        y: position.y + shadow.offset.y,
+                    },
+                    font_size,
+                    style.spacing,
+                    shadow.color.into(),
+                );
+                ffi::EndShaderMode();
+            }
+            font.set_effects(0.0, 0.0, style.outline, style.glow);
+            ffi::BeginShaderMode(*font.shader.as_ref());
+            ffi::DrawTextEx(
+                *font.as_ref(),
+                c_text.as_ptr(),
+                position,
+                font_size,
+                style.spacing,
+                style.color.into(),
+            );
+            ffi::EndShaderMode();
+        }
+    }
+
     fn draw_text_pro(
         &mut self,
         font: impl AsRef<ffi::Font>,
@@ -4,7 +4,7 @@ use std::{borrow::Cow, path::PathBuf};
 
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 #[error(
     "{message}{path}",
     path = path.as_ref().map(|p| format!("\npath: {}", p.display())).unwrap_or("".to_owned()),
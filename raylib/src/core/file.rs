@@ -2,6 +2,7 @@
 use crate::ffi;
 
 use crate::core::RaylibHandle;
+use crate::error::{error, Error};
 use std::ffi::{CStr, CString, OsString};
 
 make_thin_wrapper!(FilePathList, ffi::FilePathList, ffi::UnloadDirectoryFiles);
@@ -11,6 +12,24 @@ make_thin_wrapper!(
     ffi::UnloadDroppedFiles
 );
 
+/// Reads a whole file with raylib's `LoadFileData`, so registered file callbacks and a mounted
+/// vfs are used. Empty files count as missing, file callbacks report missing files that way.
+pub(crate) fn load_file_data(filename: &str) -> Result<Vec<u8>, Error> {
+    let c_filename =
+        CString::new(filename).map_err(|_| error!("File name contains a nul byte", filename))?;
+    let mut size = 0;
+    let data = unsafe { ffi::LoadFileData(c_filename.as_ptr(), &mut size) };
+    if data.is_null() {
+        return Err(error!("Could not read file", filename));
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(data, size.max(0) as usize) }.to_vec();
+    unsafe { ffi::UnloadFileData(data) };
+    if bytes.is_empty() {
+        return Err(error!("Could not read file", filename));
+    }
+    Ok(bytes)
+}
+
 impl FilePathList {
     /// Length of the file path list
     pub const fn count(&self) -> u32 {
@@ -89,6 +108,15 @@ impl RaylibHandle {
         unsafe { ffi::GetFileLength(c_str.as_ptr()) }
     }
 
+    /// Get file modification time (last write time), 0 if the file can't be read.
+    pub fn get_file_mod_time<A>(&self, filename: A) -> i64
+    where
+        A: Into<OsString>,
+    {
+        let c_str = CString::new(filename.into().to_string_lossy().as_bytes()).unwrap();
+        unsafe { ffi::GetFileModTime(c_str.as_ptr()) as i64 }
+    }
+
     /// Check if a given path is a file or a directory
     /// # Errors
     /// This function will return an error if the supplied bytes contain an internal 0 byte. The NulError returned will contain the bytes as well as the position of the nul byte.
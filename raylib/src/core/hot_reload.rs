@@ -0,0 +1,311 @@
+//! Reloading shaders and textures when their files change on disk, for development builds.
+//!
+//! ```no_run
+//! # use raylib::prelude::*;
+//! # let (mut rl, thread) = raylib::init().build();
+//! let mut watcher = ShaderWatcher::new();
+//! let post = watcher
+//!     .load_shader(&mut rl, &thread, None, Some("shaders/post.fs"))
+//!     .unwrap();
+//! while !rl.window_should_close() {
+//!     for event in watcher.update(&mut rl, &thread) {
+//!         if let ReloadEvent::ShaderFailed { error, .. } = event {
+//!             eprintln!("{}", error);
+//!         }
+//!     }
+//!     let mut d = rl.begin_drawing(&thread);
+//!     let mut s = d.begin_shader_mode(watcher.shader_mut(post));
+//!     s.draw_rectangle(0, 0, 100, 100, Color::WHITE);
+//! }
+//! ```
+use crate::core::error::Error;
+use crate::core::models::RaylibMaterial;
+use crate::core::shaders::Shader;
+use crate::core::texture::{Image, RaylibTexture2D, Texture2D};
+use crate::core::{RaylibHandle, RaylibThread};
+
+/// Seconds between two checks of the watched files by default.
+const DEFAULT_POLL_INTERVAL: f64 = 0.25;
+
+/// A shader loaded through a [`ShaderWatcher`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct WatchedShader(usize);
+
+/// A texture loaded through a [`ShaderWatcher`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct WatchedTexture(usize);
+
+/// What happened during a [`ShaderWatcher::update`].
+#[derive(Debug, Clone)]
+pub enum ReloadEvent {
+    /// The shader was recompiled and now has a new program id. Locations set by hand with
+    /// `locs_mut` and [`crate::core::shaders::UniformBlock`]s must be set up again. Materials keep
+    /// drawing with the previous program until [`ShaderWatcher::bind_material`] moves them to the
+    /// new one, so it stays loaded until [`ShaderWatcher::collect_retired`] or the watcher is
+    /// dropped.
+    ShaderReloaded(WatchedShader),
+    /// Recompiling failed, the last program that compiled stays in use.
+    ShaderFailed { shader: WatchedShader, error: Error },
+    /// The texture was reloaded. When its size and format didn't change it was updated in place
+    /// and copies of it keep working, otherwise `replaced` is true.
+    TextureReloaded {
+        texture: WatchedTexture,
+        replaced: bool,
+    },
+    /// Reloading failed, the last texture that loaded stays in use.
+    TextureFailed {
+        texture: WatchedTexture,
+        error: Error,
+    },
+}
+
+#[derive(Debug)]
+struct WatchedFile {
+    path: String,
+    modified: i64,
+}
+
+impl WatchedFile {
+    fn new(rl: &RaylibHandle, path: &str) -> Self {
+        WatchedFile {
+            path: path.to_owned(),
+            modified: rl.get_file_mod_time(path),
+        }
+    }
+
+    /// Modification time if it changed since the last load. Files that can't be read are
+    /// skipped, editors often delete and recreate them when saving.
+    fn changed(&self, rl: &RaylibHandle) -> Option<i64> {
+        let modified = rl.get_file_mod_time(self.path.as_str());
+        (modified != 0 && modified != self.modified).then_some(modified)
+    }
+}
+
+#[derive(Debug)]
+struct ShaderEntry {
+    vs: Option<WatchedFile>,
+    fs: Option<WatchedFile>,
+    shader: Shader,
+    /// Programs replaced by reloads. Materials and copies may still point at them, see
+    /// [`ShaderWatcher::collect_retired`].
+    retired: Vec<Shader>,
+    error: Option<Error>,
+}
+
+#[derive(Debug)]
+struct TextureEntry {
+    file: WatchedFile,
+    texture: Texture2D,
+    error: Option<Error>,
+}
+
+/// Owns shaders and textures and reloads them in place when their files change.
+///
+/// Borrow them with [`ShaderWatcher::shader_mut`] and [`ShaderWatcher::texture`] every frame
+/// instead of keeping copies, so reloads are picked up.
+#[derive(Debug)]
+pub struct ShaderWatcher {
+    shaders: Vec<ShaderEntry>,
+    textures: Vec<TextureEntry>,
+    poll_interval: f64,
+    last_poll: Option<f64>,
+    events: Vec<ReloadEvent>,
+}
+
+impl Default for ShaderWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShaderWatcher {
+    pub fn new() -> Self {
+        ShaderWatcher {
+            shaders: Vec::new(),
+            textures: Vec::new(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            last_poll: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Seconds between two checks of the watched files, 0 checks on every update.
+    pub fn poll_interval(mut self, seconds: f64) -> Self {
+        self.poll_interval = seconds;
+        self
+    }
+
+    /// Loads a shader like [`RaylibHandle::load_shader`] and watches its files.
+    pub fn load_shader(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        vs_filename: Option<&str>,
+        fs_filename: Option<&str>,
+    ) -> Result<WatchedShader, Error> {
+        let shader = rl.load_shader(thread, vs_filename, fs_filename)?;
+        self.shaders.push(ShaderEntry {
+            vs: vs_filename.map(|path| WatchedFile::new(rl, path)),
+            fs: fs_filename.map(|path| WatchedFile::new(rl, path)),
+            shader,
+            retired: Vec::new(),
+            error: None,
+        });
+        Ok(WatchedShader(self.shaders.len() - 1))
+    }
+
+    /// Loads a texture like [`RaylibHandle::load_texture`] and watches its file.
+    pub fn load_texture(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        filename: &str,
+    ) -> Result<WatchedTexture, Error> {
+        let texture = rl.load_texture(thread, filename)?;
+        self.textures.push(TextureEntry {
+            file: WatchedFile::new(rl, filename),
+            texture,
+            error: None,
+        });
+        Ok(WatchedTexture(self.textures.len() - 1))
+    }
+
+    pub fn shader(&self, shader: WatchedShader) -> &Shader {
+        &self.shaders[shader.0].shader
+    }
+
+    pub fn shader_mut(&mut self, shader: WatchedShader) -> &mut Shader {
+        &mut self.shaders[shader.0].shader
+    }
+
+    /// Error of the last failed reload, until the shader compiles again.
+    pub fn shader_error(&self, shader: WatchedShader) -> Option<&Error> {
+        self.shaders[shader.0].error.as_ref()
+    }
+
+    pub fn texture(&self, texture: WatchedTexture) -> &Texture2D {
+        &self.textures[texture.0].texture
+    }
+
+    pub fn texture_mut(&mut self, texture: WatchedTexture) -> &mut Texture2D {
+        &mut self.textures[texture.0].texture
+    }
+
+    /// Error of the last failed reload, until the texture loads again.
+    pub fn texture_error(&self, texture: WatchedTexture) -> Option<&Error> {
+        self.textures[texture.0].error.as_ref()
+    }
+
+    /// Makes `material` use the current program of `shader`. Materials keep the program they were
+    /// bound to when the shader reloads, bind them again to pick up the new one.
+    pub fn bind_material(&self, shader: WatchedShader, material: &mut impl RaylibMaterial) {
+        material.as_mut().shader = *self.shaders[shader.0].shader.as_ref();
+    }
+
+    /// Unloads the programs replaced by earlier reloads and returns how many there were.
+    ///
+    /// Only call this once every material and copy of the watched shaders has been moved to the
+    /// current program with [`ShaderWatcher::bind_material`], usually right after handling the
+    /// [`ReloadEvent::ShaderReloaded`] events of an update. Anything still pointing at a retired
+    /// program afterwards draws with a deleted program.
+    pub fn collect_retired(&mut self) -> usize {
+        self.shaders
+            .iter_mut()
+            .map(|entry| entry.retired.drain(..).count())
+            .sum()
+    }
+
+    /// Checks the watched files, at most once per poll interval, and reloads the ones that
+    /// changed.
+    pub fn update(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) -> &[ReloadEvent] {
+        self.events.clear();
+        let now = rl.get_time();
+        if let Some(last) = self.last_poll {
+            if now - last < self.poll_interval {
+                return &self.events;
+            }
+        }
+        self.last_poll = Some(now);
+
+        for (i, entry) in self.shaders.iter_mut().enumerate() {
+            let vs_changed = entry.vs.as_ref().and_then(|f| f.changed(rl));
+            let fs_changed = entry.fs.as_ref().and_then(|f| f.changed(rl));
+            if vs_changed.is_none() && fs_changed.is_none() {
+                continue;
+            }
+            if let (Some(file), Some(modified)) = (entry.vs.as_mut(), vs_changed) {
+                file.modified = modified;
+            }
+            if let (Some(file), Some(modified)) = (entry.fs.as_mut(), fs_changed) {
+                file.modified = modified;
+            }
+
+            let vs = entry.vs.as_ref().map(|f| f.path.as_str());
+            let fs = entry.fs.as_ref().map(|f| f.path.as_str());
+            match rl.load_shader(thread, vs, fs) {
+                Ok(shader) => {
+                    let old = std::mem::replace(&mut entry.shader, shader);
+                    entry.retired.push(old);
+                    entry.error = None;
+                    self.events
+                        .push(ReloadEvent::ShaderReloaded(WatchedShader(i)));
+                }
+                Err(error) => {
+                    entry.error = Some(error.clone());
+                    self.events.push(ReloadEvent::ShaderFailed {
+                        shader: WatchedShader(i),
+                        error,
+                    });
+                }
+            }
+        }
+
+        for (i, entry) in self.textures.iter_mut().enumerate() {
+            let modified = match entry.file.changed(rl) {
+                Some(modified) => modified,
+                None => continue,
+            };
+            entry.file.modified = modified;
+
+            let texture = WatchedTexture(i);
+            match reload_texture(rl, thread, &mut entry.texture, &entry.file.path) {
+                Ok(replaced) => {
+                    entry.error = None;
+                    self.events
+                        .push(ReloadEvent::TextureReloaded { texture, replaced });
+                }
+                Err(error) => {
+                    entry.error = Some(error.clone());
+                    self.events
+                        .push(ReloadEvent::TextureFailed { texture, error });
+                }
+            }
+        }
+
+        &self.events
+    }
+}
+
+/// Updates `texture` in place if the new image has the same size and format, replaces it
+/// otherwise. Returns whether it was replaced.
+fn reload_texture(
+    rl: &mut RaylibHandle,
+    thread: &RaylibThread,
+    texture: &mut Texture2D,
+    path: &str,
+) -> Result<bool, Error> {
+    let image = Image::load_image(path)?;
+    let same_layout = image.width == texture.width
+        && image.height == texture.height
+        && image.format == texture.format
+        && texture.mipmaps == 1;
+    if same_layout {
+        let pixels = unsafe {
+            std::slice::from_raw_parts(image.data as *const u8, image.get_pixel_data_size())
+        };
+        texture.update_texture(pixels)?;
+        return Ok(false);
+    }
+    *texture = rl.load_texture_from_image(thread, &image)?;
+    Ok(true)
+}
@@ -9,6 +9,9 @@ use crate::ffi;
 use std::ffi::c_char;
 use std::ffi::CStr;
 
+mod map;
+pub use map::*;
+
 impl RaylibHandle {
     /// Detect if a key has been pressed once.
     #[inline]
@@ -352,6 +355,10 @@ impl RaylibHandle {
 pub fn key_from_i32(key: i32) -> Option<crate::consts::KeyboardKey> {
     use crate::consts::KeyboardKey::*;
     match key {
+        4 => Some(KEY_BACK),
+        5 => Some(KEY_MENU),
+        24 => Some(KEY_VOLUME_UP),
+        25 => Some(KEY_VOLUME_DOWN),
         39 => Some(KEY_APOSTROPHE),
         44 => Some(KEY_COMMA),
         45 => Some(KEY_MINUS),
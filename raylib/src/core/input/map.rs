@@ -0,0 +1,813 @@
+//! Named actions and axes bound to keys, mouse buttons and gamepads.
+//!
+//! ```no_run
+//! # use raylib::prelude::*;
+//! # let (mut rl, thread) = raylib::init().build();
+//! let mut input = InputMap::new().deadzone(0.25);
+//! input
+//!     .bind_action("jump", KeyboardKey::KEY_SPACE)
+//!     .bind_action("jump", GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN)
+//!     .bind_action(
+//!         "save",
+//!         Binding::chord([
+//!             InputSource::Modifier(Modifier::Control),
+//!             InputSource::Key(KeyboardKey::KEY_S),
+//!         ]),
+//!     )
+//!     .bind_axis(
+//!         "move_x",
+//!         AxisBinding::buttons(KeyboardKey::KEY_A, KeyboardKey::KEY_D),
+//!     )
+//!     .bind_axis("move_x", AxisBinding::analog(GamepadAxis::GAMEPAD_AXIS_LEFT_X));
+//! while !rl.window_should_close() {
+//!     input.update(&mut rl);
+//!     if input.is_action_pressed("jump") {
+//!         // ...
+//!     }
+//!     let speed = input.get_axis("move_x") * 200.0;
+//! #   let _ = speed;
+//! }
+//! ```
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::consts::{GamepadAxis, GamepadButton, KeyboardKey, MouseButton};
+use crate::core::input::key_from_i32;
+use crate::core::math::Vector2;
+use crate::core::RaylibHandle;
+use crate::error::{error, Error};
+
+const MOUSE_BUTTONS: [MouseButton; 7] = [
+    MouseButton::MOUSE_BUTTON_LEFT,
+    MouseButton::MOUSE_BUTTON_RIGHT,
+    MouseButton::MOUSE_BUTTON_MIDDLE,
+    MouseButton::MOUSE_BUTTON_SIDE,
+    MouseButton::MOUSE_BUTTON_EXTRA,
+    MouseButton::MOUSE_BUTTON_FORWARD,
+    MouseButton::MOUSE_BUTTON_BACK,
+];
+
+const GAMEPAD_BUTTONS: [GamepadButton; 17] = [
+    GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_UP,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_RIGHT,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_DOWN,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_LEFT,
+    GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_UP,
+    GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_RIGHT,
+    GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN,
+    GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_LEFT,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_TRIGGER_1,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_TRIGGER_2,
+    GamepadButton::GAMEPAD_BUTTON_RIGHT_TRIGGER_1,
+    GamepadButton::GAMEPAD_BUTTON_RIGHT_TRIGGER_2,
+    GamepadButton::GAMEPAD_BUTTON_MIDDLE_LEFT,
+    GamepadButton::GAMEPAD_BUTTON_MIDDLE,
+    GamepadButton::GAMEPAD_BUTTON_MIDDLE_RIGHT,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_THUMB,
+    GamepadButton::GAMEPAD_BUTTON_RIGHT_THUMB,
+];
+
+const GAMEPAD_AXES: [GamepadAxis; 6] = [
+    GamepadAxis::GAMEPAD_AXIS_LEFT_X,
+    GamepadAxis::GAMEPAD_AXIS_LEFT_Y,
+    GamepadAxis::GAMEPAD_AXIS_RIGHT_X,
+    GamepadAxis::GAMEPAD_AXIS_RIGHT_Y,
+    GamepadAxis::GAMEPAD_AXIS_LEFT_TRIGGER,
+    GamepadAxis::GAMEPAD_AXIS_RIGHT_TRIGGER,
+];
+
+/// A modifier key on either side of the keyboard.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    Shift,
+    Control,
+    Alt,
+    Super,
+}
+
+impl Modifier {
+    const ALL: [Modifier; 4] = [
+        Modifier::Shift,
+        Modifier::Control,
+        Modifier::Alt,
+        Modifier::Super,
+    ];
+
+    pub fn keys(self) -> [KeyboardKey; 2] {
+        use crate::consts::KeyboardKey::*;
+        match self {
+            Modifier::Shift => [KEY_LEFT_SHIFT, KEY_RIGHT_SHIFT],
+            Modifier::Control => [KEY_LEFT_CONTROL, KEY_RIGHT_CONTROL],
+            Modifier::Alt => [KEY_LEFT_ALT, KEY_RIGHT_ALT],
+            Modifier::Super => [KEY_LEFT_SUPER, KEY_RIGHT_SUPER],
+        }
+    }
+
+    pub fn from_key(key: KeyboardKey) -> Option<Modifier> {
+        Self::ALL.iter().copied().find(|m| m.keys().contains(&key))
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Modifier::Shift => "SHIFT",
+            Modifier::Control => "CONTROL",
+            Modifier::Alt => "ALT",
+            Modifier::Super => "SUPER",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AxisDirection {
+    Positive,
+    Negative,
+}
+
+impl AxisDirection {
+    fn sign(self) -> f32 {
+        match self {
+            AxisDirection::Positive => 1.0,
+            AxisDirection::Negative => -1.0,
+        }
+    }
+}
+
+/// One physical input. Gamepad inputs read the gamepad set with [`InputMap::gamepad`].
+///
+/// Displays and parses as the raylib constant name, e.g. `KEY_W` or `MOUSE_BUTTON_LEFT`, with
+/// `+` or `-` after axes and `MOUSE_WHEEL`, and `SHIFT`, `CONTROL`, `ALT` or `SUPER` for
+/// modifiers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InputSource {
+    Key(KeyboardKey),
+    Modifier(Modifier),
+    Mouse(MouseButton),
+    MouseWheel(AxisDirection),
+    GamepadButton(GamepadButton),
+    /// One half of an axis, read from 0 to 1.
+    GamepadAxis(GamepadAxis, AxisDirection),
+}
+
+impl InputSource {
+    /// From 0 to 1, gamepad axes after the deadzone.
+    fn value(&self, rl: &RaylibHandle, gamepad: i32, deadzone: f32) -> f32 {
+        let digital = |down: bool| if down { 1.0 } else { 0.0 };
+        match *self {
+            InputSource::Key(key) => digital(rl.is_key_down(key)),
+            InputSource::Modifier(m) => digital(m.keys().iter().any(|&k| rl.is_key_down(k))),
+            InputSource::Mouse(button) => digital(rl.is_mouse_button_down(button)),
+            InputSource::MouseWheel(dir) => {
+                (rl.get_mouse_wheel_move() * dir.sign()).clamp(0.0, 1.0)
+            }
+            InputSource::GamepadButton(button) => {
+                digital(rl.is_gamepad_button_down(gamepad, button))
+            }
+            InputSource::GamepadAxis(axis, dir) => {
+                let v = apply_deadzone(rl.get_gamepad_axis_movement(gamepad, axis), deadzone);
+                (v * dir.sign()).max(0.0)
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suffix = |dir: &AxisDirection| match dir {
+            AxisDirection::Positive => "+",
+            AxisDirection::Negative => "-",
+        };
+        match self {
+            InputSource::Key(key) => write!(f, "{:?}", key),
+            InputSource::Modifier(m) => f.write_str(m.name()),
+            InputSource::Mouse(button) => write!(f, "{:?}", button),
+            InputSource::MouseWheel(dir) => write!(f, "MOUSE_WHEEL{}", suffix(dir)),
+            InputSource::GamepadButton(button) => write!(f, "{:?}", button),
+            InputSource::GamepadAxis(axis, dir) => write!(f, "{:?}{}", axis, suffix(dir)),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let named = |name: &str, value: &dyn fmt::Debug| format!("{:?}", value) == name;
+        let s = s.trim();
+        let (name, dir) = if let Some(name) = s.strip_suffix('+') {
+            (name, Some(AxisDirection::Positive))
+        } else if let Some(name) = s.strip_suffix('-') {
+            (name, Some(AxisDirection::Negative))
+        } else {
+            (s, None)
+        };
+        let source = match dir {
+            Some(dir) if name == "MOUSE_WHEEL" => Some(InputSource::MouseWheel(dir)),
+            Some(dir) => GAMEPAD_AXES
+                .iter()
+                .copied()
+                .find(|a| named(name, a))
+                .map(|a| InputSource::GamepadAxis(a, dir)),
+            None => Modifier::ALL
+                .iter()
+                .copied()
+                .find(|m| m.name() == name)
+                .map(InputSource::Modifier)
+                .or_else(|| {
+                    MOUSE_BUTTONS
+                        .iter()
+                        .copied()
+                        .find(|b| named(name, b))
+                        .map(InputSource::Mouse)
+                })
+                .or_else(|| {
+                    GAMEPAD_BUTTONS
+                        .iter()
+                        .copied()
+                        .find(|b| named(name, b))
+                        .map(InputSource::GamepadButton)
+                })
+                .or_else(|| {
+                    (0..=348)
+                        .filter_map(key_from_i32)
+                        .find(|k| named(name, k))
+                        .map(InputSource::Key)
+                }),
+        };
+        source.ok_or_else(|| error!(std::borrow::Cow::Owned(format!("Unknown input \"{}\"", s))))
+    }
+}
+
+impl From<KeyboardKey> for InputSource {
+    fn from(key: KeyboardKey) -> Self {
+        InputSource::Key(key)
+    }
+}
+
+impl From<MouseButton> for InputSource {
+    fn from(button: MouseButton) -> Self {
+        InputSource::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for InputSource {
+    fn from(button: GamepadButton) -> Self {
+        InputSource::GamepadButton(button)
+    }
+}
+
+impl From<Modifier> for InputSource {
+    fn from(modifier: Modifier) -> Self {
+        InputSource::Modifier(modifier)
+    }
+}
+
+/// Inputs that must all be held together, a single one for most bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Binding {
+    inputs: Vec<InputSource>,
+}
+
+impl Binding {
+    pub fn new(input: impl Into<InputSource>) -> Self {
+        Binding {
+            inputs: vec![input.into()],
+        }
+    }
+
+    pub fn chord(inputs: impl IntoIterator<Item = InputSource>) -> Self {
+        Binding {
+            inputs: inputs.into_iter().collect(),
+        }
+    }
+
+    pub fn inputs(&self) -> &[InputSource] {
+        &self.inputs
+    }
+
+    /// The weakest input of the chord, 0 for an empty binding.
+    fn value(&self, rl: &RaylibHandle, gamepad: i32, deadzone: f32) -> f32 {
+        self.inputs
+            .iter()
+            .map(|i| i.value(rl, gamepad, deadzone))
+            .reduce(f32::min)
+            .unwrap_or(0.0)
+    }
+}
+
+impl<T: Into<InputSource>> From<T> for Binding {
+    fn from(input: T) -> Self {
+        Binding::new(input)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, input) in self.inputs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" + ")?;
+            }
+            write!(f, "{}", input)?;
+        }
+        Ok(())
+    }
+}
+
+/// How an axis gets a value from -1 to 1.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisBinding {
+    /// A gamepad axis after the deadzone, times `scale`. -1 inverts it.
+    Analog {
+        #[cfg_attr(feature = "serde", serde(with = "gamepad_axis_name"))]
+        axis: GamepadAxis,
+        scale: f32,
+    },
+    /// -1 while `negative` is held, 1 while `positive` is.
+    Buttons {
+        negative: Binding,
+        positive: Binding,
+    },
+}
+
+impl AxisBinding {
+    pub fn analog(axis: GamepadAxis) -> Self {
+        AxisBinding::Analog { axis, scale: 1.0 }
+    }
+
+    pub fn buttons(negative: impl Into<Binding>, positive: impl Into<Binding>) -> Self {
+        AxisBinding::Buttons {
+            negative: negative.into(),
+            positive: positive.into(),
+        }
+    }
+
+    fn value(&self, rl: &RaylibHandle, gamepad: i32, deadzone: f32) -> f32 {
+        match self {
+            AxisBinding::Analog { axis, scale } => {
+                apply_deadzone(rl.get_gamepad_axis_movement(gamepad, *axis), deadzone) * scale
+            }
+            AxisBinding::Buttons { negative, positive } => {
+                positive.value(rl, gamepad, deadzone) - negative.value(rl, gamepad, deadzone)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod gamepad_axis_name {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(axis: &GamepadAxis, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(&format_args!("{:?}", axis))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<GamepadAxis, D::Error> {
+        let name = String::deserialize(d)?;
+        GAMEPAD_AXES
+            .iter()
+            .copied()
+            .find(|a| format!("{:?}", a) == name)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown gamepad axis {}", name)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for InputSource {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for InputSource {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let name = <String as serde::Deserialize>::deserialize(d)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Every binding of an [`InputMap`], what players save when they change their controls.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputBindings {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub actions: BTreeMap<String, Vec<Binding>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub axes: BTreeMap<String, Vec<AxisBinding>>,
+}
+
+#[cfg(feature = "with_serde")]
+impl InputBindings {
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            error!(std::borrow::Cow::Owned(format!(
+                "Could not serialize bindings: {}",
+                e
+            )))
+        })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| {
+            error!(std::borrow::Cow::Owned(format!(
+                "Could not parse bindings: {}",
+                e
+            )))
+        })
+    }
+}
+
+/// Rescales `value` so it is 0 inside the deadzone and reaches ±1 at the ends.
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value.abs() <= deadzone {
+        return 0.0;
+    }
+    (value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)).clamp(-1.0, 1.0)
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+struct ActionState {
+    down: bool,
+    was_down: bool,
+    value: f32,
+}
+
+/// What [`InputMap::update`] writes the next input to.
+#[derive(Debug, Clone)]
+enum CaptureTarget {
+    Action(String, usize),
+    AxisButton(String, usize, AxisDirection),
+    AxisAnalog(String, usize),
+}
+
+#[derive(Debug, Clone)]
+struct Capture {
+    target: CaptureTarget,
+    /// Gamepad axes when the capture started, triggers rest at -1.
+    baseline: Option<[f32; 6]>,
+    /// A modifier pressed on its own, bound alone if released before another input.
+    modifier: Option<KeyboardKey>,
+}
+
+/// Named actions and axes bound to any mix of keys, mouse buttons and gamepad inputs.
+///
+/// Call [`InputMap::update`] once per frame, then query actions with
+/// [`InputMap::is_action_pressed`], [`InputMap::is_action_down`] and
+/// [`InputMap::is_action_released`], and axes with [`InputMap::get_axis`].
+#[derive(Debug, Clone)]
+pub struct InputMap {
+    bindings: InputBindings,
+    gamepad: i32,
+    deadzone: f32,
+    press_threshold: f32,
+    cancel_key: Option<KeyboardKey>,
+    actions: HashMap<String, ActionState>,
+    axes: HashMap<String, f32>,
+    capture: Option<Capture>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        InputMap {
+            bindings: InputBindings::default(),
+            gamepad: 0,
+            deadzone: 0.2,
+            press_threshold: 0.5,
+            cancel_key: Some(KeyboardKey::KEY_ESCAPE),
+            actions: HashMap::new(),
+            axes: HashMap::new(),
+            capture: None,
+        }
+    }
+
+    pub fn with_bindings(bindings: InputBindings) -> Self {
+        InputMap {
+            bindings,
+            ..Self::new()
+        }
+    }
+
+    /// Gamepad the gamepad inputs are read from, 0 by default.
+    pub fn gamepad(mut self, gamepad: i32) -> Self {
+        self.gamepad = gamepad;
+        self
+    }
+
+    /// Gamepad axis values below this are ignored, 0.2 by default.
+    pub fn deadzone(mut self, deadzone: f32) -> Self {
+        self.deadzone = deadzone.clamp(0.0, 0.99);
+        self
+    }
+
+    /// Value at which analog inputs count as held for actions, 0.5 by default.
+    pub fn press_threshold(mut self, threshold: f32) -> Self {
+        self.press_threshold = threshold;
+        self
+    }
+
+    /// Key that cancels a capture instead of being bound, escape by default.
+    pub fn cancel_key(mut self, key: Option<KeyboardKey>) -> Self {
+        self.cancel_key = key;
+        self
+    }
+
+    pub fn set_gamepad(&mut self, gamepad: i32) {
+        self.gamepad = gamepad;
+    }
+
+    pub fn bind_action(&mut self, action: &str, binding: impl Into<Binding>) -> &mut Self {
+        self.bindings
+            .actions
+            .entry(action.to_owned())
+            .or_default()
+            .push(binding.into());
+        self
+    }
+
+    pub fn bind_axis(&mut self, axis: &str, binding: AxisBinding) -> &mut Self {
+        self.bindings
+            .axes
+            .entry(axis.to_owned())
+            .or_default()
+            .push(binding);
+        self
+    }
+
+    /// Removes every binding of `action`.
+    pub fn unbind_action(&mut self, action: &str) {
+        self.bindings.actions.remove(action);
+        self.actions.remove(action);
+    }
+
+    /// Removes every binding of `axis`.
+    pub fn unbind_axis(&mut self, axis: &str) {
+        self.bindings.axes.remove(axis);
+        self.axes.remove(axis);
+    }
+
+    pub fn action_bindings(&self, action: &str) -> &[Binding] {
+        self.bindings
+            .actions
+            .get(action)
+            .map_or(&[], |b| b.as_slice())
+    }
+
+    pub fn axis_bindings(&self, axis: &str) -> &[AxisBinding] {
+        self.bindings.axes.get(axis).map_or(&[], |b| b.as_slice())
+    }
+
+    pub fn bindings(&self) -> &InputBindings {
+        &self.bindings
+    }
+
+    /// Replaces every binding, e.g. with controls loaded from a save.
+    pub fn set_bindings(&mut self, bindings: InputBindings) {
+        self.bindings = bindings;
+        self.actions.clear();
+        self.axes.clear();
+        self.capture = None;
+    }
+
+    /// Reads the inputs for this frame. While capturing, the next input is bound instead and
+    /// actions read as released.
+    pub fn update(&mut self, rl: &mut RaylibHandle) {
+        if self.capture.is_some() {
+            self.update_capture(rl);
+        }
+        let capturing = self.capture.is_some();
+
+        for (name, bindings) in &self.bindings.actions {
+            let value = bindings
+                .iter()
+                .map(|b| b.value(rl, self.gamepad, self.deadzone))
+                .fold(0.0, f32::max);
+            let state = self.actions.entry(name.clone()).or_default();
+            state.was_down = state.down;
+            state.down = !capturing && value >= self.press_threshold;
+            state.value = if capturing { 0.0 } else { value };
+        }
+        for (name, bindings) in &self.bindings.axes {
+            let value = bindings
+                .iter()
+                .map(|b| b.value(rl, self.gamepad, self.deadzone))
+                .sum::<f32>()
+                .clamp(-1.0, 1.0);
+            self.axes
+                .insert(name.clone(), if capturing { 0.0 } else { value });
+        }
+    }
+
+    /// Whether the action started being held this frame.
+    pub fn is_action_pressed(&self, action: &str) -> bool {
+        self.actions
+            .get(action)
+            .is_some_and(|s| s.down && !s.was_down)
+    }
+
+    pub fn is_action_down(&self, action: &str) -> bool {
+        self.actions.get(action).is_some_and(|s| s.down)
+    }
+
+    /// Whether the action stopped being held this frame.
+    pub fn is_action_released(&self, action: &str) -> bool {
+        self.actions
+            .get(action)
+            .is_some_and(|s| !s.down && s.was_down)
+    }
+
+    /// Strongest binding of the action from 0 to 1, analog for gamepad axes.
+    pub fn get_action_value(&self, action: &str) -> f32 {
+        self.actions.get(action).map_or(0.0, |s| s.value)
+    }
+
+    /// Sum of the axis bindings, from -1 to 1.
+    pub fn get_axis(&self, axis: &str) -> f32 {
+        self.axes.get(axis).copied().unwrap_or(0.0)
+    }
+
+    /// Two axes as a vector no longer than 1, so diagonals aren't faster.
+    pub fn get_axis_pair(&self, x: &str, y: &str) -> Vector2 {
+        let v = Vector2::new(self.get_axis(x), self.get_axis(y));
+        if v.length() > 1.0 {
+            v.normalized()
+        } else {
+            v
+        }
+    }
+
+    /// Binds the next key, mouse button, wheel, gamepad button or axis half to `action`,
+    /// replacing its binding number `slot` or adding one if there are fewer. Modifiers held
+    /// with the input are bound with it.
+    pub fn capture_action(&mut self, action: &str, slot: usize) {
+        self.start_capture(CaptureTarget::Action(action.to_owned(), slot));
+    }
+
+    /// Binds the next digital input to one side of the [`AxisBinding::Buttons`] binding number
+    /// `slot` of `axis`, or of a new one if there are fewer.
+    pub fn capture_axis_button(&mut self, axis: &str, slot: usize, direction: AxisDirection) {
+        self.start_capture(CaptureTarget::AxisButton(axis.to_owned(), slot, direction));
+    }
+
+    /// Binds the next gamepad axis that moves to `axis` as binding number `slot`, or adds it.
+    pub fn capture_axis_analog(&mut self, axis: &str, slot: usize) {
+        self.start_capture(CaptureTarget::AxisAnalog(axis.to_owned(), slot));
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    pub fn cancel_capture(&mut self) {
+        self.capture = None;
+    }
+
+    fn start_capture(&mut self, target: CaptureTarget) {
+        self.capture = Some(Capture {
+            target,
+            baseline: None,
+            modifier: None,
+        });
+    }
+
+    fn update_capture(&mut self, rl: &mut RaylibHandle) {
+        let gamepad = self.gamepad;
+        let mut capture = match self.capture.take() {
+            Some(capture) => capture,
+            None => return,
+        };
+        let axes = GAMEPAD_AXES.map(|a| rl.get_gamepad_axis_movement(gamepad, a));
+        let baseline = *capture.baseline.get_or_insert(axes);
+        let moved = GAMEPAD_AXES
+            .iter()
+            .copied()
+            .zip(axes.iter().zip(baseline.iter()))
+            .find(|(_, (v, b))| (*v - *b).abs() > 0.5)
+            .map(|(axis, (v, b))| {
+                let dir = if v > b {
+                    AxisDirection::Positive
+                } else {
+                    AxisDirection::Negative
+                };
+                (axis, dir)
+            });
+
+        if let CaptureTarget::AxisAnalog(name, slot) = &capture.target {
+            if rl.get_key_pressed() == self.cancel_key && self.cancel_key.is_some() {
+                return;
+            }
+            match moved {
+                Some((axis, dir)) => {
+                    let binding = AxisBinding::Analog {
+                        axis,
+                        scale: dir.sign(),
+                    };
+                    replace_or_push(
+                        self.bindings.axes.entry(name.clone()).or_default(),
+                        *slot,
+                        binding,
+                    );
+                }
+                None => self.capture = Some(capture),
+            }
+            return;
+        }
+
+        let mut input = None;
+        while let Some(key) = rl.get_key_pressed() {
+            if Some(key) == self.cancel_key {
+                return;
+            }
+            if Modifier::from_key(key).is_some() {
+                capture.modifier.get_or_insert(key);
+            } else {
+                input = Some(InputSource::Key(key));
+                break;
+            }
+        }
+        input = input
+            .or_else(|| {
+                MOUSE_BUTTONS
+                    .iter()
+                    .copied()
+                    .find(|&b| rl.is_mouse_button_pressed(b))
+                    .map(InputSource::Mouse)
+            })
+            .or_else(|| {
+                let wheel = rl.get_mouse_wheel_move();
+                (wheel > 0.0)
+                    .then_some(InputSource::MouseWheel(AxisDirection::Positive))
+                    .or_else(|| {
+                        (wheel < 0.0).then_some(InputSource::MouseWheel(AxisDirection::Negative))
+                    })
+            })
+            .or_else(|| {
+                // The last pressed button is reported while it is held, so check it was just
+                // pressed.
+                rl.get_gamepad_button_pressed()
+                    .filter(|&b| rl.is_gamepad_button_pressed(gamepad, b))
+                    .map(InputSource::GamepadButton)
+            })
+            .or_else(|| moved.map(|(axis, dir)| InputSource::GamepadAxis(axis, dir)));
+
+        let binding = match (input, capture.modifier) {
+            (Some(input), _) => {
+                let mut inputs: Vec<InputSource> = Modifier::ALL
+                    .iter()
+                    .copied()
+                    .filter(|m| m.keys().iter().any(|&k| rl.is_key_down(k)))
+                    .map(InputSource::Modifier)
+                    .collect();
+                inputs.push(input);
+                Binding::chord(inputs)
+            }
+            (None, Some(key)) if !rl.is_key_down(key) => Binding::new(key),
+            (None, _) => {
+                self.capture = Some(capture);
+                return;
+            }
+        };
+
+        match capture.target {
+            CaptureTarget::Action(name, slot) => replace_or_push(
+                self.bindings.actions.entry(name).or_default(),
+                slot,
+                binding,
+            ),
+            CaptureTarget::AxisButton(name, slot, dir) => {
+                let bindings = self.bindings.axes.entry(name).or_default();
+                let empty = || AxisBinding::buttons(Binding::chord([]), Binding::chord([]));
+                if slot >= bindings.len() {
+                    bindings.push(empty());
+                }
+                let slot = slot.min(bindings.len() - 1);
+                if !matches!(bindings[slot], AxisBinding::Buttons { .. }) {
+                    bindings[slot] = empty();
+                }
+                if let AxisBinding::Buttons { negative, positive } = &mut bindings[slot] {
+                    match dir {
+                        AxisDirection::Negative => *negative = binding,
+                        AxisDirection::Positive => *positive = binding,
+                    }
+                }
+            }
+            CaptureTarget::AxisAnalog(..) => unreachable!(),
+        }
+    }
+}
+
+fn replace_or_push<T>(list: &mut Vec<T>, slot: usize, value: T) {
+    match list.get_mut(slot) {
+        Some(existing) => *existing = value,
+        None => list.push(value),
+    }
+}
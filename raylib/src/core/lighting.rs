@@ -0,0 +1,746 @@
+//! Lights, forward lighting and cascaded shadow maps.
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//! let (mut rl, thread) = raylib::init().build();
+//! let shader = rl.load_lit_shader(&thread).unwrap();
+//! let mut model = rl.load_model(&thread, "level.obj").unwrap();
+//! for material in model.materials_mut() {
+//!     material.shader = *shader;
+//! }
+//!
+//! let shadows = rl.load_shadow_map(&thread, 1024, 3).unwrap();
+//! let mut lighting = Lighting::new().shadows(shadows);
+//! lighting
+//!     .lights
+//!     .push(Light::directional(Vector3::new(-1.0, -2.0, -1.0), Color::WHITE));
+//! lighting
+//!     .lights
+//!     .push(Light::point(Vector3::new(0.0, 2.0, 0.0), Color::ORANGE).intensity(4.0));
+//!
+//! let camera = Camera3D::perspective(
+//!     Vector3::new(0.0, 5.0, 10.0),
+//!     Vector3::zero(),
+//!     Vector3::up(),
+//!     45.0,
+//! );
+//! while !rl.window_should_close() {
+//!     let aspect = rl.get_screen_width() as f32 / rl.get_screen_height() as f32;
+//!     let mut d = rl.begin_drawing(&thread);
+//!     lighting.render_shadows(&mut d, &thread, &camera, aspect, |d, _| {
+//!         d.draw_model(&model, Vector3::zero(), 1.0, Color::WHITE);
+//!     });
+//!     lighting.upload_to_model(&mut model, &camera);
+//!     d.clear_background(Color::BLACK);
+//!     let mut d3 = d.begin_mode3D(camera);
+//!     d3.draw_model(&model, Vector3::zero(), 1.0, Color::WHITE);
+//! }
+//! ```
+use crate::consts;
+use crate::core::camera::Camera3D;
+use crate::core::color::Color;
+use crate::core::drawing::{
+    RaylibDraw, RaylibMode3D, RaylibMode3DExt, RaylibTextureMode, RaylibTextureModeExt,
+};
+use crate::core::math::{Matrix, Vector3, Vector4};
+use crate::core::models::{RaylibMaterial, RaylibModel};
+use crate::core::rlgl::RaylibRlgl;
+use crate::core::shaders::{RaylibShader, Shader, ShaderUniforms, UniformBlock, UniformValue};
+use crate::core::texture::{RenderTexture2D, WeakTexture2D};
+use crate::core::{RaylibHandle, RaylibThread};
+use crate::error::{error, Error};
+use crate::ffi;
+
+/// Most lights [`Lighting`] uploads, the rest are ignored.
+pub const MAX_LIGHTS: usize = 16;
+/// Most cascades a [`ShadowMap`] splits the view into.
+pub const MAX_CASCADES: usize = 4;
+/// Texture slot the shadow map is bound to, past the ones material maps use.
+#[cfg(not(any(feature = "opengl_es_20", target_arch = "wasm32")))]
+pub const SHADOW_MAP_SLOT: i32 = 11;
+/// Texture slot the shadow map is bound to. GLES2 and WebGL only guarantee 8 texture units, so
+/// it shares the slot of `MATERIAL_MAP_CUBEMAP`; materials with a cubemap don't receive shadows.
+#[cfg(any(feature = "opengl_es_20", target_arch = "wasm32"))]
+pub const SHADOW_MAP_SLOT: i32 = 7;
+
+/// Shape of a [`Light`]. Cone angles are half-angles in radians, measured from the light's
+/// direction.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LightKind {
+    Directional,
+    Point,
+    Spot { inner_cone: f32, outer_cone: f32 },
+}
+
+/// A punctual light. `direction` is ignored for point lights and `position` for directional ones.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Light {
+    pub kind: LightKind,
+    pub position: Vector3,
+    pub direction: Vector3,
+    pub color: Color,
+    /// Scales `color`. Point and spot lights fall off with the square of the distance, so this is
+    /// their brightness one unit away.
+    pub intensity: f32,
+    /// Distance at which the light reaches zero, `None` for physically based falloff only.
+    pub range: Option<f32>,
+}
+
+impl Light {
+    pub fn directional(direction: Vector3, color: Color) -> Light {
+        Light {
+            kind: LightKind::Directional,
+            position: Vector3::zero(),
+            direction: direction.normalized(),
+            color,
+            intensity: 1.0,
+            range: None,
+        }
+    }
+
+    pub fn point(position: Vector3, color: Color) -> Light {
+        Light {
+            kind: LightKind::Point,
+            position,
+            direction: Vector3::new(0.0, -1.0, 0.0),
+            color,
+            intensity: 1.0,
+            range: None,
+        }
+    }
+
+    pub fn spot(
+        position: Vector3,
+        direction: Vector3,
+        inner_cone: f32,
+        outer_cone: f32,
+        color: Color,
+    ) -> Light {
+        Light {
+            kind: LightKind::Spot {
+                inner_cone,
+                outer_cone,
+            },
+            position,
+            direction: direction.normalized(),
+            color,
+            intensity: 1.0,
+            range: None,
+        }
+    }
+
+    pub fn intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    pub fn range(mut self, range: f32) -> Self {
+        self.range = Some(range);
+        self
+    }
+}
+
+/// Depth render texture holding the sun's shadows, with the view split into cascades that get
+/// coarser with distance. Cascades are tiled side by side, each `resolution` texels square.
+#[derive(Debug)]
+pub struct ShadowMap {
+    target: RenderTexture2D,
+    resolution: u32,
+    cascades: usize,
+    distance: f32,
+    split_lambda: f32,
+    pcf_radius: u32,
+    bias: f32,
+    splits: [f32; MAX_CASCADES],
+    light_vp: [Matrix; MAX_CASCADES],
+    rendered: bool,
+}
+
+impl ShadowMap {
+    /// View distance shadows reach, 50 by default.
+    pub fn distance(mut self, distance: f32) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Blend between even (0.0) and logarithmic (1.0) cascade splits, 0.5 by default.
+    pub fn split_lambda(mut self, lambda: f32) -> Self {
+        self.split_lambda = lambda.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Texels sampled on each side of a lookup for percentage-closer filtering, up to 2 (5x5).
+    /// 1 by default, 0 gives hard shadows.
+    pub fn pcf_radius(mut self, radius: u32) -> Self {
+        self.pcf_radius = radius.min(2);
+        self
+    }
+
+    /// Depth offset against shadow acne, grown on surfaces turned away from the light.
+    pub fn bias(mut self, bias: f32) -> Self {
+        self.bias = bias;
+        self
+    }
+
+    pub fn resolution(&self) -> u32 {
+        self.resolution
+    }
+
+    pub fn cascades(&self) -> usize {
+        self.cascades
+    }
+
+    /// View depth at which each cascade ends, from the last [`ShadowMap::fit`].
+    pub fn splits(&self) -> &[f32] {
+        &self.splits[..self.cascades]
+    }
+
+    /// Light view-projection of each cascade, from the last [`ShadowMap::fit`].
+    pub fn light_view_projections(&self) -> &[Matrix] {
+        &self.light_vp[..self.cascades]
+    }
+
+    /// The depth texture, cascades side by side.
+    pub fn depth(&self) -> &WeakTexture2D {
+        unsafe { std::mem::transmute(&self.target.depth) }
+    }
+
+    /// Splits the view of `camera` into cascades and fits an orthographic projection along
+    /// `direction` around each of them. Called by [`Lighting::render_shadows`].
+    pub fn fit(&mut self, camera: &Camera3D, aspect: f32, direction: Vector3) {
+        // Near plane `BeginMode3D` uses.
+        let near = unsafe { ffi::rlGetCullDistanceNear() } as f32;
+        let far = self.distance.max(near);
+        let forward = (camera.target - camera.position).normalized();
+        let right = forward.cross(camera.up).normalized();
+        let up = right.cross(forward);
+        let orthographic = camera.camera_type() == consts::CameraProjection::CAMERA_ORTHOGRAPHIC;
+        let half_height = |depth: f32| {
+            if orthographic {
+                camera.fovy / 2.0
+            } else {
+                (camera.fovy.to_radians() / 2.0).tan() * depth
+            }
+        };
+        let direction = direction.normalized();
+        let light_up = if direction.y.abs() > 0.99 {
+            Vector3::new(0.0, 0.0, 1.0)
+        } else {
+            Vector3::up()
+        };
+
+        let mut start = near;
+        for i in 0..self.cascades {
+            let t = (i + 1) as f32 / self.cascades as f32;
+            let logarithmic = near * (far / near).powf(t);
+            let even = near + (far - near) * t;
+            let end = self.split_lambda * logarithmic + (1.0 - self.split_lambda) * even;
+
+            // A bounding sphere keeps the cascade's size fixed as the camera turns.
+            let mut corners = [Vector3::zero(); 8];
+            for (j, depth) in [start, end].iter().enumerate() {
+                let center = camera.position + forward * *depth;
+                let h = half_height(*depth);
+                let w = h * aspect;
+                for (k, (x, y)) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)]
+                    .iter()
+                    .enumerate()
+                {
+                    corners[j * 4 + k] = center + right * (w * x) + up * (h * y);
+                }
+            }
+            let center = corners.iter().fold(Vector3::zero(), |sum, c| sum + *c) / 8.0;
+            let radius = corners
+                .iter()
+                .map(|c| c.distance_to(center))
+                .fold(0.0, f32::max);
+            let radius = (radius * 16.0).ceil() / 16.0;
+
+            // Casters up to `distance` behind the cascade still throw shadows into it.
+            let back = radius + self.distance;
+            let view = Matrix::look_at(center - direction * back, center, light_up);
+            let mut projection =
+                Matrix::ortho(-radius, radius, -radius, radius, 0.0, back + radius);
+            // Move in whole texels so shadow edges don't shimmer as the camera moves.
+            let half = self.resolution as f32 / 2.0;
+            let origin = Vector3::zero().transform_with(view * projection) * half;
+            projection.m12 += (origin.x.round() - origin.x) / half;
+            projection.m13 += (origin.y.round() - origin.y) / half;
+
+            self.light_vp[i] = view * projection;
+            self.splits[i] = end;
+            start = end;
+        }
+    }
+}
+
+impl RaylibHandle {
+    /// Loads a shadow map of `cascades` tiles, 1 to [`MAX_CASCADES`], each `resolution` texels
+    /// square.
+    pub fn load_shadow_map(
+        &mut self,
+        thread: &RaylibThread,
+        resolution: u32,
+        cascades: usize,
+    ) -> Result<ShadowMap, Error> {
+        if cascades == 0 || cascades > MAX_CASCADES {
+            return Err(error!(std::borrow::Cow::Owned(format!(
+                "shadow maps have 1 to {} cascades, not {}",
+                MAX_CASCADES, cascades
+            ))));
+        }
+        let target =
+            self.load_render_texture_depth(thread, resolution * cascades as u32, resolution)?;
+        Ok(ShadowMap {
+            target,
+            resolution,
+            cascades,
+            distance: 50.0,
+            split_lambda: 0.5,
+            pcf_radius: 1,
+            bias: 0.002,
+            splits: [0.0; MAX_CASCADES],
+            light_vp: [Matrix::identity(); MAX_CASCADES],
+            rendered: false,
+        })
+    }
+
+    /// Loads the built-in forward lit shader: the material's albedo map and color shaded by the
+    /// [`LightUniforms`] of a [`Lighting`], with Blinn-Phong highlights and filtered cascaded
+    /// shadows from the sun.
+    pub fn load_lit_shader(&mut self, thread: &RaylibThread) -> Result<Shader, Error> {
+        let vs = format!("{}{}", VS_HEADER, LIT_VS);
+        let fs = format!(
+            "{}#define MAX_LIGHTS {}\n#define MAX_CASCADES {}\n{}",
+            FS_HEADER, MAX_LIGHTS, MAX_CASCADES, LIT_FS
+        );
+        self.load_shader_from_memory(thread, Some(&vs), Some(&fs))
+    }
+}
+
+/// Uniforms [`Lighting`] uploads. Arrays hold one entry per light; shaders declare them with
+/// [`MAX_LIGHTS`] entries.
+///
+/// | Uniform | GLSL type |
+/// |---|---|
+/// | `viewPos`, `viewDir` | `vec3` |
+/// | `ambient` | `vec4` |
+/// | `lightCount` | `int` |
+/// | `lightType` | `int[]`, 0 directional, 1 point, 2 spot |
+/// | `lightPosition`, `lightDirection` | `vec3[]` |
+/// | `lightColor` | `vec3[]`, scaled by intensity |
+/// | `lightParams` | `vec4[]`, range (0 for none), cosines of the inner and outer cones, 1 if the light casts shadows |
+/// | `shadowCascades` | `int`, 0 without shadows |
+/// | `shadowSplits` | `vec4`, view depth at which each cascade ends |
+/// | `shadowParams` | `vec4`, texel size of a cascade, bias and PCF radius |
+/// | `shadowMap` | `sampler2D`, bound to [`SHADOW_MAP_SLOT`] |
+/// | `lightVP` | `mat4[MAX_CASCADES]` |
+#[derive(Debug, Clone, Default)]
+pub struct LightUniforms {
+    pub view_pos: Vector3,
+    pub view_dir: Vector3,
+    pub ambient: Color,
+    pub light_count: i32,
+    pub light_type: Vec<i32>,
+    pub light_position: Vec<Vector3>,
+    pub light_direction: Vec<Vector3>,
+    pub light_color: Vec<Vector3>,
+    pub light_params: Vec<Vector4>,
+    pub shadow_cascades: i32,
+    pub shadow_splits: Vector4,
+    pub shadow_params: Vector4,
+    pub shadow_map: i32,
+    pub light_vp: [Matrix; MAX_CASCADES],
+}
+
+impl ShaderUniforms for LightUniforms {
+    const UNIFORM_NAMES: &'static [&'static str] = &[
+        "viewPos",
+        "viewDir",
+        "ambient",
+        "lightCount",
+        "lightType",
+        "lightPosition",
+        "lightDirection",
+        "lightColor",
+        "lightParams",
+        "shadowCascades",
+        "shadowSplits",
+        "shadowParams",
+        "shadowMap",
+        "lightVP[0]",
+        "lightVP[1]",
+        "lightVP[2]",
+        "lightVP[3]",
+    ];
+
+    fn set_uniforms(&self, shader: ffi::Shader, locations: &[i32]) {
+        let values: [&dyn UniformValue; 17] = [
+            &self.view_pos,
+            &self.view_dir,
+            &self.ambient,
+            &self.light_count,
+            &self.light_type,
+            &self.light_position,
+            &self.light_direction,
+            &self.light_color,
+            &self.light_params,
+            &self.shadow_cascades,
+            &self.shadow_splits,
+            &self.shadow_params,
+            &self.shadow_map,
+            &self.light_vp[0],
+            &self.light_vp[1],
+            &self.light_vp[2],
+            &self.light_vp[3],
+        ];
+        for (value, &location) in values.iter().zip(locations) {
+            if location >= 0 {
+                value.set_uniform(shader, location);
+            }
+        }
+    }
+}
+
+/// Lights and shadows uploaded to material shaders before drawing, for
+/// [`RaylibHandle::load_lit_shader`] or custom shaders declaring [`LightUniforms`].
+#[derive(Debug)]
+pub struct Lighting {
+    /// Lights past [`MAX_LIGHTS`] are ignored.
+    pub lights: Vec<Light>,
+    pub ambient: Color,
+    shadows: Option<ShadowMap>,
+    blocks: Vec<((u32, usize), UniformBlock<LightUniforms>)>,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Lighting {
+            lights: Vec::new(),
+            ambient: Color::new(51, 51, 51, 255),
+            shadows: None,
+            blocks: Vec::new(),
+        }
+    }
+}
+
+impl Lighting {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ambient(mut self, ambient: Color) -> Self {
+        self.ambient = ambient;
+        self
+    }
+
+    /// Casts shadows from the sun into `shadows`.
+    pub fn shadows(mut self, shadows: ShadowMap) -> Self {
+        self.shadows = Some(shadows);
+        self
+    }
+
+    pub fn shadow_map(&self) -> Option<&ShadowMap> {
+        self.shadows.as_ref()
+    }
+
+    pub fn shadow_map_mut(&mut self) -> Option<&mut ShadowMap> {
+        self.shadows.as_mut()
+    }
+
+    /// Index of the sun, the first directional light, which is the one casting shadows.
+    pub fn sun(&self) -> Option<usize> {
+        self.lights
+            .iter()
+            .take(MAX_LIGHTS)
+            .position(|light| light.kind == LightKind::Directional)
+    }
+
+    /// Renders the sun's shadow map. `draw` is called once per cascade with its index and draws
+    /// the shadow casters. Does nothing without a shadow map or a sun.
+    pub fn render_shadows<D: RaylibTextureModeExt>(
+        &mut self,
+        d: &mut D,
+        thread: &RaylibThread,
+        camera: &Camera3D,
+        aspect: f32,
+        mut draw: impl FnMut(&mut RaylibMode3D<RaylibTextureMode<D>>, usize),
+    ) {
+        let direction = self.sun().map(|sun| self.lights[sun].direction);
+        let (shadows, direction) = match (self.shadows.as_mut(), direction) {
+            (Some(shadows), Some(direction)) => (shadows, direction),
+            _ => return,
+        };
+        shadows.fit(camera, aspect, direction);
+
+        // Casters drawn with the lit shader must not sample the texture being rendered.
+        unsafe {
+            ffi::rlActiveTextureSlot(SHADOW_MAP_SLOT);
+            ffi::rlDisableTexture();
+            ffi::rlActiveTextureSlot(0);
+        }
+        let resolution = shadows.resolution as i32;
+        let light_vp = shadows.light_vp;
+        let cascades = shadows.cascades;
+        {
+            let mut target = d.begin_texture_mode(thread, &mut shadows.target);
+            target.clear_background(Color::WHITE);
+            for (i, vp) in light_vp[..cascades].iter().enumerate() {
+                // Any camera will do, both matrices are replaced.
+                let mut d3 = target.begin_mode3D(*camera);
+                d3.rl_viewport(resolution * i as i32, 0, resolution, resolution);
+                unsafe {
+                    ffi::rlSetMatrixProjection(Matrix::identity().into());
+                    ffi::rlSetMatrixModelview((*vp).into());
+                }
+                draw(&mut d3, i);
+            }
+        }
+        shadows.rendered = true;
+    }
+
+    /// Uniform values for `camera`.
+    pub fn uniforms(&self, camera: &Camera3D) -> LightUniforms {
+        let sun = self.sun();
+        let shadows = self
+            .shadows
+            .as_ref()
+            .filter(|shadows| shadows.rendered && sun.is_some());
+        let mut uniforms = LightUniforms {
+            view_pos: camera.position,
+            view_dir: (camera.target - camera.position).normalized(),
+            ambient: self.ambient,
+            shadow_map: SHADOW_MAP_SLOT,
+            light_vp: [Matrix::identity(); MAX_CASCADES],
+            ..Default::default()
+        };
+        for (i, light) in self.lights.iter().take(MAX_LIGHTS).enumerate() {
+            let (kind, cos_inner, cos_outer) = match light.kind {
+                LightKind::Directional => (0, 1.0, -1.0),
+                LightKind::Point => (1, 1.0, -1.0),
+                LightKind::Spot {
+                    inner_cone,
+                    outer_cone,
+                } => {
+                    // The shader blends with smoothstep, which needs distinct edges.
+                    let cos_outer = outer_cone.cos();
+                    (2, inner_cone.cos().max(cos_outer + 1e-4), cos_outer)
+                }
+            };
+            let color = light.color.color_normalize();
+            let casts_shadows = shadows.is_some() && sun == Some(i);
+            uniforms.light_type.push(kind);
+            uniforms.light_position.push(light.position);
+            uniforms.light_direction.push(light.direction.normalized());
+            uniforms
+                .light_color
+                .push(Vector3::new(color.x, color.y, color.z) * light.intensity);
+            uniforms.light_params.push(Vector4::new(
+                light.range.unwrap_or(0.0),
+                cos_inner,
+                cos_outer,
+                if casts_shadows { 1.0 } else { 0.0 },
+            ));
+        }
+        uniforms.light_count = uniforms.light_type.len() as i32;
+        if let Some(shadows) = shadows {
+            let s = shadows.splits;
+            uniforms.shadow_cascades = shadows.cascades as i32;
+            uniforms.shadow_splits = Vector4::new(s[0], s[1], s[2], s[3]);
+            uniforms.shadow_params = Vector4::new(
+                1.0 / shadows.resolution as f32,
+                shadows.bias,
+                shadows.pcf_radius as f32,
+                0.0,
+            );
+            uniforms.light_vp = shadows.light_vp;
+        }
+        uniforms
+    }
+
+    /// Uploads the lights to `shader` and binds the shadow map. Uniform locations are looked up
+    /// the first time a shader is seen.
+    pub fn upload(&mut self, shader: &mut impl RaylibShader, camera: &Camera3D) {
+        let uniforms = self.uniforms(camera);
+        let key = (shader.as_ref().id, shader.as_ref().locs as usize);
+        let block = match self.blocks.iter().position(|(k, _)| *k == key) {
+            Some(i) => &self.blocks[i].1,
+            None => {
+                self.blocks.push((key, UniformBlock::new(shader)));
+                &self.blocks[self.blocks.len() - 1].1
+            }
+        };
+        block.upload(shader, &uniforms);
+
+        if let Some(shadows) = self.shadows.as_ref().filter(|s| s.rendered) {
+            unsafe {
+                ffi::rlActiveTextureSlot(SHADOW_MAP_SLOT);
+                ffi::rlEnableTexture(shadows.target.depth.id);
+                ffi::rlActiveTextureSlot(0);
+            }
+        }
+    }
+
+    /// Uploads the lights to the shader of `material`, unless it uses raylib's default shader.
+    pub fn upload_to_material(&mut self, material: &mut impl RaylibMaterial, camera: &Camera3D) {
+        if material.shader().id != unsafe { ffi::rlGetShaderIdDefault() } {
+            self.upload(material.shader_mut(), camera);
+        }
+    }
+
+    /// Uploads the lights once to each shader used by the materials of `model`.
+    pub fn upload_to_model(&mut self, model: &mut impl RaylibModel, camera: &Camera3D) {
+        let mut done = Vec::new();
+        for material in model.materials_mut() {
+            let id = material.shader().id;
+            if !done.contains(&id) {
+                done.push(id);
+                self.upload_to_material(material, camera);
+            }
+        }
+    }
+}
+
+#[cfg(not(any(feature = "opengl_es_20", target_arch = "wasm32")))]
+const VS_HEADER: &str = "#version 330
+#define IN in
+#define OUT out
+";
+
+#[cfg(any(feature = "opengl_es_20", target_arch = "wasm32"))]
+const VS_HEADER: &str = "#version 100
+#define IN attribute
+#define OUT varying
+";
+
+#[cfg(not(any(feature = "opengl_es_20", target_arch = "wasm32")))]
+const FS_HEADER: &str = "#version 330
+#define IN in
+out vec4 finalColor;
+#define TEX texture
+";
+
+#[cfg(any(feature = "opengl_es_20", target_arch = "wasm32"))]
+const FS_HEADER: &str = "#version 100
+precision mediump float;
+#define IN varying
+#define TEX texture2D
+#define finalColor gl_FragColor
+";
+
+const LIT_VS: &str = "
+IN vec3 vertexPosition;
+IN vec2 vertexTexCoord;
+IN vec3 vertexNormal;
+IN vec4 vertexColor;
+uniform mat4 mvp;
+uniform mat4 matModel;
+uniform mat4 matNormal;
+OUT vec3 fragPosition;
+OUT vec2 fragTexCoord;
+OUT vec4 fragColor;
+OUT vec3 fragNormal;
+void main() {
+    fragPosition = vec3(matModel * vec4(vertexPosition, 1.0));
+    fragTexCoord = vertexTexCoord;
+    fragColor = vertexColor;
+    fragNormal = normalize(vec3(matNormal * vec4(vertexNormal, 0.0)));
+    gl_Position = mvp * vec4(vertexPosition, 1.0);
+}
+";
+
+const LIT_FS: &str = "
+IN vec3 fragPosition;
+IN vec2 fragTexCoord;
+IN vec4 fragColor;
+IN vec3 fragNormal;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+
+uniform vec3 viewPos;
+uniform vec3 viewDir;
+uniform vec4 ambient;
+uniform int lightCount;
+uniform int lightType[MAX_LIGHTS];
+uniform vec3 lightPosition[MAX_LIGHTS];
+uniform vec3 lightDirection[MAX_LIGHTS];
+uniform vec3 lightColor[MAX_LIGHTS];
+uniform vec4 lightParams[MAX_LIGHTS];
+
+uniform int shadowCascades;
+uniform vec4 shadowSplits;
+uniform vec4 shadowParams;
+uniform mat4 lightVP[MAX_CASCADES];
+uniform sampler2D shadowMap;
+
+float shadow(float facing) {
+    float depth = dot(fragPosition - viewPos, viewDir);
+    mat4 vp = lightVP[0];
+    float cascade = -1.0;
+    for (int i = MAX_CASCADES - 1; i >= 0; i--) {
+        if (i < shadowCascades && depth <= shadowSplits[i]) {
+            vp = lightVP[i];
+            cascade = float(i);
+        }
+    }
+    if (cascade < 0.0) return 1.0;
+
+    vec4 clip = vp * vec4(fragPosition, 1.0);
+    vec3 p = clip.xyz / clip.w * 0.5 + 0.5;
+    if (p.z > 1.0) return 1.0;
+    float texel = shadowParams.x;
+    float bias = shadowParams.y * (1.0 + 2.0 * (1.0 - facing));
+    float lit = 0.0;
+    float samples = 0.0;
+    for (int x = -2; x <= 2; x++) {
+        for (int y = -2; y <= 2; y++) {
+            if (abs(float(x)) <= shadowParams.z && abs(float(y)) <= shadowParams.z) {
+                vec2 uv = clamp(p.xy + vec2(float(x), float(y)) * texel, 0.5 * texel, 1.0 - 0.5 * texel);
+                uv.x = (uv.x + cascade) / float(shadowCascades);
+                lit += p.z - bias > TEX(shadowMap, uv).r ? 0.0 : 1.0;
+                samples += 1.0;
+            }
+        }
+    }
+    return lit / samples;
+}
+
+void main() {
+    vec4 albedo = TEX(texture0, fragTexCoord) * colDiffuse * fragColor;
+    vec3 normal = normalize(fragNormal);
+    vec3 toView = normalize(viewPos - fragPosition);
+    vec3 diffuse = ambient.rgb;
+    vec3 specular = vec3(0.0);
+    for (int i = 0; i < MAX_LIGHTS; i++) {
+        if (i >= lightCount) break;
+        vec3 toLight = -normalize(lightDirection[i]);
+        float attenuation = 1.0;
+        if (lightType[i] != 0) {
+            vec3 offset = lightPosition[i] - fragPosition;
+            float dist2 = max(dot(offset, offset), 0.0001);
+            toLight = offset * inversesqrt(dist2);
+            attenuation = 1.0 / dist2;
+            float range = lightParams[i].x;
+            if (range > 0.0) {
+                float r = dist2 / (range * range);
+                float window = clamp(1.0 - r * r, 0.0, 1.0);
+                attenuation *= window * window;
+            }
+            if (lightType[i] == 2) {
+                float cosAngle = dot(-toLight, normalize(lightDirection[i]));
+                attenuation *= smoothstep(lightParams[i].z, lightParams[i].y, cosAngle);
+            }
+        }
+        float facing = max(dot(normal, toLight), 0.0);
+        if (lightParams[i].w > 0.5) attenuation *= shadow(facing);
+        vec3 radiance = lightColor[i] * attenuation;
+        diffuse += radiance * facing;
+        if (facing > 0.0) {
+            specular += radiance * pow(max(dot(normal, normalize(toLight + toView)), 0.0), 32.0);
+        }
+    }
+    finalColor = vec4(albedo.rgb * diffuse + specular * 0.5, albedo.a);
+}
+";
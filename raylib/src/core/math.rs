@@ -1780,6 +1780,37 @@ impl Matrix {
             self.m9, self.m10, self.m11, self.m12, self.m13, self.m14, self.m15,
         ]
     }
+
+    /// Splits the matrix into translation, rotation and scale. Shear is lost.
+    pub fn decompose(&self) -> Transform {
+        let m = self;
+        let scale = Vector3::new(
+            Vector3::new(m.m0, m.m1, m.m2).length(),
+            Vector3::new(m.m4, m.m5, m.m6).length(),
+            Vector3::new(m.m8, m.m9, m.m10).length(),
+        );
+        let inv = |s: f32| if s == 0.0 { 0.0 } else { 1.0 / s };
+        let (x, y, z) = (inv(scale.x), inv(scale.y), inv(scale.z));
+        let rotation = Matrix {
+            m0: m.m0 * x,
+            m1: m.m1 * x,
+            m2: m.m2 * x,
+            m4: m.m4 * y,
+            m5: m.m5 * y,
+            m6: m.m6 * y,
+            m8: m.m8 * z,
+            m9: m.m9 * z,
+            m10: m.m10 * z,
+            // `Quaternion::from_matrix` counts m15 in the trace.
+            m15: 0.0,
+            ..Matrix::identity()
+        };
+        Transform {
+            translation: Vector3::new(m.m12, m.m13, m.m14),
+            rotation: Quaternion::from_matrix(rotation).normalized(),
+            scale,
+        }
+    }
 }
 
 impl Add for Matrix {
@@ -2018,6 +2049,16 @@ optional_serde_struct! {
     }
 }
 
+impl Transform {
+    /// Returns the matrix that scales first, then rotates, then translates.
+    pub fn to_matrix(&self) -> Matrix {
+        // `Quaternion::to_matrix` is the transpose of raymath's `QuaternionToMatrix`.
+        Matrix::scale(self.scale.x, self.scale.y, self.scale.z)
+            * self.rotation.to_matrix().transposed()
+            * Matrix::translate(self.translation.x, self.translation.y, self.translation.z)
+    }
+}
+
 impl From<ffi::Transform> for Transform {
     fn from(r: ffi::Transform) -> Transform {
         unsafe { std::mem::transmute(r) }
@@ -2042,7 +2083,7 @@ impl From<&Transform> for ffi::Transform {
 
 #[cfg(test)]
 mod math_test {
-    use super::{Ray, Vector2, Vector3, Vector4};
+    use super::{Quaternion, Ray, Transform, Vector2, Vector3, Vector4};
     use crate::ffi;
 
     #[test]
@@ -2092,4 +2133,21 @@ mod math_test {
             "bad memory transmutation"
         )
     }
+
+    #[test]
+    fn test_transform_matrix() {
+        let t = Transform {
+            translation: Vector3::new(1.0, 2.0, 3.0),
+            rotation: Quaternion::from_euler(0.0, std::f32::consts::FRAC_PI_2, 0.0),
+            scale: Vector3::new(2.0, 2.0, 2.0),
+        };
+        let point = Vector3::new(1.0, 0.0, 0.0);
+        let expected = (point * t.scale).rotate_by(t.rotation) + t.translation;
+        assert!((point.transform_with(t.to_matrix()) - expected).length() < 1e-5);
+
+        let back = t.to_matrix().decompose();
+        assert!((back.translation - t.translation).length() < 1e-5);
+        assert!((back.scale - t.scale).length() < 1e-5);
+        assert!((point.rotate_by(back.rotation) - point.rotate_by(t.rotation)).length() < 1e-5);
+    }
 }
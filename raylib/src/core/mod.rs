@@ -1,23 +1,34 @@
 #[macro_use]
 mod macros;
 
+pub mod atlas;
 pub mod audio;
 pub mod automation;
 pub mod callbacks;
 pub mod camera;
+pub mod camera_controller;
 pub mod collision;
 pub mod color;
 pub mod data;
 pub mod drawing;
 pub mod error;
+pub mod hot_reload;
 pub mod input;
+pub mod lighting;
 pub mod logging;
 pub mod math;
 pub mod misc;
 pub mod models;
+pub mod pixels;
+pub mod postprocess;
+pub mod rlgl;
+pub mod scene;
 pub mod shaders;
+pub mod sprite;
 pub mod text;
+pub mod text_layout;
 pub mod texture;
+pub mod vfs;
 pub mod vr;
 pub mod window;
 pub mod file;
@@ -78,6 +89,8 @@ pub struct RaylibBuilder {
     window_resizable: bool,
     window_undecorated: bool,
     window_transparent: bool,
+    window_hidden: bool,
+    software_rendering: bool,
     msaa_4x_hint: bool,
     vsync_hint: bool,
     log_level: TraceLogLevel,
@@ -126,6 +139,30 @@ impl RaylibBuilder {
         self
     }
 
+    /// Creates the window hidden. Rendering still works, draw into a render texture and read it
+    /// back with [`crate::texture::RaylibRenderTexture2D::load_rendered_image`].
+    pub fn hidden(&mut self) -> &mut Self {
+        self.window_hidden = true;
+        self
+    }
+
+    /// Creates a hidden window rendered by Mesa's software rasterizer (llvmpipe), so no GPU is
+    /// needed. Useful for CI and server-side rendering.
+    ///
+    /// With the default GLFW platform an X11 or Wayland display is still required (e.g. `Xvfb`).
+    /// Running without any display needs the `sdl` feature on Linux, the only platform the build
+    /// script links SDL2 for; SDL's offscreen video driver is then used.
+    ///
+    /// [`RaylibBuilder::build`] configures Mesa and SDL through `LIBGL_ALWAYS_SOFTWARE`,
+    /// `GALLIUM_DRIVER` and `SDL_VIDEODRIVER`. Setting environment variables races with any other
+    /// thread reading them, so build the handle before spawning threads, or set those variables
+    /// yourself beforehand: variables that are already set are left untouched.
+    pub fn headless(&mut self) -> &mut Self {
+        self.window_hidden = true;
+        self.software_rendering = true;
+        self
+    }
+
     /// Hints that 4x MSAA (anti-aliasing) should be enabled. The system's graphics drivers may override this setting.
     pub fn msaa_4x(&mut self) -> &mut Self {
         self.msaa_4x_hint = true;
@@ -189,6 +226,12 @@ impl RaylibBuilder {
         if self.vsync_hint {
             flags |= FLAG_VSYNC_HINT as u32;
         }
+        if self.window_hidden {
+            flags |= FLAG_WINDOW_HIDDEN as u32;
+        }
+        if self.software_rendering {
+            use_software_rendering();
+        }
 
         unsafe {
             ffi::SetConfigFlags(flags as u32);
@@ -202,6 +245,20 @@ impl RaylibBuilder {
     }
 }
 
+/// Asks Mesa (and SDL, when used) for a context that needs neither a GPU nor a display.
+/// Must run before other threads are spawned, see [`RaylibBuilder::headless`].
+fn use_software_rendering() {
+    fn set_default(key: &str, value: &str) {
+        if std::env::var_os(key).is_none() {
+            std::env::set_var(key, value);
+        }
+    }
+    set_default("LIBGL_ALWAYS_SOFTWARE", "1");
+    set_default("GALLIUM_DRIVER", "llvmpipe");
+    #[cfg(all(feature = "sdl", target_os = "linux"))]
+    set_default("SDL_VIDEODRIVER", "offscreen");
+}
+
 /// Initializes window and OpenGL context.
 ///
 /// # Panics
@@ -9,6 +9,15 @@ use crate::{consts, ffi};
 use std::ffi::CString;
 use std::os::raw::c_void;
 
+mod animator;
+#[cfg(feature = "with_serde")]
+mod gltf;
+mod mesh_builder;
+pub use animator::*;
+#[cfg(feature = "with_serde")]
+pub use gltf::*;
+pub use mesh_builder::*;
+
 fn no_drop<T>(_thing: T) {}
 make_thin_wrapper!(Model, ffi::Model, ffi::UnloadModel);
 make_thin_wrapper!(WeakModel, ffi::Model, no_drop);
@@ -252,14 +261,43 @@ pub trait RaylibMesh: AsRef<ffi::Mesh> + AsMut<ffi::Mesh> {
     unsafe fn upload(&mut self, dynamic: bool) {
         ffi::UploadMesh(self.as_mut(), dynamic);
     }
-    unsafe fn update_buffer<A>(&mut self, index: i32, data: &[u8], offset: i32) {
-        ffi::UpdateMeshBuffer(
-            *self.as_ref(),
-            index,
-            data.as_ptr() as *const c_void,
-            data.len() as i32,
-            offset,
-        );
+    /// Overwrites elements of an uploaded vertex buffer, starting at element `offset`. `T` must
+    /// be the size of the buffer's element type, e.g. `Vector3` for positions.
+    ///
+    /// Only the GPU copy changes, edit the slices from `vertices_mut` and friends for the RAM copy.
+    fn update_buffer<T: Copy>(
+        &mut self,
+        _: &RaylibThread,
+        buffer: MeshBuffer,
+        data: &[T],
+        offset: usize,
+    ) -> Result<(), Error> {
+        let mesh = self.as_ref();
+        if mesh.vboId.is_null() || unsafe { *mesh.vboId.add(buffer as usize) } == 0 {
+            return Err(error!("mesh buffer has not been uploaded"));
+        }
+        if std::mem::size_of::<T>() != buffer.element_size() {
+            return Err(error!("data type does not match the mesh buffer"));
+        }
+        let end = offset.checked_add(data.len());
+        if end.map_or(true, |end| end > buffer.len(mesh)) {
+            return Err(error!("data does not fit in the mesh buffer"));
+        }
+        // Byte offset and size are passed to raylib as `int`
+        let end_bytes = end.and_then(|end| end.checked_mul(buffer.element_size()));
+        if end_bytes.map_or(true, |end| end > i32::MAX as usize) {
+            return Err(error!("mesh buffer update is too large"));
+        }
+        unsafe {
+            ffi::UpdateMeshBuffer(
+                *mesh,
+                buffer as i32,
+                data.as_ptr() as *const c_void,
+                std::mem::size_of_val(data) as i32,
+                (offset * buffer.element_size()) as i32,
+            );
+        }
+        Ok(())
     }
     fn vertices(&self) -> &[Vector3] {
         unsafe {
@@ -0,0 +1,1039 @@
+//! Skeletal animation playback with interpolation, crossfades, layers, root motion and events.
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//! let (mut rl, thread) = raylib::init().build();
+//! let mut model = rl.load_model(&thread, "guy.iqm").unwrap();
+//! let anims = rl.load_model_animations(&thread, "guyanim.iqm").unwrap();
+//!
+//! let mut animator = Animator::new(&model).unwrap();
+//! animator
+//!     .add_clip(AnimationClip::from_animation("walk", &anims[0], PlayMode::Loop).event(0.4, "step"))
+//!     .unwrap();
+//! animator
+//!     .add_clip(AnimationClip::from_animation("wave", &anims[1], PlayMode::Once))
+//!     .unwrap();
+//! let upper_body = animator.bone_mask("spine").unwrap();
+//! animator
+//!     .add_layer("gesture", LayerBlend::Override)
+//!     .set_mask(Some(upper_body))
+//!     .unwrap();
+//! animator.play("walk", 0.0);
+//!
+//! while !rl.window_should_close() {
+//!     if rl.is_key_pressed(KeyboardKey::KEY_SPACE) {
+//!         animator.play_on("gesture", "wave", 0.2);
+//!     }
+//!     for event in animator.update(rl.get_frame_time()) {
+//!         if let AnimationEvent::Event { name, .. } = event {
+//!             println!("{}", name);
+//!         }
+//!     }
+//!     animator.apply(&thread, &mut model).unwrap();
+//!     let hand = animator.bone_matrix("hand.R");
+//! #   let _ = hand;
+//! }
+//! ```
+use std::os::raw::c_char;
+use std::sync::Arc;
+
+use crate::core::math::{Matrix, Quaternion, Transform, Vector3};
+use crate::core::models::{RaylibModel, RaylibModelAnimation};
+use crate::core::sprite::PlayMode;
+use crate::core::RaylibThread;
+use crate::error::{error, Error};
+use crate::ffi;
+
+/// Frame rate raylib samples glTF animations at, assumed for all clips unless changed.
+pub const ANIMATION_FPS: f32 = 60.0;
+
+fn identity_transform() -> Transform {
+    Transform {
+        translation: Vector3::zero(),
+        rotation: Quaternion::identity(),
+        scale: Vector3::one(),
+    }
+}
+
+/// `b`, flipped if needed so slerping from `a` takes the short way around.
+fn align(a: Quaternion, b: Quaternion) -> Quaternion {
+    if a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w < 0.0 {
+        Quaternion::new(-b.x, -b.y, -b.z, -b.w)
+    } else {
+        b
+    }
+}
+
+fn lerp_transform(a: &Transform, b: &Transform, amount: f32) -> Transform {
+    Transform {
+        translation: a.translation.lerp(b.translation, amount),
+        rotation: a.rotation.slerp(align(a.rotation, b.rotation), amount),
+        scale: a.scale.lerp(b.scale, amount),
+    }
+}
+
+fn safe_div(a: Vector3, b: Vector3) -> Vector3 {
+    let div = |a: f32, b: f32| if b == 0.0 { 1.0 } else { a / b };
+    Vector3::new(div(a.x, b.x), div(a.y, b.y), div(a.z, b.z))
+}
+
+/// Composes bone-space transforms into model space the way raylib's loaders do.
+fn to_model(local: &[Transform], parents: &[i32], out: &mut [Transform]) {
+    for i in 0..local.len() {
+        let bone = local[i];
+        out[i] = match parents[i] {
+            p if p >= 0 && (p as usize) < i => {
+                let parent = out[p as usize];
+                Transform {
+                    translation: bone.translation.rotate_by(parent.rotation) + parent.translation,
+                    rotation: parent.rotation * bone.rotation,
+                    scale: bone.scale * parent.scale,
+                }
+            }
+            _ => bone,
+        };
+    }
+}
+
+fn to_local(model: &[Transform], parents: &[i32]) -> Vec<Transform> {
+    (0..model.len())
+        .map(|i| match parents[i] {
+            p if p >= 0 && (p as usize) < i => {
+                let parent = model[p as usize];
+                let inverse = parent.rotation.inverted();
+                Transform {
+                    translation: (model[i].translation - parent.translation).rotate_by(inverse),
+                    rotation: inverse * model[i].rotation,
+                    scale: safe_div(model[i].scale, parent.scale),
+                }
+            }
+            _ => model[i],
+        })
+        .collect()
+}
+
+fn bone_name(name: &[c_char; 32]) -> String {
+    let bytes: Vec<u8> = name
+        .iter()
+        .take_while(|c| **c != 0)
+        .map(|c| *c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// One animation copied out of a `ModelAnimation`, with its poses in bone space.
+#[derive(Debug, Clone)]
+pub struct AnimationClip {
+    name: Arc<str>,
+    parents: Vec<i32>,
+    frames: Vec<Vec<Transform>>,
+    fps: f32,
+    pub mode: PlayMode,
+    events: Vec<(f32, Arc<str>)>,
+}
+
+impl AnimationClip {
+    /// Copies the frames of `anim`, played at [`ANIMATION_FPS`].
+    pub fn from_animation(name: &str, anim: &impl RaylibModelAnimation, mode: PlayMode) -> Self {
+        let parents: Vec<i32> = anim.bones().iter().map(|b| b.parent).collect();
+        let frames = anim
+            .frame_poses()
+            .iter()
+            .map(|pose| to_local(pose, &parents))
+            .collect();
+        AnimationClip {
+            name: name.into(),
+            parents,
+            frames,
+            fps: ANIMATION_FPS,
+            mode,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn fps(mut self, fps: f32) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Adds an event `name`, emitted by `Animator::update` whenever playback passes `time`
+    /// seconds into the clip.
+    pub fn event(mut self, time: f32, name: &str) -> Self {
+        self.events.push((time, name.into()));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn bone_count(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Seconds from the first frame to the last.
+    pub fn duration(&self) -> f32 {
+        if self.fps <= 0.0 {
+            return 0.0;
+        }
+        self.frames.len().saturating_sub(1) as f32 / self.fps
+    }
+
+    /// Maps time since the clip started to a position in the clip according to `mode`.
+    fn clip_time(&self, time: f32) -> f32 {
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return 0.0;
+        }
+        match self.mode {
+            PlayMode::Loop => time.rem_euclid(duration),
+            PlayMode::PingPong => {
+                let phase = time.rem_euclid(duration * 2.0);
+                if phase <= duration {
+                    phase
+                } else {
+                    duration * 2.0 - phase
+                }
+            }
+            PlayMode::Once => time.clamp(0.0, duration),
+        }
+    }
+
+    fn sample_bone(&self, time: f32, bone: usize) -> Transform {
+        let last = self.frames.len() - 1;
+        let frame = self.clip_time(time) * self.fps;
+        let i = (frame.floor().max(0.0) as usize).min(last);
+        let j = (i + 1).min(last);
+        lerp_transform(
+            &self.frames[i][bone],
+            &self.frames[j][bone],
+            (frame - i as f32).clamp(0.0, 1.0),
+        )
+    }
+
+    /// The bone-space pose `time` seconds after the clip started.
+    pub fn sample(&self, time: f32, out: &mut [Transform]) {
+        if self.frames.is_empty() {
+            return;
+        }
+        for (bone, transform) in out.iter_mut().enumerate().take(self.parents.len()) {
+            *transform = self.sample_bone(time, bone);
+        }
+    }
+
+    /// How far `bone` moved between `from` and `to` seconds after the clip started, counting
+    /// every completed loop.
+    fn motion(&self, bone: usize, from: f32, to: f32) -> Vector3 {
+        let duration = self.duration();
+        if self.frames.is_empty() || duration <= 0.0 {
+            return Vector3::zero();
+        }
+        let mut delta =
+            self.sample_bone(to, bone).translation - self.sample_bone(from, bone).translation;
+        if self.mode == PlayMode::Loop {
+            let loops = (to / duration).floor() - (from / duration).floor();
+            let first = self.frames[0][bone].translation;
+            let last = self.frames[self.frames.len() - 1][bone].translation;
+            delta += (last - first) * loops;
+        }
+        delta
+    }
+
+    /// Calls `f` for each event passed going from `from` to `to` seconds after the clip started.
+    fn crossed_events(
+        &self,
+        from: f32,
+        to: f32,
+        include_start: bool,
+        mut f: impl FnMut(&Arc<str>),
+    ) {
+        let duration = self.duration();
+        let passed = |at: f32| at <= to && (at > from || (include_start && at == from));
+        for (time, name) in self.events.iter() {
+            let time = time.clamp(0.0, duration);
+            let (offsets, period): (&[f32], f32) = match self.mode {
+                _ if duration <= 0.0 => (&[time], f32::INFINITY),
+                PlayMode::Once => (&[time], f32::INFINITY),
+                PlayMode::Loop => (&[time], duration),
+                PlayMode::PingPong if time == 0.0 || time == duration => (&[time], duration * 2.0),
+                PlayMode::PingPong => (&[time, duration * 2.0 - time], duration * 2.0),
+            };
+            let mut at: Vec<f32> = Vec::new();
+            for offset in offsets.iter() {
+                if period.is_infinite() {
+                    at.push(*offset);
+                    continue;
+                }
+                let first = ((from - offset) / period).floor().max(0.0) as u32;
+                let last = ((to - offset) / period).floor().max(0.0) as u32;
+                at.extend((first..=last).map(|k| offset + k as f32 * period));
+            }
+            at.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for _ in at.into_iter().filter(|at| passed(*at)) {
+                f(name);
+            }
+        }
+    }
+}
+
+/// How a layer combines with the layers below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerBlend {
+    /// Replaces the pose, faded by the layer weight and mask.
+    Override,
+    /// Adds the difference between each pose and the clip's first frame, e.g. breathing or a
+    /// flinch on top of any other animation.
+    Additive,
+}
+
+/// Per-bone layer weights, 0 leaves a bone to the layers below.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoneMask(pub Vec<f32>);
+
+impl BoneMask {
+    /// Swaps the bones that are affected with those that aren't, e.g. legs from upper body.
+    pub fn inverted(mut self) -> Self {
+        self.0.iter_mut().for_each(|w| *w = 1.0 - *w);
+        self
+    }
+}
+
+/// Emitted by [`Animator::update`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnimationEvent {
+    /// Playback passed an event added with [`AnimationClip::event`].
+    Event {
+        layer: Arc<str>,
+        clip: Arc<str>,
+        name: Arc<str>,
+    },
+    /// A [`PlayMode::Once`] clip reached its last frame.
+    Finished { layer: Arc<str>, clip: Arc<str> },
+}
+
+#[derive(Debug, Clone)]
+struct Playback {
+    /// `None` fades to the pose coming from the layers below.
+    clip: Option<usize>,
+    time: f32,
+    blend: f32,
+    fade: f32,
+    started: bool,
+    finished: bool,
+}
+
+/// A set of clips playing on top of the layers added before it.
+#[derive(Debug, Clone)]
+pub struct AnimationLayer {
+    name: Arc<str>,
+    blend: LayerBlend,
+    weight: f32,
+    mask: Option<BoneMask>,
+    bone_count: usize,
+    /// Oldest first; each fades in over the ones before it.
+    playbacks: Vec<Playback>,
+}
+
+impl AnimationLayer {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn blend(&self) -> LayerBlend {
+        self.blend
+    }
+
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    pub fn set_weight(&mut self, weight: f32) -> &mut Self {
+        self.weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Limits the layer to some bones, see [`Animator::bone_mask`].
+    pub fn set_mask(&mut self, mask: Option<BoneMask>) -> Result<&mut Self, Error> {
+        if let Some(mask) = &mask {
+            if mask.0.len() != self.bone_count {
+                return Err(error!("bone mask does not match the skeleton"));
+            }
+        }
+        self.mask = mask;
+        Ok(self)
+    }
+
+    fn current(&self) -> Option<&Playback> {
+        self.playbacks.last()
+    }
+}
+
+/// Plays [`AnimationClip`]s on a model's skeleton.
+///
+/// Clips are sampled between frames and crossfaded in bone space, then layers are applied in
+/// the order they were added over the base layer. Call `update` every frame, then `apply` to
+/// pose the model's meshes.
+#[derive(Debug, Clone)]
+pub struct Animator {
+    names: Vec<String>,
+    parents: Vec<i32>,
+    bind_pose: Vec<Transform>,
+    clips: Vec<AnimationClip>,
+    layers: Vec<AnimationLayer>,
+    speed: f32,
+    root_bone: usize,
+    root_motion: bool,
+    root_delta: Vector3,
+    local: Vec<Transform>,
+    model: Vec<Transform>,
+    events: Vec<AnimationEvent>,
+}
+
+impl Animator {
+    /// An animator for the skeleton of `model`, holding its bind pose until a clip plays.
+    pub fn new(model: &impl RaylibModel) -> Result<Self, Error> {
+        let m = model.as_ref();
+        if m.bones.is_null() || m.bindPose.is_null() || m.boneCount <= 0 {
+            return Err(error!("model has no skeleton"));
+        }
+        let count = m.boneCount as usize;
+        let (bones, bind_pose) = unsafe {
+            (
+                std::slice::from_raw_parts(m.bones, count),
+                std::slice::from_raw_parts(m.bindPose as *const Transform, count),
+            )
+        };
+        let parents: Vec<i32> = bones.iter().map(|b| b.parent).collect();
+        let mut animator = Animator {
+            names: bones.iter().map(|b| bone_name(&b.name)).collect(),
+            root_bone: parents.iter().position(|p| *p < 0).unwrap_or(0),
+            parents,
+            bind_pose: bind_pose.to_vec(),
+            clips: Vec::new(),
+            layers: Vec::new(),
+            speed: 1.0,
+            root_motion: false,
+            root_delta: Vector3::zero(),
+            local: Vec::new(),
+            model: bind_pose.to_vec(),
+            events: Vec::new(),
+        };
+        animator.local = to_local(&animator.bind_pose, &animator.parents);
+        animator.add_layer("base", LayerBlend::Override);
+        Ok(animator)
+    }
+
+    /// Adds a clip, replacing any clip with the same name.
+    pub fn add_clip(&mut self, clip: AnimationClip) -> Result<&mut Self, Error> {
+        if clip.parents != self.parents {
+            return Err(error!("animation clip does not match the skeleton"));
+        }
+        if clip.frames.is_empty() {
+            return Err(error!("animation clip has no frames"));
+        }
+        match self.clips.iter().position(|c| c.name == clip.name) {
+            Some(i) => self.clips[i] = clip,
+            None => self.clips.push(clip),
+        }
+        Ok(self)
+    }
+
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.iter().find(|c| &*c.name == name)
+    }
+
+    /// Adds a layer above the existing ones, or returns the layer already called `name`. The
+    /// `"base"` layer always exists.
+    pub fn add_layer(&mut self, name: &str, blend: LayerBlend) -> &mut AnimationLayer {
+        let index = match self.layers.iter().position(|l| &*l.name == name) {
+            Some(index) => index,
+            None => {
+                self.layers.push(AnimationLayer {
+                    name: name.into(),
+                    blend,
+                    weight: 1.0,
+                    mask: None,
+                    bone_count: self.names.len(),
+                    playbacks: Vec::new(),
+                });
+                self.layers.len() - 1
+            }
+        };
+        &mut self.layers[index]
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&AnimationLayer> {
+        self.layers.iter().find(|l| &*l.name == name)
+    }
+
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut AnimationLayer> {
+        self.layers.iter_mut().find(|l| &*l.name == name)
+    }
+
+    /// Crossfades the base layer to `clip` over `fade` seconds, see `play_on`.
+    pub fn play(&mut self, clip: &str, fade: f32) -> bool {
+        self.play_on("base", clip, fade)
+    }
+
+    /// Crossfades `layer` to `clip` over `fade` seconds, from its first frame. Does nothing if
+    /// that clip is already playing there. Returns `false` if there is no such layer or clip.
+    pub fn play_on(&mut self, layer: &str, clip: &str, fade: f32) -> bool {
+        let clip = match self.clips.iter().position(|c| &*c.name == clip) {
+            Some(clip) => clip,
+            None => return false,
+        };
+        let layer = match self.layer_mut(layer) {
+            Some(layer) => layer,
+            None => return false,
+        };
+        if let Some(current) = layer.current() {
+            if current.clip == Some(clip) && !current.finished {
+                return true;
+            }
+        }
+        Self::push_playback(layer, Some(clip), fade);
+        true
+    }
+
+    /// Fades whatever `layer` plays out over `fade` seconds. The base layer returns to the bind
+    /// pose.
+    pub fn stop(&mut self, layer: &str, fade: f32) -> bool {
+        match self.layer_mut(layer) {
+            Some(layer) => {
+                Self::push_playback(layer, None, fade);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn push_playback(layer: &mut AnimationLayer, clip: Option<usize>, fade: f32) {
+        let first = layer.playbacks.is_empty();
+        layer.playbacks.push(Playback {
+            clip,
+            time: 0.0,
+            blend: if fade > 0.0 && !first { 0.0 } else { 1.0 },
+            fade,
+            started: false,
+            finished: false,
+        });
+    }
+
+    /// The clip `layer` is playing or fading in.
+    pub fn current_clip(&self, layer: &str) -> Option<&AnimationClip> {
+        let clip = self.layer(layer)?.current()?.clip?;
+        Some(&self.clips[clip])
+    }
+
+    /// Playback rate of every layer, 1.0 is normal speed.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    /// Keeps the root bone at its first-frame position in base layer clips and reports the
+    /// movement through `root_motion` instead, for moving the character with its animation.
+    pub fn set_root_motion(&mut self, enabled: bool) {
+        self.root_motion = enabled;
+    }
+
+    /// How far the root bone moved in model space during the last `update`, when root motion is
+    /// enabled.
+    pub fn root_motion(&self) -> Vector3 {
+        self.root_delta
+    }
+
+    pub fn bone_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn bone_index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn bone_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// A mask of `bone` and everything attached below it.
+    pub fn bone_mask(&self, bone: &str) -> Option<BoneMask> {
+        let root = self.bone_index(bone)?;
+        let mut weights = vec![0.0; self.names.len()];
+        weights[root] = 1.0;
+        for i in 0..weights.len() {
+            let mut parent = self.parents[i];
+            // Walk up rather than relying on parents coming first.
+            let mut depth = 0;
+            while parent >= 0 && depth < weights.len() {
+                if parent as usize == root {
+                    weights[i] = 1.0;
+                    break;
+                }
+                parent = self.parents[parent as usize];
+                depth += 1;
+            }
+        }
+        Some(BoneMask(weights))
+    }
+
+    /// Current pose with each bone relative to its parent.
+    pub fn local_pose(&self) -> &[Transform] {
+        &self.local
+    }
+
+    /// Current pose with every bone in model space.
+    pub fn model_pose(&self) -> &[Transform] {
+        &self.model
+    }
+
+    /// Model space transform of `bone`.
+    pub fn bone_transform(&self, bone: &str) -> Option<Transform> {
+        Some(self.model[self.bone_index(bone)?])
+    }
+
+    /// Model space matrix of `bone`, for attaching things to it. Multiply by the model's
+    /// transform and draw matrix to get world space.
+    pub fn bone_matrix(&self, bone: &str) -> Option<Matrix> {
+        Some(self.bone_transform(bone)?.to_matrix())
+    }
+
+    /// Advances every layer by `dt` seconds, recomputes the pose and returns the events that
+    /// happened.
+    pub fn update(&mut self, dt: f32) -> &[AnimationEvent] {
+        self.events.clear();
+        self.root_delta = Vector3::zero();
+        let dt = (dt * self.speed).max(0.0);
+
+        for (index, layer) in self.layers.iter_mut().enumerate() {
+            let layer_name = layer.name.clone();
+            let count = layer.playbacks.len();
+            let mut weight = 1.0;
+            for (i, playback) in layer.playbacks.iter_mut().enumerate().rev() {
+                let from = playback.time;
+                playback.time += dt;
+                let newest = i + 1 == count;
+                if newest && playback.blend < 1.0 {
+                    playback.blend = (playback.blend + dt / playback.fade).min(1.0);
+                }
+                // What's left of the blend after the newer playbacks took their share.
+                let own = if i == 0 { 1.0 } else { playback.blend };
+                let share = weight * own;
+                weight *= 1.0 - own;
+
+                let clip = match playback.clip {
+                    Some(clip) => &self.clips[clip],
+                    None => continue,
+                };
+                if self.root_motion && index == 0 {
+                    self.root_delta += clip.motion(self.root_bone, from, playback.time) * share;
+                }
+                if !newest || playback.finished {
+                    continue;
+                }
+                let events = &mut self.events;
+                clip.crossed_events(from, playback.time, !playback.started, |name| {
+                    events.push(AnimationEvent::Event {
+                        layer: layer_name.clone(),
+                        clip: clip.name.clone(),
+                        name: name.clone(),
+                    })
+                });
+                playback.started = true;
+                if clip.mode == PlayMode::Once && playback.time >= clip.duration() {
+                    playback.finished = true;
+                    self.events.push(AnimationEvent::Finished {
+                        layer: layer_name.clone(),
+                        clip: clip.name.clone(),
+                    });
+                }
+            }
+            // Anything under a fully faded in playback can't be seen anymore.
+            if let Some(i) = layer.playbacks.iter().rposition(|p| p.blend >= 1.0) {
+                layer.playbacks.drain(..i);
+            }
+            if matches!(layer.playbacks.as_slice(), [Playback { clip: None, .. }]) {
+                layer.playbacks.clear();
+            }
+        }
+
+        self.evaluate();
+        &self.events
+    }
+
+    fn evaluate(&mut self) {
+        let count = self.names.len();
+        let mut pose = to_local(&self.bind_pose, &self.parents);
+        let mut layer_pose = vec![identity_transform(); count];
+        let mut sampled = vec![identity_transform(); count];
+
+        for (index, layer) in self.layers.iter().enumerate() {
+            if layer.playbacks.is_empty() || layer.weight <= 0.0 {
+                continue;
+            }
+            let additive = layer.blend == LayerBlend::Additive;
+            for (i, playback) in layer.playbacks.iter().enumerate() {
+                match playback.clip.map(|c| &self.clips[c]) {
+                    Some(clip) => {
+                        clip.sample(playback.time, &mut sampled);
+                        if self.root_motion && index == 0 && !clip.frames.is_empty() {
+                            sampled[self.root_bone].translation =
+                                clip.frames[0][self.root_bone].translation;
+                        }
+                        if additive && !clip.frames.is_empty() {
+                            for (bone, t) in sampled.iter_mut().enumerate() {
+                                let reference = clip.frames[0][bone];
+                                *t = Transform {
+                                    translation: t.translation - reference.translation,
+                                    rotation: t.rotation * reference.rotation.inverted(),
+                                    scale: safe_div(t.scale, reference.scale),
+                                };
+                            }
+                        }
+                    }
+                    None if additive => sampled.iter_mut().for_each(|t| *t = identity_transform()),
+                    None => sampled.copy_from_slice(&pose),
+                }
+                if i == 0 {
+                    layer_pose.copy_from_slice(&sampled);
+                } else {
+                    for (a, b) in layer_pose.iter_mut().zip(sampled.iter()) {
+                        *a = lerp_transform(a, b, playback.blend);
+                    }
+                }
+            }
+
+            for (bone, (t, delta)) in pose.iter_mut().zip(layer_pose.iter()).enumerate() {
+                let weight = layer.weight * layer.mask.as_ref().map_or(1.0, |m| m.0[bone]);
+                if weight <= 0.0 {
+                    continue;
+                }
+                if additive {
+                    let rotation = Quaternion::identity()
+                        .slerp(align(Quaternion::identity(), delta.rotation), weight);
+                    t.translation += delta.translation * weight;
+                    t.rotation = (rotation * t.rotation).normalized();
+                    t.scale *= Vector3::one().lerp(delta.scale, weight);
+                } else {
+                    *t = lerp_transform(t, delta, weight);
+                }
+            }
+        }
+
+        to_model(&pose, &self.parents, &mut self.model);
+        self.local = pose;
+    }
+
+    /// Poses the meshes of `model`, which must have the skeleton the animator was made for.
+    pub fn apply(&self, _: &RaylibThread, model: &mut impl RaylibModel) -> Result<(), Error> {
+        let m = model.as_mut();
+        if m.bones.is_null() || m.boneCount as usize != self.names.len() {
+            return Err(error!("model does not match the animator skeleton"));
+        }
+        let mut pose: Vec<ffi::Transform> = self.model.iter().map(|t| t.into()).collect();
+        let mut frames = [pose.as_mut_ptr()];
+        unsafe {
+            let mut anim: ffi::ModelAnimation = std::mem::zeroed();
+            anim.boneCount = m.boneCount;
+            anim.frameCount = 1;
+            anim.bones = m.bones;
+            anim.framePoses = frames.as_mut_ptr();
+            ffi::UpdateModelAnimation(*m, anim, 0);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod animator_test {
+    use super::*;
+    use crate::core::models::{WeakModel, WeakModelAnimation};
+    use std::f32::consts::{FRAC_1_SQRT_2, FRAC_PI_2};
+
+    /// A root bone at the origin with a `tip` one unit above it.
+    fn bones() -> Vec<ffi::BoneInfo> {
+        ["root", "tip"]
+            .iter()
+            .zip([-1, 0].iter())
+            .map(|(name, parent)| {
+                let mut bone: ffi::BoneInfo = unsafe { std::mem::zeroed() };
+                for (c, b) in bone.name.iter_mut().zip(name.bytes()) {
+                    *c = b as c_char;
+                }
+                bone.parent = *parent;
+                bone
+            })
+            .collect()
+    }
+
+    fn at(x: f32, y: f32, z: f32) -> Transform {
+        Transform {
+            translation: Vector3::new(x, y, z),
+            ..identity_transform()
+        }
+    }
+
+    fn animator() -> Animator {
+        let mut bones = bones();
+        let mut bind_pose = vec![at(0.0, 0.0, 0.0), at(0.0, 1.0, 0.0)];
+        let model = unsafe {
+            let mut model: ffi::Model = std::mem::zeroed();
+            model.boneCount = 2;
+            model.bones = bones.as_mut_ptr();
+            model.bindPose = bind_pose.as_mut_ptr() as *mut ffi::Transform;
+            WeakModel::from_raw(model)
+        };
+        Animator::new(&model).unwrap()
+    }
+
+    /// A clip from model space `frames`, one frame per second.
+    fn clip(name: &str, frames: &[[Transform; 2]], mode: PlayMode) -> AnimationClip {
+        let mut bones = bones();
+        let mut frames = frames.to_vec();
+        let mut poses: Vec<*mut ffi::Transform> = frames
+            .iter_mut()
+            .map(|f| f.as_mut_ptr() as *mut ffi::Transform)
+            .collect();
+        let anim = unsafe {
+            let mut anim: ffi::ModelAnimation = std::mem::zeroed();
+            anim.boneCount = 2;
+            anim.frameCount = poses.len() as i32;
+            anim.bones = bones.as_mut_ptr();
+            anim.framePoses = poses.as_mut_ptr();
+            WeakModelAnimation::from_raw(anim)
+        };
+        AnimationClip::from_animation(name, &anim, mode).fps(1.0)
+    }
+
+    /// Model space pose with the root at `root` and the tip one unit above it in root space.
+    fn posed(root: Transform) -> [Transform; 2] {
+        let tip = Transform {
+            translation: Vector3::new(0.0, 1.0, 0.0).rotate_by(root.rotation) + root.translation,
+            ..root
+        };
+        [root, tip]
+    }
+
+    fn turned(angle: f32) -> Transform {
+        Transform {
+            rotation: Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), angle),
+            ..identity_transform()
+        }
+    }
+
+    fn assert_near(a: Vector3, b: Vector3) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_bone_matrix_matches_transform() {
+        let mut animator = animator();
+        animator
+            .add_clip(clip("turn", &[posed(turned(FRAC_PI_2))], PlayMode::Once))
+            .unwrap();
+        animator.play("turn", 0.0);
+        animator.update(0.0);
+
+        let tip = animator.bone_transform("tip").unwrap();
+        assert_near(tip.translation, Vector3::new(-1.0, 0.0, 0.0));
+        let point = Vector3::new(0.0, 1.0, 0.0);
+        let expected = point.rotate_by(tip.rotation) + tip.translation;
+        assert_near(expected, Vector3::new(-2.0, 0.0, 0.0));
+        let matrix = animator.bone_matrix("tip").unwrap();
+        assert_near(point.transform_with(matrix), expected);
+    }
+
+    #[test]
+    fn test_interpolates_between_frames() {
+        let mut animator = animator();
+        let end = Transform {
+            translation: Vector3::new(2.0, 0.0, 0.0),
+            ..turned(FRAC_PI_2)
+        };
+        animator
+            .add_clip(clip(
+                "slide",
+                &[posed(at(0.0, 0.0, 0.0)), posed(end)],
+                PlayMode::Once,
+            ))
+            .unwrap();
+        animator.play("slide", 0.0);
+        animator.update(0.5);
+
+        let root = animator.local_pose()[0];
+        assert_near(root.translation, Vector3::new(1.0, 0.0, 0.0));
+        let s = FRAC_1_SQRT_2;
+        assert_near(
+            Vector3::new(1.0, 0.0, 0.0).rotate_by(root.rotation),
+            Vector3::new(s, s, 0.0),
+        );
+        // The tip stays one unit above the root, in the root's rotated frame
+        assert_near(
+            animator.local_pose()[1].translation,
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+        assert_near(
+            animator.model_pose()[1].translation,
+            Vector3::new(1.0 - s, s, 0.0),
+        );
+    }
+
+    #[test]
+    fn test_crossfade_weights() {
+        let mut animator = animator();
+        animator
+            .add_clip(clip("a", &[posed(at(0.0, 0.0, 0.0))], PlayMode::Loop))
+            .unwrap()
+            .add_clip(clip("b", &[posed(at(4.0, 0.0, 0.0))], PlayMode::Loop))
+            .unwrap();
+        animator.play("a", 0.0);
+        animator.update(0.0);
+        assert!(animator.play("b", 1.0));
+
+        animator.update(0.25);
+        assert_near(
+            animator.model_pose()[0].translation,
+            Vector3::new(1.0, 0.0, 0.0),
+        );
+        assert_near(
+            animator.model_pose()[1].translation,
+            Vector3::new(1.0, 1.0, 0.0),
+        );
+        animator.update(0.25);
+        assert_near(
+            animator.model_pose()[0].translation,
+            Vector3::new(2.0, 0.0, 0.0),
+        );
+        animator.update(1.0);
+        assert_near(
+            animator.model_pose()[0].translation,
+            Vector3::new(4.0, 0.0, 0.0),
+        );
+        assert_eq!(animator.layer("base").unwrap().playbacks.len(), 1);
+    }
+
+    #[test]
+    fn test_bone_mask() {
+        let mut animator = animator();
+        let mut lifted = posed(at(0.0, 0.0, 0.0));
+        lifted[1].translation.y = 3.0;
+        animator
+            .add_clip(clip("b", &[posed(at(4.0, 0.0, 0.0))], PlayMode::Loop))
+            .unwrap()
+            .add_clip(clip("lift", &[lifted], PlayMode::Loop))
+            .unwrap();
+        let mask = animator.bone_mask("tip").unwrap();
+        assert_eq!(mask, BoneMask(vec![0.0, 1.0]));
+        assert_eq!(
+            animator.bone_mask("root").unwrap(),
+            BoneMask(vec![1.0, 1.0])
+        );
+        animator
+            .add_layer("upper", LayerBlend::Override)
+            .set_mask(Some(mask.clone()))
+            .unwrap();
+        animator.play("b", 0.0);
+        animator.play_on("upper", "lift", 0.0);
+        animator.update(0.0);
+
+        // The root follows the base layer, the tip's local pose comes from the masked layer
+        assert_near(
+            animator.model_pose()[0].translation,
+            Vector3::new(4.0, 0.0, 0.0),
+        );
+        assert_near(
+            animator.model_pose()[1].translation,
+            Vector3::new(4.0, 3.0, 0.0),
+        );
+
+        animator
+            .layer_mut("upper")
+            .unwrap()
+            .set_mask(Some(mask.inverted()))
+            .unwrap();
+        animator.update(0.0);
+        assert_near(
+            animator.model_pose()[0].translation,
+            Vector3::new(0.0, 0.0, 0.0),
+        );
+        assert_near(
+            animator.model_pose()[1].translation,
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn test_additive_layer() {
+        let mut animator = animator();
+        animator
+            .add_clip(clip("b", &[posed(at(4.0, 0.0, 0.0))], PlayMode::Loop))
+            .unwrap()
+            .add_clip(clip(
+                "nod",
+                &[posed(at(0.0, 0.0, 0.0)), posed(at(0.0, 1.0, 0.0))],
+                PlayMode::Once,
+            ))
+            .unwrap();
+        animator
+            .add_layer("breathe", LayerBlend::Additive)
+            .set_weight(0.5);
+        animator.play("b", 0.0);
+        animator.play_on("breathe", "nod", 0.0);
+
+        // The first frame of an additive clip adds nothing
+        animator.update(0.0);
+        assert_near(
+            animator.model_pose()[0].translation,
+            Vector3::new(4.0, 0.0, 0.0),
+        );
+        // Then the difference to it is added, scaled by the layer weight
+        animator.update(1.0);
+        assert_near(
+            animator.model_pose()[0].translation,
+            Vector3::new(4.0, 0.5, 0.0),
+        );
+        assert_near(
+            animator.model_pose()[1].translation,
+            Vector3::new(4.0, 1.5, 0.0),
+        );
+    }
+
+    #[test]
+    fn test_root_motion() {
+        let mut animator = animator();
+        animator
+            .add_clip(clip(
+                "walk",
+                &[
+                    posed(at(0.0, 0.0, 0.0)),
+                    posed(at(1.0, 0.0, 0.0)),
+                    posed(at(2.0, 0.0, 0.0)),
+                ],
+                PlayMode::Loop,
+            ))
+            .unwrap();
+        animator.set_root_motion(true);
+        animator.play("walk", 0.0);
+
+        animator.update(0.5);
+        assert_near(animator.root_motion(), Vector3::new(0.5, 0.0, 0.0));
+        // The root is pinned to its first frame, the motion is reported instead
+        assert_near(animator.local_pose()[0].translation, Vector3::zero());
+        assert_near(
+            animator.model_pose()[1].translation,
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+
+        // Wrapping around the loop counts the whole distance of the completed cycle
+        animator.update(2.0);
+        assert_near(animator.root_motion(), Vector3::new(2.0, 0.0, 0.0));
+        animator.update(0.0);
+        assert_near(animator.root_motion(), Vector3::zero());
+    }
+}
@@ -0,0 +1,643 @@
+//! glTF/GLB scene import that keeps node names, hierarchy, cameras and punctual lights.
+//! Only available with the `with_serde` feature.
+//!
+//! raylib bakes each node's load-time world transform into its meshes, so
+//! [`GltfScene::mesh_matrix`] gives the extra transform needed once nodes are moved.
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//! let (mut rl, thread) = raylib::init().build();
+//! let mut scene = rl.load_gltf_scene(&thread, "level.glb").unwrap();
+//!
+//! let door = scene.find("Door").unwrap();
+//! let camera = scene.cameras().next().map(|(_, c)| c).unwrap();
+//! let lights: Vec<Light> = scene.lights().map(|(_, l)| l).collect();
+//! # let _ = lights;
+//!
+//! while !rl.window_should_close() {
+//!     scene.nodes[door].transform.rotation = Quaternion::from_euler(0.0, rl.get_time() as f32, 0.0);
+//!     let mut d = rl.begin_drawing(&thread);
+//!     d.clear_background(Color::BLACK);
+//!     let mut d3 = d.begin_mode3D(camera);
+//!     scene.draw(&mut d3);
+//! }
+//! ```
+use std::borrow::Cow;
+use std::ops::Range;
+
+use serde::Deserialize;
+
+use crate::core::camera::Camera3D;
+use crate::core::color::Color;
+use crate::core::drawing::RaylibDraw3D;
+use crate::core::file::load_file_data;
+use crate::core::lighting::{Light, LightKind};
+use crate::core::math::{Matrix, Quaternion, Transform, Vector3};
+use crate::core::models::{Model, RaylibModel};
+use crate::core::{RaylibHandle, RaylibThread};
+use crate::error::{error, Error};
+
+/// Projection of a [`GltfCamera`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GltfProjection {
+    /// Vertical field of view in degrees, and the aspect ratio if the file fixes one.
+    Perspective {
+        fovy: f32,
+        aspect_ratio: Option<f32>,
+    },
+    /// Half width and half height of the view volume.
+    Orthographic { xmag: f32, ymag: f32 },
+}
+
+/// A camera as defined in the file, looking down its node's -Z axis with +Y up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfCamera {
+    pub name: String,
+    pub projection: GltfProjection,
+    pub znear: f32,
+    /// `None` for an infinite perspective projection.
+    pub zfar: Option<f32>,
+}
+
+impl GltfCamera {
+    /// Camera placed by the node world matrix `world`.
+    pub fn to_camera3d(&self, world: Matrix) -> Camera3D {
+        let position = Vector3::zero().transform_with(world);
+        let forward = (Vector3::new(0.0, 0.0, -1.0).transform_with(world) - position).normalized();
+        let up = (Vector3::up().transform_with(world) - position).normalized();
+        match self.projection {
+            GltfProjection::Perspective { fovy, .. } => {
+                Camera3D::perspective(position, position + forward, up, fovy)
+            }
+            GltfProjection::Orthographic { ymag, .. } => {
+                Camera3D::orthographic(position, position + forward, up, ymag * 2.0)
+            }
+        }
+    }
+}
+
+/// Node property an animation channel drives.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GltfChannelPath {
+    Translation,
+    Rotation,
+    Scale,
+    Weights,
+}
+
+/// An animation channel targeting a node. `animation` indexes [`GltfScene::animations`].
+///
+/// This only records which node properties an animation drives, not its keyframes. raylib
+/// only loads skinned animations, so for those play the [`super::ModelAnimation`] at the
+/// same index. Animations that only move unskinned nodes have no keyframes to play.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GltfChannel {
+    pub animation: usize,
+    pub path: GltfChannelPath,
+}
+
+#[derive(Debug, Clone)]
+pub struct GltfNode {
+    pub name: String,
+    /// Transform relative to the parent node.
+    pub transform: Transform,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// glTF mesh index.
+    pub mesh: Option<usize>,
+    /// Meshes of [`GltfScene::model`] loaded from this node, one per triangle primitive.
+    pub meshes: Range<usize>,
+    pub skin: Option<usize>,
+    pub camera: Option<GltfCamera>,
+    /// Light in node space, at the origin pointing down -Z.
+    pub light: Option<Light>,
+    pub channels: Vec<GltfChannel>,
+    bind: Matrix,
+}
+
+/// A glTF scene: the model raylib loads from the file plus the node graph it discards.
+#[derive(Debug)]
+pub struct GltfScene {
+    pub model: Model,
+    pub nodes: Vec<GltfNode>,
+    /// Root nodes of the file's default scene.
+    pub roots: Vec<usize>,
+    /// Animation names, in file order. For skinned files this is the order
+    /// [`RaylibHandle::load_model_animations`] returns them in, which loads nothing for
+    /// files whose animations only move nodes.
+    pub animations: Vec<String>,
+}
+
+impl RaylibHandle {
+    /// Loads a `.gltf` or `.glb` file as a [`GltfScene`]. Only available with the
+    /// `with_serde` feature.
+    pub fn load_gltf_scene(
+        &mut self,
+        thread: &RaylibThread,
+        filename: &str,
+    ) -> Result<GltfScene, Error> {
+        let data = load_file_data(filename)?;
+        let mut doc = parse(json_chunk(&data).map_err(|e| error!(e.message, filename))?)
+            .map_err(|e| error!(e.message, filename))?;
+        let model = self.load_model(thread, filename)?;
+        if model.meshes().len() != doc.mesh_count {
+            return Err(error!(
+                Cow::Owned(format!(
+                    "glTF has {} triangle primitives but raylib loaded {} meshes",
+                    doc.mesh_count,
+                    model.meshes().len()
+                )),
+                filename
+            ));
+        }
+        for i in 0..doc.nodes.len() {
+            doc.nodes[i].bind = world_matrix(&doc.nodes, i);
+        }
+        Ok(GltfScene {
+            model,
+            nodes: doc.nodes,
+            roots: doc.roots,
+            animations: doc.animations,
+        })
+    }
+}
+
+impl GltfScene {
+    /// Index of the first node named `name`.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.nodes.iter().position(|n| n.name == name)
+    }
+
+    /// Current world matrix of `node`.
+    pub fn world_matrix(&self, node: usize) -> Matrix {
+        world_matrix(&self.nodes, node)
+    }
+
+    /// Transform to draw `node`'s meshes with, undoing the world transform raylib baked in.
+    pub fn mesh_matrix(&self, node: usize) -> Matrix {
+        self.nodes[node].bind.inverted() * self.world_matrix(node)
+    }
+
+    /// `node`'s camera placed at its current world transform.
+    pub fn camera(&self, node: usize) -> Option<Camera3D> {
+        let camera = self.nodes[node].camera.as_ref()?;
+        Some(camera.to_camera3d(self.world_matrix(node)))
+    }
+
+    /// `node`'s light in world space.
+    pub fn light(&self, node: usize) -> Option<Light> {
+        let mut light = self.nodes[node].light?;
+        let world = self.world_matrix(node);
+        light.position = Vector3::zero().transform_with(world);
+        light.direction =
+            (Vector3::new(0.0, 0.0, -1.0).transform_with(world) - light.position).normalized();
+        Some(light)
+    }
+
+    /// Cameras of every node, in node order.
+    pub fn cameras(&self) -> impl Iterator<Item = (usize, Camera3D)> + '_ {
+        (0..self.nodes.len()).filter_map(move |i| self.camera(i).map(|c| (i, c)))
+    }
+
+    /// Lights of every node, in node order.
+    pub fn lights(&self) -> impl Iterator<Item = (usize, Light)> + '_ {
+        (0..self.nodes.len()).filter_map(move |i| self.light(i).map(|l| (i, l)))
+    }
+
+    /// Draws the meshes of every node in the default scene at their current transforms.
+    pub fn draw(&self, d: &mut impl RaylibDraw3D) {
+        let meshes = self.model.meshes();
+        let materials = self.model.materials();
+        let mesh_material = self.model.as_ref().meshMaterial;
+        let mut stack: Vec<usize> = self.roots.iter().rev().copied().collect();
+        while let Some(i) = stack.pop() {
+            let node = &self.nodes[i];
+            stack.extend(node.children.iter().rev());
+            if node.meshes.is_empty() {
+                continue;
+            }
+            let transform = self.mesh_matrix(i) * *self.model.transform();
+            for m in node.meshes.clone() {
+                let material = if mesh_material.is_null() {
+                    0
+                } else {
+                    unsafe { *mesh_material.add(m) as usize }
+                };
+                if let Some(material) = materials.get(material) {
+                    d.draw_mesh(&meshes[m], material.clone(), transform);
+                }
+            }
+        }
+    }
+}
+
+fn world_matrix(nodes: &[GltfNode], node: usize) -> Matrix {
+    let mut world = nodes[node].transform.to_matrix();
+    let mut parent = nodes[node].parent;
+    while let Some(p) = parent {
+        world *= nodes[p].transform.to_matrix();
+        parent = nodes[p].parent;
+    }
+    world
+}
+
+/// The JSON part of a `.gltf` or `.glb` file.
+fn json_chunk(data: &[u8]) -> Result<&str, Error> {
+    let json = if data.starts_with(b"glTF") {
+        let word = |at: usize| {
+            data.get(at..at + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+        };
+        match (word(12), word(16)) {
+            (Some(len), Some(0x4E4F_534A)) => data
+                .get(20..20 + len)
+                .ok_or_else(|| error!("GLB JSON chunk is truncated"))?,
+            _ => return Err(error!("GLB file has no JSON chunk")),
+        }
+    } else {
+        data
+    };
+    std::str::from_utf8(json).map_err(|_| error!("glTF JSON is not valid UTF-8"))
+}
+
+struct Document {
+    nodes: Vec<GltfNode>,
+    roots: Vec<usize>,
+    animations: Vec<String>,
+    mesh_count: usize,
+}
+
+fn parse(json: &str) -> Result<Document, Error> {
+    let root: json::Root = serde_json::from_str(json)
+        .map_err(|e| error!(Cow::Owned(format!("invalid glTF JSON: {}", e))))?;
+    let lights = root
+        .extensions
+        .and_then(|e| e.lights)
+        .map(|l| l.lights)
+        .unwrap_or_default();
+
+    let mut mesh_count = 0;
+    let mut nodes = Vec::with_capacity(root.nodes.len());
+    for (i, node) in root.nodes.iter().enumerate() {
+        let triangles = match node.mesh {
+            Some(mesh) => root
+                .meshes
+                .get(mesh)
+                .ok_or_else(|| error!(Cow::Owned(format!("node {} has no mesh {}", i, mesh))))?
+                .primitives
+                .iter()
+                .filter(|p| p.mode == 4)
+                .count(),
+            None => 0,
+        };
+        let camera = match node.camera {
+            Some(camera) => Some(
+                root.cameras
+                    .get(camera)
+                    .ok_or_else(|| {
+                        error!(Cow::Owned(format!("node {} has no camera {}", i, camera)))
+                    })?
+                    .to_camera()?,
+            ),
+            None => None,
+        };
+        let light = match node.extensions.as_ref().and_then(|e| e.light.as_ref()) {
+            Some(l) => Some(
+                lights
+                    .get(l.light)
+                    .ok_or_else(|| {
+                        error!(Cow::Owned(format!("node {} has no light {}", i, l.light)))
+                    })?
+                    .to_light()?,
+            ),
+            None => None,
+        };
+        nodes.push(GltfNode {
+            name: node.name.clone().unwrap_or_default(),
+            transform: node.transform(),
+            parent: None,
+            children: node.children.clone(),
+            mesh: node.mesh,
+            meshes: mesh_count..mesh_count + triangles,
+            skin: node.skin,
+            camera,
+            light,
+            channels: Vec::new(),
+            bind: Matrix::identity(),
+        });
+        mesh_count += triangles;
+    }
+
+    for i in 0..nodes.len() {
+        for c in nodes[i].children.clone() {
+            match nodes.get_mut(c) {
+                Some(child) if child.parent.is_none() && c != i => child.parent = Some(i),
+                _ => {
+                    return Err(error!(Cow::Owned(format!(
+                        "node {} has invalid child {}",
+                        i, c
+                    ))))
+                }
+            }
+        }
+    }
+    // A parent chain that never reaches a root is a cycle.
+    for i in 0..nodes.len() {
+        let mut parent = nodes[i].parent;
+        for _ in 0..nodes.len() {
+            parent = parent.and_then(|p| nodes[p].parent);
+        }
+        if parent.is_some() {
+            return Err(error!("glTF node hierarchy has a cycle"));
+        }
+    }
+
+    for (a, animation) in root.animations.iter().enumerate() {
+        for channel in &animation.channels {
+            let path = match channel.target.path.as_str() {
+                "translation" => GltfChannelPath::Translation,
+                "rotation" => GltfChannelPath::Rotation,
+                "scale" => GltfChannelPath::Scale,
+                "weights" => GltfChannelPath::Weights,
+                _ => continue,
+            };
+            if let Some(node) = channel.target.node.and_then(|n| nodes.get_mut(n)) {
+                node.channels.push(GltfChannel { animation: a, path });
+            }
+        }
+    }
+
+    let roots = match root.scene.or(if root.scenes.is_empty() {
+        None
+    } else {
+        Some(0)
+    }) {
+        Some(scene) => root
+            .scenes
+            .get(scene)
+            .ok_or_else(|| error!("glTF default scene does not exist"))?
+            .nodes
+            .clone(),
+        None => (0..nodes.len())
+            .filter(|&i| nodes[i].parent.is_none())
+            .collect(),
+    };
+    if roots.iter().any(|&r| r >= nodes.len()) {
+        return Err(error!("glTF scene references a missing node"));
+    }
+
+    Ok(Document {
+        nodes,
+        roots,
+        animations: root
+            .animations
+            .into_iter()
+            .map(|a| a.name.unwrap_or_default())
+            .collect(),
+        mesh_count,
+    })
+}
+
+mod json {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct Root {
+        pub scene: Option<usize>,
+        #[serde(default)]
+        pub scenes: Vec<Scene>,
+        #[serde(default)]
+        pub nodes: Vec<Node>,
+        #[serde(default)]
+        pub meshes: Vec<Mesh>,
+        #[serde(default)]
+        pub cameras: Vec<Camera>,
+        #[serde(default)]
+        pub animations: Vec<Animation>,
+        pub extensions: Option<RootExtensions>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Scene {
+        #[serde(default)]
+        pub nodes: Vec<usize>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Node {
+        pub name: Option<String>,
+        #[serde(default)]
+        pub children: Vec<usize>,
+        pub matrix: Option<[f32; 16]>,
+        pub translation: Option<[f32; 3]>,
+        pub rotation: Option<[f32; 4]>,
+        pub scale: Option<[f32; 3]>,
+        pub mesh: Option<usize>,
+        pub camera: Option<usize>,
+        pub skin: Option<usize>,
+        pub extensions: Option<NodeExtensions>,
+    }
+
+    impl Node {
+        pub fn transform(&self) -> Transform {
+            if let Some(m) = self.matrix {
+                return Matrix {
+                    m0: m[0],
+                    m1: m[1],
+                    m2: m[2],
+                    m3: m[3],
+                    m4: m[4],
+                    m5: m[5],
+                    m6: m[6],
+                    m7: m[7],
+                    m8: m[8],
+                    m9: m[9],
+                    m10: m[10],
+                    m11: m[11],
+                    m12: m[12],
+                    m13: m[13],
+                    m14: m[14],
+                    m15: m[15],
+                }
+                .decompose();
+            }
+            let t = self.translation.unwrap_or([0.0; 3]);
+            let r = self.rotation.unwrap_or([0.0, 0.0, 0.0, 1.0]);
+            let s = self.scale.unwrap_or([1.0; 3]);
+            Transform {
+                translation: Vector3::new(t[0], t[1], t[2]),
+                rotation: Quaternion::new(r[0], r[1], r[2], r[3]),
+                scale: Vector3::new(s[0], s[1], s[2]),
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct NodeExtensions {
+        #[serde(rename = "KHR_lights_punctual")]
+        pub light: Option<NodeLight>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct NodeLight {
+        pub light: usize,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Mesh {
+        #[serde(default)]
+        pub primitives: Vec<Primitive>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Primitive {
+        #[serde(default = "triangles")]
+        pub mode: u32,
+    }
+
+    fn triangles() -> u32 {
+        4
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Camera {
+        pub name: Option<String>,
+        #[serde(rename = "type")]
+        pub kind: String,
+        pub perspective: Option<Perspective>,
+        pub orthographic: Option<Orthographic>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Perspective {
+        pub yfov: f32,
+        pub znear: f32,
+        pub zfar: Option<f32>,
+        pub aspect_ratio: Option<f32>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Orthographic {
+        pub xmag: f32,
+        pub ymag: f32,
+        pub znear: f32,
+        pub zfar: f32,
+    }
+
+    impl Camera {
+        pub fn to_camera(&self) -> Result<GltfCamera, Error> {
+            let name = self.name.clone().unwrap_or_default();
+            match (self.kind.as_str(), &self.perspective, &self.orthographic) {
+                ("perspective", Some(p), _) => Ok(GltfCamera {
+                    name,
+                    projection: GltfProjection::Perspective {
+                        fovy: p.yfov.to_degrees(),
+                        aspect_ratio: p.aspect_ratio,
+                    },
+                    znear: p.znear,
+                    zfar: p.zfar,
+                }),
+                ("orthographic", _, Some(o)) => Ok(GltfCamera {
+                    name,
+                    projection: GltfProjection::Orthographic {
+                        xmag: o.xmag,
+                        ymag: o.ymag,
+                    },
+                    znear: o.znear,
+                    zfar: Some(o.zfar),
+                }),
+                (kind, _, _) => Err(error!(Cow::Owned(format!(
+                    "invalid glTF camera of type {:?}",
+                    kind
+                )))),
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Animation {
+        pub name: Option<String>,
+        #[serde(default)]
+        pub channels: Vec<Channel>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Channel {
+        pub target: Target,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Target {
+        pub node: Option<usize>,
+        pub path: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct RootExtensions {
+        #[serde(rename = "KHR_lights_punctual")]
+        pub lights: Option<Lights>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Lights {
+        #[serde(default)]
+        pub lights: Vec<PunctualLight>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct PunctualLight {
+        #[serde(rename = "type")]
+        pub kind: String,
+        pub color: Option<[f32; 3]>,
+        pub intensity: Option<f32>,
+        pub range: Option<f32>,
+        pub spot: Option<Spot>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Spot {
+        #[serde(default)]
+        pub inner_cone_angle: f32,
+        #[serde(default = "outer_cone")]
+        pub outer_cone_angle: f32,
+    }
+
+    fn outer_cone() -> f32 {
+        std::f32::consts::FRAC_PI_4
+    }
+
+    impl PunctualLight {
+        pub fn to_light(&self) -> Result<Light, Error> {
+            let kind = match self.kind.as_str() {
+                "directional" => LightKind::Directional,
+                "point" => LightKind::Point,
+                "spot" => {
+                    let spot = self.spot.as_ref();
+                    LightKind::Spot {
+                        inner_cone: spot.map_or(0.0, |s| s.inner_cone_angle),
+                        outer_cone: spot.map_or_else(outer_cone, |s| s.outer_cone_angle),
+                    }
+                }
+                kind => {
+                    return Err(error!(Cow::Owned(format!(
+                        "invalid glTF light of type {:?}",
+                        kind
+                    ))))
+                }
+            };
+            let [r, g, b] = self.color.unwrap_or([1.0; 3]);
+            let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+            Ok(Light {
+                kind,
+                position: Vector3::zero(),
+                direction: Vector3::new(0.0, 0.0, -1.0),
+                color: Color::new(channel(r), channel(g), channel(b), 255),
+                intensity: self.intensity.unwrap_or(1.0),
+                range: self.range,
+            })
+        }
+    }
+}
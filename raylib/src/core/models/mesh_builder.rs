@@ -0,0 +1,388 @@
+//! Building meshes from Rust vertex data
+use std::alloc::{handle_alloc_error, Layout};
+
+use crate::core::color::Color;
+use crate::core::math::{Matrix, Vector2, Vector3, Vector4};
+use crate::core::models::Mesh;
+use crate::core::RaylibThread;
+use crate::error::{error, Error};
+use crate::ffi;
+
+/// GPU vertex buffers of an uploaded mesh, with the element type `update_buffer` expects.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeshBuffer {
+    /// `Vector3`
+    Positions = 0,
+    /// `Vector2`
+    TexCoords = 1,
+    /// `Vector3`
+    Normals = 2,
+    /// `Color`
+    Colors = 3,
+    /// `Vector4`, with the bitangent sign in `w`
+    Tangents = 4,
+    /// `Vector2`
+    TexCoords2 = 5,
+    /// `u16`, three per triangle
+    Indices = 6,
+    /// `[u8; 4]`
+    BoneIds = 7,
+    /// `[f32; 4]`
+    BoneWeights = 8,
+}
+
+impl MeshBuffer {
+    /// Size in bytes of one element.
+    pub fn element_size(self) -> usize {
+        match self {
+            MeshBuffer::Positions | MeshBuffer::Normals => 12,
+            MeshBuffer::TexCoords | MeshBuffer::TexCoords2 => 8,
+            MeshBuffer::Colors | MeshBuffer::BoneIds => 4,
+            MeshBuffer::Tangents | MeshBuffer::BoneWeights => 16,
+            MeshBuffer::Indices => 2,
+        }
+    }
+
+    /// Number of elements the buffer holds for `mesh`.
+    pub fn len(self, mesh: &ffi::Mesh) -> usize {
+        match self {
+            MeshBuffer::Indices => mesh.triangleCount as usize * 3,
+            _ => mesh.vertexCount as usize,
+        }
+    }
+}
+
+/// Builds a `Mesh` from vertex attributes, copying them into memory raylib owns so dropping the
+/// mesh frees everything.
+///
+/// Every attribute needs one entry per position. Without indices, each three positions form a
+/// triangle. raylib stores 16 bit indices, so meshes with more than 65536 vertices are expanded
+/// into unindexed triangles.
+///
+/// ```no_run
+/// use raylib::prelude::*;
+/// let (_rl, thread) = raylib::init().build();
+/// let quad = MeshBuilder::new(vec![
+///     Vector3::new(0.0, 0.0, 0.0),
+///     Vector3::new(1.0, 0.0, 0.0),
+///     Vector3::new(1.0, 1.0, 0.0),
+///     Vector3::new(0.0, 1.0, 0.0),
+/// ])
+/// .texcoords(vec![
+///     Vector2::new(0.0, 1.0),
+///     Vector2::new(1.0, 1.0),
+///     Vector2::new(1.0, 0.0),
+///     Vector2::new(0.0, 0.0),
+/// ])
+/// .indices(vec![0, 1, 2, 0, 2, 3])
+/// .compute_normals()
+/// .compute_tangents()
+/// .upload(&thread, false)
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MeshBuilder {
+    positions: Vec<Vector3>,
+    normals: Option<Vec<Vector3>>,
+    texcoords: Option<Vec<Vector2>>,
+    texcoords2: Option<Vec<Vector2>>,
+    colors: Option<Vec<Color>>,
+    tangents: Option<Vec<Vector4>>,
+    bone_ids: Option<Vec<[u8; 4]>>,
+    bone_weights: Option<Vec<[f32; 4]>>,
+    indices: Option<Vec<u32>>,
+    compute_normals: bool,
+    compute_tangents: bool,
+}
+
+impl MeshBuilder {
+    pub fn new(positions: Vec<Vector3>) -> Self {
+        MeshBuilder {
+            positions,
+            ..Default::default()
+        }
+    }
+
+    pub fn normals(mut self, normals: Vec<Vector3>) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    pub fn texcoords(mut self, texcoords: Vec<Vector2>) -> Self {
+        self.texcoords = Some(texcoords);
+        self
+    }
+
+    /// Second UV set, used for lightmaps.
+    pub fn texcoords2(mut self, texcoords2: Vec<Vector2>) -> Self {
+        self.texcoords2 = Some(texcoords2);
+        self
+    }
+
+    pub fn colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Tangents with the bitangent sign in `w`.
+    pub fn tangents(mut self, tangents: Vec<Vector4>) -> Self {
+        self.tangents = Some(tangents);
+        self
+    }
+
+    /// Up to four bones per vertex and their weights, which should add up to 1.
+    pub fn bones(mut self, ids: Vec<[u8; 4]>, weights: Vec<[f32; 4]>) -> Self {
+        self.bone_ids = Some(ids);
+        self.bone_weights = Some(weights);
+        self
+    }
+
+    /// Triangle list, counter-clockwise for front faces.
+    pub fn indices(mut self, indices: Vec<u32>) -> Self {
+        self.indices = Some(indices);
+        self
+    }
+
+    /// Generates smooth normals weighted by triangle area, replacing any set with `normals`.
+    pub fn compute_normals(mut self) -> Self {
+        self.compute_normals = true;
+        self
+    }
+
+    /// Generates tangents from the positions, normals and texcoords, replacing any set with
+    /// `tangents`.
+    pub fn compute_tangents(mut self) -> Self {
+        self.compute_tangents = true;
+        self
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let count = self.positions.len();
+        if count == 0 {
+            return Err(error!("mesh has no vertices"));
+        }
+        if count > i32::MAX as usize {
+            return Err(error!("mesh has too many vertices"));
+        }
+        let lengths = [
+            ("normals", self.normals.as_ref().map(Vec::len)),
+            ("texcoords", self.texcoords.as_ref().map(Vec::len)),
+            ("texcoords2", self.texcoords2.as_ref().map(Vec::len)),
+            ("colors", self.colors.as_ref().map(Vec::len)),
+            ("tangents", self.tangents.as_ref().map(Vec::len)),
+            ("bone ids", self.bone_ids.as_ref().map(Vec::len)),
+            ("bone weights", self.bone_weights.as_ref().map(Vec::len)),
+        ];
+        for (name, len) in lengths.iter() {
+            if let Some(len) = len {
+                if *len != count {
+                    return Err(error!(std::borrow::Cow::Owned(format!(
+                        "mesh has {} {} for {} vertices",
+                        len, name, count
+                    ))));
+                }
+            }
+        }
+        match &self.indices {
+            Some(indices) => {
+                if indices.is_empty() || !indices.len().is_multiple_of(3) {
+                    return Err(error!("mesh index count must be a non-zero multiple of 3"));
+                }
+                if let Some(index) = indices.iter().find(|i| **i as usize >= count) {
+                    return Err(error!(std::borrow::Cow::Owned(format!(
+                        "mesh index {} is out of range for {} vertices",
+                        index, count
+                    ))));
+                }
+            }
+            None if !count.is_multiple_of(3) => {
+                return Err(error!(
+                    "mesh without indices needs a multiple of 3 vertices"
+                ))
+            }
+            None => {}
+        }
+        if self.compute_tangents && self.texcoords.is_none() {
+            return Err(error!("mesh tangents can't be computed without texcoords"));
+        }
+        Ok(())
+    }
+
+    fn triangles(&self) -> Vec<[usize; 3]> {
+        match &self.indices {
+            Some(indices) => indices
+                .chunks_exact(3)
+                .map(|t| [t[0] as usize, t[1] as usize, t[2] as usize])
+                .collect(),
+            None => (0..self.positions.len() / 3)
+                .map(|t| [t * 3, t * 3 + 1, t * 3 + 2])
+                .collect(),
+        }
+    }
+
+    fn generate(&mut self) {
+        let triangles = self.triangles();
+        let p = &self.positions;
+        if self.compute_normals {
+            let mut normals = vec![Vector3::zero(); p.len()];
+            for t in triangles.iter() {
+                // Left unnormalized, the cross product weighs each face by its area.
+                let n = (p[t[1]] - p[t[0]]).cross(p[t[2]] - p[t[0]]);
+                for i in t.iter() {
+                    normals[*i] += n;
+                }
+            }
+            normals.iter_mut().for_each(|n| n.normalize());
+            self.normals = Some(normals);
+        }
+
+        if self.compute_tangents {
+            let uv = self.texcoords.as_ref().unwrap();
+            let mut tan = vec![Vector3::zero(); p.len()];
+            let mut bitan = vec![Vector3::zero(); p.len()];
+            for t in triangles.iter() {
+                let (e1, e2) = (p[t[1]] - p[t[0]], p[t[2]] - p[t[0]]);
+                let (d1, d2) = (uv[t[1]] - uv[t[0]], uv[t[2]] - uv[t[0]]);
+                let det = d1.x * d2.y - d2.x * d1.y;
+                let r = if det.abs() < f32::EPSILON {
+                    0.0
+                } else {
+                    1.0 / det
+                };
+                let sdir = (e1 * d2.y - e2 * d1.y) * r;
+                let tdir = (e2 * d1.x - e1 * d2.x) * r;
+                for i in t.iter() {
+                    tan[*i] += sdir;
+                    bitan[*i] += tdir;
+                }
+            }
+            let normals = self.normals.as_ref();
+            let tangents = (0..p.len())
+                .map(|i| {
+                    let t = tan[i];
+                    let (t, w) = match normals {
+                        // Gram-Schmidt orthogonalize against the normal.
+                        Some(normals) => {
+                            let n = normals[i];
+                            let t = (t - n * n.dot(t)).normalized();
+                            let w = if n.cross(t).dot(bitan[i]) < 0.0 {
+                                -1.0
+                            } else {
+                                1.0
+                            };
+                            (t, w)
+                        }
+                        None => (t.normalized(), 1.0),
+                    };
+                    Vector4::new(t.x, t.y, t.z, w)
+                })
+                .collect();
+            self.tangents = Some(tangents);
+        }
+    }
+
+    /// Replaces indexed vertices with one vertex per index.
+    fn unindex(&mut self) {
+        let indices = match self.indices.take() {
+            Some(indices) => indices,
+            None => return,
+        };
+        fn expand<T: Copy>(data: &mut Option<Vec<T>>, indices: &[u32]) {
+            if let Some(data) = data {
+                *data = indices.iter().map(|i| data[*i as usize]).collect();
+            }
+        }
+        self.positions = indices
+            .iter()
+            .map(|i| self.positions[*i as usize])
+            .collect();
+        expand(&mut self.normals, &indices);
+        expand(&mut self.texcoords, &indices);
+        expand(&mut self.texcoords2, &indices);
+        expand(&mut self.colors, &indices);
+        expand(&mut self.tangents, &indices);
+        expand(&mut self.bone_ids, &indices);
+        expand(&mut self.bone_weights, &indices);
+    }
+
+    /// Builds the mesh with its data in RAM only. Upload it before drawing.
+    pub fn build(mut self, _: &RaylibThread) -> Result<Mesh, Error> {
+        self.validate()?;
+        self.generate();
+        if self.positions.len() > u16::MAX as usize + 1 {
+            self.unindex();
+            if self.positions.len() > i32::MAX as usize {
+                return Err(error!("mesh has too many vertices"));
+            }
+        }
+
+        unsafe {
+            let mut mesh: ffi::Mesh = std::mem::zeroed();
+            mesh.vertexCount = self.positions.len() as i32;
+            mesh.vertices = raylib_copy(&self.positions);
+            if let Some(normals) = &self.normals {
+                mesh.normals = raylib_copy(normals);
+            }
+            if let Some(texcoords) = &self.texcoords {
+                mesh.texcoords = raylib_copy(texcoords);
+            }
+            if let Some(texcoords2) = &self.texcoords2 {
+                mesh.texcoords2 = raylib_copy(texcoords2);
+            }
+            if let Some(colors) = &self.colors {
+                mesh.colors = raylib_copy(colors);
+            }
+            if let Some(tangents) = &self.tangents {
+                mesh.tangents = raylib_copy(tangents);
+            }
+            match &self.indices {
+                Some(indices) => {
+                    let indices: Vec<u16> = indices.iter().map(|i| *i as u16).collect();
+                    mesh.triangleCount = (indices.len() / 3) as i32;
+                    mesh.indices = raylib_copy(&indices);
+                }
+                None => mesh.triangleCount = mesh.vertexCount / 3,
+            }
+            if let (Some(ids), Some(weights)) = (&self.bone_ids, &self.bone_weights) {
+                mesh.boneIds = raylib_copy(ids);
+                mesh.boneWeights = raylib_copy(weights);
+                // CPU skinning writes the posed vertices here, GPU skinning reads the matrices.
+                mesh.animVertices = raylib_copy(&self.positions);
+                if let Some(normals) = &self.normals {
+                    mesh.animNormals = raylib_copy(normals);
+                }
+                let bone_count = ids.iter().flatten().max().map_or(0, |b| *b as usize + 1);
+                let matrices = vec![Matrix::identity(); bone_count];
+                mesh.boneCount = bone_count as i32;
+                mesh.boneMatrices = raylib_copy(&matrices);
+            }
+            Ok(Mesh(mesh))
+        }
+    }
+
+    /// Builds the mesh and uploads it to the GPU. A `dynamic` mesh is meant to be changed often
+    /// with `update_buffer`.
+    pub fn upload(self, thread: &RaylibThread, dynamic: bool) -> Result<Mesh, Error> {
+        let mut mesh = self.build(thread)?;
+        unsafe { ffi::UploadMesh(&mut mesh.0, dynamic) };
+        if mesh.0.vboId.is_null() {
+            return Err(error!("failed to upload mesh"));
+        }
+        Ok(mesh)
+    }
+}
+
+/// Copies `data` into memory allocated with raylib's allocator, to be freed by `UnloadMesh`.
+unsafe fn raylib_copy<T: Copy, U>(data: &[T]) -> *mut U {
+    let layout = Layout::for_value(data);
+    if layout.size() > u32::MAX as usize {
+        handle_alloc_error(layout);
+    }
+    let ptr = ffi::MemAlloc(layout.size() as u32) as *mut T;
+    if ptr.is_null() {
+        handle_alloc_error(layout);
+    }
+    std::ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+    ptr as *mut U
+}
@@ -0,0 +1,390 @@
+//! Typed, zero-copy access to [`Image`] pixel data.
+//!
+//! Every uncompressed layout a safe view is provided for has a pixel type implementing
+//! [`ImagePixel`]. Views borrow the raylib allocation directly, so nothing is copied:
+//! ```no_run
+//! # use raylib::prelude::*;
+//! let mut image = Image::gen_image_color(256, 256, Color::BLACK);
+//! for (y, row) in image.rows_mut::<Rgba8>().unwrap().enumerate() {
+//!     for (x, pixel) in row.iter_mut().enumerate() {
+//!         *pixel = Color::new(x as u8, y as u8, 0, 255);
+//!     }
+//! }
+//! ```
+use crate::consts::PixelFormat;
+use crate::core::color::Color;
+use crate::core::texture::Image;
+use crate::error::{error, Error};
+use crate::ffi;
+use std::mem::size_of_val;
+use std::slice::{ChunksExact, ChunksExactMut};
+
+/// A pixel type whose memory layout matches one of raylib's uncompressed pixel formats.
+///
+/// # Safety
+/// Implementors must have the exact size of one pixel of `FORMAT`, contain no padding and be
+/// valid for any bit pattern.
+pub unsafe trait ImagePixel: Copy + 'static {
+    /// The format images must be in to be viewed as this pixel type.
+    const FORMAT: PixelFormat;
+}
+
+/// 8 bits per channel RGBA, the default format of most images.
+pub type Rgba8 = Color;
+
+/// 8 bit grayscale.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Gray8(pub u8);
+
+/// 8 bit grayscale with alpha.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct GrayAlpha8 {
+    pub l: u8,
+    pub a: u8,
+}
+
+/// 8 bits per channel RGB.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Rgb8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// 16 bit packed RGB, 5 bits red, 6 bits green and 5 bits blue.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Rgb565(pub u16);
+
+/// 32 bit float, single channel.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct R32(pub f32);
+
+/// 32 bit float per channel RGB.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Rgb32f {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+/// 32 bit float per channel RGBA.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Rgba32f {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+unsafe impl ImagePixel for Color {
+    const FORMAT: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8;
+}
+unsafe impl ImagePixel for Gray8 {
+    const FORMAT: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAYSCALE;
+}
+unsafe impl ImagePixel for GrayAlpha8 {
+    const FORMAT: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA;
+}
+unsafe impl ImagePixel for Rgb8 {
+    const FORMAT: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8;
+}
+unsafe impl ImagePixel for Rgb565 {
+    const FORMAT: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R5G6B5;
+}
+unsafe impl ImagePixel for R32 {
+    const FORMAT: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32;
+}
+unsafe impl ImagePixel for Rgb32f {
+    const FORMAT: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32G32B32;
+}
+unsafe impl ImagePixel for Rgba32f {
+    const FORMAT: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R32G32B32A32;
+}
+
+impl Rgb565 {
+    /// Packs 8 bit channels, dropping the low bits.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb565(((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3))
+    }
+    /// Red channel expanded to 8 bits.
+    pub const fn r(self) -> u8 {
+        let v = (self.0 >> 11) as u8 & 0x1f;
+        (v << 3) | (v >> 2)
+    }
+    /// Green channel expanded to 8 bits.
+    pub const fn g(self) -> u8 {
+        let v = (self.0 >> 5) as u8 & 0x3f;
+        (v << 2) | (v >> 4)
+    }
+    /// Blue channel expanded to 8 bits.
+    pub const fn b(self) -> u8 {
+        let v = self.0 as u8 & 0x1f;
+        (v << 3) | (v >> 2)
+    }
+}
+
+impl From<Color> for Gray8 {
+    fn from(c: Color) -> Self {
+        // Same weights raylib uses in ImageFormat
+        Gray8((c.r as f32 * 0.299 + c.g as f32 * 0.587 + c.b as f32 * 0.114) as u8)
+    }
+}
+
+impl From<Gray8> for Color {
+    fn from(p: Gray8) -> Self {
+        Color::new(p.0, p.0, p.0, 255)
+    }
+}
+
+impl From<Color> for Rgb8 {
+    fn from(c: Color) -> Self {
+        Rgb8 {
+            r: c.r,
+            g: c.g,
+            b: c.b,
+        }
+    }
+}
+
+impl From<Rgb8> for Color {
+    fn from(p: Rgb8) -> Self {
+        Color::new(p.r, p.g, p.b, 255)
+    }
+}
+
+impl From<Color> for Rgb565 {
+    fn from(c: Color) -> Self {
+        Rgb565::new(c.r, c.g, c.b)
+    }
+}
+
+impl From<Rgb565> for Color {
+    fn from(p: Rgb565) -> Self {
+        Color::new(p.r(), p.g(), p.b(), 255)
+    }
+}
+
+impl From<Color> for Rgba32f {
+    fn from(c: Color) -> Self {
+        Rgba32f {
+            r: c.r as f32 / 255.0,
+            g: c.g as f32 / 255.0,
+            b: c.b as f32 / 255.0,
+            a: c.a as f32 / 255.0,
+        }
+    }
+}
+
+impl From<Rgba32f> for Color {
+    fn from(p: Rgba32f) -> Self {
+        let c = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color::new(c(p.r), c(p.g), c(p.b), c(p.a))
+    }
+}
+
+impl Image {
+    fn check_pixel_format<P: ImagePixel>(&self) -> Result<(), Error> {
+        if self.format != P::FORMAT as i32 {
+            return Err(error!(std::borrow::Cow::Owned(format!(
+                "image format is {:?}, expected {:?}",
+                self.format(),
+                P::FORMAT
+            ))));
+        }
+        Ok(())
+    }
+
+    fn pixel_count(&self) -> usize {
+        if self.data.is_null() {
+            0
+        } else {
+            self.width.max(0) as usize * self.height.max(0) as usize
+        }
+    }
+
+    fn pixel_index(&self, x: i32, y: i32) -> Result<usize, Error> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return Err(error!(std::borrow::Cow::Owned(format!(
+                "pixel ({}, {}) is outside of the {}x{} image",
+                x, y, self.width, self.height
+            ))));
+        }
+        Ok(y as usize * self.width as usize + x as usize)
+    }
+
+    /// Pixels of the base mipmap level, row by row. Fails if the image isn't in `P::FORMAT`.
+    pub fn pixels<P: ImagePixel>(&self) -> Result<&[P], Error> {
+        self.check_pixel_format::<P>()?;
+        let len = self.pixel_count();
+        if len == 0 {
+            return Ok(&[]);
+        }
+        // raylib allocates pixel data with malloc, which is aligned for every pixel type
+        unsafe { Ok(std::slice::from_raw_parts(self.data as *const P, len)) }
+    }
+
+    /// Mutable pixels of the base mipmap level, row by row. Fails if the image isn't in `P::FORMAT`.
+    pub fn pixels_mut<P: ImagePixel>(&mut self) -> Result<&mut [P], Error> {
+        self.check_pixel_format::<P>()?;
+        let len = self.pixel_count();
+        if len == 0 {
+            return Ok(&mut []);
+        }
+        unsafe { Ok(std::slice::from_raw_parts_mut(self.data as *mut P, len)) }
+    }
+
+    /// Iterator over the rows of the base mipmap level.
+    pub fn rows<P: ImagePixel>(&self) -> Result<ChunksExact<'_, P>, Error> {
+        let width = self.width.max(1) as usize;
+        Ok(self.pixels::<P>()?.chunks_exact(width))
+    }
+
+    /// Mutable iterator over the rows of the base mipmap level.
+    pub fn rows_mut<P: ImagePixel>(&mut self) -> Result<ChunksExactMut<'_, P>, Error> {
+        let width = self.width.max(1) as usize;
+        Ok(self.pixels_mut::<P>()?.chunks_exact_mut(width))
+    }
+
+    /// Reads the pixel at (x, y) without copying the image.
+    pub fn get_pixel<P: ImagePixel>(&self, x: i32, y: i32) -> Result<P, Error> {
+        let i = self.pixel_index(x, y)?;
+        Ok(self.pixels::<P>()?[i])
+    }
+
+    /// Writes the pixel at (x, y) in place.
+    pub fn set_pixel<P: ImagePixel>(&mut self, x: i32, y: i32, pixel: P) -> Result<(), Error> {
+        let i = self.pixel_index(x, y)?;
+        self.pixels_mut::<P>()?[i] = pixel;
+        Ok(())
+    }
+
+    /// Creates an image in `P::FORMAT` from `width * height` pixels.
+    pub fn from_pixels<P: ImagePixel>(
+        width: i32,
+        height: i32,
+        pixels: &[P],
+    ) -> Result<Image, Error> {
+        if width <= 0
+            || height <= 0
+            || (width as usize).checked_mul(height as usize) != Some(pixels.len())
+        {
+            return Err(error!(std::borrow::Cow::Owned(format!(
+                "{} pixels don't make a {}x{} image",
+                pixels.len(),
+                width,
+                height
+            ))));
+        }
+        let size = size_of_val(pixels);
+        unsafe {
+            let data = ffi::MemAlloc(size as u32);
+            if data.is_null() {
+                return Err(error!("could not allocate image data"));
+            }
+            std::ptr::copy_nonoverlapping(pixels.as_ptr() as *const u8, data as *mut u8, size);
+            Ok(Image(ffi::Image {
+                data,
+                width,
+                height,
+                mipmaps: 1,
+                format: P::FORMAT as i32,
+            }))
+        }
+    }
+}
+
+#[cfg(feature = "convert_image")]
+mod image_crate {
+    use super::*;
+    use image::{ImageBuffer, Luma, LumaA, Pixel, Rgb, Rgba};
+    use std::mem::size_of;
+
+    /// An [`ImagePixel`] with the same memory layout as a pixel type of the `image` crate.
+    ///
+    /// # Safety
+    /// `Self` and `Self::Pixel` must have the same size and channel order.
+    pub unsafe trait ImageCratePixel: ImagePixel {
+        type Pixel: Pixel;
+    }
+
+    unsafe impl ImageCratePixel for Color {
+        type Pixel = Rgba<u8>;
+    }
+    unsafe impl ImageCratePixel for Gray8 {
+        type Pixel = Luma<u8>;
+    }
+    unsafe impl ImageCratePixel for GrayAlpha8 {
+        type Pixel = LumaA<u8>;
+    }
+    unsafe impl ImageCratePixel for Rgb8 {
+        type Pixel = Rgb<u8>;
+    }
+    unsafe impl ImageCratePixel for R32 {
+        type Pixel = Luma<f32>;
+    }
+    unsafe impl ImageCratePixel for Rgb32f {
+        type Pixel = Rgb<f32>;
+    }
+    unsafe impl ImageCratePixel for Rgba32f {
+        type Pixel = Rgba<f32>;
+    }
+
+    type Subpixel<P> = <<P as ImageCratePixel>::Pixel as Pixel>::Subpixel;
+
+    fn subpixels<P: ImageCratePixel>(pixels: &[P]) -> &[Subpixel<P>] {
+        let len = size_of_val(pixels) / size_of::<Subpixel<P>>();
+        unsafe { std::slice::from_raw_parts(pixels.as_ptr() as *const Subpixel<P>, len) }
+    }
+
+    fn subpixels_mut<P: ImageCratePixel>(pixels: &mut [P]) -> &mut [Subpixel<P>] {
+        let len = size_of_val(pixels) / size_of::<Subpixel<P>>();
+        unsafe { std::slice::from_raw_parts_mut(pixels.as_mut_ptr() as *mut Subpixel<P>, len) }
+    }
+
+    impl Image {
+        /// Borrows the base mipmap level as an `image::ImageBuffer`, without copying.
+        pub fn as_image_buffer<P: ImageCratePixel>(
+            &self,
+        ) -> Result<ImageBuffer<P::Pixel, &[Subpixel<P>]>, Error> {
+            let (width, height) = (self.width as u32, self.height as u32);
+            ImageBuffer::from_raw(width, height, subpixels(self.pixels::<P>()?))
+                .ok_or_else(|| error!("image data doesn't fit an ImageBuffer"))
+        }
+
+        /// Mutably borrows the base mipmap level as an `image::ImageBuffer`, without copying.
+        pub fn as_image_buffer_mut<P: ImageCratePixel>(
+            &mut self,
+        ) -> Result<ImageBuffer<P::Pixel, &mut [Subpixel<P>]>, Error> {
+            let (width, height) = (self.width as u32, self.height as u32);
+            ImageBuffer::from_raw(width, height, subpixels_mut(self.pixels_mut::<P>()?))
+                .ok_or_else(|| error!("image data doesn't fit an ImageBuffer"))
+        }
+
+        /// Creates an image from an `image::ImageBuffer`. raylib must own the pixel data of its
+        /// images, so the buffer is copied once.
+        pub fn from_image_buffer<P, C>(buffer: &ImageBuffer<P::Pixel, C>) -> Result<Image, Error>
+        where
+            P: ImageCratePixel,
+            C: std::ops::Deref<Target = [Subpixel<P>]>,
+        {
+            let raw: &[Subpixel<P>] = buffer.as_raw();
+            let len = (buffer.width() * buffer.height()) as usize;
+            let bytes = len * size_of::<P>();
+            if size_of_val(raw) < bytes {
+                return Err(error!("ImageBuffer is smaller than its dimensions"));
+            }
+            let pixels = unsafe { std::slice::from_raw_parts(raw.as_ptr() as *const P, len) };
+            Image::from_pixels(buffer.width() as i32, buffer.height() as i32, pixels)
+        }
+    }
+}
+
+#[cfg(feature = "convert_image")]
+pub use image_crate::ImageCratePixel;
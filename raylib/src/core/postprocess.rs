@@ -0,0 +1,761 @@
+//! Multi-pass post-processing over render textures.
+//!
+//! ```no_run
+//! # use raylib::prelude::*;
+//! # let (mut rl, thread) = raylib::init().resizable().build();
+//! let mut chain = PostProcessChain::new(&mut rl, &thread).unwrap();
+//! chain.add_bloom(&mut rl, &thread, BloomSettings::default()).unwrap();
+//! chain.add_tonemap(&mut rl, &thread, ToneMapSettings::default()).unwrap();
+//! chain.add_fxaa(&mut rl, &thread).unwrap();
+//! chain.add_vignette(&mut rl, &thread, VignetteSettings::default()).unwrap();
+//! while !rl.window_should_close() {
+//!     chain.update(&mut rl, &thread).unwrap();
+//!     {
+//!         let mut d = rl.begin_texture_mode(&thread, chain.scene_mut());
+//!         d.clear_background(Color::BLACK);
+//!         d.draw_circle(200, 200, 50.0, Color::YELLOW);
+//!     }
+//!     chain.apply(&mut rl, &thread).unwrap();
+//!     let mut d = rl.begin_drawing(&thread);
+//!     chain.draw(&mut d);
+//! }
+//! ```
+use crate::consts::{TextureFilter, TextureWrap};
+use crate::core::color::Color;
+use crate::core::drawing::RaylibDraw;
+use crate::core::math::{Rectangle, Vector2};
+use crate::core::shaders::{RaylibShader, Shader, UniformValue};
+use crate::core::texture::{RaylibRenderTexture2D, RenderTexture2D, WeakTexture2D};
+use crate::core::{RaylibHandle, RaylibThread};
+use crate::error::{error, Error};
+use crate::ffi;
+
+#[cfg(not(any(feature = "opengl_es_20", target_arch = "wasm32")))]
+const HEADER: &str = "#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+out vec4 finalColor;
+#define TEX texture
+";
+
+#[cfg(any(feature = "opengl_es_20", target_arch = "wasm32"))]
+const HEADER: &str = "#version 100
+precision mediump float;
+varying vec2 fragTexCoord;
+varying vec4 fragColor;
+uniform sampler2D texture0;
+#define TEX texture2D
+#define finalColor gl_FragColor
+";
+
+const BRIGHT_FS: &str = "
+uniform float threshold;
+void main() {
+    vec4 c = TEX(texture0, fragTexCoord);
+    float l = max(c.r, max(c.g, c.b));
+    float k = max(l - threshold, 0.0) / max(l, 0.0001);
+    finalColor = vec4(c.rgb * k, 1.0);
+}
+";
+
+const BLUR_FS: &str = "
+uniform vec2 resolution;
+uniform vec2 direction;
+void main() {
+    vec2 off = direction / resolution;
+    vec4 c = TEX(texture0, fragTexCoord) * 0.2270270270;
+    c += TEX(texture0, fragTexCoord + off * 1.3846153846) * 0.3162162162;
+    c += TEX(texture0, fragTexCoord - off * 1.3846153846) * 0.3162162162;
+    c += TEX(texture0, fragTexCoord + off * 3.2307692308) * 0.0702702703;
+    c += TEX(texture0, fragTexCoord - off * 3.2307692308) * 0.0702702703;
+    finalColor = c;
+}
+";
+
+const BLOOM_FS: &str = "
+uniform sampler2D bloomTexture;
+uniform float intensity;
+void main() {
+    vec4 base = TEX(texture0, fragTexCoord);
+    vec3 bloom = TEX(bloomTexture, fragTexCoord).rgb;
+    finalColor = vec4(base.rgb + bloom * intensity, base.a);
+}
+";
+
+const FXAA_FS: &str = "
+uniform vec2 resolution;
+#define FXAA_REDUCE_MIN (1.0 / 128.0)
+#define FXAA_REDUCE_MUL (1.0 / 8.0)
+#define FXAA_SPAN_MAX 8.0
+void main() {
+    vec2 inv = 1.0 / resolution;
+    vec3 luma = vec3(0.299, 0.587, 0.114);
+    vec4 m = TEX(texture0, fragTexCoord);
+    float lumaNW = dot(TEX(texture0, fragTexCoord + vec2(-1.0, -1.0) * inv).rgb, luma);
+    float lumaNE = dot(TEX(texture0, fragTexCoord + vec2(1.0, -1.0) * inv).rgb, luma);
+    float lumaSW = dot(TEX(texture0, fragTexCoord + vec2(-1.0, 1.0) * inv).rgb, luma);
+    float lumaSE = dot(TEX(texture0, fragTexCoord + vec2(1.0, 1.0) * inv).rgb, luma);
+    float lumaM = dot(m.rgb, luma);
+    float lumaMin = min(lumaM, min(min(lumaNW, lumaNE), min(lumaSW, lumaSE)));
+    float lumaMax = max(lumaM, max(max(lumaNW, lumaNE), max(lumaSW, lumaSE)));
+
+    vec2 dir = vec2(-((lumaNW + lumaNE) - (lumaSW + lumaSE)), (lumaNW + lumaSW) - (lumaNE + lumaSE));
+    float reduce = max((lumaNW + lumaNE + lumaSW + lumaSE) * (0.25 * FXAA_REDUCE_MUL), FXAA_REDUCE_MIN);
+    float rcpDirMin = 1.0 / (min(abs(dir.x), abs(dir.y)) + reduce);
+    dir = clamp(dir * rcpDirMin, vec2(-FXAA_SPAN_MAX), vec2(FXAA_SPAN_MAX)) * inv;
+
+    vec3 rgbA = 0.5 * (TEX(texture0, fragTexCoord + dir * (1.0 / 3.0 - 0.5)).rgb
+        + TEX(texture0, fragTexCoord + dir * (2.0 / 3.0 - 0.5)).rgb);
+    vec3 rgbB = rgbA * 0.5 + 0.25 * (TEX(texture0, fragTexCoord - dir * 0.5).rgb
+        + TEX(texture0, fragTexCoord + dir * 0.5).rgb);
+    float lumaB = dot(rgbB, luma);
+    if (lumaB < lumaMin || lumaB > lumaMax) {
+        finalColor = vec4(rgbA, m.a);
+    } else {
+        finalColor = vec4(rgbB, m.a);
+    }
+}
+";
+
+const TONEMAP_FS: &str = "
+uniform float exposure;
+uniform float gamma;
+uniform int mode;
+vec3 aces(vec3 x) {
+    return clamp((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14), 0.0, 1.0);
+}
+void main() {
+    vec4 c = TEX(texture0, fragTexCoord);
+    vec3 lin = pow(c.rgb, vec3(gamma)) * exposure;
+    vec3 mapped = mode == 0 ? aces(lin) : lin / (1.0 + lin);
+    finalColor = vec4(pow(mapped, vec3(1.0 / gamma)), c.a);
+}
+";
+
+const VIGNETTE_FS: &str = "
+uniform vec2 resolution;
+uniform float radius;
+uniform float softness;
+uniform float intensity;
+void main() {
+    vec4 c = TEX(texture0, fragTexCoord);
+    vec2 p = (fragTexCoord - 0.5) * vec2(resolution.x / resolution.y, 1.0);
+    float v = 1.0 - smoothstep(radius - softness, radius, length(p));
+    finalColor = vec4(c.rgb * mix(1.0, v, intensity), c.a);
+}
+";
+
+/// Settings of the passes added by [`PostProcessChain::add_bloom`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BloomSettings {
+    /// Brightness above which pixels start to glow.
+    pub threshold: f32,
+    /// How much of the blurred highlights is added back.
+    pub intensity: f32,
+    /// Distance between blur taps, in texels of the downscaled targets.
+    pub spread: f32,
+    /// Size of the blur targets relative to the screen.
+    pub scale: f32,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        BloomSettings {
+            threshold: 0.7,
+            intensity: 1.0,
+            spread: 1.0,
+            scale: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ToneMapOperator {
+    Aces = 0,
+    Reinhard = 1,
+}
+
+/// Settings of the pass added by [`PostProcessChain::add_tonemap`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ToneMapSettings {
+    pub operator: ToneMapOperator,
+    pub exposure: f32,
+    pub gamma: f32,
+}
+
+impl Default for ToneMapSettings {
+    fn default() -> Self {
+        ToneMapSettings {
+            operator: ToneMapOperator::Aces,
+            exposure: 1.0,
+            gamma: 2.2,
+        }
+    }
+}
+
+/// Settings of the pass added by [`PostProcessChain::add_vignette`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VignetteSettings {
+    /// Distance from the center, in screen heights, where the darkening is complete.
+    pub radius: f32,
+    /// Width of the fade towards `radius`.
+    pub softness: f32,
+    /// 0 disables the effect, 1 fades to black.
+    pub intensity: f32,
+}
+
+impl Default for VignetteSettings {
+    fn default() -> Self {
+        VignetteSettings {
+            radius: 0.9,
+            softness: 0.5,
+            intensity: 1.0,
+        }
+    }
+}
+
+struct PassInput {
+    pass: String,
+    location: i32,
+}
+
+struct PassUniform {
+    name: String,
+    location: i32,
+    value: Box<dyn UniformValue>,
+}
+
+/// One fullscreen shader pass of a [`PostProcessChain`].
+///
+/// The shader samples its source through `texture0`, by default the output of the previous
+/// pass. A `resolution` uniform, if declared, is set to the size of the pass output.
+pub struct PostPass {
+    name: String,
+    shader: Shader,
+    source: Option<String>,
+    inputs: Vec<PassInput>,
+    uniforms: Vec<PassUniform>,
+    resolution_loc: i32,
+    scale: f32,
+    enabled: bool,
+    retained: bool,
+    target: Option<RenderTexture2D>,
+}
+
+impl std::fmt::Debug for PostPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostPass")
+            .field("name", &self.name)
+            .field("shader", &self.shader)
+            .field("source", &self.source)
+            .field(
+                "inputs",
+                &self.inputs.iter().map(|i| &i.pass).collect::<Vec<_>>(),
+            )
+            .field(
+                "uniforms",
+                &self.uniforms.iter().map(|u| &u.name).collect::<Vec<_>>(),
+            )
+            .field("scale", &self.scale)
+            .field("enabled", &self.enabled)
+            .finish()
+    }
+}
+
+impl PostPass {
+    pub fn new(name: &str, shader: Shader) -> Self {
+        let resolution_loc = shader.get_shader_location("resolution");
+        PostPass {
+            name: name.to_owned(),
+            shader,
+            source: None,
+            inputs: Vec::new(),
+            uniforms: Vec::new(),
+            resolution_loc,
+            scale: 1.0,
+            enabled: true,
+            retained: false,
+            target: None,
+        }
+    }
+
+    /// Draws the output of the pass called `pass` instead of the previous one.
+    pub fn source(mut self, pass: &str) -> Self {
+        self.source = Some(pass.to_owned());
+        self
+    }
+
+    /// Binds the output of the pass called `pass` to the sampler `uniform`. raylib batches at
+    /// most 3 textures besides `texture0`.
+    pub fn input(mut self, uniform: &str, pass: &str) -> Self {
+        let location = self.shader.get_shader_location(uniform);
+        self.inputs.push(PassInput {
+            pass: pass.to_owned(),
+            location,
+        });
+        self
+    }
+
+    /// Size of the output relative to the chain, e.g. 0.5 for half resolution.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets a uniform uploaded before every draw of the pass.
+    pub fn uniform(mut self, name: &str, value: impl UniformValue + 'static) -> Self {
+        self.set_uniform(name, value);
+        self
+    }
+
+    pub fn set_uniform(&mut self, name: &str, value: impl UniformValue + 'static) {
+        let value = Box::new(value);
+        match self.uniforms.iter_mut().find(|u| u.name == name) {
+            Some(uniform) => uniform.value = value,
+            None => {
+                let location = self.shader.get_shader_location(name);
+                self.uniforms.push(PassUniform {
+                    name: name.to_owned(),
+                    location,
+                    value,
+                });
+            }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn shader(&self) -> &Shader {
+        &self.shader
+    }
+
+    pub fn shader_mut(&mut self) -> &mut Shader {
+        &mut self.shader
+    }
+
+    /// Disabled passes are skipped, passes reading their output get the previous one instead.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn references(&self) -> impl Iterator<Item = &str> {
+        self.source
+            .iter()
+            .chain(self.inputs.iter().map(|i| &i.pass))
+            .map(String::as_str)
+    }
+}
+
+/// Where the output of a pass ended up during [`PostProcessChain::apply`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Slot {
+    Scene,
+    Ping(usize),
+    Pass(usize),
+}
+
+/// An ordered list of fullscreen shader passes applied to a scene render texture.
+///
+/// Draw the scene into [`PostProcessChain::scene_mut`] with `begin_texture_mode`, run the passes
+/// with [`PostProcessChain::apply`] and draw the result with [`PostProcessChain::draw`], to the
+/// screen or into another render texture. Intermediate results go through
+/// two ping-pong targets; passes whose output is read later by name, or that render at another
+/// scale, get their own target.
+#[derive(Debug)]
+pub struct PostProcessChain {
+    width: i32,
+    height: i32,
+    scene: RenderTexture2D,
+    ping: [Option<RenderTexture2D>; 2],
+    passes: Vec<PostPass>,
+    result: Slot,
+}
+
+impl PostProcessChain {
+    /// Name under which passes can read the scene texture.
+    pub const SCENE: &'static str = "scene";
+
+    /// Creates a chain the size of the screen.
+    pub fn new(rl: &mut RaylibHandle, thread: &RaylibThread) -> Result<Self, Error> {
+        let (width, height) = (rl.get_screen_width(), rl.get_screen_height());
+        Self::with_size(rl, thread, width, height)
+    }
+
+    pub fn with_size(
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        width: i32,
+        height: i32,
+    ) -> Result<Self, Error> {
+        Ok(PostProcessChain {
+            width,
+            height,
+            scene: load_target(rl, thread, width, height)?,
+            ping: [None, None],
+            passes: Vec::new(),
+            result: Slot::Scene,
+        })
+    }
+
+    /// Appends a pass. Fails if its name is taken or it reads a pass that doesn't come before
+    /// it.
+    pub fn add_pass(&mut self, pass: PostPass) -> Result<&mut Self, Error> {
+        if pass.name == Self::SCENE || self.pass(&pass.name).is_some() {
+            return Err(error!(std::borrow::Cow::Owned(format!(
+                "post process pass \"{}\" already exists",
+                pass.name
+            ))));
+        }
+        for name in pass.references() {
+            if name != Self::SCENE && self.pass(name).is_none() {
+                return Err(error!(std::borrow::Cow::Owned(format!(
+                    "post process pass \"{}\" reads unknown pass \"{}\"",
+                    pass.name, name
+                ))));
+            }
+        }
+        let referenced: Vec<String> = pass.references().map(str::to_owned).collect();
+        for p in self.passes.iter_mut() {
+            if referenced.contains(&p.name) && !p.retained {
+                p.retained = true;
+                p.target = None;
+            }
+        }
+        self.passes.push(pass);
+        Ok(self)
+    }
+
+    /// Adds a bright pass, a separable blur at [`BloomSettings::scale`] and a composite, named
+    /// `bloom_bright`, `bloom_blur_h`, `bloom_blur_v` and `bloom`.
+    pub fn add_bloom(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        settings: BloomSettings,
+    ) -> Result<&mut Self, Error> {
+        let base = self
+            .passes
+            .last()
+            .map_or(Self::SCENE, |p| p.name.as_str())
+            .to_owned();
+        let bright = load_builtin(rl, thread, BRIGHT_FS)?;
+        let blur_h = load_builtin(rl, thread, BLUR_FS)?;
+        let blur_v = load_builtin(rl, thread, BLUR_FS)?;
+        let bloom = load_builtin(rl, thread, BLOOM_FS)?;
+        self.add_pass(
+            PostPass::new("bloom_bright", bright)
+                .scale(settings.scale)
+                .uniform("threshold", settings.threshold),
+        )?;
+        self.add_pass(
+            PostPass::new("bloom_blur_h", blur_h)
+                .scale(settings.scale)
+                .uniform("direction", Vector2::new(settings.spread, 0.0)),
+        )?;
+        self.add_pass(
+            PostPass::new("bloom_blur_v", blur_v)
+                .scale(settings.scale)
+                .uniform("direction", Vector2::new(0.0, settings.spread)),
+        )?;
+        self.add_pass(
+            PostPass::new("bloom", bloom)
+                .source(&base)
+                .input("bloomTexture", "bloom_blur_v")
+                .uniform("intensity", settings.intensity),
+        )
+    }
+
+    /// Adds a fast approximate anti-aliasing pass named `fxaa`.
+    pub fn add_fxaa(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+    ) -> Result<&mut Self, Error> {
+        let shader = load_builtin(rl, thread, FXAA_FS)?;
+        self.add_pass(PostPass::new("fxaa", shader))
+    }
+
+    /// Adds a tone mapping pass named `tonemap`.
+    pub fn add_tonemap(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        settings: ToneMapSettings,
+    ) -> Result<&mut Self, Error> {
+        let shader = load_builtin(rl, thread, TONEMAP_FS)?;
+        self.add_pass(
+            PostPass::new("tonemap", shader)
+                .uniform("mode", settings.operator as i32)
+                .uniform("exposure", settings.exposure)
+                .uniform("gamma", settings.gamma),
+        )
+    }
+
+    /// Adds a vignette pass named `vignette`.
+    pub fn add_vignette(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        settings: VignetteSettings,
+    ) -> Result<&mut Self, Error> {
+        let shader = load_builtin(rl, thread, VIGNETTE_FS)?;
+        self.add_pass(
+            PostPass::new("vignette", shader)
+                .uniform("radius", settings.radius)
+                .uniform("softness", settings.softness)
+                .uniform("intensity", settings.intensity),
+        )
+    }
+
+    pub fn passes(&self) -> &[PostPass] {
+        &self.passes
+    }
+
+    pub fn pass(&self, name: &str) -> Option<&PostPass> {
+        self.passes.iter().find(|p| p.name == name)
+    }
+
+    pub fn pass_mut(&mut self, name: &str) -> Option<&mut PostPass> {
+        self.passes.iter_mut().find(|p| p.name == name)
+    }
+
+    /// The render texture the scene is drawn into.
+    pub fn scene(&self) -> &RenderTexture2D {
+        &self.scene
+    }
+
+    pub fn scene_mut(&mut self) -> &mut RenderTexture2D {
+        &mut self.scene
+    }
+
+    /// Output of the scene or of a pass that is read by name, as of the last apply.
+    pub fn output(&self, name: &str) -> Option<&WeakTexture2D> {
+        if name == Self::SCENE {
+            return Some(self.scene.texture());
+        }
+        self.pass(name)?.target.as_ref().map(|t| t.texture())
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Recreates all targets at the new size.
+    pub fn resize(
+        &mut self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        width: i32,
+        height: i32,
+    ) -> Result<(), Error> {
+        self.scene = load_target(rl, thread, width, height)?;
+        self.width = width;
+        self.height = height;
+        self.ping = [None, None];
+        self.result = Slot::Scene;
+        for pass in self.passes.iter_mut() {
+            pass.target = None;
+        }
+        Ok(())
+    }
+
+    /// Follows the screen size, returns whether the targets were recreated. Call it once per
+    /// frame before drawing the scene.
+    pub fn update(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) -> Result<bool, Error> {
+        let (width, height) = (rl.get_screen_width(), rl.get_screen_height());
+        if !rl.is_window_resized() && (width, height) == (self.width, self.height) {
+            return Ok(false);
+        }
+        self.resize(rl, thread, width, height)?;
+        Ok(true)
+    }
+
+    /// Runs the enabled passes. Each pass renders into its own texture mode, so call it after the
+    /// scene is drawn and outside of any other texture mode.
+    pub fn apply(&mut self, _: &mut RaylibHandle, _: &RaylibThread) -> Result<(), Error> {
+        if unsafe { ffi::rlGetActiveFramebuffer() } != 0 {
+            return Err(error!(
+                "post-processing passes can't be applied inside a texture mode"
+            ));
+        }
+        let mut previous = Slot::Scene;
+        // Output of every pass so far, disabled passes forward the one before them.
+        let mut outputs = Vec::with_capacity(self.passes.len());
+        for i in 0..self.passes.len() {
+            let pass = &self.passes[i];
+            if !pass.enabled {
+                outputs.push(previous);
+                continue;
+            }
+            let source = match &pass.source {
+                Some(name) => self.slot_of(name, &outputs),
+                None => previous,
+            };
+            let (width, height) = (
+                ((self.width as f32 * pass.scale) as i32).max(1),
+                ((self.height as f32 * pass.scale) as i32).max(1),
+            );
+            // A ping-pong target is free unless this pass or a later one still reads it.
+            let free = (0..2).find(|&k| {
+                Slot::Ping(k) != previous
+                    && Slot::Ping(k) != source
+                    && !self.passes[i..]
+                        .iter()
+                        .flat_map(|p| p.references())
+                        .filter_map(|name| self.position(name))
+                        .filter(|&j| j < outputs.len())
+                        .any(|j| outputs[j] == Slot::Ping(k))
+            });
+            let slot = match free {
+                Some(k) if !pass.retained && pass.scale == 1.0 => {
+                    if self.ping[k].is_none() {
+                        self.ping[k] = Some(create_target(width, height)?);
+                    }
+                    Slot::Ping(k)
+                }
+                _ => {
+                    if self.passes[i].target.is_none() {
+                        self.passes[i].target = Some(create_target(width, height)?);
+                    }
+                    Slot::Pass(i)
+                }
+            };
+
+            let source = *self.texture_of(source);
+            let inputs: Vec<(i32, ffi::Texture2D)> = self.passes[i]
+                .inputs
+                .iter()
+                .map(|input| {
+                    let slot = self.slot_of(&input.pass, &outputs);
+                    (input.location, *self.texture_of(slot))
+                })
+                .collect();
+            let target = *self.render_texture_of(slot).as_ref();
+            let pass = &self.passes[i];
+            let shader = *pass.shader.as_ref();
+            unsafe {
+                ffi::BeginTextureMode(target);
+                ffi::ClearBackground(Color::BLANK.into());
+                ffi::BeginShaderMode(shader);
+                // Samplers must be set after the shader is bound, binding it flushes the batch.
+                for (location, texture) in inputs {
+                    if location >= 0 {
+                        ffi::SetShaderValueTexture(shader, location, texture);
+                    }
+                }
+                for uniform in pass.uniforms.iter().filter(|u| u.location >= 0) {
+                    uniform.value.set_uniform(shader, uniform.location);
+                }
+                if pass.resolution_loc >= 0 {
+                    Vector2::new(width as f32, height as f32)
+                        .set_uniform(shader, pass.resolution_loc);
+                }
+                ffi::DrawTexturePro(
+                    source,
+                    flipped(&source),
+                    Rectangle::new(0.0, 0.0, width as f32, height as f32).into(),
+                    Vector2::zero().into(),
+                    0.0,
+                    Color::WHITE.into(),
+                );
+                ffi::EndShaderMode();
+                ffi::EndTextureMode();
+            }
+            outputs.push(slot);
+            previous = slot;
+        }
+        self.result = previous;
+        Ok(())
+    }
+
+    /// Draws the output of the last [`PostProcessChain::apply`] over the chain's size at the
+    /// origin of `d`, which may be a texture mode.
+    pub fn draw<D: RaylibDraw>(&self, d: &mut D) {
+        let result = self.render_texture_of(self.result).texture();
+        d.draw_texture_pro(
+            result,
+            flipped(result.as_ref()),
+            Rectangle::new(0.0, 0.0, self.width as f32, self.height as f32),
+            Vector2::zero(),
+            0.0,
+            Color::WHITE,
+        );
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.passes.iter().position(|p| p.name == name)
+    }
+
+    fn slot_of(&self, name: &str, outputs: &[Slot]) -> Slot {
+        self.position(name).map_or(Slot::Scene, |i| outputs[i])
+    }
+
+    fn render_texture_of(&self, slot: Slot) -> &RenderTexture2D {
+        match slot {
+            Slot::Scene => &self.scene,
+            Slot::Ping(k) => self.ping[k].as_ref().unwrap(),
+            Slot::Pass(i) => self.passes[i].target.as_ref().unwrap(),
+        }
+    }
+
+    fn texture_of(&self, slot: Slot) -> &ffi::Texture2D {
+        &self.render_texture_of(slot).texture
+    }
+}
+
+/// Source rectangle drawing a render texture upright.
+fn flipped(texture: &ffi::Texture2D) -> ffi::Rectangle {
+    Rectangle::new(0.0, 0.0, texture.width as f32, -texture.height as f32).into()
+}
+
+fn load_builtin(rl: &mut RaylibHandle, thread: &RaylibThread, fs: &str) -> Result<Shader, Error> {
+    rl.load_shader_from_memory(thread, None, Some(&format!("{}{}", HEADER, fs)))
+}
+
+fn load_target(
+    rl: &mut RaylibHandle,
+    thread: &RaylibThread,
+    width: i32,
+    height: i32,
+) -> Result<RenderTexture2D, Error> {
+    let target = rl.load_render_texture(thread, width.max(1) as u32, height.max(1) as u32)?;
+    configure_target(&target);
+    Ok(target)
+}
+
+/// Loads a pass target while drawing, where the handle is borrowed.
+fn create_target(width: i32, height: i32) -> Result<RenderTexture2D, Error> {
+    let target = unsafe { ffi::LoadRenderTexture(width, height) };
+    if target.id == 0 {
+        return Err(error!("failed to create render texture."));
+    }
+    let target = RenderTexture2D(target);
+    configure_target(&target);
+    Ok(target)
+}
+
+/// Scaled passes are sampled at other sizes, and blurs read past the edges.
+fn configure_target(target: &RenderTexture2D) {
+    unsafe {
+        ffi::SetTextureFilter(
+            target.texture,
+            TextureFilter::TEXTURE_FILTER_BILINEAR as i32,
+        );
+        ffi::SetTextureWrap(target.texture, TextureWrap::TEXTURE_WRAP_CLAMP as i32);
+    }
+}
@@ -0,0 +1,872 @@
+//! Safe wrappers around rlgl, the OpenGL abstraction layer raylib is built on.
+//!
+//! Matrix stack and immediate-mode calls are scoped by guards, so every `rlPushMatrix` is matched by
+//! a `rlPopMatrix` and every `rlBegin` by a `rlEnd`. Render state changes flush the active batch
+//! first so they only apply to geometry submitted afterwards.
+use crate::consts::{rlCullMode, rlFramebufferAttachTextureType, rlFramebufferAttachType};
+use crate::core::drawing::{RaylibDraw, RaylibDraw3D};
+use crate::core::math::{Matrix, Vector2, Vector3};
+use crate::core::shaders::ShaderV;
+use crate::core::{RaylibHandle, RaylibThread};
+use crate::error::{error, Error};
+use crate::ffi;
+
+use std::convert::TryInto;
+use std::os::raw::c_void;
+
+/// Primitive type assembled by an immediate-mode batch.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RlDrawMode {
+    Lines,
+    Triangles,
+    Quads,
+}
+
+impl RlDrawMode {
+    fn as_raw(self) -> i32 {
+        (match self {
+            RlDrawMode::Lines => ffi::RL_LINES,
+            RlDrawMode::Triangles => ffi::RL_TRIANGLES,
+            RlDrawMode::Quads => ffi::RL_QUADS,
+        }) as i32
+    }
+}
+
+/// Blend factor used with [`RaylibRlgl::rl_set_blend_factors`]. Only applies while drawing with `BlendMode::BLEND_CUSTOM`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RlBlendFactor {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstAlpha,
+    OneMinusDstAlpha,
+    DstColor,
+    OneMinusDstColor,
+    SrcAlphaSaturate,
+    ConstantColor,
+    OneMinusConstantColor,
+    ConstantAlpha,
+    OneMinusConstantAlpha,
+}
+
+impl RlBlendFactor {
+    fn as_raw(self) -> i32 {
+        (match self {
+            RlBlendFactor::Zero => ffi::RL_ZERO,
+            RlBlendFactor::One => ffi::RL_ONE,
+            RlBlendFactor::SrcColor => ffi::RL_SRC_COLOR,
+            RlBlendFactor::OneMinusSrcColor => ffi::RL_ONE_MINUS_SRC_COLOR,
+            RlBlendFactor::SrcAlpha => ffi::RL_SRC_ALPHA,
+            RlBlendFactor::OneMinusSrcAlpha => ffi::RL_ONE_MINUS_SRC_ALPHA,
+            RlBlendFactor::DstAlpha => ffi::RL_DST_ALPHA,
+            RlBlendFactor::OneMinusDstAlpha => ffi::RL_ONE_MINUS_DST_ALPHA,
+            RlBlendFactor::DstColor => ffi::RL_DST_COLOR,
+            RlBlendFactor::OneMinusDstColor => ffi::RL_ONE_MINUS_DST_COLOR,
+            RlBlendFactor::SrcAlphaSaturate => ffi::RL_SRC_ALPHA_SATURATE,
+            RlBlendFactor::ConstantColor => ffi::RL_CONSTANT_COLOR,
+            RlBlendFactor::OneMinusConstantColor => ffi::RL_ONE_MINUS_CONSTANT_COLOR,
+            RlBlendFactor::ConstantAlpha => ffi::RL_CONSTANT_ALPHA,
+            RlBlendFactor::OneMinusConstantAlpha => ffi::RL_ONE_MINUS_CONSTANT_ALPHA,
+        }) as i32
+    }
+}
+
+/// Blend equation used with [`RaylibRlgl::rl_set_blend_factors`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RlBlendEquation {
+    Add,
+    Subtract,
+    ReverseSubtract,
+    Min,
+    Max,
+}
+
+impl RlBlendEquation {
+    fn as_raw(self) -> i32 {
+        (match self {
+            RlBlendEquation::Add => ffi::RL_FUNC_ADD,
+            RlBlendEquation::Subtract => ffi::RL_FUNC_SUBTRACT,
+            RlBlendEquation::ReverseSubtract => ffi::RL_FUNC_REVERSE_SUBTRACT,
+            RlBlendEquation::Min => ffi::RL_MIN,
+            RlBlendEquation::Max => ffi::RL_MAX,
+        }) as i32
+    }
+}
+
+/// Component type of a vertex attribute.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RlAttribType {
+    Float,
+    UnsignedByte,
+}
+
+/// Layout of one vertex attribute inside a [`VertexBuffer`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct VertexAttribute {
+    /// Shader attribute location.
+    pub index: u32,
+    /// Number of components (1 to 4).
+    pub components: i32,
+    pub kind: RlAttribType,
+    pub normalized: bool,
+    /// Distance in bytes between two consecutive vertices, 0 when tightly packed.
+    pub stride: i32,
+    /// Offset in bytes of the first component.
+    pub offset: i32,
+}
+
+impl VertexAttribute {
+    /// Tightly packed float attribute, e.g. `VertexAttribute::floats(0, 3)` for positions.
+    pub const fn floats(index: u32, components: i32) -> Self {
+        VertexAttribute {
+            index,
+            components,
+            kind: RlAttribType::Float,
+            normalized: false,
+            stride: 0,
+            offset: 0,
+        }
+    }
+
+    /// Tightly packed normalized byte attribute, e.g. `VertexAttribute::bytes(3, 4)` for colors.
+    pub const fn bytes(index: u32, components: i32) -> Self {
+        VertexAttribute {
+            index,
+            components,
+            kind: RlAttribType::UnsignedByte,
+            normalized: true,
+            stride: 0,
+            offset: 0,
+        }
+    }
+}
+
+/// Draw handles that can issue rlgl calls. Implemented for every [`RaylibDraw`] type.
+pub trait RaylibRlgl
+where
+    Self: Sized,
+{
+    /// Push the current matrix. The matrix is popped when the returned guard is dropped.
+    #[must_use]
+    fn rl_push_matrix(&mut self) -> RlMatrix<'_, Self> {
+        unsafe { ffi::rlPushMatrix() }
+        RlMatrix(self)
+    }
+
+    /// Closure version of [`RaylibRlgl::rl_push_matrix`].
+    fn rl_start_matrix(&mut self, mut func: impl FnMut(RlMatrix<'_, Self>)) {
+        func(self.rl_push_matrix());
+    }
+
+    /// Begin an immediate-mode batch of `mode` primitives. `rlEnd` is called when the returned guard is dropped.
+    #[must_use]
+    fn rl_begin(&mut self, mode: RlDrawMode) -> RlImmediate<'_, Self> {
+        unsafe { ffi::rlBegin(mode.as_raw()) }
+        RlImmediate(self, false)
+    }
+
+    /// Same as [`RaylibRlgl::rl_begin`] but samples `texture`. Texture coordinates should be set for every vertex.
+    #[must_use]
+    fn rl_begin_textured(
+        &mut self,
+        mode: RlDrawMode,
+        texture: &impl AsRef<ffi::Texture2D>,
+    ) -> RlImmediate<'_, Self> {
+        unsafe {
+            ffi::rlSetTexture(texture.as_ref().id);
+            ffi::rlBegin(mode.as_raw());
+        }
+        RlImmediate(self, true)
+    }
+
+    /// Make sure the batch has room for `vertex_count` more vertices, flushing it otherwise.
+    /// Returns true if the batch was flushed.
+    #[inline]
+    fn rl_check_render_batch_limit(&mut self, vertex_count: i32) -> bool {
+        unsafe { ffi::rlCheckRenderBatchLimit(vertex_count) }
+    }
+
+    /// Draw everything submitted so far.
+    #[inline]
+    fn rl_draw_render_batch_active(&mut self) {
+        unsafe { ffi::rlDrawRenderBatchActive() }
+    }
+
+    /// Current modelview matrix.
+    #[inline]
+    fn rl_get_matrix_modelview(&self) -> Matrix {
+        unsafe { ffi::rlGetMatrixModelview().into() }
+    }
+
+    /// Current projection matrix.
+    #[inline]
+    fn rl_get_matrix_projection(&self) -> Matrix {
+        unsafe { ffi::rlGetMatrixProjection().into() }
+    }
+
+    /// Transform matrix accumulated by the matrix stack.
+    #[inline]
+    fn rl_get_matrix_transform(&self) -> Matrix {
+        unsafe { ffi::rlGetMatrixTransform().into() }
+    }
+
+    /// Enable depth testing.
+    fn rl_enable_depth_test(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlEnableDepthTest();
+        }
+    }
+
+    /// Disable depth testing.
+    fn rl_disable_depth_test(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlDisableDepthTest();
+        }
+    }
+
+    /// Enable depth writes.
+    fn rl_enable_depth_mask(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlEnableDepthMask();
+        }
+    }
+
+    /// Disable depth writes.
+    fn rl_disable_depth_mask(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlDisableDepthMask();
+        }
+    }
+
+    /// Enable face culling.
+    fn rl_enable_backface_culling(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlEnableBackfaceCulling();
+        }
+    }
+
+    /// Disable face culling.
+    fn rl_disable_backface_culling(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlDisableBackfaceCulling();
+        }
+    }
+
+    /// Choose which faces are culled while culling is enabled.
+    fn rl_set_cull_face(&mut self, mode: rlCullMode) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlSetCullFace(mode as i32);
+        }
+    }
+
+    /// Enable color blending.
+    fn rl_enable_color_blend(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlEnableColorBlend();
+        }
+    }
+
+    /// Disable color blending.
+    fn rl_disable_color_blend(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlDisableColorBlend();
+        }
+    }
+
+    /// Set the blend factors used by `BlendMode::BLEND_CUSTOM`.
+    #[inline]
+    fn rl_set_blend_factors(
+        &mut self,
+        src: RlBlendFactor,
+        dst: RlBlendFactor,
+        equation: RlBlendEquation,
+    ) {
+        unsafe { ffi::rlSetBlendFactors(src.as_raw(), dst.as_raw(), equation.as_raw()) }
+    }
+
+    /// Set separate color/alpha blend factors used by `BlendMode::BLEND_CUSTOM_SEPARATE`.
+    #[inline]
+    fn rl_set_blend_factors_separate(
+        &mut self,
+        src_rgb: RlBlendFactor,
+        dst_rgb: RlBlendFactor,
+        src_alpha: RlBlendFactor,
+        dst_alpha: RlBlendFactor,
+        equation_rgb: RlBlendEquation,
+        equation_alpha: RlBlendEquation,
+    ) {
+        unsafe {
+            ffi::rlSetBlendFactorsSeparate(
+                src_rgb.as_raw(),
+                dst_rgb.as_raw(),
+                src_alpha.as_raw(),
+                dst_alpha.as_raw(),
+                equation_rgb.as_raw(),
+                equation_alpha.as_raw(),
+            )
+        }
+    }
+
+    /// Select which color channels are written.
+    fn rl_color_mask(&mut self, r: bool, g: bool, b: bool, a: bool) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlColorMask(r, g, b, a);
+        }
+    }
+
+    /// Set the line width used by line primitives.
+    fn rl_set_line_width(&mut self, width: f32) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlSetLineWidth(width);
+        }
+    }
+
+    /// Draw wireframes until the returned guard is dropped.
+    #[must_use]
+    fn rl_begin_wire_mode(&mut self) -> RlWireMode<'_, Self> {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlEnableWireMode();
+        }
+        RlWireMode(self)
+    }
+
+    /// Set the viewport used by following draws.
+    fn rl_viewport(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlViewport(x, y, width, height);
+        }
+    }
+
+    /// Render into `framebuffer` until the returned guard is dropped. The viewport is left untouched.
+    #[must_use]
+    fn rl_begin_framebuffer<'a>(
+        &'a mut self,
+        framebuffer: &'a Framebuffer,
+    ) -> RlFramebuffer<'a, Self> {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlEnableFramebuffer(framebuffer.0);
+        }
+        RlFramebuffer(self)
+    }
+
+    /// Use `shader` for direct vertex array draws until the returned guard is dropped.
+    #[must_use]
+    fn rl_enable_shader<'a>(
+        &'a mut self,
+        shader: &'a impl AsRef<ffi::Shader>,
+    ) -> RlShaderProgram<'a, Self> {
+        unsafe { ffi::rlEnableShader(shader.as_ref().id) }
+        RlShaderProgram(self)
+    }
+}
+
+impl<D: RaylibDraw> RaylibRlgl for D {}
+
+// Matrix stack
+
+/// Pushed matrix. Transformations applied through it are undone when it is dropped.
+pub struct RlMatrix<'a, T>(&'a mut T);
+
+impl<'a, T> RlMatrix<'a, T> {
+    /// Multiply the current matrix by a translation.
+    #[inline]
+    pub fn translate(&mut self, x: f32, y: f32, z: f32) {
+        unsafe { ffi::rlTranslatef(x, y, z) }
+    }
+
+    /// Multiply the current matrix by a rotation of `angle` degrees around the given axis.
+    #[inline]
+    pub fn rotate(&mut self, angle: f32, x: f32, y: f32, z: f32) {
+        unsafe { ffi::rlRotatef(angle, x, y, z) }
+    }
+
+    /// Multiply the current matrix by a scaling.
+    #[inline]
+    pub fn scale(&mut self, x: f32, y: f32, z: f32) {
+        unsafe { ffi::rlScalef(x, y, z) }
+    }
+
+    /// Multiply the current matrix by `mat`.
+    #[inline]
+    pub fn mult_matrix(&mut self, mat: Matrix) {
+        let values = mat.to_array();
+        unsafe { ffi::rlMultMatrixf(values.as_ptr()) }
+    }
+
+    /// Reset the current matrix to identity.
+    #[inline]
+    pub fn load_identity(&mut self) {
+        unsafe { ffi::rlLoadIdentity() }
+    }
+}
+
+impl<'a, T> Drop for RlMatrix<'a, T> {
+    fn drop(&mut self) {
+        unsafe { ffi::rlPopMatrix() }
+    }
+}
+impl<'a, T> std::ops::Deref for RlMatrix<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+impl<'a, T> std::ops::DerefMut for RlMatrix<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.0
+    }
+}
+impl<'a, T> RaylibDraw for RlMatrix<'a, T> {}
+impl<'a, T: RaylibDraw3D> RaylibDraw3D for RlMatrix<'a, T> {}
+
+// Immediate mode
+
+/// Open `rlBegin` batch. Only vertex data can be submitted until it is dropped.
+pub struct RlImmediate<'a, T>(&'a mut T, bool);
+
+impl<'a, T> RlImmediate<'a, T> {
+    /// Submit a 2D vertex.
+    #[inline]
+    pub fn vertex2(&mut self, position: impl Into<ffi::Vector2>) {
+        let v = position.into();
+        unsafe { ffi::rlVertex2f(v.x, v.y) }
+    }
+
+    /// Submit a 3D vertex.
+    #[inline]
+    pub fn vertex3(&mut self, position: impl Into<ffi::Vector3>) {
+        let v = position.into();
+        unsafe { ffi::rlVertex3f(v.x, v.y, v.z) }
+    }
+
+    /// Texture coordinate for the next vertex.
+    #[inline]
+    pub fn texcoord(&mut self, uv: impl Into<ffi::Vector2>) {
+        let uv = uv.into();
+        unsafe { ffi::rlTexCoord2f(uv.x, uv.y) }
+    }
+
+    /// Normal for the next vertex.
+    #[inline]
+    pub fn normal(&mut self, normal: impl Into<ffi::Vector3>) {
+        let n = normal.into();
+        unsafe { ffi::rlNormal3f(n.x, n.y, n.z) }
+    }
+
+    /// Color for the next vertex.
+    #[inline]
+    pub fn color(&mut self, color: impl Into<ffi::Color>) {
+        let c = color.into();
+        unsafe { ffi::rlColor4ub(c.r, c.g, c.b, c.a) }
+    }
+
+    /// Submit a colored 3D vertex.
+    #[inline]
+    pub fn colored_vertex3(
+        &mut self,
+        position: impl Into<ffi::Vector3>,
+        color: impl Into<ffi::Color>,
+    ) {
+        self.color(color);
+        self.vertex3(position);
+    }
+
+    /// Submit a whole triangle.
+    pub fn triangle(&mut self, v1: Vector3, v2: Vector3, v3: Vector3) {
+        self.vertex3(v1);
+        self.vertex3(v2);
+        self.vertex3(v3);
+    }
+
+    /// Submit a 2D textured quad, `positions` and `uvs` in counter-clockwise order.
+    pub fn textured_quad(&mut self, positions: [Vector2; 4], uvs: [Vector2; 4]) {
+        for (p, uv) in positions.iter().zip(uvs.iter()) {
+            self.texcoord(*uv);
+            self.vertex2(*p);
+        }
+    }
+}
+
+impl<'a, T> Drop for RlImmediate<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rlEnd();
+            if self.1 {
+                ffi::rlSetTexture(0);
+            }
+        }
+    }
+}
+
+// Render state
+
+/// Wireframe rendering, disabled again when dropped.
+pub struct RlWireMode<'a, T>(&'a mut T);
+
+impl<'a, T> Drop for RlWireMode<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlDisableWireMode();
+        }
+    }
+}
+impl<'a, T> std::ops::Deref for RlWireMode<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+impl<'a, T> std::ops::DerefMut for RlWireMode<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.0
+    }
+}
+impl<'a, T> RaylibDraw for RlWireMode<'a, T> {}
+impl<'a, T: RaylibDraw3D> RaylibDraw3D for RlWireMode<'a, T> {}
+
+/// Bound [`Framebuffer`], the default framebuffer is restored when dropped.
+pub struct RlFramebuffer<'a, T>(&'a mut T);
+
+impl<'a, T> Drop for RlFramebuffer<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rlDrawRenderBatchActive();
+            ffi::rlDisableFramebuffer();
+        }
+    }
+}
+impl<'a, T> std::ops::Deref for RlFramebuffer<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+impl<'a, T> std::ops::DerefMut for RlFramebuffer<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.0
+    }
+}
+impl<'a, T> RaylibDraw for RlFramebuffer<'a, T> {}
+impl<'a, T: RaylibDraw3D> RaylibDraw3D for RlFramebuffer<'a, T> {}
+
+/// Shader program bound for direct draws. Disabled when dropped.
+pub struct RlShaderProgram<'a, T>(&'a mut T);
+
+impl<'a, T> RlShaderProgram<'a, T> {
+    /// Set a uniform of the bound program.
+    pub fn set_uniform<S: ShaderV>(&mut self, location: i32, value: S) {
+        unsafe { ffi::rlSetUniform(location, value.value(), S::UNIFORM_TYPE as i32, 1) }
+    }
+
+    /// Set a matrix uniform of the bound program.
+    #[inline]
+    pub fn set_uniform_matrix(&mut self, location: i32, mat: impl Into<ffi::Matrix>) {
+        unsafe { ffi::rlSetUniformMatrix(location, mat.into()) }
+    }
+
+    /// Bind `texture` to a sampler uniform of the bound program.
+    #[inline]
+    pub fn set_uniform_sampler(&mut self, location: i32, texture: &impl AsRef<ffi::Texture2D>) {
+        unsafe { ffi::rlSetUniformSampler(location, texture.as_ref().id) }
+    }
+
+    /// Draw `count` vertices of `vao` starting at `offset`.
+    pub fn draw_vertex_array(&mut self, vao: &VertexArray, offset: i32, count: i32) {
+        if unsafe { ffi::rlEnableVertexArray(vao.0) } {
+            unsafe {
+                ffi::rlDrawVertexArray(offset, count);
+                ffi::rlDisableVertexArray();
+            }
+        }
+    }
+
+    /// Draw `count` indices of the element buffer attached to `vao` starting at `offset`.
+    pub fn draw_vertex_array_elements(&mut self, vao: &VertexArray, offset: i32, count: i32) {
+        if unsafe { ffi::rlEnableVertexArray(vao.0) } {
+            unsafe {
+                ffi::rlDrawVertexArrayElements(offset, count, std::ptr::null());
+                ffi::rlDisableVertexArray();
+            }
+        }
+    }
+
+    /// Instanced version of [`RlShaderProgram::draw_vertex_array`].
+    pub fn draw_vertex_array_instanced(
+        &mut self,
+        vao: &VertexArray,
+        offset: i32,
+        count: i32,
+        instances: i32,
+    ) {
+        if unsafe { ffi::rlEnableVertexArray(vao.0) } {
+            unsafe {
+                ffi::rlDrawVertexArrayInstanced(offset, count, instances);
+                ffi::rlDisableVertexArray();
+            }
+        }
+    }
+}
+
+impl<'a, T> Drop for RlShaderProgram<'a, T> {
+    fn drop(&mut self) {
+        unsafe { ffi::rlDisableShader() }
+    }
+}
+
+// Vertex arrays and buffers
+
+make_thin_wrapper!(VertexArray, u32, ffi::rlUnloadVertexArray, false);
+
+impl VertexArray {
+    /// OpenGL id of the vertex array.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+
+    /// Bind `buffer` to this vertex array and describe how `attribute` is read from it.
+    pub fn set_attribute(&mut self, buffer: &VertexBuffer, attribute: VertexAttribute) {
+        let kind = match attribute.kind {
+            RlAttribType::Float => ffi::RL_FLOAT,
+            RlAttribType::UnsignedByte => ffi::RL_UNSIGNED_BYTE,
+        };
+        unsafe {
+            if ffi::rlEnableVertexArray(self.0) {
+                ffi::rlEnableVertexBuffer(buffer.id);
+                ffi::rlSetVertexAttribute(
+                    attribute.index,
+                    attribute.components,
+                    kind as i32,
+                    attribute.normalized,
+                    attribute.stride,
+                    attribute.offset,
+                );
+                ffi::rlEnableVertexAttribute(attribute.index);
+                ffi::rlDisableVertexBuffer();
+                ffi::rlDisableVertexArray();
+            }
+        }
+    }
+
+    /// Advance `index` once per `divisor` instances instead of once per vertex.
+    pub fn set_attribute_divisor(&mut self, index: u32, divisor: i32) {
+        unsafe {
+            if ffi::rlEnableVertexArray(self.0) {
+                ffi::rlSetVertexAttributeDivisor(index, divisor);
+                ffi::rlDisableVertexArray();
+            }
+        }
+    }
+
+    /// Use `buffer` as the index buffer of this vertex array.
+    pub fn set_element_buffer(&mut self, buffer: &ElementBuffer) {
+        unsafe {
+            if ffi::rlEnableVertexArray(self.0) {
+                ffi::rlEnableVertexBufferElement(buffer.id);
+                ffi::rlDisableVertexArray();
+            }
+        }
+    }
+}
+
+/// GPU buffer holding vertex data.
+#[derive(Debug)]
+pub struct VertexBuffer {
+    id: u32,
+    size: usize,
+}
+
+/// GPU buffer holding vertex indices.
+#[derive(Debug)]
+pub struct ElementBuffer {
+    id: u32,
+    size: usize,
+}
+
+macro_rules! impl_gl_buffer {
+    ($name:ident, $update:expr) => {
+        impl $name {
+            /// OpenGL id of the buffer.
+            #[inline]
+            pub fn id(&self) -> u32 {
+                self.id
+            }
+
+            /// Size of the buffer in bytes.
+            #[inline]
+            pub fn size(&self) -> usize {
+                self.size
+            }
+
+            /// Overwrite part of the buffer, `offset` is in bytes.
+            pub fn update<T: Copy>(&mut self, data: &[T], offset: usize) -> Result<(), Error> {
+                let len = std::mem::size_of_val(data);
+                if offset.checked_add(len).map_or(true, |end| end > self.size) {
+                    return Err(error!("buffer update out of bounds"));
+                }
+                unsafe {
+                    ($update)(
+                        self.id,
+                        data.as_ptr() as *const c_void,
+                        len as i32,
+                        offset as i32,
+                    )
+                }
+                Ok(())
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                unsafe { ffi::rlUnloadVertexBuffer(self.id) }
+            }
+        }
+    };
+}
+
+impl_gl_buffer!(VertexBuffer, ffi::rlUpdateVertexBuffer);
+impl_gl_buffer!(ElementBuffer, ffi::rlUpdateVertexBufferElements);
+
+// Framebuffers
+
+make_thin_wrapper!(Framebuffer, u32, ffi::rlUnloadFramebuffer, false);
+
+impl Framebuffer {
+    /// OpenGL id of the framebuffer.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+
+    /// Attach `texture` at `attach_type`.
+    pub fn attach(
+        &mut self,
+        texture: &impl AsRef<ffi::Texture2D>,
+        attach_type: rlFramebufferAttachType,
+        texture_type: rlFramebufferAttachTextureType,
+        mip_level: i32,
+    ) {
+        unsafe {
+            ffi::rlFramebufferAttach(
+                self.0,
+                texture.as_ref().id,
+                attach_type as i32,
+                texture_type as i32,
+                mip_level,
+            )
+        }
+    }
+
+    /// Check that all attachments are valid. The framebuffer is left unbound.
+    pub fn is_complete(&self) -> bool {
+        unsafe { ffi::rlFramebufferComplete(self.0) }
+    }
+}
+
+impl RaylibHandle {
+    /// Create an empty vertex array. Fails on OpenGL versions without vertex array support.
+    pub fn load_vertex_array(&mut self, _: &RaylibThread) -> Result<VertexArray, Error> {
+        let id = unsafe { ffi::rlLoadVertexArray() };
+        if id == 0 {
+            return Err(error!("vertex arrays are not supported"));
+        }
+        Ok(VertexArray(id))
+    }
+
+    /// Upload `data` to a new vertex buffer.
+    pub fn load_vertex_buffer<T: Copy>(
+        &mut self,
+        _: &RaylibThread,
+        data: &[T],
+        dynamic: bool,
+    ) -> Result<VertexBuffer, Error> {
+        let size = std::mem::size_of_val(data);
+        let id = unsafe {
+            ffi::rlLoadVertexBuffer(
+                data.as_ptr() as *const c_void,
+                size.try_into()
+                    .map_err(|_| error!("vertex buffer too large"))?,
+                dynamic,
+            )
+        };
+        if id == 0 {
+            return Err(error!("could not load vertex buffer"));
+        }
+        Ok(VertexBuffer { id, size })
+    }
+
+    /// Upload `indices` to a new element buffer.
+    pub fn load_element_buffer(
+        &mut self,
+        _: &RaylibThread,
+        indices: &[u16],
+        dynamic: bool,
+    ) -> Result<ElementBuffer, Error> {
+        let size = std::mem::size_of_val(indices);
+        let id = unsafe {
+            ffi::rlLoadVertexBufferElement(
+                indices.as_ptr() as *const c_void,
+                size.try_into()
+                    .map_err(|_| error!("element buffer too large"))?,
+                dynamic,
+            )
+        };
+        if id == 0 {
+            return Err(error!("could not load element buffer"));
+        }
+        Ok(ElementBuffer { id, size })
+    }
+
+    /// Create a framebuffer with no attachments.
+    pub fn load_framebuffer(&mut self, _: &RaylibThread) -> Result<Framebuffer, Error> {
+        let id = unsafe { ffi::rlLoadFramebuffer() };
+        if id == 0 {
+            return Err(error!("could not load framebuffer"));
+        }
+        Ok(Framebuffer(id))
+    }
+
+    /// Create a depth texture that can be attached to a [`Framebuffer`] and sampled later.
+    pub fn load_texture_depth(
+        &mut self,
+        _: &RaylibThread,
+        width: i32,
+        height: i32,
+    ) -> Result<crate::core::texture::Texture2D, Error> {
+        let id = unsafe { ffi::rlLoadTextureDepth(width, height, false) };
+        if id == 0 {
+            return Err(error!("could not load depth texture"));
+        }
+        Ok(unsafe {
+            crate::core::texture::Texture2D::from_raw(ffi::Texture2D {
+                id,
+                width,
+                height,
+                mipmaps: 1,
+                // DEPTH_COMPONENT_24BIT, same as LoadRenderTexture
+                format: 19,
+            })
+        })
+    }
+}
@@ -0,0 +1,729 @@
+//! Scene graph with hierarchical transforms, frustum culling and depth sorted transparency.
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//! let (mut rl, thread) = raylib::init().build();
+//! let mut scene = SceneGraph::new();
+//! let ship = scene.add_model(rl.load_model(&thread, "ship.obj").unwrap());
+//! let glow = scene.add_texture(rl.load_texture(&thread, "glow.png").unwrap());
+//!
+//! let body = scene.add_root("body");
+//! scene.attach(body, Attachment::model(ship, Color::WHITE)).unwrap();
+//! let engine = scene.add_child(body, "engine").unwrap();
+//! scene.transform_mut(engine).unwrap().translation = Vector3::new(0.0, 0.0, -2.0);
+//! scene.attach(engine, Attachment::billboard(glow, 1.0)).unwrap();
+//! scene
+//!     .attach(engine, Attachment::light(Light::point(Vector3::zero(), Color::ORANGE)))
+//!     .unwrap();
+//!
+//! let camera = Camera3D::perspective(
+//!     Vector3::new(0.0, 5.0, 10.0),
+//!     Vector3::zero(),
+//!     Vector3::up(),
+//!     45.0,
+//! );
+//! while !rl.window_should_close() {
+//!     let t = rl.get_time() as f32;
+//!     scene.transform_mut(body).unwrap().rotation = Quaternion::from_euler(0.0, t, 0.0);
+//!     let mut d = rl.begin_drawing(&thread);
+//!     d.clear_background(Color::BLACK);
+//!     let mut d3 = d.begin_mode3D(camera);
+//!     scene.draw(&mut d3, &camera);
+//! }
+//! ```
+use crate::core::camera::Camera3D;
+use crate::core::color::Color;
+use crate::core::drawing::RaylibDraw3D;
+use crate::core::lighting::Light;
+use crate::core::math::{BoundingBox, Matrix, Quaternion, Rectangle, Transform, Vector2};
+use crate::core::math::{Vector3, Vector4};
+use crate::core::models::{Model, RaylibModel};
+use crate::core::texture::Texture2D;
+use crate::error::{error, Error};
+use crate::{consts, ffi};
+
+/// Near and far clip distances raylib's `BeginMode3D` uses.
+const CULL_DISTANCE_NEAR: f32 = 0.01;
+const CULL_DISTANCE_FAR: f32 = 1000.0;
+
+/// The six clip planes of a camera, pointing inwards.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Frustum {
+    planes: [Vector4; 6],
+}
+
+impl Frustum {
+    /// Frustum of a combined view and projection matrix (`view * projection`).
+    pub fn from_matrix(view_projection: Matrix) -> Frustum {
+        let m = view_projection;
+        let row = |a: f32, b: f32, c: f32, d: f32| Vector4::new(a, b, c, d);
+        let r0 = row(m.m0, m.m4, m.m8, m.m12);
+        let r1 = row(m.m1, m.m5, m.m9, m.m13);
+        let r2 = row(m.m2, m.m6, m.m10, m.m14);
+        let r3 = row(m.m3, m.m7, m.m11, m.m15);
+        let add = |a: Vector4, b: Vector4| row(a.x + b.x, a.y + b.y, a.z + b.z, a.w + b.w);
+        let sub = |a: Vector4, b: Vector4| row(a.x - b.x, a.y - b.y, a.z - b.z, a.w - b.w);
+        Frustum {
+            planes: [
+                add(r3, r0),
+                sub(r3, r0),
+                add(r3, r1),
+                sub(r3, r1),
+                add(r3, r2),
+                sub(r3, r2),
+            ],
+        }
+    }
+
+    /// Frustum `BeginMode3D` would use for `camera` on a target with the given aspect ratio.
+    pub fn from_camera(camera: &Camera3D, aspect: f32) -> Frustum {
+        let view = Matrix::look_at(camera.position, camera.target, camera.up);
+        let projection = match camera.camera_type() {
+            consts::CameraProjection::CAMERA_ORTHOGRAPHIC => {
+                let top = camera.fovy / 2.0;
+                let right = top * aspect;
+                Matrix::ortho(
+                    -right,
+                    right,
+                    -top,
+                    top,
+                    CULL_DISTANCE_NEAR,
+                    CULL_DISTANCE_FAR,
+                )
+            }
+            _ => Matrix::perspective(
+                camera.fovy.to_radians(),
+                aspect,
+                CULL_DISTANCE_NEAR,
+                CULL_DISTANCE_FAR,
+            ),
+        };
+        Frustum::from_matrix(view * projection)
+    }
+
+    fn distance(plane: &Vector4, p: Vector3) -> f32 {
+        plane.x * p.x + plane.y * p.y + plane.z * p.z + plane.w
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.planes
+            .iter()
+            .all(|p| Frustum::distance(p, point) >= 0.0)
+    }
+
+    /// Whether any part of `bounds` may be inside. Boxes close to a frustum corner can pass
+    /// without being visible.
+    pub fn intersects_box(&self, bounds: &BoundingBox) -> bool {
+        self.planes.iter().all(|p| {
+            let corner = Vector3::new(
+                if p.x >= 0.0 {
+                    bounds.max.x
+                } else {
+                    bounds.min.x
+                },
+                if p.y >= 0.0 {
+                    bounds.max.y
+                } else {
+                    bounds.min.y
+                },
+                if p.z >= 0.0 {
+                    bounds.max.z
+                } else {
+                    bounds.min.z
+                },
+            );
+            Frustum::distance(p, corner) >= 0.0
+        })
+    }
+}
+
+/// Handle to a node of a [`SceneGraph`]. Stale once the node is removed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId {
+    index: u32,
+    generation: u32,
+}
+
+/// Handle to a model owned by a [`SceneGraph`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModelId(usize);
+
+/// Handle to a texture owned by a [`SceneGraph`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TextureId(usize);
+
+/// Something placed at a node. Positions and directions are relative to the node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Attachment {
+    /// Transparent models are drawn after opaque ones, farthest first.
+    Model {
+        model: ModelId,
+        tint: Color,
+        transparent: bool,
+    },
+    /// Camera facing quad at the node origin, scaled by the node's world scale. Always drawn as
+    /// transparent.
+    Billboard {
+        texture: TextureId,
+        /// Part of the texture to draw, the whole texture when `None`.
+        source: Option<Rectangle>,
+        size: Vector2,
+        tint: Color,
+    },
+    Light(Light),
+}
+
+impl Attachment {
+    pub fn model(model: ModelId, tint: Color) -> Attachment {
+        Attachment::Model {
+            model,
+            tint,
+            transparent: false,
+        }
+    }
+
+    pub fn transparent_model(model: ModelId, tint: Color) -> Attachment {
+        Attachment::Model {
+            model,
+            tint,
+            transparent: true,
+        }
+    }
+
+    pub fn billboard(texture: TextureId, size: f32) -> Attachment {
+        Attachment::Billboard {
+            texture,
+            source: None,
+            size: Vector2::new(size, size),
+            tint: Color::WHITE,
+        }
+    }
+
+    pub fn light(light: Light) -> Attachment {
+        Attachment::Light(light)
+    }
+}
+
+/// An attachment that passed culling, in the order [`SceneGraph::draw`] draws them.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DrawItem {
+    pub node: NodeId,
+    /// Index into the node's [`SceneGraph::attachments`].
+    pub attachment: usize,
+    pub transparent: bool,
+    /// Distance from the camera to the node origin.
+    pub distance: f32,
+}
+
+#[derive(Debug)]
+struct Node {
+    name: String,
+    transform: Transform,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    attachments: Vec<Attachment>,
+    visible: bool,
+    world: Matrix,
+    /// Set on nodes whose world matrix is stale. Every descendant of a dirty node is dirty too.
+    dirty: bool,
+}
+
+#[derive(Debug)]
+struct Slot {
+    generation: u32,
+    node: Option<Node>,
+}
+
+/// Tree of nodes with local transforms, owning the models and textures attached to them.
+///
+/// Iteration, culling and drawing follow depth first order: roots in the order they were
+/// added, then children in the order they were added, so the output is deterministic.
+#[derive(Debug, Default)]
+pub struct SceneGraph {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    roots: Vec<NodeId>,
+    models: Vec<(Model, BoundingBox)>,
+    textures: Vec<Texture2D>,
+}
+
+impl SceneGraph {
+    pub fn new() -> SceneGraph {
+        SceneGraph::default()
+    }
+
+    /// Takes ownership of `model`. Its bounding box is cached for culling.
+    pub fn add_model(&mut self, model: Model) -> ModelId {
+        let bounds = model.get_model_bounding_box();
+        self.models.push((model, bounds));
+        ModelId(self.models.len() - 1)
+    }
+
+    pub fn model(&self, model: ModelId) -> &Model {
+        &self.models[model.0].0
+    }
+
+    /// Mutable access to a model. Call [`SceneGraph::update_model_bounds`] after changing its
+    /// meshes.
+    pub fn model_mut(&mut self, model: ModelId) -> &mut Model {
+        &mut self.models[model.0].0
+    }
+
+    /// Recomputes the cached bounding box of `model`.
+    pub fn update_model_bounds(&mut self, model: ModelId) {
+        let (model, bounds) = &mut self.models[model.0];
+        *bounds = model.get_model_bounding_box();
+    }
+
+    pub fn add_texture(&mut self, texture: Texture2D) -> TextureId {
+        self.textures.push(texture);
+        TextureId(self.textures.len() - 1)
+    }
+
+    pub fn texture(&self, texture: TextureId) -> &Texture2D {
+        &self.textures[texture.0]
+    }
+
+    fn node(&self, id: NodeId) -> Option<&Node> {
+        let slot = self.slots.get(id.index as usize)?;
+        if slot.generation == id.generation {
+            slot.node.as_ref()
+        } else {
+            None
+        }
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation == id.generation {
+            slot.node.as_mut()
+        } else {
+            None
+        }
+    }
+
+    fn get(&self, id: NodeId) -> Result<&Node, Error> {
+        self.node(id)
+            .ok_or_else(|| error!("scene node does not exist"))
+    }
+
+    fn get_mut(&mut self, id: NodeId) -> Result<&mut Node, Error> {
+        self.node_mut(id)
+            .ok_or_else(|| error!("scene node does not exist"))
+    }
+
+    fn insert(&mut self, name: &str, parent: Option<NodeId>) -> NodeId {
+        let node = Node {
+            name: name.to_owned(),
+            transform: Transform {
+                translation: Vector3::zero(),
+                rotation: Quaternion::identity(),
+                scale: Vector3::one(),
+            },
+            parent,
+            children: Vec::new(),
+            attachments: Vec::new(),
+            visible: true,
+            world: Matrix::identity(),
+            dirty: true,
+        };
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.node = Some(node);
+                NodeId {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                NodeId {
+                    index: self.slots.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Adds a node with an identity transform at the top level.
+    pub fn add_root(&mut self, name: &str) -> NodeId {
+        let id = self.insert(name, None);
+        self.roots.push(id);
+        id
+    }
+
+    /// Adds a node with an identity transform as the last child of `parent`.
+    pub fn add_child(&mut self, parent: NodeId, name: &str) -> Result<NodeId, Error> {
+        self.get(parent)?;
+        let id = self.insert(name, Some(parent));
+        self.get_mut(parent)?.children.push(id);
+        Ok(id)
+    }
+
+    /// Removes `node` and all of its descendants.
+    pub fn remove(&mut self, node: NodeId) -> Result<(), Error> {
+        match self.get(node)?.parent {
+            Some(parent) => self.get_mut(parent)?.children.retain(|&c| c != node),
+            None => self.roots.retain(|&r| r != node),
+        }
+        let mut stack = vec![node];
+        while let Some(id) = stack.pop() {
+            let slot = &mut self.slots[id.index as usize];
+            if let Some(removed) = slot.node.take() {
+                stack.extend(removed.children);
+            }
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free.push(id.index);
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, node: NodeId) -> bool {
+        self.node(node).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every node in depth first order.
+    pub fn nodes(&self) -> Vec<NodeId> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut stack: Vec<NodeId> = self.roots.iter().rev().copied().collect();
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            if let Some(node) = self.node(id) {
+                stack.extend(node.children.iter().rev());
+            }
+        }
+        order
+    }
+
+    /// First node named `name` in depth first order.
+    pub fn find(&self, name: &str) -> Option<NodeId> {
+        self.nodes()
+            .into_iter()
+            .find(|&id| self.node(id).is_some_and(|n| n.name == name))
+    }
+
+    pub fn name(&self, node: NodeId) -> Option<&str> {
+        self.node(node).map(|n| n.name.as_str())
+    }
+
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.node(node)?.parent
+    }
+
+    pub fn children(&self, node: NodeId) -> &[NodeId] {
+        self.node(node).map_or(&[], |n| &n.children)
+    }
+
+    /// Moves `node` under `parent`, or to the top level for `None`, keeping its local transform.
+    pub fn set_parent(&mut self, node: NodeId, parent: Option<NodeId>) -> Result<(), Error> {
+        let old = self.get(node)?.parent;
+        let mut ancestor = parent;
+        while let Some(a) = ancestor {
+            if a == node {
+                return Err(error!("scene node can't be parented to its own descendant"));
+            }
+            ancestor = self.get(a)?.parent;
+        }
+        match old {
+            Some(old) => self.get_mut(old)?.children.retain(|&c| c != node),
+            None => self.roots.retain(|&r| r != node),
+        }
+        match parent {
+            Some(parent) => self.get_mut(parent)?.children.push(node),
+            None => self.roots.push(node),
+        }
+        self.get_mut(node)?.parent = parent;
+        self.mark_dirty(node);
+        Ok(())
+    }
+
+    /// Transform relative to the parent node.
+    pub fn transform(&self, node: NodeId) -> Option<&Transform> {
+        self.node(node).map(|n| &n.transform)
+    }
+
+    /// Mutable local transform. Marks the node and its descendants for a world matrix update.
+    pub fn transform_mut(&mut self, node: NodeId) -> Option<&mut Transform> {
+        self.node(node)?;
+        self.mark_dirty(node);
+        self.node_mut(node).map(|n| &mut n.transform)
+    }
+
+    pub fn set_transform(&mut self, node: NodeId, transform: Transform) -> Result<(), Error> {
+        self.get(node)?;
+        self.mark_dirty(node);
+        self.get_mut(node)?.transform = transform;
+        Ok(())
+    }
+
+    pub fn is_visible(&self, node: NodeId) -> bool {
+        self.node(node).is_some_and(|n| n.visible)
+    }
+
+    /// Hidden nodes and their descendants are skipped by culling, drawing and [`SceneGraph::lights`].
+    pub fn set_visible(&mut self, node: NodeId, visible: bool) -> Result<(), Error> {
+        self.get_mut(node)?.visible = visible;
+        Ok(())
+    }
+
+    /// Attaches `attachment` to `node` and returns its index in [`SceneGraph::attachments`].
+    pub fn attach(&mut self, node: NodeId, attachment: Attachment) -> Result<usize, Error> {
+        match &attachment {
+            Attachment::Model { model, .. } if model.0 >= self.models.len() => {
+                return Err(error!("scene model does not exist"))
+            }
+            Attachment::Billboard { texture, .. } if texture.0 >= self.textures.len() => {
+                return Err(error!("scene texture does not exist"))
+            }
+            _ => {}
+        }
+        let attachments = &mut self.get_mut(node)?.attachments;
+        attachments.push(attachment);
+        Ok(attachments.len() - 1)
+    }
+
+    /// Removes the attachment at `index`, shifting later ones down.
+    pub fn detach(&mut self, node: NodeId, index: usize) -> Result<Attachment, Error> {
+        let attachments = &mut self.get_mut(node)?.attachments;
+        if index >= attachments.len() {
+            return Err(error!("scene node has no attachment at that index"));
+        }
+        Ok(attachments.remove(index))
+    }
+
+    pub fn attachments(&self, node: NodeId) -> &[Attachment] {
+        self.node(node).map_or(&[], |n| &n.attachments)
+    }
+
+    fn mark_dirty(&mut self, node: NodeId) {
+        let mut stack = vec![node];
+        while let Some(id) = stack.pop() {
+            if let Some(n) = self.node_mut(id) {
+                if !n.dirty {
+                    n.dirty = true;
+                    stack.extend(n.children.iter().copied());
+                }
+            }
+        }
+    }
+
+    /// World matrix of `node`, recomputing it and its stale ancestors.
+    pub fn world_matrix(&mut self, node: NodeId) -> Option<Matrix> {
+        let mut chain = vec![node];
+        let mut parent = self.node(node)?.parent;
+        while let Some(p) = parent {
+            let n = self.node(p)?;
+            if !n.dirty {
+                break;
+            }
+            chain.push(p);
+            parent = n.parent;
+        }
+        for id in chain.into_iter().rev() {
+            self.refresh(id);
+        }
+        self.node(node).map(|n| n.world)
+    }
+
+    /// Recomputes `id`'s world matrix if its parent's is current.
+    fn refresh(&mut self, id: NodeId) {
+        let (parent, dirty) = match self.node(id) {
+            Some(n) => (n.parent, n.dirty),
+            None => return,
+        };
+        if !dirty {
+            return;
+        }
+        let parent_world = parent
+            .and_then(|p| self.node(p))
+            .map_or(Matrix::identity(), |p| p.world);
+        if let Some(n) = self.node_mut(id) {
+            n.world = n.transform.to_matrix() * parent_world;
+            n.dirty = false;
+        }
+    }
+
+    /// Recomputes every stale world matrix.
+    pub fn update(&mut self) {
+        for id in self.nodes() {
+            self.refresh(id);
+        }
+    }
+
+    /// Updates world matrices and returns the visible nodes in depth first order.
+    fn visible_nodes(&mut self) -> Vec<NodeId> {
+        self.update();
+        let mut order = Vec::new();
+        let mut stack: Vec<NodeId> = self.roots.iter().rev().copied().collect();
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.node(id).filter(|n| n.visible) {
+                order.push(id);
+                stack.extend(node.children.iter().rev());
+            }
+        }
+        order
+    }
+
+    /// World space lights of every visible node, in depth first order.
+    pub fn lights(&mut self) -> Vec<(NodeId, Light)> {
+        let mut lights = Vec::new();
+        for id in self.visible_nodes() {
+            let node = &self.slots[id.index as usize].node.as_ref().unwrap();
+            for attachment in &node.attachments {
+                if let Attachment::Light(light) = attachment {
+                    let mut light = *light;
+                    let origin = light.position.transform_with(node.world);
+                    let tip = (light.position + light.direction).transform_with(node.world);
+                    light.position = origin;
+                    light.direction = (tip - origin).normalized();
+                    lights.push((id, light));
+                }
+            }
+        }
+        lights
+    }
+
+    fn world_bounds(bounds: &BoundingBox, world: &Matrix) -> BoundingBox {
+        let mut min = Vector3::new(f32::MAX, f32::MAX, f32::MAX);
+        let mut max = Vector3::new(f32::MIN, f32::MIN, f32::MIN);
+        for i in 0..8 {
+            let corner = Vector3::new(
+                if i & 1 == 0 {
+                    bounds.min.x
+                } else {
+                    bounds.max.x
+                },
+                if i & 2 == 0 {
+                    bounds.min.y
+                } else {
+                    bounds.max.y
+                },
+                if i & 4 == 0 {
+                    bounds.min.z
+                } else {
+                    bounds.max.z
+                },
+            )
+            .transform_with(*world);
+            min = min.min(corner);
+            max = max.max(corner);
+        }
+        BoundingBox { min, max }
+    }
+
+    /// Attachments inside `frustum`, in draw order: opaque models depth first, then
+    /// transparent models and billboards farthest first. Ties keep depth first order.
+    pub fn visible_items(&mut self, camera: &Camera3D, frustum: &Frustum) -> Vec<DrawItem> {
+        let mut opaque = Vec::new();
+        let mut transparent = Vec::new();
+        for id in self.visible_nodes() {
+            let node = self.slots[id.index as usize].node.as_ref().unwrap();
+            let origin = Vector3::zero().transform_with(node.world);
+            let distance = camera.position.distance_to(origin);
+            for (i, attachment) in node.attachments.iter().enumerate() {
+                let (bounds, is_transparent) = match attachment {
+                    Attachment::Model {
+                        model, transparent, ..
+                    } => (
+                        SceneGraph::world_bounds(&self.models[model.0].1, &node.world),
+                        *transparent,
+                    ),
+                    Attachment::Billboard { size, .. } => {
+                        let scale = node.world.decompose().scale;
+                        let r = (size.x * scale.x).max(size.y * scale.y) / 2.0;
+                        let extent = Vector3::new(r, r, r);
+                        (
+                            BoundingBox {
+                                min: origin - extent,
+                                max: origin + extent,
+                            },
+                            true,
+                        )
+                    }
+                    Attachment::Light(_) => continue,
+                };
+                if !frustum.intersects_box(&bounds) {
+                    continue;
+                }
+                let item = DrawItem {
+                    node: id,
+                    attachment: i,
+                    transparent: is_transparent,
+                    distance,
+                };
+                if is_transparent {
+                    transparent.push(item);
+                } else {
+                    opaque.push(item);
+                }
+            }
+        }
+        transparent.sort_by(|a, b| b.distance.total_cmp(&a.distance));
+        opaque.extend(transparent);
+        opaque
+    }
+
+    /// Draws every visible model and billboard. Must be called inside a 3D mode using `camera`.
+    pub fn draw(&mut self, d: &mut impl RaylibDraw3D, camera: &Camera3D) {
+        let frustum = unsafe {
+            Frustum::from_matrix(
+                Matrix::from(ffi::rlGetMatrixModelview())
+                    * Matrix::from(ffi::rlGetMatrixProjection()),
+            )
+        };
+        for item in self.visible_items(camera, &frustum) {
+            let node = self.slots[item.node.index as usize].node.as_ref().unwrap();
+            match &node.attachments[item.attachment] {
+                Attachment::Model { model, tint, .. } => {
+                    let t = node.world.decompose();
+                    let (axis, angle) = t.rotation.to_axis_angle();
+                    d.draw_model_ex(
+                        &self.models[model.0].0,
+                        t.translation,
+                        axis,
+                        angle.to_degrees(),
+                        t.scale,
+                        *tint,
+                    );
+                }
+                Attachment::Billboard {
+                    texture,
+                    source,
+                    size,
+                    tint,
+                } => {
+                    let texture = &self.textures[texture.0];
+                    let t = node.world.decompose();
+                    let source = source.unwrap_or_else(|| {
+                        Rectangle::new(0.0, 0.0, texture.width as f32, texture.height as f32)
+                    });
+                    d.draw_billboard_rec(
+                        *camera,
+                        texture,
+                        source,
+                        t.translation,
+                        Vector2::new(size.x * t.scale.x, size.y * t.scale.y),
+                        *tint,
+                    );
+                }
+                Attachment::Light(_) => {}
+            }
+        }
+    }
+}
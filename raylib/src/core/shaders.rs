@@ -1,14 +1,21 @@
 //! Code for the safe manipulation of shaders
-use thiserror::Error;
-
-use crate::consts::ShaderUniformDataType;
+use crate::callbacks::capture_trace_log;
+use crate::consts::{ShaderUniformDataType, TraceLogLevel};
+use crate::core::color::Color;
 use crate::core::math::Matrix;
 use crate::core::math::{Vector2, Vector3, Vector4};
+use crate::core::texture::{RenderTexture2D, Texture2D, WeakRenderTexture2D, WeakTexture2D};
 use crate::core::{RaylibHandle, RaylibThread};
+use crate::error::{error, Error};
 use crate::ffi;
+use std::borrow::Cow;
 use std::ffi::CString;
+use std::marker::PhantomData;
 use std::os::raw::{c_char, c_void};
 
+#[cfg(feature = "derive")]
+pub use raylib_derive::ShaderUniforms;
+
 fn no_drop<T>(_thing: T) {}
 make_thin_wrapper!(Shader, ffi::Shader, ffi::UnloadShader);
 make_thin_wrapper!(WeakShader, ffi::Shader, no_drop);
@@ -20,38 +27,46 @@ make_thin_wrapper!(WeakShader, ffi::Shader, no_drop);
 
 impl RaylibHandle {
     /// Loads a custom shader and binds default locations.
+    ///
+    /// Fails with raylib's compile/link log if a stage can't be read or compiled, or if the
+    /// program doesn't link. Passing `None` for both stages loads the default shader.
     pub fn load_shader(
         &mut self,
         _: &RaylibThread,
         vs_filename: Option<&str>,
         fs_filename: Option<&str>,
-    ) -> Shader {
+    ) -> Result<Shader, Error> {
         let c_vs_filename = vs_filename.map(|f| CString::new(f).unwrap());
         let c_fs_filename = fs_filename.map(|f| CString::new(f).unwrap());
 
         // Trust me, I have tried ALL the RUST option ergonamics. This is the only way
         // to get this to work without raylib breaking for whatever reason
         // UPDATE FOR 2024 FROM ANOTHER PERSON: Yes this is still true, doing although "for some reason" is likely due to the pointer getting freed too early if you don't do it this way.
-        let shader = match (c_vs_filename, c_fs_filename) {
+        let (shader, log) = capture_trace_log(|| match (c_vs_filename, c_fs_filename) {
             (Some(vs), Some(fs)) => unsafe { Shader(ffi::LoadShader(vs.as_ptr(), fs.as_ptr())) },
             (None, Some(fs)) => unsafe { Shader(ffi::LoadShader(std::ptr::null(), fs.as_ptr())) },
             (Some(vs), None) => unsafe { Shader(ffi::LoadShader(vs.as_ptr(), std::ptr::null())) },
             (None, None) => unsafe { Shader(ffi::LoadShader(std::ptr::null(), std::ptr::null())) },
-        };
+        });
 
-        return shader;
+        let path = fs_filename.or(vs_filename);
+        check_loaded_shader(shader, path.is_some(), path, log)
     }
 
     /// Loads shader from code strings and binds default locations.
+    ///
+    /// Fails with raylib's compile/link log if a stage doesn't compile or the program doesn't
+    /// link. Passing `None` for both stages loads the default shader.
     pub fn load_shader_from_memory(
         &mut self,
         _: &RaylibThread,
         vs_code: Option<&str>,
         fs_code: Option<&str>,
-    ) -> Shader {
+    ) -> Result<Shader, Error> {
         let c_vs_code = vs_code.map(|f| CString::new(f).unwrap());
         let c_fs_code = fs_code.map(|f| CString::new(f).unwrap());
-        return match (c_vs_code, c_fs_code) {
+        let custom = vs_code.is_some() || fs_code.is_some();
+        let (shader, log) = capture_trace_log(|| match (c_vs_code, c_fs_code) {
             (Some(vs), Some(fs)) => unsafe {
                 Shader(ffi::LoadShaderFromMemory(
                     vs.as_ptr() as *mut c_char,
@@ -76,7 +91,9 @@ impl RaylibHandle {
                     std::ptr::null_mut(),
                 ))
             },
-        };
+        });
+
+        check_loaded_shader(shader, custom, None, log)
     }
 
     /// Get default shader. Modifying it modifies everthing that uses that shader
@@ -91,6 +108,34 @@ impl RaylibHandle {
     }
 }
 
+/// raylib falls back on the default shader (or an invalid one) when loading fails, turn that into
+/// an error carrying the warnings it logged. `custom` is false when the default shader was asked
+/// for.
+fn check_loaded_shader(
+    shader: Shader,
+    custom: bool,
+    path: Option<&str>,
+    log: Vec<(TraceLogLevel, String)>,
+) -> Result<Shader, Error> {
+    let fell_back = custom && shader.id == unsafe { ffi::rlGetShaderIdDefault() };
+    if shader.id != 0 && !shader.locs.is_null() && !fell_back {
+        return Ok(shader);
+    }
+
+    let mut message = String::from("could not load shader");
+    for (_, text) in log
+        .iter()
+        .filter(|(level, _)| *level as u32 >= TraceLogLevel::LOG_WARNING as u32)
+    {
+        message.push('\n');
+        message.push_str(text);
+    }
+    Err(match path {
+        Some(path) => error!(Cow::Owned(message), path),
+        None => error!(Cow::Owned(message)),
+    })
+}
+
 pub trait ShaderV {
     const UNIFORM_TYPE: ShaderUniformDataType;
     unsafe fn value(&self) -> *const c_void;
@@ -313,6 +358,346 @@ pub trait RaylibShader: AsRef<ffi::Shader> + AsMut<ffi::Shader> {
     }
 }
 
+/// A value that can be uploaded to a single uniform, see [`ShaderUniforms`].
+pub trait UniformValue {
+    /// Uploads `self` to the uniform at `location`.
+    fn set_uniform(&self, shader: ffi::Shader, location: i32);
+}
+
+macro_rules! impl_uniform_value {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl UniformValue for $t {
+                #[inline]
+                fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+                    unsafe {
+                        ffi::SetShaderValue(
+                            shader,
+                            location,
+                            self.value(),
+                            (<$t as ShaderV>::UNIFORM_TYPE as u32) as i32,
+                        )
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_uniform_value!(
+    f32, i32, Vector2, Vector3, Vector4, [i32; 2], [i32; 3], [i32; 4], [f32; 2], [f32; 3],
+    [f32; 4],
+);
+
+impl UniformValue for bool {
+    #[inline]
+    fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+        (*self as i32).set_uniform(shader, location)
+    }
+}
+
+/// Uploaded as a normalized `vec4`, like `colDiffuse`.
+impl UniformValue for Color {
+    #[inline]
+    fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+        self.color_normalize().set_uniform(shader, location)
+    }
+}
+
+impl UniformValue for Matrix {
+    #[inline]
+    fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+        unsafe { ffi::SetShaderValueMatrix(shader, location, (*self).into()) }
+    }
+}
+
+/// Uniform arrays, `uniform float weights[8]` for example.
+impl<T: ShaderV> UniformValue for [T] {
+    #[inline]
+    fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+        unsafe {
+            ffi::SetShaderValueV(
+                shader,
+                location,
+                self.as_ptr() as *const c_void,
+                (T::UNIFORM_TYPE as u32) as i32,
+                self.len() as i32,
+            )
+        }
+    }
+}
+
+impl<T: ShaderV> UniformValue for Vec<T> {
+    #[inline]
+    fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+        self.as_slice().set_uniform(shader, location)
+    }
+}
+
+macro_rules! impl_uniform_value_texture {
+    ($($t:ty),*) => {
+        $(
+            /// Binds the texture to the sampler, raylib picks the texture slot.
+            impl UniformValue for $t {
+                #[inline]
+                fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+                    unsafe { ffi::SetShaderValueTexture(shader, location, *self.as_ref()) }
+                }
+            }
+        )*
+    };
+}
+
+impl_uniform_value_texture!(
+    Texture2D,
+    WeakTexture2D,
+    RenderTexture2D,
+    WeakRenderTexture2D
+);
+
+impl<T: UniformValue + ?Sized> UniformValue for &T {
+    #[inline]
+    fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+        (**self).set_uniform(shader, location)
+    }
+}
+
+impl<T: UniformValue> UniformValue for Option<T> {
+    /// `None` leaves the uniform untouched.
+    #[inline]
+    fn set_uniform(&self, shader: ffi::Shader, location: i32) {
+        if let Some(value) = self {
+            value.set_uniform(shader, location)
+        }
+    }
+}
+
+/// A struct uploaded to a shader's uniforms in one call, through a [`UniformBlock`].
+///
+/// Usually derived with `#[derive(ShaderUniforms)]` (`derive` feature). Fields are matched to
+/// uniforms by name; `#[uniform(name = "...")]` renames a field and `#[uniform(skip)]` ignores it.
+/// ```ignore
+/// #[derive(ShaderUniforms)]
+/// struct Bloom<'a> {
+///     threshold: f32,
+///     #[uniform(name = "tint")]
+///     color: Color,
+///     blurred: &'a RenderTexture2D,
+/// }
+/// ```
+pub trait ShaderUniforms {
+    /// Uniform names, in the order [`ShaderUniforms::set_uniforms`] expects their locations.
+    const UNIFORM_NAMES: &'static [&'static str];
+
+    /// Uploads every uniform. `locations` holds one location per name, -1 for uniforms the shader
+    /// doesn't have.
+    fn set_uniforms(&self, shader: ffi::Shader, locations: &[i32]);
+}
+
+/// Uniforms raylib sets by itself when drawing.
+const RAYLIB_UNIFORMS: [&str; 10] = [
+    "mvp",
+    "matView",
+    "matProjection",
+    "matModel",
+    "matNormal",
+    "colDiffuse",
+    "texture0",
+    "texture1",
+    "texture2",
+    "boneMatrices",
+];
+
+/// Locations of the uniforms of `U` in a shader, looked up once when the block is created.
+pub struct UniformBlock<U: ShaderUniforms + ?Sized> {
+    shader_id: u32,
+    locations: Vec<i32>,
+    missing: Vec<&'static str>,
+    unset: Vec<String>,
+    _uniforms: PhantomData<fn(&U)>,
+}
+
+impl<U: ShaderUniforms + ?Sized> std::fmt::Debug for UniformBlock<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UniformBlock")
+            .field("shader_id", &self.shader_id)
+            .field("locations", &self.locations)
+            .field("missing", &self.missing)
+            .field("unset", &self.unset)
+            .finish()
+    }
+}
+
+impl<U: ShaderUniforms + ?Sized> UniformBlock<U> {
+    /// Looks up the uniforms of `U` in `shader`, logging a warning for each one it doesn't have.
+    pub fn new(shader: &impl RaylibShader) -> Self {
+        Self::lookup(shader, None)
+    }
+
+    /// Like [`UniformBlock::new`], with the GLSL `sources` of the shader stages to tell uniforms
+    /// the compiler removed because they are unused from unknown ones, and to warn about declared
+    /// uniforms `U` doesn't set.
+    pub fn with_sources(shader: &impl RaylibShader, sources: &[&str]) -> Self {
+        let declared = sources
+            .iter()
+            .flat_map(|source| declared_uniforms(source))
+            .collect::<Vec<_>>();
+        Self::lookup(shader, Some(declared))
+    }
+
+    fn lookup(shader: &impl RaylibShader, declared: Option<Vec<String>>) -> Self {
+        let shader_id = shader.as_ref().id;
+        let locations = U::UNIFORM_NAMES
+            .iter()
+            .map(|name| shader.get_shader_location(name))
+            .collect::<Vec<_>>();
+        let missing = U::UNIFORM_NAMES
+            .iter()
+            .zip(&locations)
+            .filter(|(_, location)| **location < 0)
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+
+        for name in &missing {
+            let is_declared = declared
+                .as_ref()
+                .map(|declared| declared.iter().any(|d| uniform_matches(name, d)));
+            let reason = match is_declared {
+                Some(true) => "is unused and was removed by the GLSL compiler",
+                Some(false) => "is not declared",
+                None => "is unknown or unused",
+            };
+            warn(&format!(
+                "SHADER: [ID {}] Uniform `{}` {}",
+                shader_id, name, reason
+            ));
+        }
+
+        let mut unset = Vec::new();
+        for uniform in declared.iter().flatten() {
+            let is_set = U::UNIFORM_NAMES
+                .iter()
+                .chain(RAYLIB_UNIFORMS.iter())
+                .any(|name| uniform_matches(name, uniform));
+            if !is_set && !unset.contains(uniform) {
+                warn(&format!(
+                    "SHADER: [ID {}] Uniform `{}` is never set",
+                    shader_id, uniform
+                ));
+                unset.push(uniform.clone());
+            }
+        }
+
+        UniformBlock {
+            shader_id,
+            locations,
+            missing,
+            unset,
+            _uniforms: PhantomData,
+        }
+    }
+
+    /// Locations of the uniforms, in the order of [`ShaderUniforms::UNIFORM_NAMES`].
+    pub fn locations(&self) -> &[i32] {
+        &self.locations
+    }
+
+    /// Location of the uniform called `name`, if `U` has it.
+    pub fn location(&self, name: &str) -> Option<i32> {
+        U::UNIFORM_NAMES
+            .iter()
+            .position(|n| *n == name)
+            .map(|i| self.locations[i])
+    }
+
+    /// Uniforms of `U` the shader doesn't have.
+    pub fn missing(&self) -> &[&'static str] {
+        &self.missing
+    }
+
+    /// Uniforms declared in the sources given to [`UniformBlock::with_sources`] that `U` doesn't
+    /// set.
+    pub fn unset(&self) -> &[String] {
+        &self.unset
+    }
+
+    /// Uploads every uniform of `uniforms` to `shader`, which must be the shader the block was
+    /// created for.
+    pub fn upload(&self, shader: &mut impl RaylibShader, uniforms: &U) {
+        let shader = *shader.as_mut();
+        debug_assert_eq!(
+            shader.id, self.shader_id,
+            "uniform block used with another shader"
+        );
+        uniforms.set_uniforms(shader, &self.locations);
+    }
+}
+
+fn warn(text: &str) {
+    let text = CString::new(text).unwrap();
+    unsafe {
+        ffi::TraceLog(
+            (TraceLogLevel::LOG_WARNING as u32) as i32,
+            b"%s\0".as_ptr() as *const c_char,
+            text.as_ptr(),
+        )
+    }
+}
+
+/// Whether setting `name` sets (part of) the declared uniform `declared`, `lights[0].color` sets
+/// `lights` for example.
+fn uniform_matches(name: &str, declared: &str) -> bool {
+    name.strip_prefix(declared)
+        .map(|rest| rest.is_empty() || rest.starts_with('[') || rest.starts_with('.'))
+        .unwrap_or(false)
+}
+
+fn strip_glsl_comments(source: &str) -> String {
+    let mut code = String::with_capacity(source.len());
+    let mut rest = source;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.find('\n').map(|i| &after[i..]).unwrap_or("");
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.find("*/").map(|i| &after[i + 2..]).unwrap_or("");
+            code.push(' ');
+        } else {
+            let c = rest.chars().next().unwrap();
+            code.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    code
+}
+
+/// Names of the uniforms declared in a GLSL source, uniform blocks excluded.
+pub(crate) fn declared_uniforms(source: &str) -> Vec<String> {
+    let code = strip_glsl_comments(source);
+    let mut names = Vec::new();
+    for statement in code.split(';') {
+        let mut tokens = statement.split_whitespace();
+        if !tokens.any(|t| t == "uniform") || statement.contains('{') {
+            continue;
+        }
+        let declaration = tokens
+            .filter(|t| !matches!(*t, "lowp" | "mediump" | "highp"))
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join(" ");
+        for declarator in declaration.split(',') {
+            let name = declarator
+                .trim()
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .next()
+                .unwrap_or("");
+            if !name.is_empty() {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
 impl RaylibHandle {
     /// Sets a custom projection matrix (replaces internal projection matrix).
     #[inline]
@@ -0,0 +1,637 @@
+//! Frame based sprite animation.
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//!
+//! let (mut rl, thread) = raylib::init().build();
+//! let (texture, mut anim) = SpriteAnimation::load_gif(&mut rl, &thread, "explosion.gif", 0.05).unwrap();
+//!
+//! while !rl.window_should_close() {
+//!     for event in anim.update(rl.get_frame_time()) {
+//!         if let SpriteEvent::Finished { .. } = event {
+//!             println!("boom");
+//!         }
+//!     }
+//!     let mut d = rl.begin_drawing(&thread);
+//!     d.clear_background(Color::WHITE);
+//!     anim.draw(&mut d, &texture, Vector2::new(100.0, 100.0), Color::WHITE);
+//! }
+//! ```
+use std::ops::Range;
+#[cfg(feature = "with_serde")]
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::core::color::Color;
+use crate::core::drawing::RaylibDraw;
+use crate::core::math::{Rectangle, Vector2};
+use crate::core::texture::{Image, Texture2D};
+use crate::core::{RaylibHandle, RaylibThread};
+use crate::ease::Tween;
+use crate::error::{error, Error};
+use crate::ffi;
+
+/// How a clip continues after its last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    /// Starts over from the first frame.
+    Loop,
+    /// Plays backwards to the first frame, then forwards again.
+    PingPong,
+    /// Stops on the last frame.
+    Once,
+}
+
+/// A single frame of a clip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteFrame {
+    /// Source rectangle in the sprite sheet.
+    pub source: Rectangle,
+    /// Time the frame is shown, in seconds.
+    pub duration: f32,
+}
+
+/// A named sequence of frames.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteClip {
+    name: Arc<str>,
+    pub frames: Vec<SpriteFrame>,
+    pub mode: PlayMode,
+}
+
+impl SpriteClip {
+    pub fn new(name: &str, mode: PlayMode) -> Self {
+        SpriteClip {
+            name: name.into(),
+            frames: Vec::new(),
+            mode,
+        }
+    }
+
+    /// Frames laid out left to right, top to bottom in a grid with `columns` cells per row, all
+    /// shown for `duration`. `frames` counts cells the same way.
+    pub fn from_grid(
+        name: &str,
+        mode: PlayMode,
+        columns: usize,
+        frame_size: Vector2,
+        frames: Range<usize>,
+        duration: f32,
+    ) -> Self {
+        let columns = columns.max(1);
+        let mut clip = SpriteClip::new(name, mode);
+        for i in frames {
+            clip.push_frame(
+                Rectangle::new(
+                    (i % columns) as f32 * frame_size.x,
+                    (i / columns) as f32 * frame_size.y,
+                    frame_size.x,
+                    frame_size.y,
+                ),
+                duration,
+            );
+        }
+        clip
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn push_frame(&mut self, source: Rectangle, duration: f32) -> &mut Self {
+        self.frames.push(SpriteFrame { source, duration });
+        self
+    }
+
+    /// Length of one pass through the clip, in seconds. Ping-pong clips count both directions.
+    pub fn duration(&self) -> f32 {
+        self.sequence().map(|i| self.frames[i].duration).sum()
+    }
+
+    /// Frame indices of one pass through the clip.
+    fn sequence(&self) -> impl Iterator<Item = usize> {
+        let n = self.frames.len();
+        let back = match self.mode {
+            PlayMode::PingPong if n > 2 => n - 2,
+            _ => 0,
+        };
+        (0..n).chain((1..=back).rev())
+    }
+
+    fn sequence_len(&self) -> usize {
+        match self.mode {
+            PlayMode::PingPong if self.frames.len() > 2 => 2 * self.frames.len() - 2,
+            _ => self.frames.len(),
+        }
+    }
+
+    fn sequence_frame(&self, step: usize) -> usize {
+        let n = self.frames.len();
+        if step < n {
+            step
+        } else {
+            2 * n - 2 - step
+        }
+    }
+}
+
+/// Emitted by [`SpriteAnimation::update`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpriteEvent {
+    /// A frame started showing.
+    Frame { clip: Arc<str>, frame: usize },
+    /// A [`PlayMode::Once`] clip reached the end of its last frame.
+    Finished { clip: Arc<str> },
+}
+
+/// Plays [`SpriteClip`]s and tracks the current frame.
+#[derive(Debug, Clone)]
+pub struct SpriteAnimation {
+    clips: Vec<SpriteClip>,
+    current: Option<usize>,
+    step: usize,
+    frame_time: f32,
+    playing: bool,
+    finished: bool,
+    started: bool,
+    speed: f32,
+    speed_tween: Option<Tween>,
+    flip_x: bool,
+    flip_y: bool,
+    events: Vec<SpriteEvent>,
+}
+
+impl Default for SpriteAnimation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpriteAnimation {
+    pub fn new() -> Self {
+        SpriteAnimation {
+            clips: Vec::new(),
+            current: None,
+            step: 0,
+            frame_time: 0.0,
+            playing: false,
+            finished: false,
+            started: false,
+            speed: 1.0,
+            speed_tween: None,
+            flip_x: false,
+            flip_y: false,
+            events: Vec::new(),
+        }
+    }
+
+    /// Adds a clip, replacing any clip with the same name. The first clip added starts playing.
+    pub fn add_clip(&mut self, clip: SpriteClip) -> &mut Self {
+        match self.clips.iter().position(|c| c.name == clip.name) {
+            Some(i) => self.clips[i] = clip,
+            None => self.clips.push(clip),
+        }
+        if self.current.is_none() {
+            self.play_index(0);
+        }
+        self
+    }
+
+    pub fn clip(&self, name: &str) -> Option<&SpriteClip> {
+        self.clips.iter().find(|c| &*c.name == name)
+    }
+
+    pub fn clips(&self) -> &[SpriteClip] {
+        &self.clips
+    }
+
+    /// Switches to the clip called `name`, from its first frame. Does nothing if that clip is
+    /// already playing. Returns `false` if there is no such clip.
+    pub fn play(&mut self, name: &str) -> bool {
+        match self.clips.iter().position(|c| &*c.name == name) {
+            Some(i) => {
+                if self.current != Some(i) || self.finished {
+                    self.play_index(i);
+                }
+                self.playing = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Plays the current clip again from its first frame.
+    pub fn restart(&mut self) {
+        if let Some(i) = self.current {
+            self.play_index(i);
+        }
+    }
+
+    fn play_index(&mut self, i: usize) {
+        self.current = Some(i);
+        self.step = 0;
+        self.frame_time = 0.0;
+        self.playing = true;
+        self.finished = false;
+        self.started = false;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn resume(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing && !self.finished
+    }
+
+    /// Whether a [`PlayMode::Once`] clip has reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Playback rate, 1.0 is normal speed.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+        self.speed_tween = None;
+    }
+
+    /// Drives the playback rate with a tween, e.g. to slow an animation down smoothly.
+    /// The tween advances with the real (unscaled) frame time.
+    pub fn tween_speed(&mut self, tween: Tween) {
+        self.speed_tween = Some(tween);
+    }
+
+    /// Mirrors the drawn frame.
+    pub fn set_flip(&mut self, flip_x: bool, flip_y: bool) {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+    }
+
+    pub fn current_clip(&self) -> Option<&SpriteClip> {
+        self.current.map(|i| &self.clips[i])
+    }
+
+    /// Index of the frame being shown in the current clip.
+    pub fn current_frame(&self) -> usize {
+        self.current_clip()
+            .filter(|c| !c.frames.is_empty())
+            .map_or(0, |c| c.sequence_frame(self.step))
+    }
+
+    /// Source rectangle of the frame being shown, flipped as set by [`SpriteAnimation::set_flip`].
+    pub fn source_rec(&self) -> Option<Rectangle> {
+        let clip = self.current_clip()?;
+        let mut rec = clip.frames.get(self.current_frame())?.source;
+        if self.flip_x {
+            rec.width = -rec.width;
+        }
+        if self.flip_y {
+            rec.height = -rec.height;
+        }
+        Some(rec)
+    }
+
+    /// Advances the animation by `dt` seconds and returns the events that happened.
+    pub fn update(&mut self, dt: f32) -> &[SpriteEvent] {
+        self.events.clear();
+        if let Some(tween) = &mut self.speed_tween {
+            self.speed = tween.apply(dt);
+            if tween.has_completed() {
+                self.speed_tween = None;
+            }
+        }
+        let clip = match self.current {
+            Some(i) if !self.clips[i].frames.is_empty() => &self.clips[i],
+            _ => return &self.events,
+        };
+        if !self.started {
+            self.started = true;
+            self.events.push(SpriteEvent::Frame {
+                clip: clip.name.clone(),
+                frame: 0,
+            });
+        }
+        if !self.playing || self.finished {
+            return &self.events;
+        }
+
+        let cycle = clip.duration();
+        if cycle <= 0.0 {
+            return &self.events;
+        }
+        let mut dt = (dt * self.speed).max(0.0);
+        if clip.mode != PlayMode::Once && dt > cycle {
+            // Whole passes don't change where we end up, but keep one so its frames are reported
+            dt = cycle + dt % cycle;
+        }
+        let len = clip.sequence_len();
+        loop {
+            let duration = clip.frames[clip.sequence_frame(self.step)].duration;
+            if self.frame_time + dt < duration {
+                self.frame_time += dt;
+                break;
+            }
+            dt -= duration - self.frame_time;
+            self.frame_time = 0.0;
+            if self.step + 1 == len {
+                if clip.mode == PlayMode::Once {
+                    self.frame_time = duration;
+                    self.finished = true;
+                    self.events.push(SpriteEvent::Finished {
+                        clip: clip.name.clone(),
+                    });
+                    break;
+                }
+                self.step = 0;
+            } else {
+                self.step += 1;
+            }
+            self.events.push(SpriteEvent::Frame {
+                clip: clip.name.clone(),
+                frame: clip.sequence_frame(self.step),
+            });
+        }
+        &self.events
+    }
+
+    /// Draws the current frame at `position`, unscaled.
+    pub fn draw(
+        &self,
+        d: &mut impl RaylibDraw,
+        texture: &Texture2D,
+        position: impl Into<Vector2>,
+        tint: impl Into<ffi::Color>,
+    ) {
+        if let Some(src) = self.source_rec() {
+            let position = position.into();
+            let dest = Rectangle::new(position.x, position.y, src.width.abs(), src.height.abs());
+            d.draw_texture_pro(texture, src, dest, Vector2::zero(), 0.0, tint);
+        }
+    }
+
+    /// Draws the current frame into `dest`, rotated by `rotation` degrees around `origin`
+    /// (relative to `dest`), like `draw_texture_pro`.
+    pub fn draw_pro(
+        &self,
+        d: &mut impl RaylibDraw,
+        texture: &Texture2D,
+        dest: impl Into<ffi::Rectangle>,
+        origin: impl Into<ffi::Vector2>,
+        rotation: f32,
+        tint: impl Into<ffi::Color>,
+    ) {
+        if let Some(src) = self.source_rec() {
+            d.draw_texture_pro(texture, src, dest, origin, rotation, tint);
+        }
+    }
+
+    /// Loads an animated GIF into a sprite sheet image with a single looping clip called
+    /// `"default"`. raylib doesn't keep GIF frame delays, so every frame lasts `frame_duration`.
+    pub fn load_gif_sheet(
+        filename: &str,
+        frame_duration: f32,
+    ) -> Result<(Image, SpriteAnimation), Error> {
+        let mut count = 0;
+        let anim = Image::load_image_anim(filename, &mut count);
+        if anim.0.data.is_null() || count <= 0 {
+            return Err(error!("Could not load animated image", filename));
+        }
+        let (w, h) = (anim.width(), anim.height());
+        let columns = (count as f32).sqrt().ceil() as i32;
+        let rows = (count + columns - 1) / columns;
+
+        // Frames are stored one after another, which is the same layout as a vertical strip
+        let strip = ffi::Image {
+            height: h * count,
+            ..anim.0
+        };
+        let mut sheet = Image::gen_image_color(w * columns, h * rows, Color::BLANK);
+        let mut clip = SpriteClip::new("default", PlayMode::Loop);
+        for i in 0..count {
+            let dst = Rectangle::new(
+                ((i % columns) * w) as f32,
+                ((i / columns) * h) as f32,
+                w as f32,
+                h as f32,
+            );
+            let src = Rectangle::new(0.0, (i * h) as f32, w as f32, h as f32);
+            unsafe {
+                ffi::ImageDraw(
+                    &mut sheet.0,
+                    strip,
+                    src.into(),
+                    dst.into(),
+                    Color::WHITE.into(),
+                )
+            };
+            clip.push_frame(dst, frame_duration);
+        }
+        let mut animation = SpriteAnimation::new();
+        animation.add_clip(clip);
+        Ok((sheet, animation))
+    }
+
+    /// [`SpriteAnimation::load_gif_sheet`], uploaded to a texture.
+    pub fn load_gif(
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        filename: &str,
+        frame_duration: f32,
+    ) -> Result<(Texture2D, SpriteAnimation), Error> {
+        let (sheet, animation) = Self::load_gif_sheet(filename, frame_duration)?;
+        Ok((rl.load_texture_from_image(thread, &sheet)?, animation))
+    }
+
+    /// Reads clips from an Aseprite JSON export (hash or array). Every frame tag becomes a clip,
+    /// or all frames form a looping `"default"` clip if there are no tags. Tags with a repeat
+    /// count of 1 play once, others loop.
+    #[cfg(feature = "with_serde")]
+    pub fn from_aseprite_json(json: &str) -> Result<Self, Error> {
+        Ok(aseprite::parse(json)?.0)
+    }
+
+    /// Loads an Aseprite JSON export and the sheet image named in its `meta.image`, relative to
+    /// the JSON file.
+    #[cfg(feature = "with_serde")]
+    pub fn load_aseprite(
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        json_path: impl AsRef<Path>,
+    ) -> Result<(Texture2D, SpriteAnimation), Error> {
+        let json_path = json_path.as_ref();
+        let json = std::fs::read_to_string(json_path)
+            .map_err(|_| error!("Could not read Aseprite export", json_path))?;
+        let (animation, image) = aseprite::parse(&json)?;
+        let image = image.ok_or_else(|| error!("Aseprite export has no meta.image", json_path))?;
+        let image_path = json_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(image);
+        let texture = rl.load_texture(
+            thread,
+            image_path
+                .to_str()
+                .ok_or_else(|| error!("Invalid sheet image path", &image_path))?,
+        )?;
+        Ok((texture, animation))
+    }
+}
+
+#[cfg(feature = "with_serde")]
+mod aseprite {
+    use super::*;
+    use serde::de::{Deserializer, MapAccess, Visitor};
+    use serde::Deserialize;
+    use std::fmt;
+
+    #[derive(Deserialize)]
+    struct Export {
+        frames: Frames,
+        #[serde(default)]
+        meta: Meta,
+    }
+
+    /// Hash exports are in frame order, so keep the map order instead of sorting by name.
+    enum Frames {
+        Hash(Vec<Frame>),
+        Array(Vec<Frame>),
+    }
+
+    impl<'de> Deserialize<'de> for Frames {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct FramesVisitor;
+
+            impl<'de> Visitor<'de> for FramesVisitor {
+                type Value = Frames;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("a frame array or map")
+                }
+
+                fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                    self,
+                    mut seq: A,
+                ) -> Result<Frames, A::Error> {
+                    let mut frames = Vec::new();
+                    while let Some(frame) = seq.next_element()? {
+                        frames.push(frame);
+                    }
+                    Ok(Frames::Array(frames))
+                }
+
+                fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Frames, A::Error> {
+                    let mut frames = Vec::new();
+                    while let Some((_, frame)) = map.next_entry::<String, Frame>()? {
+                        frames.push(frame);
+                    }
+                    Ok(Frames::Hash(frames))
+                }
+            }
+
+            deserializer.deserialize_any(FramesVisitor)
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Frame {
+        frame: Rect,
+        #[serde(default)]
+        rotated: bool,
+        /// Milliseconds
+        #[serde(default = "default_duration")]
+        duration: f32,
+    }
+
+    fn default_duration() -> f32 {
+        100.0
+    }
+
+    #[derive(Deserialize)]
+    struct Rect {
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+    }
+
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Meta {
+        #[serde(default)]
+        image: Option<String>,
+        #[serde(default)]
+        frame_tags: Vec<Tag>,
+    }
+
+    #[derive(Deserialize)]
+    struct Tag {
+        name: String,
+        from: usize,
+        to: usize,
+        #[serde(default)]
+        direction: Option<String>,
+        #[serde(default)]
+        repeat: Option<String>,
+    }
+
+    pub(super) fn parse(json: &str) -> Result<(SpriteAnimation, Option<String>), Error> {
+        let export: Export = serde_json::from_str(json).map_err(|e| {
+            error!(std::borrow::Cow::Owned(format!(
+                "Could not parse Aseprite export: {}",
+                e
+            )))
+        })?;
+        let frames = match export.frames {
+            Frames::Hash(f) | Frames::Array(f) => f,
+        };
+        if frames.iter().any(|f| f.rotated) {
+            return Err(error!("Rotated frames are not supported"));
+        }
+        let frames: Vec<SpriteFrame> = frames
+            .into_iter()
+            .map(|f| SpriteFrame {
+                source: Rectangle::new(f.frame.x, f.frame.y, f.frame.w, f.frame.h),
+                duration: f.duration / 1000.0,
+            })
+            .collect();
+
+        let mut animation = SpriteAnimation::new();
+        if export.meta.frame_tags.is_empty() {
+            let mut clip = SpriteClip::new("default", PlayMode::Loop);
+            clip.frames = frames;
+            animation.add_clip(clip);
+            return Ok((animation, export.meta.image));
+        }
+        for tag in export.meta.frame_tags {
+            if tag.from > tag.to || tag.to >= frames.len() {
+                return Err(error!(std::borrow::Cow::Owned(format!(
+                    "Tag `{}` references frames outside the export",
+                    tag.name
+                ))));
+            }
+            let direction = tag.direction.as_deref().unwrap_or("forward");
+            let mode = if tag.repeat.as_deref() == Some("1") {
+                PlayMode::Once
+            } else if direction.starts_with("pingpong") {
+                PlayMode::PingPong
+            } else {
+                PlayMode::Loop
+            };
+            let mut clip = SpriteClip::new(&tag.name, mode);
+            clip.frames = frames[tag.from..=tag.to].to_vec();
+            if direction == "reverse" || direction == "pingpong_reverse" {
+                clip.frames.reverse();
+            }
+            animation.add_clip(clip);
+        }
+        Ok((animation, export.meta.image))
+    }
+}
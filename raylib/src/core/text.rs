@@ -13,6 +13,15 @@ use std::convert::{AsMut, AsRef};
 use std::ffi::{CString, OsString};
 use std::mem::ManuallyDrop;
 use std::ops::Deref;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+mod dynamic;
+mod sdf;
+pub use dynamic::*;
+pub use sdf::*;
+
+/// Mirrors raylib's `textLineSpacing`, which has no getter. Starts at raylib's default of 2.
+static TEXT_LINE_SPACING: AtomicI32 = AtomicI32::new(2);
 
 fn no_drop<T>(_thing: T) {}
 make_thin_wrapper!(Font, ffi::Font, ffi::UnloadFont);
@@ -434,6 +443,16 @@ impl RaylibHandle {
     }
 
     pub fn set_text_line_spacing(&self, spacing: i32) {
+        TEXT_LINE_SPACING.store(spacing, Ordering::Relaxed);
         unsafe { ffi::SetTextLineSpacing(spacing) }
     }
+
+    /// Get the line spacing last set with `set_text_line_spacing`.
+    pub fn get_text_line_spacing(&self) -> i32 {
+        text_line_spacing()
+    }
+}
+
+pub(crate) fn text_line_spacing() -> i32 {
+    TEXT_LINE_SPACING.load(Ordering::Relaxed)
 }
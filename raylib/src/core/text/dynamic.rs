@@ -0,0 +1,510 @@
+//! Fonts that rasterize glyphs the first time they are drawn
+use crate::core::file::load_file_data;
+use crate::core::math::Vector2;
+use crate::core::text::{RaylibFont, WeakFont};
+use crate::core::texture::Texture2D;
+use crate::core::{RaylibHandle, RaylibThread};
+use crate::error::{error, Error};
+use crate::ffi;
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Blank border kept around every glyph so filtering doesn't bleed between cells.
+const PADDING: i32 = 2;
+
+/// A rasterized glyph waiting to be copied into a page.
+struct Raster {
+    info: ffi::GlyphInfo,
+    width: i32,
+    height: i32,
+    pixels: Vec<u8>,
+}
+
+#[derive(Clone, Copy)]
+struct Cell {
+    codepoint: Option<char>,
+    last_used: u64,
+}
+
+/// One atlas texture, exposed to raylib as a `Font` holding whichever glyphs are resident.
+struct GlyphPage {
+    font: WeakFont,
+    // Owns `font.texture`.
+    _texture: Texture2D,
+    glyphs: Box<[ffi::GlyphInfo]>,
+    recs: Box<[ffi::Rectangle]>,
+    cells: Vec<Cell>,
+    index: HashMap<char, usize>,
+}
+
+impl GlyphPage {
+    fn free_cells(&self) -> usize {
+        self.cells.iter().filter(|c| c.codepoint.is_none()).count()
+    }
+}
+
+/// A TTF/OTF font with a chain of fallbacks, rasterized on demand into a few atlas pages.
+///
+/// Unlike `load_font_ex`, nothing is baked up front: `font_for` rasterizes the glyphs a string
+/// needs, evicting the least recently used ones when the pages are full, and returns a font that
+/// works with `draw_text_ex`, `measure_text` and `TextLayout`.
+///
+/// ```no_run
+/// use raylib::prelude::*;
+/// let (mut rl, thread) = raylib::init().build();
+/// let mut font = rl
+///     .load_dynamic_font(&thread, "NotoSansJP-Regular.otf", 32)
+///     .unwrap();
+/// font.add_fallback_file("NotoSansSymbols2-Regular.ttf").unwrap();
+///
+/// let line = "こんにちは、世界 ♞";
+/// let mut d = rl.begin_drawing(&thread);
+/// let page = font.font_for(&thread, line).unwrap();
+/// d.draw_text_ex(page, line, Vector2::new(20.0, 20.0), 32.0, 1.0, Color::BLACK);
+/// ```
+pub struct DynamicFont {
+    sources: Vec<Vec<u8>>,
+    base_size: i32,
+    page_size: i32,
+    max_pages: usize,
+    pages: Vec<GlyphPage>,
+    /// Which source provides a codepoint, `None` when none of them do.
+    resolved: HashMap<char, Option<usize>>,
+    tick: u64,
+}
+
+impl fmt::Debug for DynamicFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicFont")
+            .field("sources", &self.sources.len())
+            .field("base_size", &self.base_size)
+            .field("page_size", &self.page_size)
+            .field("max_pages", &self.max_pages)
+            .field("pages", &self.pages.len())
+            .field("glyphs", &self.glyph_count())
+            .finish()
+    }
+}
+
+impl RaylibHandle {
+    /// Loads a TTF/OTF font whose glyphs are rasterized at `font_size` when first used.
+    pub fn load_dynamic_font(
+        &mut self,
+        thread: &RaylibThread,
+        filename: &str,
+        font_size: i32,
+    ) -> Result<DynamicFont, Error> {
+        let data = load_file_data(filename)?;
+        self.load_dynamic_font_from_memory(thread, data, font_size)
+    }
+
+    /// Loads a dynamic font from TTF/OTF data in memory.
+    pub fn load_dynamic_font_from_memory(
+        &mut self,
+        _: &RaylibThread,
+        data: impl Into<Vec<u8>>,
+        font_size: i32,
+    ) -> Result<DynamicFont, Error> {
+        if font_size <= 0 {
+            return Err(error!("Dynamic font size must be positive"));
+        }
+        let data = data.into();
+        check_font_data(&data)?;
+        Ok(DynamicFont {
+            sources: vec![data],
+            base_size: font_size,
+            page_size: 1024,
+            max_pages: 4,
+            pages: Vec::new(),
+            resolved: HashMap::new(),
+            tick: 0,
+        })
+    }
+}
+
+impl DynamicFont {
+    /// Width and height of each atlas page. Defaults to 1024. Drops cached glyphs.
+    pub fn page_size(mut self, page_size: i32) -> Self {
+        self.page_size = page_size;
+        self.clear();
+        self
+    }
+
+    /// How many pages may be created before glyphs are evicted. Defaults to 4.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages.max(1);
+        self.pages.truncate(self.max_pages);
+        self
+    }
+
+    /// Use `data` for codepoints missing from every font added before it.
+    pub fn add_fallback(&mut self, data: impl Into<Vec<u8>>) -> Result<&mut Self, Error> {
+        let data = data.into();
+        check_font_data(&data)?;
+        self.sources.push(data);
+        self.resolved.retain(|_, source| source.is_some());
+        Ok(self)
+    }
+
+    /// Loads a fallback font from a file, see `add_fallback`.
+    pub fn add_fallback_file(&mut self, filename: &str) -> Result<&mut Self, Error> {
+        let data = load_file_data(filename)?;
+        self.add_fallback(data)
+    }
+
+    /// Size glyphs are rasterized at.
+    pub fn base_size(&self) -> i32 {
+        self.base_size
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Number of glyphs currently resident, counting copies on different pages.
+    pub fn glyph_count(&self) -> usize {
+        self.pages.iter().map(|p| p.index.len()).sum()
+    }
+
+    /// Glyphs a single page can hold.
+    pub fn glyphs_per_page(&self) -> usize {
+        let columns = (self.page_size / self.cell_size()).max(0) as usize;
+        columns * columns
+    }
+
+    /// Unloads every page. Glyphs are rasterized again when next used.
+    pub fn clear(&mut self) {
+        self.pages.clear();
+    }
+
+    fn cell_size(&self) -> i32 {
+        // Leave room for glyphs that overhang the nominal size, like accents and emoji.
+        (self.base_size as f32 * 1.25).ceil() as i32 + 2 * PADDING
+    }
+
+    /// Makes every glyph of `text` resident on one page and returns that page's font.
+    ///
+    /// Codepoints that no font in the chain provides are drawn as `'?'`, like raylib does.
+    /// Fails if `text` uses more distinct glyphs than fit on a page.
+    pub fn font_for(&mut self, _: &RaylibThread, text: &str) -> Result<&WeakFont, Error> {
+        self.tick += 1;
+        let mut wanted: Vec<char> = text.chars().filter(|c| *c != '\n' && *c != '\r').collect();
+        wanted.sort_unstable();
+        wanted.dedup();
+
+        let mut rasters = self.resolve(&wanted);
+        if wanted.iter().any(|c| self.resolved[c].is_none()) {
+            wanted.retain(|c| self.resolved[c].is_some());
+            if !wanted.contains(&'?') {
+                rasters.extend(self.resolve(&['?']));
+                if self.resolved[&'?'].is_some() {
+                    wanted.push('?');
+                }
+            }
+        }
+        if wanted.len() > self.glyphs_per_page() {
+            return Err(error!(std::borrow::Cow::Owned(format!(
+                "text needs {} glyphs but a page of the dynamic font holds {}",
+                wanted.len(),
+                self.glyphs_per_page()
+            ))));
+        }
+
+        let page = self.choose_page(&wanted)?;
+        let tick = self.tick;
+        let missing: Vec<char> = {
+            let page = &mut self.pages[page];
+            for c in wanted.iter() {
+                if let Some(&cell) = page.index.get(c) {
+                    page.cells[cell].last_used = tick;
+                }
+            }
+            wanted
+                .iter()
+                .copied()
+                .filter(|c| !page.index.contains_key(c))
+                .collect()
+        };
+
+        // Glyphs cached on other pages have to be rasterized again for this one.
+        let mut by_source: HashMap<usize, Vec<char>> = HashMap::new();
+        for c in missing.iter().filter(|c| !rasters.contains_key(c)) {
+            if let Some(source) = self.resolved[c] {
+                by_source.entry(source).or_default().push(*c);
+            }
+        }
+        for (source, chars) in by_source {
+            let glyphs = rasterize(&self.sources[source], self.base_size, &chars);
+            for (c, raster) in chars.into_iter().zip(glyphs) {
+                if let Some(raster) = raster {
+                    rasters.insert(c, raster);
+                }
+            }
+        }
+
+        for c in missing {
+            if let Some(raster) = rasters.get(&c) {
+                self.store(page, c, raster);
+            }
+        }
+        Ok(&self.pages[page].font)
+    }
+
+    /// Measures `text` like `RaylibFont::measure_text`, rasterizing its glyphs if needed.
+    pub fn measure_text(
+        &mut self,
+        thread: &RaylibThread,
+        text: &str,
+        font_size: f32,
+        spacing: f32,
+    ) -> Result<Vector2, Error> {
+        Ok(self
+            .font_for(thread, text)?
+            .measure_text(text, font_size, spacing))
+    }
+
+    /// Finds the source of every unresolved codepoint, returning the glyphs rasterized on the way.
+    fn resolve(&mut self, chars: &[char]) -> HashMap<char, Raster> {
+        let mut rasters = HashMap::new();
+        let mut pending: Vec<char> = chars
+            .iter()
+            .copied()
+            .filter(|c| !self.resolved.contains_key(c))
+            .collect();
+        for (source, data) in self.sources.iter().enumerate() {
+            if pending.is_empty() {
+                break;
+            }
+            let glyphs = rasterize(data, self.base_size, &pending);
+            let mut unresolved = Vec::new();
+            for (c, raster) in pending.into_iter().zip(glyphs) {
+                match raster {
+                    Some(raster) => {
+                        self.resolved.insert(c, Some(source));
+                        rasters.insert(c, raster);
+                    }
+                    None => unresolved.push(c),
+                }
+            }
+            pending = unresolved;
+        }
+        for c in pending {
+            self.resolved.insert(c, None);
+        }
+        rasters
+    }
+
+    /// Picks the page holding most of `wanted`, adding a page rather than evicting while allowed.
+    fn choose_page(&mut self, wanted: &[char]) -> Result<usize, Error> {
+        let best = self
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(i, page)| {
+                let hits = wanted.iter().filter(|c| page.index.contains_key(c)).count();
+                (hits, page.free_cells(), i)
+            })
+            .max();
+        if let Some((hits, free, i)) = best {
+            if hits + free >= wanted.len() || self.pages.len() >= self.max_pages {
+                return Ok(i);
+            }
+        }
+        let page = self.new_page()?;
+        self.pages.push(page);
+        Ok(self.pages.len() - 1)
+    }
+
+    fn new_page(&self) -> Result<GlyphPage, Error> {
+        let count = self.glyphs_per_page();
+        let mut glyphs: Box<[ffi::GlyphInfo]> =
+            vec![unsafe { std::mem::zeroed::<ffi::GlyphInfo>() }; count].into_boxed_slice();
+        let mut recs: Box<[ffi::Rectangle]> = vec![
+            ffi::Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 0.0,
+                height: 0.0,
+            };
+            count
+        ]
+        .into_boxed_slice();
+        let texture = unsafe {
+            let mut image = ffi::GenImageColor(
+                self.page_size,
+                self.page_size,
+                ffi::Color {
+                    r: 0,
+                    g: 0,
+                    b: 0,
+                    a: 0,
+                },
+            );
+            ffi::ImageFormat(
+                &mut image,
+                ffi::PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA as i32,
+            );
+            let texture = ffi::LoadTextureFromImage(image);
+            ffi::UnloadImage(image);
+            texture
+        };
+        if texture.id == 0 {
+            return Err(error!("Could not create a dynamic font page"));
+        }
+        let font = ffi::Font {
+            baseSize: self.base_size,
+            glyphCount: count as i32,
+            glyphPadding: PADDING,
+            texture,
+            recs: recs.as_mut_ptr(),
+            glyphs: glyphs.as_mut_ptr(),
+        };
+        Ok(GlyphPage {
+            font: WeakFont(font),
+            _texture: Texture2D(texture),
+            glyphs,
+            recs,
+            cells: vec![
+                Cell {
+                    codepoint: None,
+                    last_used: 0,
+                };
+                count
+            ],
+            index: HashMap::new(),
+        })
+    }
+
+    /// Copies `raster` into a free or least recently used cell of `page`.
+    fn store(&mut self, page: usize, codepoint: char, raster: &Raster) {
+        let (size, tick) = (self.cell_size(), self.tick);
+        let columns = (self.page_size / size) as usize;
+        let page = &mut self.pages[page];
+        let cell = match page.cells.iter().position(|c| c.codepoint.is_none()) {
+            Some(cell) => cell,
+            None => {
+                let (cell, _) = page
+                    .cells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.last_used < tick)
+                    .min_by_key(|(_, c)| c.last_used)
+                    .expect("font_for checked the text fits on a page");
+                if let Some(old) = page.cells[cell].codepoint {
+                    page.index.remove(&old);
+                }
+                cell
+            }
+        };
+
+        let x = (cell % columns) as i32 * size;
+        let y = (cell / columns) as i32 * size;
+        let inner = size - 2 * PADDING;
+        let (width, height) = (raster.width.min(inner), raster.height.min(inner));
+        let mut pixels = vec![0u8; (size * size * 2) as usize];
+        for row in 0..height {
+            for col in 0..width {
+                let value = raster.pixels[(row * raster.width + col) as usize];
+                let at = (((row + PADDING) * size + col + PADDING) * 2) as usize;
+                pixels[at] = 255;
+                pixels[at + 1] = value;
+            }
+        }
+        unsafe {
+            ffi::UpdateTextureRec(
+                page.font.texture,
+                ffi::Rectangle {
+                    x: x as f32,
+                    y: y as f32,
+                    width: size as f32,
+                    height: size as f32,
+                },
+                pixels.as_ptr() as *const std::os::raw::c_void,
+            );
+        }
+
+        page.glyphs[cell] = ffi::GlyphInfo {
+            value: codepoint as i32,
+            ..raster.info
+        };
+        page.recs[cell] = ffi::Rectangle {
+            x: (x + PADDING) as f32,
+            y: (y + PADDING) as f32,
+            width: width as f32,
+            height: height as f32,
+        };
+        page.cells[cell] = Cell {
+            codepoint: Some(codepoint),
+            last_used: tick,
+        };
+        page.index.insert(codepoint, cell);
+    }
+}
+
+fn check_font_data(data: &[u8]) -> Result<(), Error> {
+    let mut codepoint = ' ' as i32;
+    let glyphs = unsafe {
+        ffi::LoadFontData(
+            data.as_ptr(),
+            data.len() as i32,
+            16,
+            &mut codepoint,
+            1,
+            ffi::FontType::FONT_DEFAULT as i32,
+        )
+    };
+    if glyphs.is_null() {
+        return Err(error!("Font data is not a valid TTF/OTF font"));
+    }
+    unsafe { ffi::UnloadFontData(glyphs, 1) };
+    Ok(())
+}
+
+/// Rasterizes `chars` from one font, with `None` for codepoints the font lacks.
+fn rasterize(data: &[u8], size: i32, chars: &[char]) -> Vec<Option<Raster>> {
+    let mut codepoints: Vec<i32> = chars.iter().map(|&c| c as i32).collect();
+    let glyphs = unsafe {
+        ffi::LoadFontData(
+            data.as_ptr(),
+            data.len() as i32,
+            size,
+            codepoints.as_mut_ptr(),
+            codepoints.len() as i32,
+            ffi::FontType::FONT_DEFAULT as i32,
+        )
+    };
+    if glyphs.is_null() {
+        return chars.iter().map(|_| None).collect();
+    }
+
+    let rasters = unsafe { std::slice::from_raw_parts(glyphs, chars.len()) }
+        .iter()
+        .map(|glyph| {
+            let image = &glyph.image;
+            // raylib leaves glyphs the font doesn't have zeroed.
+            if glyph.advanceX == 0 && image.data.is_null() {
+                return None;
+            }
+            let grayscale = ffi::PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAYSCALE as i32;
+            let (width, height, pixels) = if image.data.is_null() || image.format != grayscale {
+                (0, 0, Vec::new())
+            } else {
+                let len = (image.width * image.height) as usize;
+                let data = unsafe { std::slice::from_raw_parts(image.data as *const u8, len) };
+                (image.width, image.height, data.to_vec())
+            };
+            Some(Raster {
+                info: ffi::GlyphInfo {
+                    image: unsafe { std::mem::zeroed() },
+                    ..*glyph
+                },
+                width,
+                height,
+                pixels,
+            })
+        })
+        .collect();
+    unsafe { ffi::UnloadFontData(glyphs, chars.len() as i32) };
+    rasters
+}
@@ -0,0 +1,325 @@
+//! Signed distance field fonts, which stay sharp at any draw size
+use crate::consts::{ShaderUniformDataType, TextureFilter};
+use crate::core::color::Color;
+use crate::core::file::load_file_data;
+use crate::core::math::Vector2;
+use crate::core::shaders::{RaylibShader, Shader};
+use crate::core::text::{Font, RaylibFont};
+use crate::core::{RaylibHandle, RaylibThread};
+use crate::error::{error, Error};
+use crate::ffi;
+
+#[cfg(not(any(feature = "opengl_es_20", target_arch = "wasm32")))]
+const HEADER: &str = "#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+out vec4 finalColor;
+#define TEX texture
+";
+
+#[cfg(any(feature = "opengl_es_20", target_arch = "wasm32"))]
+const HEADER: &str = "#version 100
+#extension GL_OES_standard_derivatives : enable
+precision mediump float;
+varying vec2 fragTexCoord;
+varying vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+#define TEX texture2D
+#define finalColor gl_FragColor
+";
+
+// raylib stores distances as 128 on the edge, changing by 64 per texel.
+const SDF_FS: &str = "
+uniform float dilate;
+uniform float softness;
+uniform float outlineWidth;
+uniform vec4 outlineColor;
+uniform float glowRadius;
+uniform vec4 glowColor;
+void main() {
+    float dist = (TEX(texture0, fragTexCoord).a * 255.0 - 128.0) / 64.0 + dilate;
+    float aa = length(vec2(dFdx(dist), dFdy(dist))) * 0.7071 + softness;
+    aa = max(aa, 0.0001);
+    vec4 fill = fragColor * colDiffuse;
+    float inside = smoothstep(-aa, aa, dist);
+    vec4 color = fill;
+    float alpha = inside;
+    if (outlineWidth > 0.0) {
+        color = mix(outlineColor, fill, inside);
+        alpha = smoothstep(-aa, aa, dist + outlineWidth);
+    }
+    color.a *= alpha;
+    if (glowRadius > 0.0) {
+        float glow = clamp(1.0 + (dist + outlineWidth) / glowRadius, 0.0, 1.0) * glowColor.a;
+        float a = color.a + glow * (1.0 - color.a);
+        vec3 rgb = (color.rgb * color.a + glowColor.rgb * glow * (1.0 - color.a)) / max(a, 0.0001);
+        color = vec4(rgb, a);
+    }
+    finalColor = color;
+}
+";
+
+/// A font whose atlas holds signed distance fields, drawn with `RaylibDraw::draw_text_sdf`.
+///
+/// Effect sizes in `SdfTextStyle` are measured in atlas texels, i.e. pixels at the base size the
+/// font was loaded at. raylib's distance fields reach about 2 texels past each glyph edge, so load
+/// at a larger base size for thicker outlines and glows relative to the text.
+///
+/// ```no_run
+/// use raylib::prelude::*;
+/// let (mut rl, thread) = raylib::init().build();
+/// let font = rl.load_font_sdf(&thread, "font.ttf", 48, None).unwrap();
+/// let style = SdfTextStyle::new(Color::WHITE)
+///     .outline(1.0, Color::BLACK)
+///     .shadow(Vector2::new(3.0, 3.0), 0.5, Color::BLACK);
+/// let mut d = rl.begin_drawing(&thread);
+/// d.draw_text_sdf(&font, "Game Over", Vector2::new(40.0, 40.0), 160.0, &style);
+/// ```
+#[derive(Debug)]
+pub struct SdfFont {
+    pub(crate) font: Font,
+    pub(crate) shader: Shader,
+    locations: [i32; 6],
+}
+
+impl AsRef<ffi::Font> for SdfFont {
+    fn as_ref(&self) -> &ffi::Font {
+        self.font.as_ref()
+    }
+}
+
+impl AsMut<ffi::Font> for SdfFont {
+    fn as_mut(&mut self) -> &mut ffi::Font {
+        self.font.as_mut()
+    }
+}
+
+impl RaylibFont for SdfFont {}
+
+impl SdfFont {
+    /// The built-in SDF text shader.
+    pub fn shader(&self) -> &Shader {
+        &self.shader
+    }
+
+    pub(crate) fn set_effects(
+        &self,
+        dilate: f32,
+        softness: f32,
+        outline: Option<SdfOutline>,
+        glow: Option<SdfGlow>,
+    ) {
+        let outline = outline.unwrap_or(SdfOutline {
+            width: 0.0,
+            color: Color::BLANK,
+        });
+        let glow = glow.unwrap_or(SdfGlow {
+            radius: 0.0,
+            color: Color::BLANK,
+        });
+        let floats = [
+            (self.locations[0], dilate),
+            (self.locations[1], softness),
+            (self.locations[2], outline.width.max(0.0)),
+            (self.locations[4], glow.radius.max(0.0)),
+        ];
+        let colors = [
+            (self.locations[3], outline.color),
+            (self.locations[5], glow.color),
+        ];
+        unsafe {
+            for (location, value) in floats.iter() {
+                ffi::SetShaderValue(
+                    *self.shader.as_ref(),
+                    *location,
+                    value as *const f32 as *const std::os::raw::c_void,
+                    ShaderUniformDataType::SHADER_UNIFORM_FLOAT as i32,
+                );
+            }
+            for (location, color) in colors.iter() {
+                let value = color.color_normalize();
+                ffi::SetShaderValue(
+                    *self.shader.as_ref(),
+                    *location,
+                    &value as *const _ as *const std::os::raw::c_void,
+                    ShaderUniformDataType::SHADER_UNIFORM_VEC4 as i32,
+                );
+            }
+        }
+    }
+}
+
+/// Outline drawn around the glyphs, `width` texels thick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SdfOutline {
+    pub width: f32,
+    pub color: Color,
+}
+
+/// Glow fading out over `radius` texels from the glyph or outline edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SdfGlow {
+    pub radius: f32,
+    pub color: Color,
+}
+
+/// Copy of the text drawn behind it, `offset` pixels away on screen and blurred by `softness` texels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SdfShadow {
+    pub offset: Vector2,
+    pub softness: f32,
+    pub color: Color,
+}
+
+/// How `draw_text_sdf` renders text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SdfTextStyle {
+    pub color: Color,
+    /// Extra space between characters, as in `draw_text_ex`.
+    pub spacing: f32,
+    pub outline: Option<SdfOutline>,
+    pub glow: Option<SdfGlow>,
+    pub shadow: Option<SdfShadow>,
+}
+
+impl Default for SdfTextStyle {
+    fn default() -> Self {
+        SdfTextStyle::new(Color::BLACK)
+    }
+}
+
+impl SdfTextStyle {
+    pub fn new(color: impl Into<Color>) -> Self {
+        SdfTextStyle {
+            color: color.into(),
+            spacing: 1.0,
+            outline: None,
+            glow: None,
+            shadow: None,
+        }
+    }
+
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn outline(mut self, width: f32, color: impl Into<Color>) -> Self {
+        self.outline = Some(SdfOutline {
+            width,
+            color: color.into(),
+        });
+        self
+    }
+
+    pub fn glow(mut self, radius: f32, color: impl Into<Color>) -> Self {
+        self.glow = Some(SdfGlow {
+            radius,
+            color: color.into(),
+        });
+        self
+    }
+
+    pub fn shadow(
+        mut self,
+        offset: impl Into<Vector2>,
+        softness: f32,
+        color: impl Into<Color>,
+    ) -> Self {
+        self.shadow = Some(SdfShadow {
+            offset: offset.into(),
+            softness,
+            color: color.into(),
+        });
+        self
+    }
+}
+
+impl RaylibHandle {
+    /// Loads a TTF/OTF font as signed distance fields at `font_size`, with the SDF text shader.
+    /// Supplying None for chars loads the default character set, an empty string is an error.
+    pub fn load_font_sdf(
+        &mut self,
+        thread: &RaylibThread,
+        filename: &str,
+        font_size: i32,
+        chars: Option<&str>,
+    ) -> Result<SdfFont, Error> {
+        let data = load_file_data(filename)?;
+        self.load_font_sdf_from_memory(thread, &data, font_size, chars)
+    }
+
+    /// Loads an SDF font from TTF/OTF data in memory, see `load_font_sdf`.
+    pub fn load_font_sdf_from_memory(
+        &mut self,
+        thread: &RaylibThread,
+        data: &[u8],
+        font_size: i32,
+        chars: Option<&str>,
+    ) -> Result<SdfFont, Error> {
+        if chars == Some("") {
+            return Err(error!("No characters to load in the SDF font"));
+        }
+        let mut codepoints: Option<Vec<i32>> = chars.map(|c| c.chars().map(|c| c as i32).collect());
+        let (codepoints_ptr, count) = match codepoints.as_mut() {
+            Some(c) => (c.as_mut_ptr(), c.len() as i32),
+            None => (std::ptr::null_mut(), 0),
+        };
+        let font = unsafe {
+            let glyphs = ffi::LoadFontData(
+                data.as_ptr(),
+                data.len() as i32,
+                font_size,
+                codepoints_ptr,
+                count,
+                ffi::FontType::FONT_SDF as i32,
+            );
+            if glyphs.is_null() {
+                return Err(error!(
+                    "Error loading SDF font. Check the font data is TTF/OTF"
+                ));
+            }
+            // Without codepoints raylib loads the 95 printable ASCII characters.
+            let count = if codepoints_ptr.is_null() { 95 } else { count };
+            let mut font: ffi::Font = std::mem::zeroed();
+            font.baseSize = font_size;
+            font.glyphCount = count;
+            font.glyphs = glyphs;
+            let atlas = ffi::GenImageFontAtlas(glyphs, &mut font.recs, count, font_size, 0, 1);
+            font.texture = ffi::LoadTextureFromImage(atlas);
+            ffi::UnloadImage(atlas);
+            ffi::SetTextureFilter(font.texture, TextureFilter::TEXTURE_FILTER_BILINEAR as i32);
+            Font(font)
+        };
+        if font.texture.id == 0 {
+            return Err(error!("Error loading SDF font atlas"));
+        }
+
+        let shader =
+            self.load_shader_from_memory(thread, None, Some(&format!("{}{}", HEADER, SDF_FS)))?;
+        let mut locations = [0; 6];
+        let names = [
+            "dilate",
+            "softness",
+            "outlineWidth",
+            "outlineColor",
+            "glowRadius",
+            "glowColor",
+        ];
+        for (location, name) in locations.iter_mut().zip(names.iter()) {
+            *location = shader.get_shader_location(name);
+        }
+        Ok(SdfFont {
+            font,
+            shader,
+            locations,
+        })
+    }
+}
@@ -0,0 +1,731 @@
+//! Multi-line text layout: wrapping, alignment, truncation and inline markup
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//! let (mut rl, thread) = raylib::init().build();
+//! let font = rl.get_font_default();
+//! let layout = TextLayout::new(&font, 20.0)
+//!     .width(240.0)
+//!     .align(TextAlign::Center)
+//!     .max_lines(3);
+//! let text = layout
+//!     .layout_markup("Press [color=#e6c84a]SPACE[/color] to [size=28]jump[/size]")
+//!     .unwrap();
+//! let mut d = rl.begin_drawing(&thread);
+//! text.draw(&mut d, Vector2::new(20.0, 20.0));
+//! ```
+use crate::core::color::Color;
+use crate::core::drawing::RaylibDraw;
+use crate::core::math::{Rectangle, Vector2};
+use crate::core::text::{text_line_spacing, WeakFont};
+use crate::error::{error, Error};
+use crate::ffi;
+
+use std::ops::Range;
+
+/// How lines are broken when they exceed the layout width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextWrap {
+    /// Break between words, splitting words that don't fit on a line of their own.
+    Word,
+    /// Break between any two characters.
+    Character,
+    /// Only break on `'\n'`. Lines wider than the layout width are truncated with the ellipsis.
+    None,
+}
+
+/// Horizontal alignment of each line inside the layout width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+    /// Stretch spaces so wrapped lines fill the width. Last lines of paragraphs are left aligned.
+    Justify,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Style {
+    font: usize,
+    size: f32,
+    color: Color,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Item {
+    ch: char,
+    byte: usize,
+    style: Style,
+    advance: f32,
+}
+
+#[derive(Debug)]
+struct LineDraft {
+    items: Vec<Item>,
+    bytes: Range<usize>,
+    style: Style,
+    paragraph_end: bool,
+    truncated: bool,
+}
+
+/// Builder for laying out a block of text with one or more fonts.
+///
+/// Sizes and spacing are in pixels, like `draw_text_ex`. The gap between lines defaults to
+/// the value set with `RaylibHandle::set_text_line_spacing`.
+#[derive(Debug, Clone)]
+pub struct TextLayout<'a> {
+    fonts: Vec<(String, &'a ffi::Font)>,
+    font_size: f32,
+    spacing: f32,
+    color: Color,
+    width: Option<f32>,
+    wrap: TextWrap,
+    align: TextAlign,
+    max_lines: Option<usize>,
+    ellipsis: String,
+    line_spacing: Option<f32>,
+}
+
+impl<'a> TextLayout<'a> {
+    /// Lay out text in `font` at `font_size` pixels.
+    pub fn new(font: &'a impl AsRef<ffi::Font>, font_size: f32) -> Self {
+        TextLayout {
+            fonts: vec![(String::new(), font.as_ref())],
+            font_size,
+            spacing: 1.0,
+            color: Color::BLACK,
+            width: None,
+            wrap: TextWrap::Word,
+            align: TextAlign::Left,
+            max_lines: None,
+            ellipsis: "...".to_owned(),
+            line_spacing: None,
+        }
+    }
+
+    /// Register a font that markup can switch to with `[font=name]`.
+    pub fn font(mut self, name: &str, font: &'a impl AsRef<ffi::Font>) -> Self {
+        match self.fonts.iter_mut().skip(1).find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = font.as_ref(),
+            None => self.fonts.push((name.to_owned(), font.as_ref())),
+        }
+        self
+    }
+
+    /// Extra space between characters, as in `draw_text_ex`. Defaults to 1.
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Color of text outside `[color]` spans.
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Width to wrap and align within. Without one, lines only break on `'\n'` and are aligned
+    /// to the widest line.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn wrap(mut self, wrap: TextWrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Drop lines past `max_lines`, ending the last one with the ellipsis.
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Text appended to truncated lines. Defaults to `"..."`, pass `""` to cut without one.
+    pub fn ellipsis(mut self, ellipsis: impl Into<String>) -> Self {
+        self.ellipsis = ellipsis.into();
+        self
+    }
+
+    /// Gap between lines in pixels, overriding the one set with `set_text_line_spacing`.
+    pub fn line_spacing(mut self, line_spacing: f32) -> Self {
+        self.line_spacing = Some(line_spacing);
+        self
+    }
+
+    fn base_style(&self) -> Style {
+        Style {
+            font: 0,
+            size: self.font_size,
+            color: self.color,
+        }
+    }
+
+    /// Lay out plain text. Byte indices in the result refer to `text`.
+    pub fn layout(&self, text: &str) -> TextBlock<'a> {
+        let style = self.base_style();
+        let chars = text.char_indices().map(|(byte, ch)| (ch, byte, style));
+        self.build(chars.collect(), text.len())
+    }
+
+    /// Lay out text containing markup spans:
+    ///
+    /// * `[color=#rrggbb]`, `[color=#rrggbbaa]` or `[color=red]` ... `[/color]`
+    /// * `[size=32]` ... `[/size]`
+    /// * `[font=name]` ... `[/font]`, for fonts registered with `TextLayout::font`
+    ///
+    /// Spans nest and must be closed in order. `[[` is a literal `[`. Byte indices in the
+    /// result refer to `markup`, so they skip over the tags.
+    pub fn layout_markup(&self, markup: &str) -> Result<TextBlock<'a>, Error> {
+        let chars = self.parse_markup(markup)?;
+        Ok(self.build(chars, markup.len()))
+    }
+
+    fn parse_markup(&self, markup: &str) -> Result<Vec<(char, usize, Style)>, Error> {
+        let mut chars = Vec::with_capacity(markup.len());
+        let mut stack: Vec<(&str, Style)> = Vec::new();
+        let mut style = self.base_style();
+        let mut rest = markup.char_indices();
+        while let Some((byte, ch)) = rest.next() {
+            if ch != '[' {
+                chars.push((ch, byte, style));
+                continue;
+            }
+            let tail = &markup[byte + 1..];
+            if tail.starts_with('[') {
+                rest.next();
+                chars.push(('[', byte, style));
+                continue;
+            }
+            let end = tail.find(']').ok_or_else(|| {
+                error!(std::borrow::Cow::Owned(format!(
+                    "unterminated markup tag at byte {}",
+                    byte
+                )))
+            })?;
+            let tag = &tail[..end];
+            for _ in 0..=tag.chars().count() {
+                rest.next();
+            }
+
+            if let Some(name) = tag.strip_prefix('/') {
+                match stack.pop() {
+                    Some((open, previous)) if open == name => style = previous,
+                    _ => {
+                        return Err(error!(std::borrow::Cow::Owned(format!(
+                            "unexpected closing tag [{}] at byte {}",
+                            tag, byte
+                        ))))
+                    }
+                }
+                continue;
+            }
+
+            let (name, value) = tag.split_once('=').unwrap_or((tag, ""));
+            let previous = style;
+            match name {
+                "color" => style.color = parse_color(value)?,
+                "size" => {
+                    style.size = value.trim().parse().map_err(|_| {
+                        error!(std::borrow::Cow::Owned(format!(
+                            "invalid size \"{}\"",
+                            value
+                        )))
+                    })?
+                }
+                "font" => {
+                    style.font = self
+                        .fonts
+                        .iter()
+                        .skip(1)
+                        .position(|(n, _)| n == value)
+                        .map(|i| i + 1)
+                        .ok_or_else(|| {
+                            error!(std::borrow::Cow::Owned(format!(
+                                "unknown font \"{}\"",
+                                value
+                            )))
+                        })?
+                }
+                _ => {
+                    return Err(error!(std::borrow::Cow::Owned(format!(
+                        "unknown markup tag [{}] at byte {}",
+                        tag, byte
+                    ))))
+                }
+            }
+            stack.push((name, previous));
+        }
+        if let Some((open, _)) = stack.last() {
+            return Err(error!(std::borrow::Cow::Owned(format!(
+                "unclosed markup tag [{}]",
+                open
+            ))));
+        }
+        Ok(chars)
+    }
+
+    fn item(&self, ch: char, byte: usize, style: Style) -> Item {
+        let font = self.fonts[style.font].1;
+        let advance = if ch == '\t' {
+            glyph_advance(font, ' ', style.size) * 4.0
+        } else {
+            glyph_advance(font, ch, style.size)
+        };
+        Item {
+            ch,
+            byte,
+            style,
+            advance: advance + self.spacing,
+        }
+    }
+
+    /// Width of `items` up to the last visible glyph.
+    fn visible_width(&self, items: &[Item]) -> f32 {
+        let end = visible_end(items);
+        if end == 0 {
+            return 0.0;
+        }
+        items[..end].iter().map(|i| i.advance).sum::<f32>() - self.spacing
+    }
+
+    fn build(&self, chars: Vec<(char, usize, Style)>, len: usize) -> TextBlock<'a> {
+        let mut drafts = Vec::new();
+        let mut paragraph = Vec::new();
+        let mut start = 0;
+        for (ch, byte, style) in chars {
+            match ch {
+                '\r' => {}
+                '\n' => {
+                    self.break_paragraph(&mut drafts, &paragraph, start..byte, style);
+                    paragraph.clear();
+                    start = byte + 1;
+                }
+                _ => paragraph.push(self.item(ch, byte, style)),
+            }
+        }
+        let style = paragraph.last().map_or(self.base_style(), |i| i.style);
+        self.break_paragraph(&mut drafts, &paragraph, start..len, style);
+
+        let mut truncated = false;
+        if let Some(max_lines) = self.max_lines {
+            if drafts.len() > max_lines {
+                drafts.truncate(max_lines);
+                if let Some(last) = drafts.last_mut() {
+                    self.truncate(last);
+                }
+                truncated = true;
+            }
+        }
+        if let (TextWrap::None, Some(width)) = (self.wrap, self.width) {
+            for line in drafts.iter_mut() {
+                if !line.truncated && self.visible_width(&line.items) > width {
+                    self.truncate(line);
+                    truncated = true;
+                }
+            }
+        }
+        self.place(drafts, truncated)
+    }
+
+    fn break_paragraph(
+        &self,
+        drafts: &mut Vec<LineDraft>,
+        items: &[Item],
+        bytes: Range<usize>,
+        style: Style,
+    ) {
+        let width = match (self.wrap, self.width) {
+            (TextWrap::None, _) | (_, None) => f32::INFINITY,
+            (_, Some(width)) => width,
+        };
+        let mut start = 0;
+        loop {
+            let mut end = start;
+            let mut line_width = 0.0;
+            let mut word_break = None;
+            while end < items.len() {
+                let item = &items[end];
+                let visible = !item.ch.is_whitespace();
+                if visible && end > start && line_width + item.advance - self.spacing > width {
+                    break;
+                }
+                line_width += item.advance;
+                end += 1;
+                if !visible {
+                    word_break = Some(end);
+                }
+            }
+            if end < items.len() && self.wrap == TextWrap::Word {
+                if let Some(b) = word_break {
+                    end = b;
+                }
+            }
+            // Spaces at a break hang off the end of the line.
+            while end < items.len() && items[end].ch.is_whitespace() {
+                end += 1;
+            }
+
+            let last = end == items.len();
+            let line_bytes = Range {
+                start: if start == 0 {
+                    bytes.start
+                } else {
+                    items[start].byte
+                },
+                end: if last { bytes.end } else { items[end].byte },
+            };
+            drafts.push(LineDraft {
+                items: items[start..end].to_vec(),
+                bytes: line_bytes,
+                style: items.get(start).map_or(style, |i| i.style),
+                paragraph_end: last,
+                truncated: false,
+            });
+            if last {
+                break;
+            }
+            start = end;
+        }
+    }
+
+    /// Cut `line` so it fits together with the ellipsis.
+    fn truncate(&self, line: &mut LineDraft) {
+        let style = line.items.last().map_or(line.style, |i| i.style);
+        let mut dots: Vec<Item> = self
+            .ellipsis
+            .chars()
+            .map(|ch| self.item(ch, 0, style))
+            .collect();
+        let dots_width: f32 = dots.iter().map(|i| i.advance).sum();
+        if let Some(width) = self.width {
+            loop {
+                line.items.truncate(visible_end(&line.items));
+                let content: f32 = line.items.iter().map(|i| i.advance).sum();
+                if line.items.is_empty() || content + dots_width - self.spacing <= width {
+                    break;
+                }
+                line.items.pop();
+            }
+        } else {
+            line.items.truncate(visible_end(&line.items));
+        }
+
+        let cut = line
+            .items
+            .last()
+            .map_or(line.bytes.start, |i| i.byte + i.ch.len_utf8());
+        for dot in dots.iter_mut() {
+            dot.byte = cut;
+        }
+        line.items.extend(dots);
+        line.bytes.end = cut;
+        line.truncated = true;
+    }
+
+    fn place(&self, drafts: Vec<LineDraft>, truncated: bool) -> TextBlock<'a> {
+        let line_spacing = self
+            .line_spacing
+            .unwrap_or_else(|| text_line_spacing() as f32);
+        let widths: Vec<f32> = drafts
+            .iter()
+            .map(|l| self.visible_width(&l.items))
+            .collect();
+        let widest = widths.iter().cloned().fold(0.0, f32::max);
+        let container = self.width.unwrap_or(widest);
+
+        let mut glyphs = Vec::new();
+        let mut lines = Vec::with_capacity(drafts.len());
+        let mut y = 0.0;
+        let mut block_width: f32 = 0.0;
+        for (index, (draft, width)) in drafts.into_iter().zip(widths).enumerate() {
+            let size = draft
+                .items
+                .iter()
+                .map(|i| i.style.size)
+                .fold(f32::NAN, f32::max);
+            let size = if size.is_nan() {
+                draft.style.size
+            } else {
+                size
+            };
+            let visible = visible_end(&draft.items);
+            let spaces = draft.items[..visible]
+                .iter()
+                .filter(|i| i.ch.is_whitespace())
+                .count();
+            let (start_x, stretch) = match self.align {
+                TextAlign::Left => (0.0, 0.0),
+                TextAlign::Center => ((container - width) / 2.0, 0.0),
+                TextAlign::Right => (container - width, 0.0),
+                TextAlign::Justify if !draft.paragraph_end && !draft.truncated && spaces > 0 => {
+                    (0.0, (container - width).max(0.0) / spaces as f32)
+                }
+                TextAlign::Justify => (0.0, 0.0),
+            };
+
+            let first = glyphs.len();
+            let mut x = start_x;
+            for (i, item) in draft.items.iter().enumerate() {
+                let mut advance = item.advance;
+                if i < visible && item.ch.is_whitespace() {
+                    advance += stretch;
+                }
+                glyphs.push(LayoutGlyph {
+                    codepoint: item.ch,
+                    byte_index: item.byte,
+                    line: index,
+                    rect: Rectangle::new(x, y + size - item.style.size, advance, item.style.size),
+                    font_size: item.style.size,
+                    color: item.style.color,
+                    font: item.style.font,
+                });
+                x += advance;
+            }
+            let line_width = width + stretch * spaces as f32;
+            block_width = block_width.max(line_width);
+            lines.push(TextLine {
+                glyphs: first..glyphs.len(),
+                bytes: draft.bytes,
+                rect: Rectangle::new(start_x, y, line_width, size),
+            });
+            y += size + line_spacing;
+        }
+        let height = if lines.is_empty() {
+            0.0
+        } else {
+            y - line_spacing
+        };
+
+        TextBlock {
+            fonts: self.fonts.iter().map(|(_, f)| *f).collect(),
+            glyphs,
+            lines,
+            size: Vector2::new(block_width, height),
+            line_spacing,
+            truncated,
+        }
+    }
+}
+
+/// A positioned character of a `TextBlock`. Whitespace is included so carets can be placed on
+/// it, but is not drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutGlyph {
+    pub codepoint: char,
+    /// Byte offset of the character in the laid out string. Ellipsis glyphs use the offset
+    /// where the text was cut.
+    pub byte_index: usize,
+    pub line: usize,
+    /// Area the glyph covers, from the pen position to the next glyph and down the font size.
+    pub rect: Rectangle,
+    pub font_size: f32,
+    pub color: Color,
+    font: usize,
+}
+
+/// A line of a `TextBlock`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    /// Indices into `TextBlock::glyphs`.
+    pub glyphs: Range<usize>,
+    /// Byte range of the source text shown on this line.
+    pub bytes: Range<usize>,
+    /// Bounds of the visible glyphs. The height is the largest font size on the line.
+    pub rect: Rectangle,
+}
+
+/// Result of `TextLayout::layout`, positioned relative to its top left corner.
+#[derive(Debug, Clone)]
+pub struct TextBlock<'a> {
+    fonts: Vec<&'a ffi::Font>,
+    glyphs: Vec<LayoutGlyph>,
+    lines: Vec<TextLine>,
+    size: Vector2,
+    line_spacing: f32,
+    truncated: bool,
+}
+
+impl<'a> TextBlock<'a> {
+    pub fn glyphs(&self) -> &[LayoutGlyph] {
+        &self.glyphs
+    }
+
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// Size of the laid out text, up to the widest line.
+    pub fn size(&self) -> Vector2 {
+        self.size
+    }
+
+    /// Whether lines or characters were dropped to respect `max_lines` or the width.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Index of the glyph under `point`, relative to the block.
+    pub fn hit_test(&self, point: impl Into<Vector2>) -> Option<usize> {
+        let p = point.into();
+        self.glyphs.iter().position(|g| {
+            p.x >= g.rect.x
+                && p.x < g.rect.x + g.rect.width
+                && p.y >= g.rect.y
+                && p.y < g.rect.y + g.rect.height
+        })
+    }
+
+    /// Byte offset of the caret position closest to `point`, relative to the block.
+    pub fn caret_index(&self, point: impl Into<Vector2>) -> usize {
+        let p = point.into();
+        let index = match self
+            .lines
+            .iter()
+            .position(|l| p.y < l.rect.y + l.rect.height + self.line_spacing / 2.0)
+        {
+            Some(i) => i,
+            None if self.lines.is_empty() => return 0,
+            None => self.lines.len() - 1,
+        };
+        let line = &self.lines[index];
+        let glyphs = &self.glyphs[line.glyphs.clone()];
+        if let Some(g) = glyphs.iter().find(|g| p.x < g.rect.x + g.rect.width / 2.0) {
+            return g.byte_index;
+        }
+        let wrapped = self
+            .lines
+            .get(index + 1)
+            .is_some_and(|next| next.bytes.start == line.bytes.end);
+        match glyphs.last() {
+            // Stay in front of the hanging space instead of moving to the next line.
+            Some(g) if wrapped && g.codepoint.is_whitespace() => g.byte_index,
+            _ => line.bytes.end,
+        }
+    }
+
+    /// Rectangle of a one pixel wide caret in front of byte offset `index`.
+    pub fn caret_rect(&self, index: usize) -> Rectangle {
+        let count = self.lines.len();
+        for (i, line) in self.lines.iter().enumerate() {
+            let next_start = self.lines.get(i + 1).map(|l| l.bytes.start);
+            let on_line = index < line.bytes.end
+                || (index == line.bytes.end && !matches!(next_start, Some(s) if s <= index))
+                || i + 1 == count;
+            if !on_line {
+                continue;
+            }
+            let glyphs = &self.glyphs[line.glyphs.clone()];
+            let x = match glyphs.iter().find(|g| g.byte_index >= index) {
+                Some(g) => g.rect.x,
+                None => glyphs
+                    .last()
+                    .map_or(line.rect.x, |g| g.rect.x + g.rect.width),
+            };
+            return Rectangle::new(x, line.rect.y, 1.0, line.rect.height);
+        }
+        Rectangle::new(0.0, 0.0, 1.0, 0.0)
+    }
+
+    /// Draw the text with its top left corner at `position`.
+    pub fn draw(&self, d: &mut impl RaylibDraw, position: impl Into<Vector2>) {
+        let origin = position.into();
+        for g in self.glyphs.iter().filter(|g| !g.codepoint.is_whitespace()) {
+            d.draw_text_codepoint(
+                WeakFont(*self.fonts[g.font]),
+                g.codepoint as i32,
+                Vector2::new(origin.x + g.rect.x, origin.y + g.rect.y),
+                g.font_size,
+                g.color,
+            );
+        }
+    }
+}
+
+fn visible_end(items: &[Item]) -> usize {
+    items
+        .iter()
+        .rposition(|i| !i.ch.is_whitespace())
+        .map_or(0, |p| p + 1)
+}
+
+/// Horizontal advance of `ch`, using the same glyph lookup and fallback as raylib.
+fn glyph_advance(font: &ffi::Font, ch: char, size: f32) -> f32 {
+    let count = font.glyphCount.max(0) as usize;
+    if font.glyphs.is_null() || count == 0 || font.baseSize == 0 {
+        return 0.0;
+    }
+    let glyphs = unsafe { std::slice::from_raw_parts(font.glyphs, count) };
+    let codepoint = ch as i32;
+    let fast = (codepoint as usize).wrapping_sub(32);
+    let index = if fast < count && glyphs[fast].value == codepoint {
+        fast
+    } else {
+        glyphs
+            .iter()
+            .position(|g| g.value == codepoint)
+            .or_else(|| glyphs.iter().position(|g| g.value == '?' as i32))
+            .unwrap_or(0)
+    };
+    let scale = size / font.baseSize as f32;
+    if glyphs[index].advanceX != 0 || font.recs.is_null() {
+        glyphs[index].advanceX as f32 * scale
+    } else {
+        unsafe { (*font.recs.add(index)).width * scale }
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, Error> {
+    let invalid = || {
+        error!(std::borrow::Cow::Owned(format!(
+            "invalid color \"{}\"",
+            value
+        )))
+    };
+    if let Some(hex) = value.strip_prefix('#') {
+        let rgba = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+        return match hex.len() {
+            6 => Ok(Color::new(
+                (rgba >> 16) as u8,
+                (rgba >> 8) as u8,
+                rgba as u8,
+                255,
+            )),
+            8 => Ok(Color::new(
+                (rgba >> 24) as u8,
+                (rgba >> 16) as u8,
+                (rgba >> 8) as u8,
+                rgba as u8,
+            )),
+            _ => Err(invalid()),
+        };
+    }
+    let color = match value.to_ascii_lowercase().as_str() {
+        "white" => Color::WHITE,
+        "black" => Color::BLACK,
+        "gray" | "grey" => Color::GRAY,
+        "red" => Color::RED,
+        "orange" => Color::ORANGE,
+        "yellow" => Color::YELLOW,
+        "gold" => Color::GOLD,
+        "green" => Color::GREEN,
+        "lime" => Color::LIME,
+        "blue" => Color::BLUE,
+        "skyblue" => Color::SKYBLUE,
+        "purple" => Color::PURPLE,
+        "violet" => Color::VIOLET,
+        "pink" => Color::PINK,
+        "magenta" => Color::MAGENTA,
+        "brown" => Color::BROWN,
+        _ => return Err(invalid()),
+    };
+    Ok(color)
+}
@@ -5,6 +5,7 @@ use raylib_sys::{
     LoadImageAnimFromMemory, PixelFormat,
 };
 
+use crate::consts::{rlFramebufferAttachTextureType, rlFramebufferAttachType};
 use crate::core::color::Color;
 use crate::core::math::Rectangle;
 use crate::core::{RaylibHandle, RaylibThread};
@@ -133,6 +134,18 @@ pub trait RaylibRenderTexture2D: AsRef<ffi::RenderTexture2D> + AsMut<ffi::Render
     fn texture_mut(&mut self) -> &mut WeakTexture2D {
         unsafe { std::mem::transmute(&mut self.as_mut().texture) }
     }
+
+    /// Reads what was rendered back into an `Image`. OpenGL stores render textures bottom-up,
+    /// the image is flipped so its first row is the top of the render texture.
+    fn load_rendered_image(&self) -> Result<Image, Error> {
+        let i = unsafe { ffi::LoadImageFromTexture(self.as_ref().texture) };
+        if i.data.is_null() {
+            return Err(error!("Render texture cannot be read back to an image"));
+        }
+        let mut image = Image(i);
+        image.flip_vertical();
+        Ok(image)
+    }
 }
 
 impl Clone for Image {
@@ -164,7 +177,7 @@ impl Image {
         unsafe { ffi::ImageRotate(&mut self.0, degrees) }
     }
     /// Get image pixel color at (x, y) position
-    pub fn get_color(&mut self, x: i32, y: i32) -> Color {
+    pub fn get_color(&self, x: i32, y: i32) -> Color {
         Color::from(unsafe { ffi::GetImageColor(self.0, x, y) })
     }
     /// Draw circle outline within an image
@@ -1192,6 +1205,41 @@ impl RaylibHandle {
         }
         Ok(RenderTexture2D(t))
     }
+
+    /// Loads a render texture with only a depth attachment, for shadow maps. `texture` has no id
+    /// but keeps the size so texture mode sets the right viewport; sample `depth` instead.
+    pub fn load_render_texture_depth(
+        &mut self,
+        thread: &RaylibThread,
+        width: u32,
+        height: u32,
+    ) -> Result<RenderTexture2D, Error> {
+        let (width, height) = (width as i32, height as i32);
+        let mut framebuffer = self.load_framebuffer(thread)?;
+        let depth = self.load_texture_depth(thread, width, height)?;
+        framebuffer.attach(
+            &depth,
+            rlFramebufferAttachType::RL_ATTACHMENT_DEPTH,
+            rlFramebufferAttachTextureType::RL_ATTACHMENT_TEXTURE2D,
+            0,
+        );
+        // Unloading the framebuffer deletes the depth texture too.
+        let depth = depth.to_raw();
+        if !framebuffer.is_complete() {
+            return Err(error!("depth render texture is incomplete."));
+        }
+        Ok(RenderTexture2D(ffi::RenderTexture2D {
+            id: framebuffer.to_raw(),
+            texture: ffi::Texture2D {
+                id: 0,
+                width,
+                height,
+                mipmaps: 1,
+                format: 0,
+            },
+            depth,
+        }))
+    }
 }
 
 impl RaylibHandle {
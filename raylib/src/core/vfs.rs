@@ -0,0 +1,713 @@
+//! Virtual filesystem. Mount directories, in-memory files and pack archives, then every raylib
+//! `Load*` function reads through them.
+//!
+//! ```no_run
+//! use raylib::prelude::*;
+//! use raylib::vfs::{DirectoryFs, PackFs, Vfs};
+//!
+//! let (mut rl, thread) = raylib::init().build();
+//!
+//! let mut vfs = Vfs::new();
+//! vfs.mount("", DirectoryFs::new("."));
+//! vfs.mount("assets", PackFs::open("assets.pak").unwrap());
+//! let _vfs = rl.mount_vfs(vfs);
+//!
+//! // Served from assets.pak
+//! let texture = rl.load_texture(&thread, "assets/player.png").unwrap();
+//! ```
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    ffi::{c_char, CString},
+    fs::{self, File},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use crate::{
+    callbacks::{CallbackGuard, TraceLogLevel},
+    data::{DeflateDecoder, DeflateEncoder},
+    ffi, RaylibHandle,
+};
+
+/// Source of files for a [`Vfs`] mount point. Paths are relative to the mount point, use `/` as
+/// separator and never contain `.` or `..` components.
+pub trait FileSystem: Send {
+    /// Reads the whole file.
+    fn read(&self, path: &str) -> io::Result<Vec<u8>>;
+
+    /// Replaces the file with `data`. File systems are read-only unless they override this.
+    fn write(&mut self, path: &str, data: &[u8]) -> io::Result<()> {
+        let _ = (path, data);
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "read-only file system",
+        ))
+    }
+
+    /// Checks if the file exists.
+    fn exists(&self, path: &str) -> bool {
+        self.read(path).is_ok()
+    }
+}
+
+/// Absolute paths bypass the mounts and are read straight from disk.
+fn is_absolute(path: &str) -> bool {
+    path.starts_with(['/', '\\']) || Path::new(path).is_absolute()
+}
+
+/// Turns `path` into the canonical form used by [`FileSystem`], resolving `.` and `..`.
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+fn not_found(path: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("{}: file not found", path))
+}
+
+/// Files of a directory on disk.
+#[derive(Debug, Clone)]
+pub struct DirectoryFs {
+    root: PathBuf,
+}
+
+impl DirectoryFs {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Directory the mount reads from.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl FileSystem for DirectoryFs {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.root.join(path))
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> io::Result<()> {
+        let path = self.root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, data)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.root.join(path).is_file()
+    }
+}
+
+/// Files kept in memory. Writable.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFs {
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl MemoryFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces a file.
+    pub fn insert(&mut self, path: &str, data: impl Into<Vec<u8>>) {
+        self.files.insert(normalize_path(path), data.into());
+    }
+
+    /// Removes a file, returning its contents.
+    pub fn remove(&mut self, path: &str) -> Option<Vec<u8>> {
+        self.files.remove(&normalize_path(path))
+    }
+
+    /// Iterates over the paths of every file.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+}
+
+impl FileSystem for MemoryFs {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        self.files.get(path).cloned().ok_or_else(|| not_found(path))
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> io::Result<()> {
+        self.files.insert(path.to_owned(), data.to_vec());
+        Ok(())
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.contains_key(path)
+    }
+}
+
+/// Identifies pack archives, followed by the format version.
+const PACK_MAGIC: &[u8; 4] = b"RPAK";
+const PACK_VERSION: u32 = 1;
+/// Magic, version, entry count and index offset.
+const PACK_HEADER_SIZE: u64 = 4 + 4 + 4 + 8;
+
+/// Location of one file inside a pack archive.
+#[derive(Debug, Clone, Copy)]
+struct PackEntry {
+    offset: u64,
+    stored_size: u64,
+    size: u64,
+    compressed: bool,
+}
+
+/// Writes a pack archive readable by [`PackFs`].
+///
+/// File data is written as it is added, the index is written by [`PackBuilder::finish`].
+/// ```no_run
+/// use raylib::vfs::PackBuilder;
+///
+/// let mut pack = PackBuilder::create("assets.pak").unwrap();
+/// pack.add_dir("", "resources").unwrap();
+/// pack.finish().unwrap();
+/// ```
+pub struct PackBuilder<W: Write + Seek> {
+    writer: W,
+    start: u64,
+    entries: Vec<(String, PackEntry)>,
+    compress: bool,
+}
+
+impl PackBuilder<File> {
+    /// Creates the archive file at `path`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(File::create(path)?)
+    }
+}
+
+impl<W: Write + Seek> PackBuilder<W> {
+    /// Starts an archive at the current position of `writer`.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        let start = writer.stream_position()?;
+        // Header is filled in by `finish`
+        writer.write_all(&[0; PACK_HEADER_SIZE as usize])?;
+        Ok(Self {
+            writer,
+            start,
+            entries: Vec::new(),
+            compress: true,
+        })
+    }
+
+    /// Whether files are DEFLATE compressed, on by default. Files that don't get smaller are
+    /// always stored as is.
+    pub fn compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Adds a file. Adding the same path twice replaces the first one.
+    pub fn add(&mut self, path: &str, data: &[u8]) -> io::Result<()> {
+        let path = normalize_path(path);
+        if path.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "pack entry needs a file name",
+            ));
+        }
+
+        let compressed = if self.compress {
+            let mut encoder = DeflateEncoder::new(Vec::new());
+            encoder.write_all(data)?;
+            Some(encoder.finish()?).filter(|c| c.len() < data.len())
+        } else {
+            None
+        };
+        let stored = compressed.as_deref().unwrap_or(data);
+
+        let offset = self.writer.stream_position()? - self.start;
+        self.writer.write_all(stored)?;
+
+        let entry = PackEntry {
+            offset,
+            stored_size: stored.len() as u64,
+            size: data.len() as u64,
+            compressed: compressed.is_some(),
+        };
+        self.entries.retain(|(p, _)| *p != path);
+        self.entries.push((path, entry));
+        Ok(())
+    }
+
+    /// Adds every file below the `dir` directory on disk, under the `prefix` directory of the archive.
+    pub fn add_dir(&mut self, prefix: &str, dir: impl AsRef<Path>) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let path = format!("{}/{}", prefix, name.to_string_lossy());
+            if entry.file_type()?.is_dir() {
+                self.add_dir(&path, entry.path())?;
+            } else {
+                self.add(&path, &fs::read(entry.path())?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the index and returns the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let index_offset = self.writer.stream_position()? - self.start;
+        for (path, entry) in &self.entries {
+            self.writer.write_all(&(path.len() as u16).to_le_bytes())?;
+            self.writer.write_all(path.as_bytes())?;
+            self.writer.write_all(&entry.offset.to_le_bytes())?;
+            self.writer.write_all(&entry.stored_size.to_le_bytes())?;
+            self.writer.write_all(&entry.size.to_le_bytes())?;
+            self.writer.write_all(&[entry.compressed as u8])?;
+        }
+        let end = self.writer.stream_position()?;
+
+        self.writer.seek(SeekFrom::Start(self.start))?;
+        self.writer.write_all(PACK_MAGIC)?;
+        self.writer.write_all(&PACK_VERSION.to_le_bytes())?;
+        self.writer
+            .write_all(&(self.entries.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&index_offset.to_le_bytes())?;
+        self.writer.seek(SeekFrom::Start(end))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Read-only mount serving the files of a pack archive written by [`PackBuilder`].
+pub struct PackFs<R: Read + Seek + Send = BufReader<File>> {
+    reader: Mutex<R>,
+    start: u64,
+    entries: HashMap<String, PackEntry>,
+}
+
+impl PackFs {
+    /// Opens the archive file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek + Send> PackFs<R> {
+    /// Reads the index of the archive starting at the current position of `reader`.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        fn invalid(message: &'static str) -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        }
+        fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+            let mut buf = [0; 8];
+            reader.read_exact(&mut buf)?;
+            Ok(u64::from_le_bytes(buf))
+        }
+
+        let start = reader.stream_position()?;
+        let mut header = [0; PACK_HEADER_SIZE as usize];
+        reader.read_exact(&mut header)?;
+        if &header[0..4] != PACK_MAGIC {
+            return Err(invalid("not a pack archive"));
+        }
+        if u32::from_le_bytes(header[4..8].try_into().unwrap()) != PACK_VERSION {
+            return Err(invalid("unsupported pack archive version"));
+        }
+        let count = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        let index_offset = u64::from_le_bytes(header[12..20].try_into().unwrap());
+
+        reader.seek(SeekFrom::Start(start + index_offset))?;
+        let mut entries = HashMap::with_capacity(count as usize);
+        for _ in 0..count {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            let mut path = vec![0; u16::from_le_bytes(len) as usize];
+            reader.read_exact(&mut path)?;
+            let path =
+                String::from_utf8(path).map_err(|_| invalid("pack entry name isn't utf-8"))?;
+
+            let offset = read_u64(&mut reader)?;
+            let stored_size = read_u64(&mut reader)?;
+            let size = read_u64(&mut reader)?;
+            let mut compressed = [0; 1];
+            reader.read_exact(&mut compressed)?;
+
+            entries.insert(
+                path,
+                PackEntry {
+                    offset,
+                    stored_size,
+                    size,
+                    compressed: compressed[0] != 0,
+                },
+            );
+        }
+
+        Ok(Self {
+            reader: Mutex::new(reader),
+            start,
+            entries,
+        })
+    }
+
+    /// Iterates over the paths of every file in the archive.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Uncompressed size of a file.
+    pub fn file_size(&self, path: &str) -> Option<u64> {
+        self.entries.get(&normalize_path(path)).map(|e| e.size)
+    }
+}
+
+impl<R: Read + Seek + Send> FileSystem for PackFs<R> {
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        let entry = self.entries.get(path).ok_or_else(|| not_found(path))?;
+
+        let mut reader = self.reader.lock().unwrap();
+        reader.seek(SeekFrom::Start(self.start + entry.offset))?;
+        let mut stored = (&mut *reader).take(entry.stored_size);
+
+        let mut data = Vec::with_capacity(entry.size as usize);
+        if entry.compressed {
+            DeflateDecoder::new(stored).read_to_end(&mut data)?;
+        } else {
+            stored.read_to_end(&mut data)?;
+        }
+        if data.len() as u64 != entry.size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: pack entry is truncated", path),
+            ));
+        }
+        Ok(data)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+}
+
+/// A set of mounted [`FileSystem`]s. When several mounts contain a path, the one mounted last wins.
+/// Absolute paths are not resolved through the mounts, they are read and written on disk as-is.
+#[derive(Default)]
+pub struct Vfs {
+    mounts: Vec<(String, Box<dyn FileSystem>)>,
+}
+
+impl Vfs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts `fs` at `point`. An empty mount point serves every path.
+    pub fn mount(&mut self, point: &str, fs: impl FileSystem + 'static) {
+        self.mounts.push((normalize_path(point), Box::new(fs)));
+    }
+
+    /// Removes every file system mounted at `point`.
+    pub fn unmount(&mut self, point: &str) {
+        let point = normalize_path(point);
+        self.mounts.retain(|(p, _)| *p != point);
+    }
+
+    /// Mounts which may contain `path`, most recent first, with the path relative to each mount.
+    fn resolve<'a>(&'a self, path: &'a str) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+        self.mounts
+            .iter()
+            .enumerate()
+            .rev()
+            .filter_map(move |(i, (point, _))| {
+                if point.is_empty() {
+                    Some((i, path))
+                } else {
+                    path.strip_prefix(point.as_str())
+                        .and_then(|rest| rest.strip_prefix('/'))
+                        .map(|rest| (i, rest))
+                }
+            })
+    }
+
+    /// Reads a file from the first mount that has it.
+    pub fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        if is_absolute(path) {
+            return fs::read(path);
+        }
+        let path = normalize_path(path);
+        for (i, rel) in self.resolve(&path) {
+            match self.mounts[i].1.read(rel) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                result => return result,
+            }
+        }
+        Err(not_found(&path))
+    }
+
+    /// Reads a text file from the first mount that has it.
+    pub fn read_to_string(&self, path: &str) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes a file to the most recent writable mount that can hold `path`.
+    pub fn write(&mut self, path: &str, data: &[u8]) -> io::Result<()> {
+        if is_absolute(path) {
+            return fs::write(path, data);
+        }
+        let path = normalize_path(path);
+        let candidates: Vec<(usize, String)> = self
+            .resolve(&path)
+            .map(|(i, rel)| (i, rel.to_owned()))
+            .collect();
+
+        let mut error = None;
+        for (i, rel) in candidates {
+            match self.mounts[i].1.write(&rel, data) {
+                Err(e) if e.kind() == io::ErrorKind::PermissionDenied => error = Some(e),
+                result => return result,
+            }
+        }
+        Err(error.unwrap_or_else(|| not_found(&path)))
+    }
+
+    /// Checks if any mount has the file.
+    pub fn exists(&self, path: &str) -> bool {
+        if is_absolute(path) {
+            return Path::new(path).is_file();
+        }
+        let path = normalize_path(path);
+        let exists = self
+            .resolve(&path)
+            .any(|(i, rel)| self.mounts[i].1.exists(rel));
+        exists
+    }
+}
+
+/// Every mounted [`Vfs`], the most recent last. Each [`VfsGuard`] removes only its own entry, so
+/// guards can be dropped in any order.
+static MOUNTED_VFS: Mutex<Vec<Arc<Mutex<Vfs>>>> = Mutex::new(Vec::new());
+
+/// The most recently mounted [`Vfs`] that is still mounted.
+fn mounted_vfs() -> Option<Arc<Mutex<Vfs>>> {
+    MOUNTED_VFS.lock().unwrap().last().cloned()
+}
+
+fn vfs_warning(message: String) {
+    let message = CString::new(message.replace('\0', "")).unwrap();
+    unsafe {
+        ffi::TraceLog(
+            TraceLogLevel::LOG_WARNING as i32,
+            b"VFS: %s\0".as_ptr() as *const c_char,
+            message.as_ptr(),
+        )
+    };
+}
+
+/// Keeps a [`Vfs`] mounted. Dropping it unmounts this vfs only; the most recent one still mounted
+/// takes over.
+#[must_use = "the vfs is unmounted as soon as the guard is dropped"]
+pub struct VfsGuard {
+    vfs: Arc<Mutex<Vfs>>,
+    _callbacks: [CallbackGuard; 4],
+}
+
+impl VfsGuard {
+    /// Locks the mounted [`Vfs`], to change its mounts at runtime.
+    pub fn vfs(&self) -> MutexGuard<'_, Vfs> {
+        self.vfs.lock().unwrap()
+    }
+}
+
+impl Drop for VfsGuard {
+    fn drop(&mut self) {
+        MOUNTED_VFS
+            .lock()
+            .unwrap()
+            .retain(|vfs| !Arc::ptr_eq(vfs, &self.vfs));
+    }
+}
+
+impl RaylibHandle {
+    /// Routes raylib file loading and saving through `vfs`. Textures, fonts, sounds, models,
+    /// shaders and gui styles are all read from its mounts.
+    pub fn mount_vfs(&mut self, vfs: Vfs) -> VfsGuard {
+        let vfs = Arc::new(Mutex::new(vfs));
+
+        let load_data = {
+            let vfs = vfs.clone();
+            self.set_load_file_data_callback(move |path| {
+                vfs.lock().unwrap().read(path).unwrap_or_else(|e| {
+                    vfs_warning(format!("[{}] Failed to read file: {}", path, e));
+                    Vec::new()
+                })
+            })
+        };
+        let save_data = {
+            let vfs = vfs.clone();
+            self.set_save_file_data_callback(move |path, data| {
+                vfs.lock()
+                    .unwrap()
+                    .write(path, data)
+                    .map_err(|e| vfs_warning(format!("[{}] Failed to write file: {}", path, e)))
+                    .is_ok()
+            })
+        };
+        let load_text = {
+            let vfs = vfs.clone();
+            self.set_load_file_text_callback(move |path| {
+                vfs.lock()
+                    .unwrap()
+                    .read_to_string(path)
+                    .unwrap_or_else(|e| {
+                        vfs_warning(format!("[{}] Failed to read text file: {}", path, e));
+                        String::new()
+                    })
+            })
+        };
+        let save_text = {
+            let vfs = vfs.clone();
+            self.set_save_file_text_callback(move |path, text| {
+                vfs.lock()
+                    .unwrap()
+                    .write(path, text.as_bytes())
+                    .map_err(|e| vfs_warning(format!("[{}] Failed to write file: {}", path, e)))
+                    .is_ok()
+            })
+        };
+
+        MOUNTED_VFS.lock().unwrap().push(vfs.clone());
+        VfsGuard {
+            vfs,
+            _callbacks: [load_data, save_data, load_text, save_text],
+        }
+    }
+}
+
+/// raygui reads style files with `fopen`, so styles are loaded here when a [`Vfs`] is mounted.
+/// Returns false if no vfs is mounted.
+pub(crate) fn gui_load_style(path: &str) -> bool {
+    let vfs = match mounted_vfs() {
+        Some(vfs) => vfs,
+        None => return false,
+    };
+    let data = match vfs.lock().unwrap().read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            vfs_warning(format!("[{}] Failed to read style: {}", path, e));
+            return true;
+        }
+    };
+
+    if data.first() == Some(&b'#') {
+        gui_load_style_text(path, &String::from_utf8_lossy(&data));
+    } else {
+        // Binary styles embed their font, so raygui can load them from a scratch copy
+        let scratch =
+            std::env::temp_dir().join(format!("raylib-vfs-style-{}.rgs", std::process::id()));
+        if let Err(e) = fs::write(&scratch, &data) {
+            vfs_warning(format!("[{}] Failed to stage style: {}", path, e));
+            return true;
+        }
+        let c_path = CString::new(scratch.to_string_lossy().as_bytes()).unwrap();
+        unsafe { ffi::GuiLoadStyle(c_path.as_ptr()) };
+        let _ = fs::remove_file(scratch);
+    }
+    true
+}
+
+/// Mirrors the text style parser of raygui's `GuiLoadStyle`, with files resolved through the vfs.
+fn gui_load_style_text(path: &str, text: &str) {
+    let dir = normalize_path(path);
+    let dir = dir.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+    // A leading `/` would make the path absolute and skip the mounts
+    let in_dir = |file: &str| {
+        if dir.is_empty() {
+            file.to_owned()
+        } else {
+            format!("{}/{}", dir, file)
+        }
+    };
+
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            // p <control_id> <property_id> <property_value> <property_name>
+            Some("p") => {
+                let control = fields.next().and_then(|f| f.parse::<i32>().ok());
+                let property = fields.next().and_then(|f| f.parse::<i32>().ok());
+                let value = fields
+                    .next()
+                    .and_then(|f| u32::from_str_radix(f.trim_start_matches("0x"), 16).ok());
+                if let (Some(control), Some(property), Some(value)) = (control, property, value) {
+                    unsafe { ffi::GuiSetStyle(control, property, value as i32) };
+                }
+            }
+            // f <gen_font_size> <charmap_file> <font_file>
+            Some("f") => {
+                let size = fields
+                    .next()
+                    .and_then(|f| f.parse::<i32>().ok())
+                    .unwrap_or(0);
+                let charmap = fields.next().unwrap_or("0");
+                let font_file = fields.collect::<Vec<_>>().join(" ");
+
+                let mut codepoints: Vec<i32> = Vec::new();
+                if !charmap.starts_with('0') {
+                    if let Ok(chars) = read_mounted_text(&in_dir(charmap)) {
+                        codepoints = chars.chars().map(|c| c as i32).collect();
+                    }
+                }
+
+                unsafe {
+                    let mut font = ffi::GetFontDefault();
+                    if !font_file.is_empty() {
+                        let font_path = CString::new(in_dir(&font_file)).unwrap();
+                        font = ffi::LoadFontEx(
+                            font_path.as_ptr(),
+                            size,
+                            if codepoints.is_empty() {
+                                std::ptr::null_mut()
+                            } else {
+                                codepoints.as_mut_ptr()
+                            },
+                            codepoints.len() as i32,
+                        );
+                    }
+                    if font.texture.id == 0 {
+                        font = ffi::GetFontDefault();
+                        ffi::GuiSetStyle(
+                            ffi::GuiControl::DEFAULT as i32,
+                            ffi::GuiDefaultProperty::TEXT_SIZE as i32,
+                            10,
+                        );
+                        ffi::GuiSetStyle(
+                            ffi::GuiControl::DEFAULT as i32,
+                            ffi::GuiDefaultProperty::TEXT_SPACING as i32,
+                            1,
+                        );
+                    }
+                    if font.texture.id > 0 && font.glyphCount > 0 {
+                        ffi::GuiSetFont(font);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn read_mounted_text(path: &str) -> io::Result<String> {
+    match mounted_vfs() {
+        Some(vfs) => vfs.lock().unwrap().read_to_string(path),
+        None => Err(not_found(path)),
+    }
+}
@@ -26,6 +26,7 @@ use std::f32::consts::PI;
 pub type EaseFn = fn(f32, f32, f32, f32) -> f32;
 
 /// A manager for a tween on a single `f32` value.
+#[derive(Debug, Clone)]
 pub struct Tween {
     easer: EaseFn,
     start_value: f32,
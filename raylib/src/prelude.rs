@@ -26,21 +26,32 @@ Permission is granted to anyone to use this software for any purpose, including
 
 pub use crate::callbacks::*;
 pub use crate::consts::*;
+pub use crate::core::atlas::*;
 pub use crate::core::audio::*;
 pub use crate::core::automation::*;
 pub use crate::core::camera::*;
+pub use crate::core::camera_controller::*;
 pub use crate::core::collision::*;
 pub use crate::core::color::*;
 pub use crate::core::data::*;
 pub use crate::core::drawing::*;
+pub use crate::core::hot_reload::*;
 pub use crate::core::input::*;
+pub use crate::core::lighting::*;
 pub use crate::core::logging::*;
 pub use crate::core::math::*;
 pub use crate::core::misc::*;
 pub use crate::core::models::*;
+pub use crate::core::pixels::*;
+pub use crate::core::postprocess::*;
+pub use crate::core::rlgl::*;
+pub use crate::core::scene::*;
 pub use crate::core::shaders::*;
+pub use crate::core::sprite::*;
 pub use crate::core::text::*;
+pub use crate::core::text_layout::*;
 pub use crate::core::texture::*;
+pub use crate::core::vfs::*;
 pub use crate::core::vr::*;
 pub use crate::core::window::*;
 pub use crate::core::*;
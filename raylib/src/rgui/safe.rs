@@ -32,6 +32,17 @@ impl IntoCStr for Option<&CStr> {
     }
 }
 
+/// Loads a style through the mounted [`crate::vfs::Vfs`], if there is one.
+fn load_style(filename: *const std::os::raw::c_char) {
+    if !filename.is_null() {
+        let path = unsafe { CStr::from_ptr(filename) }.to_string_lossy();
+        if crate::core::vfs::gui_load_style(&path) {
+            return;
+        }
+    }
+    unsafe { ffi::GuiLoadStyle(filename) }
+}
+
 /// Global gui modification functions
 impl RaylibHandle {
     /// Enable gui controls (global state)
@@ -95,7 +106,7 @@ impl RaylibHandle {
     /// Load style file (.rgs)
     #[inline]
     pub fn gui_load_style(&mut self, filename: impl IntoCStr) {
-        unsafe { ffi::GuiLoadStyle(filename.as_cstr_ptr()) }
+        load_style(filename.as_cstr_ptr())
     }
     /// Load style default over global style
     #[inline]
@@ -168,7 +179,7 @@ pub trait RaylibDrawGui {
     /// Load style file (.rgs)
     #[inline]
     fn gui_load_style(&mut self, filename: impl IntoCStr) {
-        unsafe { ffi::GuiLoadStyle(filename.as_cstr_ptr()) }
+        load_style(filename.as_cstr_ptr())
     }
     /// Load style default over global style
     #[inline]
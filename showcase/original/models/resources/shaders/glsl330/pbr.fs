@@ -12,9 +12,10 @@
 #define     MAX_DEPTH_LAYER         20
 #define     MIN_DEPTH_LAYER         10
 
-#define     MAX_LIGHTS              4
+#define     MAX_LIGHTS              16      // Same as raylib-rs lighting::MAX_LIGHTS
 #define     LIGHT_DIRECTIONAL       0
 #define     LIGHT_POINT             1
+#define     LIGHT_SPOT              2
 
 struct MaterialProperty {
     vec3 color;
@@ -22,14 +23,6 @@ struct MaterialProperty {
     sampler2D sampler;
 };
 
-struct Light {
-    int enabled;
-    int type;
-    vec3 position;
-    vec3 target;
-    vec4 color;
-};
-
 // Input vertex attributes (from vertex shader)
 in vec3 fragPosition;
 in vec2 fragTexCoord;
@@ -46,13 +39,14 @@ uniform MaterialProperty occlusion;
 uniform MaterialProperty emission;
 uniform MaterialProperty height;
 
-// Input uniform values
-uniform samplerCube irradianceMap;
-uniform samplerCube prefilterMap;
-uniform sampler2D brdfLUT;
-
-// Input lighting values
-uniform Light lights[MAX_LIGHTS];
+// Input lighting values, uploaded by raylib-rs Lighting (see LightUniforms)
+uniform vec4 ambient;
+uniform int lightCount;
+uniform int lightType[MAX_LIGHTS];
+uniform vec3 lightPosition[MAX_LIGHTS];
+uniform vec3 lightDirection[MAX_LIGHTS];
+uniform vec3 lightColor[MAX_LIGHTS];
+uniform vec4 lightParams[MAX_LIGHTS];
 
 // Other uniform values
 uniform int renderMode;
@@ -192,7 +186,7 @@ void main()
     vec3 color = texture(albedo.sampler, texCoord).rgb; //ComputeMaterialProperty(albedo);
     vec3 metal = texture(metalness.sampler, texCoord).rgb; //ComputeMaterialProperty(metalness);
     vec3 rough = texture(roughness.sampler, texCoord).rgb; //ComputeMaterialProperty(roughness);
-    vec3 emiss = texture(emission.sampler, texCoord).rgb; //ComputeMaterialProperty(emission);
+    vec3 emiss = (emission.useSampler == 1)? texture(emission.sampler, texCoord).rgb : emission.color;
     vec3 ao = texture(occlusion.sampler, texCoord).rgb; //ComputeMaterialProperty(occlusion);
 
     // Check if normal mapping is enabled
@@ -217,66 +211,71 @@ void main()
 
     for (int i = 0; i < MAX_LIGHTS; i++)
     {
-        if (lights[i].enabled == 1)
+        if (i >= lightCount) break;
+
+        // Calculate per-light radiance
+        vec3 light = -normalize(lightDirection[i]);
+        vec3 radiance = lightColor[i];
+        if (lightType[i] != LIGHT_DIRECTIONAL)
         {
-            // Calculate per-light radiance
-            vec3 light = vec3(0.0);
-            vec3 radiance = lights[i].color.rgb;
-            if (lights[i].type == LIGHT_DIRECTIONAL) light = -normalize(lights[i].target - lights[i].position);
-            else if (lights[i].type == LIGHT_POINT)
+            light = normalize(lightPosition[i] - fragPosition);
+            float distance = length(lightPosition[i] - fragPosition);
+            float attenuation = 1.0/(distance*distance);
+
+            // Fade out towards the range of the light, if it has one
+            float range = lightParams[i].x;
+            if (range > 0.0)
             {
-                light = normalize(lights[i].position - fragPosition);
-                float distance = length(lights[i].position - fragPosition);
-                float attenuation = 1.0/(distance*distance);
-                radiance *= attenuation;
+                float r = distance*distance/(range*range);
+                float window = clamp(1.0 - r*r, 0.0, 1.0);
+                attenuation *= window*window;
             }
 
-            // Cook-torrance BRDF
-            vec3 high = normalize(view + light);
-            float NDF = DistributionGGX(normal, high, rough.r);
-            float G = GeometrySmith(normal, view, light, rough.r);
-            vec3 F = fresnelSchlick(max(dot(high, view), 0.0), F0);
-            vec3 nominator = NDF*G*F;
-            float denominator = 4*max(dot(normal, view), 0.0)*max(dot(normal, light), 0.0) + 0.001;
-            vec3 brdf = nominator/denominator;
-
-            // Store to kS the fresnel value and calculate energy conservation
-            vec3 kS = F;
-            vec3 kD = vec3(1.0) - kS;
-
-            // Multiply kD by the inverse metalness such that only non-metals have diffuse lighting
-            kD *= 1.0 - metal.r;
-
-            // Scale light by dot product between normal and light direction
-            float NdotL = max(dot(normal, light), 0.0);
-
-            // Add to outgoing radiance Lo
-            // Note: BRDF is already multiplied by the Fresnel so it doesn't need to be multiplied again
-            Lo += (kD*color/PI + brdf)*radiance*NdotL*lights[i].color.a;
-            lightDot += radiance*NdotL + brdf*lights[i].color.a;
+            // Spot lights fade between the cosines of their inner and outer cones
+            if (lightType[i] == LIGHT_SPOT) attenuation *= smoothstep(lightParams[i].z, lightParams[i].y, dot(-light, normalize(lightDirection[i])));
+
+            radiance *= attenuation;
         }
+
+        // Cook-torrance BRDF
+        vec3 high = normalize(view + light);
+        float NDF = DistributionGGX(normal, high, rough.r);
+        float G = GeometrySmith(normal, view, light, rough.r);
+        vec3 F = fresnelSchlick(max(dot(high, view), 0.0), F0);
+        vec3 nominator = NDF*G*F;
+        float denominator = 4*max(dot(normal, view), 0.0)*max(dot(normal, light), 0.0) + 0.001;
+        vec3 brdf = nominator/denominator;
+
+        // Store to kS the fresnel value and calculate energy conservation
+        vec3 kS = F;
+        vec3 kD = vec3(1.0) - kS;
+
+        // Multiply kD by the inverse metalness such that only non-metals have diffuse lighting
+        kD *= 1.0 - metal.r;
+
+        // Scale light by dot product between normal and light direction
+        float NdotL = max(dot(normal, light), 0.0);
+
+        // Add to outgoing radiance Lo
+        // Note: BRDF is already multiplied by the Fresnel so it doesn't need to be multiplied again
+        Lo += (kD*color/PI + brdf)*radiance*NdotL;
+        lightDot += radiance*NdotL + brdf;
     }
 
-    // Calculate ambient lighting using IBL
+    // Calculate ambient lighting from the ambient color, there are no IBL maps
     vec3 F = fresnelSchlickRoughness(max(dot(normal, view), 0.0), F0, rough.r);
     vec3 kS = F;
     vec3 kD = 1.0 - kS;
     kD *= 1.0 - metal.r;
 
-    // Calculate indirect diffuse
-    vec3 irradiance = texture(irradianceMap, fragNormal).rgb;
-    vec3 diffuse = color*irradiance;
-
-    // Sample both the prefilter map and the BRDF lut and combine them together as per the Split-Sum approximation
-    vec3 prefilterColor = textureLod(prefilterMap, refl, rough.r*MAX_REFLECTION_LOD).rgb;
-    vec2 brdf = texture(brdfLUT, vec2(max(dot(normal, view), 0.0), rough.r)).rg;
-    vec3 reflection = prefilterColor*(F*brdf.x + brdf.y);
+    vec3 diffuse = color*ambient.rgb;
+    vec3 reflection = F*ambient.rgb;
 
     // Calculate final lighting
-    vec3 ambient = (kD*diffuse + reflection)*ao;
+    vec3 ambientLight = (kD*diffuse + reflection)*ao;
 
     // Calculate fragment color based on render mode
-    vec3 fragmentColor = ambient + Lo + emiss;                              // Physically Based Rendering
+    vec3 fragmentColor = ambientLight + Lo + emiss;                         // Physically Based Rendering
 
     if (renderMode == 1) fragmentColor = color;                             // Albedo
     else if (renderMode == 2) fragmentColor = normal;                       // Normals
@@ -286,8 +285,7 @@ void main()
     else if (renderMode == 6) fragmentColor = emiss;                        // Emission
     else if (renderMode == 7) fragmentColor = lightDot;                     // Lighting
     else if (renderMode == 8) fragmentColor = kS;                           // Fresnel
-    else if (renderMode == 9) fragmentColor = irradiance;                   // Irradiance
-    else if (renderMode == 10) fragmentColor = reflection;                  // Reflection
+    else if (renderMode == 9) fragmentColor = reflection;                   // Reflection
 
     // Apply HDR tonemapping
     fragmentColor = fragmentColor/(fragmentColor + vec3(1.0));
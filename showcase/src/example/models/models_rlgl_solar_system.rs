@@ -12,7 +12,6 @@
 ********************************************************************************************/
 
 pub use raylib::prelude::*;
-use raylib::ffi;
 
 
 //------------------------------------------------------------------------------------
@@ -76,33 +75,33 @@ pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut
             {
                 let mut d = d.begin_mode3D(&camera);
 
-                unsafe {
-                    ffi::rlPushMatrix();
-                        ffi::rlScalef(sunRadius, sunRadius, sunRadius);          // Scale Sun
-                        DrawSphereBasic(Color::GOLD);                              // Draw the Sun
-                    ffi::rlPopMatrix();
-
-                    ffi::rlPushMatrix();
-                        ffi::rlRotatef(earthOrbitRotation, 0.0, 1.0, 0.0);    // Rotation for Earth orbit around Sun
-                        ffi::rlTranslatef(earthOrbitRadius, 0.0, 0.0);         // Translation for Earth orbit
-                        ffi::rlRotatef(-earthOrbitRotation, 0.0, 1.0, 0.0);   // Rotation for Earth orbit around Sun inverted
-
-                        ffi::rlPushMatrix();
-                            ffi::rlRotatef(earthRotation, 0.25, 1.0, 0.0);       // Rotation for Earth itself
-                            ffi::rlScalef(earthRadius, earthRadius, earthRadius);// Scale Earth
-
-                            DrawSphereBasic(Color::BLUE);                          // Draw the Earth
-                        ffi::rlPopMatrix();
-
-                        ffi::rlRotatef(moonOrbitRotation, 0.0, 1.0, 0.0);     // Rotation for Moon orbit around Earth
-                        ffi::rlTranslatef(moonOrbitRadius, 0.0, 0.0);          // Translation for Moon orbit
-                        ffi::rlRotatef(-moonOrbitRotation, 0.0, 1.0, 0.0);    // Rotation for Moon orbit around Earth inverted
-                        ffi::rlRotatef(moonRotation, 0.0, 1.0, 0.0);          // Rotation for Moon itself
-                        ffi::rlScalef(moonRadius, moonRadius, moonRadius);       // Scale Moon
-
-                        DrawSphereBasic(Color::LIGHTGRAY);                         // Draw the Moon
-                    ffi::rlPopMatrix();
+                {
+                    let mut sun = d.rl_push_matrix();
+                        sun.scale(sunRadius, sunRadius, sunRadius);          // Scale Sun
+                        DrawSphereBasic(&mut *sun, Color::GOLD);              // Draw the Sun
+                }
 
+                {
+                    let mut earth = d.rl_push_matrix();
+                        earth.rotate(earthOrbitRotation, 0.0, 1.0, 0.0);    // Rotation for Earth orbit around Sun
+                        earth.translate(earthOrbitRadius, 0.0, 0.0);         // Translation for Earth orbit
+                        earth.rotate(-earthOrbitRotation, 0.0, 1.0, 0.0);   // Rotation for Earth orbit around Sun inverted
+
+                        {
+                            let mut earth = earth.rl_push_matrix();
+                            earth.rotate(earthRotation, 0.25, 1.0, 0.0);       // Rotation for Earth itself
+                            earth.scale(earthRadius, earthRadius, earthRadius);// Scale Earth
+
+                            DrawSphereBasic(&mut *earth, Color::BLUE);          // Draw the Earth
+                        }
+
+                        earth.rotate(moonOrbitRotation, 0.0, 1.0, 0.0);     // Rotation for Moon orbit around Earth
+                        earth.translate(moonOrbitRadius, 0.0, 0.0);          // Translation for Moon orbit
+                        earth.rotate(-moonOrbitRotation, 0.0, 1.0, 0.0);    // Rotation for Moon orbit around Earth inverted
+                        earth.rotate(moonRotation, 0.0, 1.0, 0.0);          // Rotation for Moon itself
+                        earth.scale(moonRadius, moonRadius, moonRadius);       // Scale Moon
+
+                        DrawSphereBasic(&mut *earth, Color::LIGHTGRAY);       // Draw the Moon
                 }
     
                     // Some reference elements (not affected by previous matrix transformations)
@@ -123,16 +122,16 @@ pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut
 
 // Draw sphere without any matrix transformation
 // NOTE: Sphere is drawn in woffi::rld position ( 0, 0, 0 ) with radius 1.0
-fn DrawSphereBasic(color: Color)
+fn DrawSphereBasic(d: &mut impl RaylibRlgl, color: Color)
 {
     let rings = 16;
     let slices = 16;
 
-    unsafe {
-        ffi::rlCheckRenderBatchLimit((rings + 2) * slices * 6);
+    {
+        d.rl_check_render_batch_limit((rings + 2) * slices * 6);
 
-        ffi::rlBegin(ffi::RL_TRIANGLES as i32);
-            ffi::rlColor4ub(color.r, color.g, color.b, color.a);
+        let mut tris = d.rl_begin(RlDrawMode::Triangles);
+            tris.color(color);
 
             for i in 0..(rings + 2)
             {
@@ -140,28 +139,27 @@ fn DrawSphereBasic(color: Color)
                 {
                     let deg2rad: f32 = consts::DEG2RAD as f32;
 
-                    ffi::rlVertex3f((deg2rad*(270+(180/(rings + 1))*i) as f32).cos()*(deg2rad*(j*360/slices) as f32).sin(),
+                    tris.vertex3(Vector3::new((deg2rad*(270+(180/(rings + 1))*i) as f32).cos()*(deg2rad*(j*360/slices) as f32).sin(),
                                     (deg2rad*(270+(180/(rings + 1))*i) as f32).sin(),
-                                    (deg2rad*(270+(180/(rings + 1))*i) as f32).cos()*(deg2rad*(j*360/slices) as f32).cos());
-                    ffi::rlVertex3f((deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).sin(),
+                                    (deg2rad*(270+(180/(rings + 1))*i) as f32).cos()*(deg2rad*(j*360/slices) as f32).cos()));
+                    tris.vertex3(Vector3::new((deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).sin(),
                                     (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).sin(),
-                                    (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).cos());
-                    ffi::rlVertex3f((deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*(j*360/slices) as f32).sin(),
+                                    (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).cos()));
+                    tris.vertex3(Vector3::new((deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*(j*360/slices) as f32).sin(),
                                     (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).sin(),
-                                    (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*(j*360/slices) as f32).cos());
+                                    (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*(j*360/slices) as f32).cos()));
 
-                    ffi::rlVertex3f((deg2rad*(270+(180/(rings + 1))*i) as f32).cos()*(deg2rad*(j*360/slices) as f32).sin(),
+                    tris.vertex3(Vector3::new((deg2rad*(270+(180/(rings + 1))*i) as f32).cos()*(deg2rad*(j*360/slices) as f32).sin(),
                                     (deg2rad*(270+(180/(rings + 1))*i) as f32).sin(),
-                                    (deg2rad*(270+(180/(rings + 1))*i) as f32).cos()*(deg2rad*(j*360/slices) as f32).cos());
-                    ffi::rlVertex3f((deg2rad*(270+(180/(rings + 1))*(i)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).sin(),
+                                    (deg2rad*(270+(180/(rings + 1))*i) as f32).cos()*(deg2rad*(j*360/slices) as f32).cos()));
+                    tris.vertex3(Vector3::new((deg2rad*(270+(180/(rings + 1))*(i)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).sin(),
                                     (deg2rad*(270+(180/(rings + 1))*(i)) as f32).sin(),
-                                    (deg2rad*(270+(180/(rings + 1))*(i)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).cos());
-                    ffi::rlVertex3f((deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).sin(),
+                                    (deg2rad*(270+(180/(rings + 1))*(i)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).cos()));
+                    tris.vertex3(Vector3::new((deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).sin(),
                                     (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).sin(),
-                                    (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).cos());
+                                    (deg2rad*(270+(180/(rings + 1))*(i+1)) as f32).cos()*(deg2rad*((j+1)*360/slices) as f32).cos()));
                 }
             }
-        ffi::rlEnd();
     }
 
 }
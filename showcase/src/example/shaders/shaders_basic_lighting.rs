@@ -27,11 +27,6 @@
 
 use raylib::prelude::*;
 
-#[cfg(not(target_arch = "wasm32"))]
-const GLSL_VERSION: i32 = 330;
-#[cfg(target_arch = "wasm32")]
-const GLSL_VERSION: i32 = 100;
-
 pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut {
     // Initialization
     //--------------------------------------------------------------------------------------
@@ -93,31 +88,8 @@ pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut {
         &texture,
     );
 
-    let mut shader = unsafe {
-        rl.load_shader(
-            thread,
-            Some(&format!(
-                "original/shaders/resources/shaders/glsl{}/base_lighting.vs",
-                GLSL_VERSION
-            )),
-            Some(&format!(
-                "original/shaders/resources/shaders/glsl{}/lighting.fs",
-                GLSL_VERSION
-            )),
-        )
-        .unwrap()
-        .make_weak()
-    };
-
-    // Get some shader loactions
-    shader.locs_mut()[raylib::consts::ShaderLocationIndex::SHADER_LOC_MATRIX_MODEL as usize] =
-        shader.get_shader_location("matModel");
-    shader.locs_mut()[raylib::consts::ShaderLocationIndex::SHADER_LOC_VECTOR_VIEW as usize] =
-        shader.get_shader_location("viewPos");
-
-    // ambient light level
-    let ambientLoc = shader.get_shader_location("ambient");
-    shader.set_shader_value(ambientLoc, Vector4::new(0.2, 0.2, 0.2, 1.0));
+    // Built-in lit shader, lights are uploaded to it by `Lighting`
+    let mut shader = rl.load_lit_shader(thread).unwrap();
 
     let mut angle: f32 = 6.282;
 
@@ -126,39 +98,18 @@ pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut {
     modelB.materials_mut()[0].shader = *shader;
     modelC.materials_mut()[0].shader = *shader;
 
-    // Using 4 point lights, white,Color::RED, green and blue
+    // Using 4 point lights, white, red, green and blue
+    // Point lights fade with the square of the distance, intensity is their brightness one unit away
     let mut lights = [
-        create_light(
-            LightType::LIGHT_POINT,
-            rvec3(4, 2, 4),
-            Vector3::zero(),
-            Color::WHITE,
-            &mut shader,
-        ),
-        create_light(
-            LightType::LIGHT_POINT,
-            rvec3(4, 2, 4),
-            Vector3::zero(),
-            Color::RED,
-            &mut shader,
-        ),
-        create_light(
-            LightType::LIGHT_POINT,
-            rvec3(0, 4, 2),
-            Vector3::zero(),
-            Color::GREEN,
-            &mut shader,
-        ),
-        create_light(
-            LightType::LIGHT_POINT,
-            rvec3(0, 4, 2),
-            Vector3::zero(),
-            Color::BLUE,
-            &mut shader,
-        ),
+        Light::point(rvec3(4, 2, 4), Color::WHITE).intensity(16.0),
+        Light::point(rvec3(4, 2, 4), Color::RED).intensity(16.0),
+        Light::point(rvec3(0, 4, 2), Color::GREEN).intensity(16.0),
+        Light::point(rvec3(0, 4, 2), Color::BLUE).intensity(16.0),
     ];
+    let mut enabled = [true; 4];
 
-    rl.set_camera_mode(&camera, raylib::consts::CameraMode::CAMERA_ORBITAL); // Set an orbital camera mode
+    // ambient light level
+    let mut lighting = Lighting::new().ambient(Color::new(51, 51, 51, 255));
 
     rl.set_target_fps(60); // Set our game to run at 60 frames-per-second
                            //--------------------------------------------------------------------------------------
@@ -173,22 +124,22 @@ pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut {
         //----------------------------------------------------------------------------------
         if rl.is_key_pressed(raylib::consts::KeyboardKey::KEY_W)
         {
-            lights[0].enabled = !lights[0].enabled;
+            enabled[0] = !enabled[0];
         }
         if rl.is_key_pressed(raylib::consts::KeyboardKey::KEY_R)
         {
-            lights[1].enabled = !lights[1].enabled;
+            enabled[1] = !enabled[1];
         }
         if rl.is_key_pressed(raylib::consts::KeyboardKey::KEY_G)
         {
-            lights[2].enabled = !lights[2].enabled;
+            enabled[2] = !enabled[2];
         }
         if rl.is_key_pressed(raylib::consts::KeyboardKey::KEY_B)
         {
-            lights[3].enabled = !lights[3].enabled;
+            enabled[3] = !enabled[3];
         }
 
-        rl.update_camera(&mut camera); // Update camera
+        rl.update_camera(&mut camera, raylib::consts::CameraMode::CAMERA_ORBITAL); // Update camera
 
         // Make the lights do differing orbits
         angle -= 0.02;
@@ -201,19 +152,19 @@ pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut {
         lights[3].position.y = (-angle * 0.35).cos() * 4.0;
         lights[3].position.z = (-angle * 0.35).sin() * 4.0;
 
-        update_light_values(&mut shader, lights[0].clone());
-        update_light_values(&mut shader, lights[1].clone());
-        update_light_values(&mut shader, lights[2].clone());
-        update_light_values(&mut shader, lights[3].clone());
+        lighting.lights = lights
+            .iter()
+            .zip(&enabled)
+            .filter(|(_, on)| **on)
+            .map(|(light, _)| *light)
+            .collect();
 
         // Rotate the torus
         modelA.set_transform(&(*modelA.transform() * Matrix::rotate_x(-0.025)));
         modelA.set_transform(&(*modelA.transform() * Matrix::rotate_z(0.012)));
 
-        // Update the light shader with the camera view position
-        let mut cameraPos = Vector3::new(camera.position.x, camera.position.y, camera.position.z);
-        let loc = shader.locs_mut()[raylib::consts::ShaderLocationIndex::SHADER_LOC_VECTOR_VIEW as usize];
-        shader.set_shader_value( loc, cameraPos);
+        // Update the light shader with the lights and the camera view position
+        lighting.upload(&mut shader, &camera);
         //----------------------------------------------------------------------------------
 
         // Draw
@@ -230,19 +181,19 @@ pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut {
         d.draw_model(&modelC, rvec3(1.6, 0,  0), 1.0, Color::WHITE);
 
         // Draw markers to show where the lights are
-        if lights[0].enabled
+        if enabled[0]
         {
             d.draw_sphere_ex(lights[0].position, 0.2, 8, 8, Color::WHITE);
         }
-        if lights[1].enabled
+        if enabled[1]
         {
             d.draw_sphere_ex(lights[1].position, 0.2, 8, 8,Color::RED);
         }
-        if lights[2].enabled
+        if enabled[2]
         {
             d.draw_sphere_ex(lights[2].position, 0.2, 8, 8, Color::GREEN);
         }
-        if lights[3].enabled
+        if enabled[3]
         {
             d.draw_sphere_ex(lights[3].position, 0.2, 8, 8, Color::BLUE);
         }
@@ -272,96 +223,3 @@ pub fn run(rl: &mut RaylibHandle, thread: &RaylibThread) -> crate::SampleOut {
 
     // return 0;
 }
-
-const MAX_LIGHTS: u32 = 4;
-
-#[repr(i32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum LightType {
-    LIGHT_DIRECTIONAL = 0,
-    LIGHT_POINT = 1,
-}
-
-impl Default for LightType {
-    fn default() -> Self {
-        Self::LIGHT_DIRECTIONAL
-    }
-}
-
-#[derive(Debug, Default, Clone)]
-pub struct Light {
-    pub enabled: bool,
-    pub light_type: LightType,
-    pub position: Vector3,
-    pub target: Vector3,
-    pub color: Color,
-    pub enabled_loc: i32,
-    pub type_loc: i32,
-    pub pos_loc: i32,
-    pub target_loc: i32,
-    pub color_loc: i32,
-}
-
-static mut LIGHTS_COUNT: i32 = 0;
-
-// Defines a light and get locations from PBR shader
-pub fn create_light(
-    light_type: LightType,
-    pos: Vector3,
-    targ: Vector3,
-    color: Color,
-    shader: &mut WeakShader,
-) -> Light {
-    let mut light = Light::default();
-
-    if (unsafe { LIGHTS_COUNT as u32 } < MAX_LIGHTS) {
-        light.enabled = true;
-        light.light_type = light_type;
-        light.position = pos.clone();
-        light.target = targ.clone();
-        light.color = color.clone();
-
-        // TODO: Below code doesn't look good to me,
-        // it assumes a specific shader naming and structure
-        // Probably this implementation could be improved
-
-        let lights_count = unsafe { LIGHTS_COUNT };
-        let enabledName = format!("lights[{}].enabled", lights_count);
-        let typeName = format!("lights[{}].type", lights_count);
-        let posName = format!("lights[{}].position", lights_count);
-        let targetName = format!("lights[{}].target", lights_count);
-        let colorName = format!("lights[{}].color", lights_count);
-
-        // Set location name [x] depending on lights count
-
-        light.enabled_loc = shader.get_shader_location(&enabledName);
-        light.type_loc = shader.get_shader_location(&typeName);
-        light.pos_loc = shader.get_shader_location(&posName);
-        light.target_loc = shader.get_shader_location(&targetName);
-        light.color_loc = shader.get_shader_location(&colorName);
-
-        update_light_values(shader, light.clone());
-        unsafe {
-            LIGHTS_COUNT += 1;
-        }
-    }
-
-    return light;
-}
-
-pub fn update_light_values(shader: &mut WeakShader, light: Light) {
-    // Send to shader light enabled state and type
-    shader.set_shader_value(light.enabled_loc, light.enabled as i32);
-    shader.set_shader_value(light.type_loc, light.light_type as i32);
-
-    // Send to shader light position values
-    shader.set_shader_value(light.pos_loc, light.position);
-
-    // Send to shader light target position values
-    shader.set_shader_value(light.target_loc, light.target);
-
-    // Send to shader light color values
-
-    let color: Vector4 = light.color.into();
-    shader.set_shader_value(light.color_loc, color);
-}